@@ -1,13 +1,12 @@
-use picopb::encode::Encode;
 use picopb::encode::ToWire;
 use std::ops::Deref;
 pub enum Error {
     ErrorInvalidKey,
     ErrorNotFound,
 }
-impl TryFrom<usize> for Error {
+impl TryFrom<i32> for Error {
     type Error = String;
-    fn try_from(value: usize) -> Result<Self, Self::Error> {
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
         match value {
             1 => Ok(Error::ErrorInvalidKey),
             2 => Ok(Error::ErrorNotFound),
@@ -15,7 +14,7 @@ impl TryFrom<usize> for Error {
         }
     }
 }
-impl From<Error> for usize {
+impl From<Error> for i32 {
     fn from(val: Error) -> Self {
         match val {
             Error::ErrorInvalidKey => 1,
@@ -27,31 +26,38 @@ pub struct Query {
     pub key: bytes::Bytes,
     pub opaque: bytes::Bytes,
 }
-pub struct QueryFieldsType {
-    pub key: picopb::common::ConstMessageField,
-    pub opaque: picopb::common::ConstMessageField,
+pub struct QueryFieldsType<'a> {
+    pub key: picopb::common::ConstMessageField<'a>,
+    pub opaque: picopb::common::ConstMessageField<'a>,
 }
 const QUERY_FIELDS: QueryFieldsType = QueryFieldsType {
     key: picopb::common::ConstMessageField {
         qualifier: picopb::common::FieldQualifier::Required,
         field_type: picopb::common::FieldType::UnboundedBytes,
         identifier: "key",
+        json_name: "key",
         ordinal: picopb::common::Field(1),
     },
     opaque: picopb::common::ConstMessageField {
         qualifier: picopb::common::FieldQualifier::Required,
         field_type: picopb::common::FieldType::UnboundedBytes,
         identifier: "opaque",
+        json_name: "opaque",
         ordinal: picopb::common::Field(2),
     },
 };
 impl Query {
-    fn fields(&self) -> QueryFieldsType {
+    fn fields(&self) -> QueryFieldsType<'static> {
         QUERY_FIELDS
     }
 }
-impl picopb::encode::Encode for &Query {
-    fn encode(&self, buf: &mut picopb::encode::EncodeBuffer) -> std::io::Result<usize> {
+impl picopb::wiretypes::WireTyped for &Query {
+    fn wiretype(&self) -> picopb::wiretypes::WireType {
+        picopb::wiretypes::WireType::Len
+    }
+}
+impl picopb::encode::ToWire for &Query {
+    fn append<W: picopb::encode::EncodeSink>(&self, buf: &mut W) -> std::io::Result<usize> {
         let mut total_size = 0;
         total_size += buf.encode(self.key.deref(), self.fields().key.ordinal)?;
         total_size += buf.encode(self.opaque.deref(), self.fields().opaque.ordinal)?;
@@ -78,7 +84,7 @@ fn main() {
     };
     let mut static_buffer: [u8; 512] = [0; 512];
     let mut buffer = picopb::encode::EncodeBuffer::from_static(&mut static_buffer);
-    (&q).encode(&mut buffer).expect("not error");
+    (&q).append(&mut buffer).expect("not error");
 
     eprintln!("b buffer: {:#04X?}", &buffer.as_slice());
     std::io::stdout().write_all(buffer.as_slice()).unwrap();
@@ -1,4 +1,3 @@
-use picopb::encode::Encode;
 use picopb::encode::ToWire;
 use picopb::randomizer::{randomized, Randomize};
 use std::ops::Deref;
@@ -8,9 +7,9 @@ pub enum Error {
     ErrorInvalidKey,
     ErrorNotFound,
 }
-impl TryFrom<usize> for Error {
+impl TryFrom<i32> for Error {
     type Error = String;
-    fn try_from(value: usize) -> Result<Self, Self::Error> {
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
         match value {
             1 => Ok(Error::ErrorInvalidKey),
             2 => Ok(Error::ErrorNotFound),
@@ -18,7 +17,7 @@ impl TryFrom<usize> for Error {
         }
     }
 }
-impl From<Error> for usize {
+impl From<Error> for i32 {
     fn from(val: Error) -> Self {
         match val {
             Error::ErrorInvalidKey => 1,
@@ -32,31 +31,38 @@ pub struct Query {
     pub opaque: bytes::Bytes,
 }
 #[derive(Debug)]
-pub struct QueryFieldsType {
-    pub key: picopb::common::ConstMessageField,
-    pub opaque: picopb::common::ConstMessageField,
+pub struct QueryFieldsType<'a> {
+    pub key: picopb::common::ConstMessageField<'a>,
+    pub opaque: picopb::common::ConstMessageField<'a>,
 }
 const QUERY_FIELDS: QueryFieldsType = QueryFieldsType {
     key: picopb::common::ConstMessageField {
         qualifier: picopb::common::FieldQualifier::Required,
         field_type: picopb::common::FieldType::UnboundedBytes,
         identifier: "key",
+        json_name: "key",
         ordinal: picopb::common::Field(1),
     },
     opaque: picopb::common::ConstMessageField {
         qualifier: picopb::common::FieldQualifier::Required,
         field_type: picopb::common::FieldType::UnboundedBytes,
         identifier: "opaque",
+        json_name: "opaque",
         ordinal: picopb::common::Field(2),
     },
 };
 impl Query {
-    fn fields(&self) -> QueryFieldsType {
+    fn fields(&self) -> QueryFieldsType<'static> {
         QUERY_FIELDS
     }
 }
-impl picopb::encode::Encode for &Query {
-    fn encode(&self, buf: &mut picopb::encode::EncodeBuffer) -> std::io::Result<usize> {
+impl picopb::wiretypes::WireTyped for &Query {
+    fn wiretype(&self) -> picopb::wiretypes::WireType {
+        picopb::wiretypes::WireType::Len
+    }
+}
+impl picopb::encode::ToWire for &Query {
+    fn append<W: picopb::encode::EncodeSink>(&self, buf: &mut W) -> std::io::Result<usize> {
         let mut total_size = 0;
         total_size += buf.encode(self.key.deref(), self.fields().key.ordinal)?;
         total_size += buf.encode(self.opaque.deref(), self.fields().opaque.ordinal)?;
@@ -69,11 +75,11 @@ impl picopb::encode::Encode for &Query {
         total_size
     }
 }
-impl Randomize<Query> for Query {
-    fn randomized() -> Query {
+impl Randomize for Query {
+    fn randomized<R: picopb::randomizer::Rng + ?Sized>(rng: &mut R) -> Self {
         Self {
-            key: randomized::<bytes::Bytes>(),
-            opaque: randomized::<bytes::Bytes>(),
+            key: randomized(rng),
+            opaque: randomized(rng),
         }
     }
 }
@@ -81,11 +87,12 @@ impl Randomize<Query> for Query {
 use std::io::Write;
 
 fn main() {
+    let mut rng = picopb::randomizer::seeded(0);
     (0..1000).for_each(|_| {
-        let q = Query::randomized();
+        let q = Query::randomized(&mut rng);
         let mut static_buffer: [u8; 20 * 10000] = [0; 20 * 10000];
         let mut buffer = picopb::encode::EncodeBuffer::from_static(&mut static_buffer);
-        (&q).encode(&mut buffer).expect("not error");
+        (&q).append(&mut buffer).expect("not error");
         std::io::stdout().write_all(buffer.as_slice()).unwrap();
     });
 }
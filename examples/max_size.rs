@@ -5,8 +5,8 @@ fn main() {
         "
         syntax = \"proto2\";
         message Query {
-            required bytes key = 1; [(nanopb).max_size=128]
-            required bytes value = 2; [(nanopb).max_size=64]
+            required bytes key = 1 [(nanopb).max_size=128];
+            required bytes value = 2 [(nanopb).max_size=64];
         }
     ",
     );
@@ -1,12 +1,11 @@
-use picopb::encode::Encode;
 use picopb::encode::ToWire;
 pub struct MessageWithOptionalField {
     pub d: Option<String>,
     pub e: Vec<i32>,
 }
-pub struct MessageWithOptionalFieldFieldsType {
-    pub d: picopb::common::ConstMessageField,
-    pub e: picopb::common::ConstMessageField,
+pub struct MessageWithOptionalFieldFieldsType<'a> {
+    pub d: picopb::common::ConstMessageField<'a>,
+    pub e: picopb::common::ConstMessageField<'a>,
 }
 const MESSAGE_WITH_OPTIONAL_FIELD_FIELDS: MessageWithOptionalFieldFieldsType =
     MessageWithOptionalFieldFieldsType {
@@ -14,27 +13,36 @@ const MESSAGE_WITH_OPTIONAL_FIELD_FIELDS: MessageWithOptionalFieldFieldsType =
             qualifier: picopb::common::FieldQualifier::Optional,
             field_type: picopb::common::FieldType::UnboundedString,
             identifier: "d",
+            json_name: "d",
             ordinal: picopb::common::Field(4),
         },
         e: picopb::common::ConstMessageField {
             qualifier: picopb::common::FieldQualifier::RepeatedUnbounded,
             field_type: picopb::common::FieldType::Int32,
             identifier: "e",
+            json_name: "e",
             ordinal: picopb::common::Field(5),
         },
     };
 impl MessageWithOptionalField {
-    fn fields(&self) -> MessageWithOptionalFieldFieldsType {
+    fn fields(&self) -> MessageWithOptionalFieldFieldsType<'static> {
         MESSAGE_WITH_OPTIONAL_FIELD_FIELDS
     }
 }
-impl picopb::encode::Encode for &MessageWithOptionalField {
-    fn encode(&self, buf: &mut picopb::encode::EncodeBuffer) -> std::io::Result<usize> {
+impl picopb::wiretypes::WireTyped for &MessageWithOptionalField {
+    fn wiretype(&self) -> picopb::wiretypes::WireType {
+        picopb::wiretypes::WireType::Len
+    }
+}
+impl picopb::encode::ToWire for &MessageWithOptionalField {
+    fn append<W: picopb::encode::EncodeSink>(&self, buf: &mut W) -> std::io::Result<usize> {
         let mut total_size = 0;
         if let Some(d) = &self.d {
             total_size += buf.encode(d.as_str(), self.fields().d.ordinal)?;
         }
-        total_size += buf.encode(self.e.as_slice(), self.fields().e.ordinal)?;
+        for value_e in self.e.iter() {
+            total_size += buf.encode(*value_e, self.fields().e.ordinal)?;
+        }
         Ok(total_size)
     }
     fn precalculate_size(&self) -> usize {
@@ -42,7 +50,9 @@ impl picopb::encode::Encode for &MessageWithOptionalField {
         if let Some(d) = &self.d {
             total_size += d.as_str().precalculate_size();
         }
-        total_size += self.e.as_slice().precalculate_size();
+        for value_e in self.e.iter() {
+            total_size += (*value_e).precalculate_field_size(self.fields().e.ordinal);
+        }
         total_size
     }
 }
@@ -55,7 +65,7 @@ fn main() {
     };
     let mut static_buffer: [u8; 512] = [0; 512];
     let mut buffer = picopb::encode::EncodeBuffer::from_static(&mut static_buffer);
-    (&q).encode(&mut buffer).expect("not error");
+    (&q).append(&mut buffer).expect("not error");
 
     eprintln!("b buffer: {:#04X?}", &buffer.as_slice());
     std::io::stdout().write_all(buffer.as_slice()).unwrap();
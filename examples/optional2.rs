@@ -1,4 +1,3 @@
-use picopb::encode::Encode;
 use picopb::encode::ToWire;
 use picopb::randomizer::{randomized, Randomize};
 
@@ -9,10 +8,10 @@ pub struct MessageWithOptionalField {
     pub e: Vec<i32>,
 }
 #[derive(Debug)]
-pub struct MessageWithOptionalFieldFieldsType {
-    pub a: picopb::common::ConstMessageField,
-    pub b: picopb::common::ConstMessageField,
-    pub e: picopb::common::ConstMessageField,
+pub struct MessageWithOptionalFieldFieldsType<'a> {
+    pub a: picopb::common::ConstMessageField<'a>,
+    pub b: picopb::common::ConstMessageField<'a>,
+    pub e: picopb::common::ConstMessageField<'a>,
 }
 const MESSAGE_WITH_OPTIONAL_FIELD_FIELDS: MessageWithOptionalFieldFieldsType =
     MessageWithOptionalFieldFieldsType {
@@ -20,34 +19,46 @@ const MESSAGE_WITH_OPTIONAL_FIELD_FIELDS: MessageWithOptionalFieldFieldsType =
             qualifier: picopb::common::FieldQualifier::Optional,
             field_type: picopb::common::FieldType::UnboundedString,
             identifier: "a",
+            json_name: "a",
             ordinal: picopb::common::Field(1),
         },
         b: picopb::common::ConstMessageField {
             qualifier: picopb::common::FieldQualifier::RepeatedUnbounded,
             field_type: picopb::common::FieldType::UnboundedString,
             identifier: "b",
+            json_name: "b",
             ordinal: picopb::common::Field(4),
         },
         e: picopb::common::ConstMessageField {
             qualifier: picopb::common::FieldQualifier::RepeatedUnbounded,
             field_type: picopb::common::FieldType::Int32,
             identifier: "e",
+            json_name: "e",
             ordinal: picopb::common::Field(5),
         },
     };
 impl MessageWithOptionalField {
-    fn fields(&self) -> MessageWithOptionalFieldFieldsType {
+    fn fields(&self) -> MessageWithOptionalFieldFieldsType<'static> {
         MESSAGE_WITH_OPTIONAL_FIELD_FIELDS
     }
 }
-impl picopb::encode::Encode for &MessageWithOptionalField {
-    fn encode(&self, buf: &mut picopb::encode::EncodeBuffer) -> std::io::Result<usize> {
+impl picopb::wiretypes::WireTyped for &MessageWithOptionalField {
+    fn wiretype(&self) -> picopb::wiretypes::WireType {
+        picopb::wiretypes::WireType::Len
+    }
+}
+impl picopb::encode::ToWire for &MessageWithOptionalField {
+    fn append<W: picopb::encode::EncodeSink>(&self, buf: &mut W) -> std::io::Result<usize> {
         let mut total_size = 0;
         if let Some(value_a) = &self.a {
             total_size += buf.encode(value_a.as_str(), self.fields().a.ordinal)?;
         }
-        total_size += buf.encode(self.b.as_slice(), self.fields().b.ordinal)?;
-        total_size += buf.encode(self.e.as_slice(), self.fields().e.ordinal)?;
+        for value_b in self.b.iter() {
+            total_size += buf.encode(value_b.as_str(), self.fields().b.ordinal)?;
+        }
+        for value_e in self.e.iter() {
+            total_size += buf.encode(*value_e, self.fields().e.ordinal)?;
+        }
         Ok(total_size)
     }
     fn precalculate_size(&self) -> usize {
@@ -55,16 +66,20 @@ impl picopb::encode::Encode for &MessageWithOptionalField {
         if let Some(value_a) = &self.a {
             total_size += value_a.as_str().precalculate_size();
         }
-        total_size += self.b.as_slice().precalculate_size();
-        total_size += self.e.as_slice().precalculate_size();
+        for value_b in self.b.iter() {
+            total_size += value_b.as_str().precalculate_field_size(self.fields().b.ordinal);
+        }
+        for value_e in self.e.iter() {
+            total_size += (*value_e).precalculate_field_size(self.fields().e.ordinal);
+        }
         total_size
     }
 }
-impl Randomize<MessageWithOptionalField> for MessageWithOptionalField {
-    fn randomized() -> MessageWithOptionalField {
+impl Randomize for MessageWithOptionalField {
+    fn randomized<R: picopb::randomizer::Rng + ?Sized>(rng: &mut R) -> Self {
         Self {
-            a: randomized::<Option<String>>(),
-            b: vec![], //randomized::<Vec<String>>(),
+            a: randomized(rng),
+            b: vec![], //randomized(rng),
             e: vec![1],
         }
     }
@@ -72,10 +87,10 @@ impl Randomize<MessageWithOptionalField> for MessageWithOptionalField {
 
 use std::io::Write;
 fn main() {
-    let message = MessageWithOptionalField::randomized();
+    let message = MessageWithOptionalField::randomized(&mut picopb::randomizer::seeded(0));
     let mut static_buffer: [u8; 512 * 1024] = [0; 512 * 1024];
     let mut buffer = picopb::encode::EncodeBuffer::from_static(&mut static_buffer);
-    (&message).encode(&mut buffer).expect("not error");
+    (&message).append(&mut buffer).expect("not error");
 
     eprintln!("b buffer: {:#04X?}", &buffer.as_slice());
     std::io::stdout().write_all(buffer.as_slice()).unwrap();
@@ -0,0 +1,597 @@
+use picopb::common::*;
+use picopb::encode::{EncodeSink, ToWire};
+use picopb::wiretypes::{WireType, WireTyped};
+use picopb::randomizer::{randomized, Randomize};
+use std::ops::Deref;
+#[allow(clippy::enum_variant_names)]
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub enum Mode {
+    #[default]
+    ModeUnspecified,
+    ModeFast,
+    ModeSlow,
+}
+impl TryFrom<i32> for Mode {
+    type Error = String;
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Mode::ModeUnspecified),
+            1 => Ok(Mode::ModeFast),
+            2 => Ok(Mode::ModeSlow),
+            _ => Err(format!("invalid ordinal value: {} for enum Mode", value)),
+        }
+    }
+}
+impl From<Mode> for i32 {
+    fn from(value: Mode) -> Self {
+        match value {
+            Mode::ModeUnspecified => 0,
+            Mode::ModeFast => 1,
+            Mode::ModeSlow => 2,
+        }
+    }
+}
+impl picopb::wiretypes::WireTyped for Mode {
+    fn wiretype(&self) -> WireType {
+        WireType::VarInt
+    }
+}
+impl picopb::encode::ToWire for Mode {
+    fn append<W: picopb::encode::EncodeSink>(&self, buf: &mut W) -> std::io::Result<usize> {
+        i32::from(*self).append(buf)
+    }
+    fn precalculate_size(&self) -> usize {
+        i32::from(*self).precalculate_size()
+    }
+}
+impl picopb::decode::FromWire for Mode {
+    fn wire_type() -> WireType {
+        WireType::VarInt
+    }
+    fn read(buf: &mut picopb::decode::DecodeBuffer<'_>) -> picopb::decode::Result<Self> {
+        let value = buf.read_varint()? as i64;
+        Self::try_from(value as i32).map_err(|_| picopb::decode::DecodeError::InvalidEnumValue(value))
+    }
+}
+impl picopb::text_format::ToText for Mode {
+    fn write_text<W: std::fmt::Write>(&self, out: &mut W) -> std::fmt::Result {
+        let name = match self {
+            Mode::ModeUnspecified => "MODE_UNSPECIFIED",
+            Mode::ModeFast => "MODE_FAST",
+            Mode::ModeSlow => "MODE_SLOW",
+        };
+        out.write_str(name)
+    }
+}
+impl picopb::text_format::FromText for Mode {
+    fn from_text(text: &picopb::text_format::TextValue) -> picopb::text_format::Result<Self> {
+        match text.as_literal()? {
+            "MODE_UNSPECIFIED" => Ok(Self::ModeUnspecified),
+            "MODE_FAST" => Ok(Self::ModeFast),
+            "MODE_SLOW" => Ok(Self::ModeSlow),
+            literal => {
+                let invalid = || picopb::text_format::TextFormatError::InvalidEnumValue(literal.to_string());
+                let value: i32 = picopb::text_format::FromText::from_text(text).map_err(|_| invalid())?;
+                Self::try_from(value).map_err(|_| invalid())
+            }
+        }
+    }
+}
+impl picopb::reflect::ReflectField for Mode {
+    fn to_value(&self) -> picopb::reflect::FieldValue<'_> {
+        picopb::reflect::FieldValue::Enum(i32::from(*self))
+    }
+    fn from_value(value: &picopb::reflect::FieldValue) -> picopb::reflect::Result<Self> {
+        match value {
+            picopb::reflect::FieldValue::Enum(value) => Self::try_from(*value).map_err(|_| picopb::reflect::ReflectError::InvalidEnumValue(*value)),
+            _ => Err(picopb::reflect::ReflectError::UnexpectedValue("enum")),
+        }
+    }
+}
+impl Randomize for Mode {
+    fn randomized<R: picopb::randomizer::Rng + ?Sized>(rng: &mut R) -> Self {
+        const VALUES: [Mode; 3] = [Mode::ModeUnspecified, Mode::ModeFast, Mode::ModeSlow];
+        VALUES[rng.gen_range(0..VALUES.len())]
+    }
+}
+#[derive(Debug)]
+pub struct Frame {
+    pub name: picopb::arraystring::ArrayString<picopb::arraystring::typenum::U8>,
+    pub label: Option<picopb::arraystring::ArrayString<picopb::arraystring::typenum::U4>>,
+    pub payload: [u8; 16],
+    pub samples: picopb::arrayvec::ArrayVec<Sample, 3>,
+    pub offsets: picopb::common::Packed<picopb::arrayvec::ArrayVec<i32, 4>>,
+    pub mode: Mode,
+    pub flag: Option<bool>,
+}
+impl Default for Frame {
+    fn default() -> Self {
+        Self {
+            name: Default::default(),
+            label: Default::default(),
+            payload: [0; 16],
+            samples: Default::default(),
+            offsets: picopb::common::Packed(Default::default()),
+            mode: Default::default(),
+            flag: Default::default(),
+        }
+    }
+}
+#[derive(Debug)]
+pub struct FrameFieldsType<'a> {
+    pub name: picopb::common::ConstMessageField<'a>,
+    pub label: picopb::common::ConstMessageField<'a>,
+    pub payload: picopb::common::ConstMessageField<'a>,
+    pub samples: picopb::common::ConstMessageField<'a>,
+    pub offsets: picopb::common::ConstMessageField<'a>,
+    pub mode: picopb::common::ConstMessageField<'a>,
+    pub flag: picopb::common::ConstMessageField<'a>,
+}
+const FRAME_FIELDS: FrameFieldsType = FrameFieldsType {
+    name: picopb::common::ConstMessageField {
+        qualifier: picopb::common::FieldQualifier::Required,
+        field_type: picopb::common::FieldType::String(8),
+        identifier: "name",
+        json_name: "name",
+        ordinal: picopb::common::Field(1),
+    },
+    label: picopb::common::ConstMessageField {
+        qualifier: picopb::common::FieldQualifier::Optional,
+        field_type: picopb::common::FieldType::String(4),
+        identifier: "label",
+        json_name: "label",
+        ordinal: picopb::common::Field(2),
+    },
+    payload: picopb::common::ConstMessageField {
+        qualifier: picopb::common::FieldQualifier::Required,
+        field_type: picopb::common::FieldType::Bytes(16),
+        identifier: "payload",
+        json_name: "payload",
+        ordinal: picopb::common::Field(3),
+    },
+    samples: picopb::common::ConstMessageField {
+        qualifier: picopb::common::FieldQualifier::Repeated(3),
+        field_type: picopb::common::FieldType::MessageType("Sample"),
+        identifier: "samples",
+        json_name: "samples",
+        ordinal: picopb::common::Field(4),
+    },
+    offsets: picopb::common::ConstMessageField {
+        qualifier: picopb::common::FieldQualifier::PackedRepeated(4),
+        field_type: picopb::common::FieldType::Int32,
+        identifier: "offsets",
+        json_name: "offsets",
+        ordinal: picopb::common::Field(5),
+    },
+    mode: picopb::common::ConstMessageField {
+        qualifier: picopb::common::FieldQualifier::Required,
+        field_type: picopb::common::FieldType::EnumType("Mode"),
+        identifier: "mode",
+        json_name: "mode",
+        ordinal: picopb::common::Field(6),
+    },
+    flag: picopb::common::ConstMessageField {
+        qualifier: picopb::common::FieldQualifier::Optional,
+        field_type: picopb::common::FieldType::Bool,
+        identifier: "flag",
+        json_name: "flag",
+        ordinal: picopb::common::Field(7),
+    },
+};
+impl Frame {
+    fn fields(&self) -> FrameFieldsType<'static> {
+        FRAME_FIELDS
+    }
+}
+impl Frame {
+    pub const MAX_ENCODED_LEN: usize = 146;
+}
+impl picopb::wiretypes::WireTyped for &Frame {
+    fn wiretype(&self) -> WireType {
+        WireType::Len
+    }
+}
+impl picopb::encode::ToWire for &Frame {
+    fn append<W: picopb::encode::EncodeSink>(&self, buf: &mut W) -> std::io::Result<usize> {
+        let mut total_size = 0;
+        total_size += buf.encode(self.name.as_str(), self.fields().name.ordinal)?;
+        if let Some(value_label) = &self.label {
+            total_size += buf.encode(value_label.as_str(), self.fields().label.ordinal)?;
+        }
+        total_size += buf.encode(self.payload.as_slice(), self.fields().payload.ordinal)?;
+        for value_samples in self.samples.iter() {
+            total_size += buf.encode_sub_message(value_samples, self.fields().samples.ordinal)?;
+        }
+        if !self.offsets.0.is_empty() {
+            total_size += buf.encode(picopb::common::Packed(self.offsets.0.as_slice()), self.fields().offsets.ordinal)?;
+        }
+        total_size += buf.encode(self.mode, self.fields().mode.ordinal)?;
+        if let Some(value_flag) = &self.flag {
+            total_size += buf.encode(*value_flag, self.fields().flag.ordinal)?;
+        }
+        Ok(total_size)
+    }
+    fn precalculate_size(&self) -> usize {
+        let mut total_size = 0;
+        total_size += self.name.as_str().precalculate_field_size(self.fields().name.ordinal);
+        if let Some(value_label) = &self.label {
+            total_size += value_label.as_str().precalculate_field_size(self.fields().label.ordinal);
+        }
+        total_size += self.payload.as_slice().precalculate_field_size(self.fields().payload.ordinal);
+        for value_samples in self.samples.iter() {
+            total_size += value_samples.precalculate_sub_message_size(self.fields().samples.ordinal);
+        }
+        if !self.offsets.0.is_empty() {
+            total_size += picopb::common::Packed(self.offsets.0.as_slice()).precalculate_field_size(self.fields().offsets.ordinal);
+        }
+        total_size += self.mode.precalculate_field_size(self.fields().mode.ordinal);
+        if let Some(value_flag) = &self.flag {
+            total_size += (*value_flag).precalculate_field_size(self.fields().flag.ordinal);
+        }
+        total_size
+    }
+}
+impl picopb::decode::DecodeMessage for Frame {
+    fn decode_from(buf: &mut picopb::decode::DecodeBuffer<'_>) -> picopb::decode::Result<Self> {
+        let mut message = Self::default();
+        let mut has_name = false;
+        let mut has_payload = false;
+        let mut has_mode = false;
+        while !buf.is_empty() {
+            let (field, wire_type) = buf.read_tag()?;
+            match field.0 {
+                1 => {
+                    message.name = picopb::decode::read_field(buf, field, wire_type)?;
+                    has_name = true;
+                }
+                2 => {
+                    message.label = Some(picopb::decode::read_field(buf, field, wire_type)?);
+                }
+                3 => {
+                    message.payload = picopb::decode::read_field(buf, field, wire_type)?;
+                    has_payload = true;
+                }
+                4 => {
+                    picopb::decode::read_repeated(buf, field, wire_type, |value| {
+                        picopb::decode::push_bounded(&mut message.samples, value)
+                    })?;
+                }
+                5 => {
+                    picopb::decode::read_repeated(buf, field, wire_type, |value| {
+                        picopb::decode::push_bounded(&mut message.offsets.0, value)
+                    })?;
+                }
+                6 => {
+                    message.mode = picopb::decode::read_field(buf, field, wire_type)?;
+                    has_mode = true;
+                }
+                7 => {
+                    message.flag = Some(picopb::decode::read_field(buf, field, wire_type)?);
+                }
+                _ => buf.skip_unknown(field, wire_type)?,
+            }
+        }
+        if !has_name {
+            return Err(picopb::decode::DecodeError::MissingRequiredField("name"));
+        }
+        if !has_payload {
+            return Err(picopb::decode::DecodeError::MissingRequiredField("payload"));
+        }
+        if !has_mode {
+            return Err(picopb::decode::DecodeError::MissingRequiredField("mode"));
+        }
+        Ok(message)
+    }
+}
+impl picopb::decode::FromWire for Frame {
+    fn wire_type() -> WireType {
+        WireType::Len
+    }
+    fn read(buf: &mut picopb::decode::DecodeBuffer<'_>) -> picopb::decode::Result<Self> {
+        <Self as picopb::decode::DecodeMessage>::decode_from(&mut buf.read_sub_buffer()?)
+    }
+}
+impl Randomize for Frame {
+    fn randomized<R: picopb::randomizer::Rng + ?Sized>(rng: &mut R) -> Self {
+        Self {
+            name: randomized(rng),
+            label: randomized(rng),
+            payload: randomized(rng),
+            samples: randomized(rng),
+            offsets: randomized(rng),
+            mode: randomized(rng),
+            flag: randomized(rng),
+        }
+    }
+}
+impl picopb::text_format::ToText for Frame {
+    fn write_text<W: std::fmt::Write>(&self, out: &mut W) -> std::fmt::Result {
+        let mut writer = picopb::text_format::FieldWriter::new(out);
+        writer.field(self.fields().name.identifier, &self.name)?;
+        if let Some(value_label) = &self.label {
+            writer.field(self.fields().label.identifier, value_label)?;
+        }
+        writer.field(self.fields().payload.identifier, &self.payload)?;
+        for value_samples in self.samples.iter() {
+            writer.message(self.fields().samples.identifier, value_samples)?;
+        }
+        for value_offsets in self.offsets.0.iter() {
+            writer.field(self.fields().offsets.identifier, value_offsets)?;
+        }
+        writer.field(self.fields().mode.identifier, &self.mode)?;
+        if let Some(value_flag) = &self.flag {
+            writer.field(self.fields().flag.identifier, value_flag)?;
+        }
+        Ok(())
+    }
+}
+impl picopb::text_format::FromText for Frame {
+    fn from_text(text: &picopb::text_format::TextValue) -> picopb::text_format::Result<Self> {
+        let mut message = Self::default();
+        let mut has_name = false;
+        let mut has_payload = false;
+        let mut has_mode = false;
+        for (name, value) in text.as_message()? {
+            match name.as_str() {
+                "name" => {
+                    message.name = picopb::text_format::FromText::from_text(value)?;
+                    has_name = true;
+                }
+                "label" => {
+                    message.label = Some(picopb::text_format::FromText::from_text(value)?);
+                }
+                "payload" => {
+                    message.payload = picopb::text_format::FromText::from_text(value)?;
+                    has_payload = true;
+                }
+                "samples" => {
+                    picopb::text_format::read_repeated(value, |value| {
+                        picopb::text_format::push_bounded(&mut message.samples, value)
+                    })?;
+                }
+                "offsets" => {
+                    picopb::text_format::read_repeated(value, |value| {
+                        picopb::text_format::push_bounded(&mut message.offsets.0, value)
+                    })?;
+                }
+                "mode" => {
+                    message.mode = picopb::text_format::FromText::from_text(value)?;
+                    has_mode = true;
+                }
+                "flag" => {
+                    message.flag = Some(picopb::text_format::FromText::from_text(value)?);
+                }
+                _ => return Err(picopb::text_format::TextFormatError::UnknownField(name.clone())),
+            }
+        }
+        if !has_name {
+            return Err(picopb::text_format::TextFormatError::MissingRequiredField("name"));
+        }
+        if !has_payload {
+            return Err(picopb::text_format::TextFormatError::MissingRequiredField("payload"));
+        }
+        if !has_mode {
+            return Err(picopb::text_format::TextFormatError::MissingRequiredField("mode"));
+        }
+        Ok(message)
+    }
+}
+impl Frame {
+    pub const DESCRIPTOR: picopb::reflect::MessageDescriptor = picopb::reflect::MessageDescriptor {
+        name: "Frame",
+        fields: &[
+            FRAME_FIELDS.name,
+            FRAME_FIELDS.label,
+            FRAME_FIELDS.payload,
+            FRAME_FIELDS.samples,
+            FRAME_FIELDS.offsets,
+            FRAME_FIELDS.mode,
+            FRAME_FIELDS.flag,
+        ],
+    };
+}
+impl picopb::reflect::Reflect for Frame {
+    fn descriptor(&self) -> &'static picopb::reflect::MessageDescriptor {
+        &Self::DESCRIPTOR
+    }
+    fn get(&self, number: u32) -> Option<Vec<picopb::reflect::FieldValue<'_>>> {
+        match number {
+            1 => Some(vec![picopb::reflect::ReflectField::to_value(&self.name)]),
+            2 => Some(self.label.iter().map(picopb::reflect::ReflectField::to_value).collect()),
+            3 => Some(vec![picopb::reflect::ReflectField::to_value(&self.payload)]),
+            4 => Some(self.samples.iter().map(picopb::reflect::ReflectField::to_value).collect()),
+            5 => Some(self.offsets.0.iter().map(picopb::reflect::ReflectField::to_value).collect()),
+            6 => Some(vec![picopb::reflect::ReflectField::to_value(&self.mode)]),
+            7 => Some(self.flag.iter().map(picopb::reflect::ReflectField::to_value).collect()),
+            _ => None,
+        }
+    }
+    fn set(&mut self, number: u32, values: &[picopb::reflect::FieldValue]) -> picopb::reflect::Result<()> {
+        match number {
+            1 => picopb::reflect::set_required(&mut self.name, values),
+            2 => picopb::reflect::set_optional(&mut self.label, values),
+            3 => picopb::reflect::set_required(&mut self.payload, values),
+            4 => picopb::reflect::set_bounded(&mut self.samples, values),
+            5 => picopb::reflect::set_bounded(&mut self.offsets.0, values),
+            6 => picopb::reflect::set_required(&mut self.mode, values),
+            7 => picopb::reflect::set_optional(&mut self.flag, values),
+            _ => Err(picopb::reflect::ReflectError::UnknownField(number.to_string())),
+        }
+    }
+}
+impl picopb::reflect::ReflectField for Frame {
+    fn to_value(&self) -> picopb::reflect::FieldValue<'_> {
+        picopb::reflect::FieldValue::Message(self)
+    }
+    fn from_value(value: &picopb::reflect::FieldValue) -> picopb::reflect::Result<Self> {
+        let mut message = Self::default();
+        picopb::reflect::copy_fields(&mut message, value.as_message()?)?;
+        Ok(message)
+    }
+}
+#[derive(Default, Debug)]
+pub struct Sample {
+    pub index: u32,
+    pub mode: Option<Mode>,
+}
+#[derive(Debug)]
+pub struct SampleFieldsType<'a> {
+    pub index: picopb::common::ConstMessageField<'a>,
+    pub mode: picopb::common::ConstMessageField<'a>,
+}
+const SAMPLE_FIELDS: SampleFieldsType = SampleFieldsType {
+    index: picopb::common::ConstMessageField {
+        qualifier: picopb::common::FieldQualifier::Required,
+        field_type: picopb::common::FieldType::Uint32,
+        identifier: "index",
+        json_name: "index",
+        ordinal: picopb::common::Field(1),
+    },
+    mode: picopb::common::ConstMessageField {
+        qualifier: picopb::common::FieldQualifier::Optional,
+        field_type: picopb::common::FieldType::EnumType("Mode"),
+        identifier: "mode",
+        json_name: "mode",
+        ordinal: picopb::common::Field(2),
+    },
+};
+impl Sample {
+    fn fields(&self) -> SampleFieldsType<'static> {
+        SAMPLE_FIELDS
+    }
+}
+impl Sample {
+    pub const MAX_ENCODED_LEN: usize = 17;
+}
+impl picopb::wiretypes::WireTyped for &Sample {
+    fn wiretype(&self) -> WireType {
+        WireType::Len
+    }
+}
+impl picopb::encode::ToWire for &Sample {
+    fn append<W: picopb::encode::EncodeSink>(&self, buf: &mut W) -> std::io::Result<usize> {
+        let mut total_size = 0;
+        total_size += buf.encode(self.index, self.fields().index.ordinal)?;
+        if let Some(value_mode) = &self.mode {
+            total_size += buf.encode(*value_mode, self.fields().mode.ordinal)?;
+        }
+        Ok(total_size)
+    }
+    fn precalculate_size(&self) -> usize {
+        let mut total_size = 0;
+        total_size += self.index.precalculate_field_size(self.fields().index.ordinal);
+        if let Some(value_mode) = &self.mode {
+            total_size += (*value_mode).precalculate_field_size(self.fields().mode.ordinal);
+        }
+        total_size
+    }
+}
+impl picopb::decode::DecodeMessage for Sample {
+    fn decode_from(buf: &mut picopb::decode::DecodeBuffer<'_>) -> picopb::decode::Result<Self> {
+        let mut message = Self::default();
+        let mut has_index = false;
+        while !buf.is_empty() {
+            let (field, wire_type) = buf.read_tag()?;
+            match field.0 {
+                1 => {
+                    message.index = picopb::decode::read_field(buf, field, wire_type)?;
+                    has_index = true;
+                }
+                2 => {
+                    message.mode = Some(picopb::decode::read_field(buf, field, wire_type)?);
+                }
+                _ => buf.skip_unknown(field, wire_type)?,
+            }
+        }
+        if !has_index {
+            return Err(picopb::decode::DecodeError::MissingRequiredField("index"));
+        }
+        Ok(message)
+    }
+}
+impl picopb::decode::FromWire for Sample {
+    fn wire_type() -> WireType {
+        WireType::Len
+    }
+    fn read(buf: &mut picopb::decode::DecodeBuffer<'_>) -> picopb::decode::Result<Self> {
+        <Self as picopb::decode::DecodeMessage>::decode_from(&mut buf.read_sub_buffer()?)
+    }
+}
+impl Randomize for Sample {
+    fn randomized<R: picopb::randomizer::Rng + ?Sized>(rng: &mut R) -> Self {
+        Self {
+            index: randomized(rng),
+            mode: randomized(rng),
+        }
+    }
+}
+impl picopb::text_format::ToText for Sample {
+    fn write_text<W: std::fmt::Write>(&self, out: &mut W) -> std::fmt::Result {
+        let mut writer = picopb::text_format::FieldWriter::new(out);
+        writer.field(self.fields().index.identifier, &self.index)?;
+        if let Some(value_mode) = &self.mode {
+            writer.field(self.fields().mode.identifier, value_mode)?;
+        }
+        Ok(())
+    }
+}
+impl picopb::text_format::FromText for Sample {
+    fn from_text(text: &picopb::text_format::TextValue) -> picopb::text_format::Result<Self> {
+        let mut message = Self::default();
+        let mut has_index = false;
+        for (name, value) in text.as_message()? {
+            match name.as_str() {
+                "index" => {
+                    message.index = picopb::text_format::FromText::from_text(value)?;
+                    has_index = true;
+                }
+                "mode" => {
+                    message.mode = Some(picopb::text_format::FromText::from_text(value)?);
+                }
+                _ => return Err(picopb::text_format::TextFormatError::UnknownField(name.clone())),
+            }
+        }
+        if !has_index {
+            return Err(picopb::text_format::TextFormatError::MissingRequiredField("index"));
+        }
+        Ok(message)
+    }
+}
+impl Sample {
+    pub const DESCRIPTOR: picopb::reflect::MessageDescriptor = picopb::reflect::MessageDescriptor {
+        name: "Sample",
+        fields: &[
+            SAMPLE_FIELDS.index,
+            SAMPLE_FIELDS.mode,
+        ],
+    };
+}
+impl picopb::reflect::Reflect for Sample {
+    fn descriptor(&self) -> &'static picopb::reflect::MessageDescriptor {
+        &Self::DESCRIPTOR
+    }
+    fn get(&self, number: u32) -> Option<Vec<picopb::reflect::FieldValue<'_>>> {
+        match number {
+            1 => Some(vec![picopb::reflect::ReflectField::to_value(&self.index)]),
+            2 => Some(self.mode.iter().map(picopb::reflect::ReflectField::to_value).collect()),
+            _ => None,
+        }
+    }
+    fn set(&mut self, number: u32, values: &[picopb::reflect::FieldValue]) -> picopb::reflect::Result<()> {
+        match number {
+            1 => picopb::reflect::set_required(&mut self.index, values),
+            2 => picopb::reflect::set_optional(&mut self.mode, values),
+            _ => Err(picopb::reflect::ReflectError::UnknownField(number.to_string())),
+        }
+    }
+}
+impl picopb::reflect::ReflectField for Sample {
+    fn to_value(&self) -> picopb::reflect::FieldValue<'_> {
+        picopb::reflect::FieldValue::Message(self)
+    }
+    fn from_value(value: &picopb::reflect::FieldValue) -> picopb::reflect::Result<Self> {
+        let mut message = Self::default();
+        picopb::reflect::copy_fields(&mut message, value.as_message()?)?;
+        Ok(message)
+    }
+}
@@ -0,0 +1,2579 @@
+use picopb::common::*;
+use picopb::encode::{EncodeSink, ToWire};
+use picopb::wiretypes::{WireType, WireTyped};
+use picopb::randomizer::{randomized, Randomize};
+use std::ops::Deref;
+#[derive(Default, Debug)]
+pub struct CodeGeneratorRequest {
+    pub file_to_generate: Vec<String>,
+    pub parameter: Option<String>,
+    pub proto_file: Vec<FileDescriptorProto>,
+}
+#[derive(Debug)]
+pub struct CodeGeneratorRequestFieldsType<'a> {
+    pub file_to_generate: picopb::common::ConstMessageField<'a>,
+    pub parameter: picopb::common::ConstMessageField<'a>,
+    pub proto_file: picopb::common::ConstMessageField<'a>,
+}
+const CODE_GENERATOR_REQUEST_FIELDS: CodeGeneratorRequestFieldsType = CodeGeneratorRequestFieldsType {
+    file_to_generate: picopb::common::ConstMessageField {
+        qualifier: picopb::common::FieldQualifier::RepeatedUnbounded,
+        field_type: picopb::common::FieldType::UnboundedString,
+        identifier: "file_to_generate",
+        json_name: "fileToGenerate",
+        ordinal: picopb::common::Field(1),
+    },
+    parameter: picopb::common::ConstMessageField {
+        qualifier: picopb::common::FieldQualifier::Optional,
+        field_type: picopb::common::FieldType::UnboundedString,
+        identifier: "parameter",
+        json_name: "parameter",
+        ordinal: picopb::common::Field(2),
+    },
+    proto_file: picopb::common::ConstMessageField {
+        qualifier: picopb::common::FieldQualifier::RepeatedUnbounded,
+        field_type: picopb::common::FieldType::MessageType("FileDescriptorProto"),
+        identifier: "proto_file",
+        json_name: "protoFile",
+        ordinal: picopb::common::Field(15),
+    },
+};
+impl CodeGeneratorRequest {
+    fn fields(&self) -> CodeGeneratorRequestFieldsType<'static> {
+        CODE_GENERATOR_REQUEST_FIELDS
+    }
+}
+// CodeGeneratorRequest has no MAX_ENCODED_LEN, unbounded: CodeGeneratorRequest.file_to_generate
+impl picopb::wiretypes::WireTyped for &CodeGeneratorRequest {
+    fn wiretype(&self) -> WireType {
+        WireType::Len
+    }
+}
+impl picopb::encode::ToWire for &CodeGeneratorRequest {
+    fn append<W: picopb::encode::EncodeSink>(&self, buf: &mut W) -> std::io::Result<usize> {
+        let mut total_size = 0;
+        for value_file_to_generate in self.file_to_generate.iter() {
+            total_size += buf.encode(value_file_to_generate.as_str(), self.fields().file_to_generate.ordinal)?;
+        }
+        if let Some(value_parameter) = &self.parameter {
+            total_size += buf.encode(value_parameter.as_str(), self.fields().parameter.ordinal)?;
+        }
+        for value_proto_file in self.proto_file.iter() {
+            total_size += buf.encode_sub_message(value_proto_file, self.fields().proto_file.ordinal)?;
+        }
+        Ok(total_size)
+    }
+    fn precalculate_size(&self) -> usize {
+        let mut total_size = 0;
+        for value_file_to_generate in self.file_to_generate.iter() {
+            total_size += value_file_to_generate.as_str().precalculate_field_size(self.fields().file_to_generate.ordinal);
+        }
+        if let Some(value_parameter) = &self.parameter {
+            total_size += value_parameter.as_str().precalculate_field_size(self.fields().parameter.ordinal);
+        }
+        for value_proto_file in self.proto_file.iter() {
+            total_size += value_proto_file.precalculate_sub_message_size(self.fields().proto_file.ordinal);
+        }
+        total_size
+    }
+}
+impl picopb::decode::DecodeMessage for CodeGeneratorRequest {
+    fn decode_from(buf: &mut picopb::decode::DecodeBuffer<'_>) -> picopb::decode::Result<Self> {
+        let mut message = Self::default();
+        while !buf.is_empty() {
+            let (field, wire_type) = buf.read_tag()?;
+            match field.0 {
+                1 => {
+                    picopb::decode::read_repeated(buf, field, wire_type, |value| {
+                        message.file_to_generate.push(value);
+                        Ok(())
+                    })?;
+                }
+                2 => {
+                    message.parameter = Some(picopb::decode::read_field(buf, field, wire_type)?);
+                }
+                15 => {
+                    picopb::decode::read_repeated(buf, field, wire_type, |value| {
+                        message.proto_file.push(value);
+                        Ok(())
+                    })?;
+                }
+                _ => buf.skip_unknown(field, wire_type)?,
+            }
+        }
+        Ok(message)
+    }
+}
+impl picopb::decode::FromWire for CodeGeneratorRequest {
+    fn wire_type() -> WireType {
+        WireType::Len
+    }
+    fn read(buf: &mut picopb::decode::DecodeBuffer<'_>) -> picopb::decode::Result<Self> {
+        <Self as picopb::decode::DecodeMessage>::decode_from(&mut buf.read_sub_buffer()?)
+    }
+}
+impl Randomize for CodeGeneratorRequest {
+    fn randomized<R: picopb::randomizer::Rng + ?Sized>(rng: &mut R) -> Self {
+        Self {
+            file_to_generate: randomized(rng),
+            parameter: randomized(rng),
+            proto_file: randomized(rng),
+        }
+    }
+}
+impl picopb::text_format::ToText for CodeGeneratorRequest {
+    fn write_text<W: std::fmt::Write>(&self, out: &mut W) -> std::fmt::Result {
+        let mut writer = picopb::text_format::FieldWriter::new(out);
+        for value_file_to_generate in self.file_to_generate.iter() {
+            writer.field(self.fields().file_to_generate.identifier, value_file_to_generate)?;
+        }
+        if let Some(value_parameter) = &self.parameter {
+            writer.field(self.fields().parameter.identifier, value_parameter)?;
+        }
+        for value_proto_file in self.proto_file.iter() {
+            writer.message(self.fields().proto_file.identifier, value_proto_file)?;
+        }
+        Ok(())
+    }
+}
+impl picopb::text_format::FromText for CodeGeneratorRequest {
+    fn from_text(text: &picopb::text_format::TextValue) -> picopb::text_format::Result<Self> {
+        let mut message = Self::default();
+        for (name, value) in text.as_message()? {
+            match name.as_str() {
+                "file_to_generate" => {
+                    picopb::text_format::read_repeated(value, |value| {
+                        message.file_to_generate.push(value);
+                        Ok(())
+                    })?;
+                }
+                "parameter" => {
+                    message.parameter = Some(picopb::text_format::FromText::from_text(value)?);
+                }
+                "proto_file" => {
+                    picopb::text_format::read_repeated(value, |value| {
+                        message.proto_file.push(value);
+                        Ok(())
+                    })?;
+                }
+                _ => return Err(picopb::text_format::TextFormatError::UnknownField(name.clone())),
+            }
+        }
+        Ok(message)
+    }
+}
+impl CodeGeneratorRequest {
+    pub const DESCRIPTOR: picopb::reflect::MessageDescriptor = picopb::reflect::MessageDescriptor {
+        name: "CodeGeneratorRequest",
+        fields: &[
+            CODE_GENERATOR_REQUEST_FIELDS.file_to_generate,
+            CODE_GENERATOR_REQUEST_FIELDS.parameter,
+            CODE_GENERATOR_REQUEST_FIELDS.proto_file,
+        ],
+    };
+}
+impl picopb::reflect::Reflect for CodeGeneratorRequest {
+    fn descriptor(&self) -> &'static picopb::reflect::MessageDescriptor {
+        &Self::DESCRIPTOR
+    }
+    fn get(&self, number: u32) -> Option<Vec<picopb::reflect::FieldValue<'_>>> {
+        match number {
+            1 => Some(self.file_to_generate.iter().map(picopb::reflect::ReflectField::to_value).collect()),
+            2 => Some(self.parameter.iter().map(picopb::reflect::ReflectField::to_value).collect()),
+            15 => Some(self.proto_file.iter().map(picopb::reflect::ReflectField::to_value).collect()),
+            _ => None,
+        }
+    }
+    fn set(&mut self, number: u32, values: &[picopb::reflect::FieldValue]) -> picopb::reflect::Result<()> {
+        match number {
+            1 => picopb::reflect::set_repeated(&mut self.file_to_generate, values),
+            2 => picopb::reflect::set_optional(&mut self.parameter, values),
+            15 => picopb::reflect::set_repeated(&mut self.proto_file, values),
+            _ => Err(picopb::reflect::ReflectError::UnknownField(number.to_string())),
+        }
+    }
+}
+impl picopb::reflect::ReflectField for CodeGeneratorRequest {
+    fn to_value(&self) -> picopb::reflect::FieldValue<'_> {
+        picopb::reflect::FieldValue::Message(self)
+    }
+    fn from_value(value: &picopb::reflect::FieldValue) -> picopb::reflect::Result<Self> {
+        let mut message = Self::default();
+        picopb::reflect::copy_fields(&mut message, value.as_message()?)?;
+        Ok(message)
+    }
+}
+#[derive(Default, Debug)]
+pub struct CodeGeneratorResponse {
+    pub error: Option<String>,
+    pub supported_features: Option<u64>,
+    pub file: Vec<CodeGeneratorResponseFile>,
+}
+#[derive(Debug)]
+pub struct CodeGeneratorResponseFieldsType<'a> {
+    pub error: picopb::common::ConstMessageField<'a>,
+    pub supported_features: picopb::common::ConstMessageField<'a>,
+    pub file: picopb::common::ConstMessageField<'a>,
+}
+const CODE_GENERATOR_RESPONSE_FIELDS: CodeGeneratorResponseFieldsType = CodeGeneratorResponseFieldsType {
+    error: picopb::common::ConstMessageField {
+        qualifier: picopb::common::FieldQualifier::Optional,
+        field_type: picopb::common::FieldType::UnboundedString,
+        identifier: "error",
+        json_name: "error",
+        ordinal: picopb::common::Field(1),
+    },
+    supported_features: picopb::common::ConstMessageField {
+        qualifier: picopb::common::FieldQualifier::Optional,
+        field_type: picopb::common::FieldType::Uint64,
+        identifier: "supported_features",
+        json_name: "supportedFeatures",
+        ordinal: picopb::common::Field(2),
+    },
+    file: picopb::common::ConstMessageField {
+        qualifier: picopb::common::FieldQualifier::RepeatedUnbounded,
+        field_type: picopb::common::FieldType::MessageType("CodeGeneratorResponseFile"),
+        identifier: "file",
+        json_name: "file",
+        ordinal: picopb::common::Field(15),
+    },
+};
+impl CodeGeneratorResponse {
+    fn fields(&self) -> CodeGeneratorResponseFieldsType<'static> {
+        CODE_GENERATOR_RESPONSE_FIELDS
+    }
+}
+// CodeGeneratorResponse has no MAX_ENCODED_LEN, unbounded: CodeGeneratorResponse.error
+impl picopb::wiretypes::WireTyped for &CodeGeneratorResponse {
+    fn wiretype(&self) -> WireType {
+        WireType::Len
+    }
+}
+impl picopb::encode::ToWire for &CodeGeneratorResponse {
+    fn append<W: picopb::encode::EncodeSink>(&self, buf: &mut W) -> std::io::Result<usize> {
+        let mut total_size = 0;
+        if let Some(value_error) = &self.error {
+            total_size += buf.encode(value_error.as_str(), self.fields().error.ordinal)?;
+        }
+        if let Some(value_supported_features) = &self.supported_features {
+            total_size += buf.encode(*value_supported_features, self.fields().supported_features.ordinal)?;
+        }
+        for value_file in self.file.iter() {
+            total_size += buf.encode_sub_message(value_file, self.fields().file.ordinal)?;
+        }
+        Ok(total_size)
+    }
+    fn precalculate_size(&self) -> usize {
+        let mut total_size = 0;
+        if let Some(value_error) = &self.error {
+            total_size += value_error.as_str().precalculate_field_size(self.fields().error.ordinal);
+        }
+        if let Some(value_supported_features) = &self.supported_features {
+            total_size += (*value_supported_features).precalculate_field_size(self.fields().supported_features.ordinal);
+        }
+        for value_file in self.file.iter() {
+            total_size += value_file.precalculate_sub_message_size(self.fields().file.ordinal);
+        }
+        total_size
+    }
+}
+impl picopb::decode::DecodeMessage for CodeGeneratorResponse {
+    fn decode_from(buf: &mut picopb::decode::DecodeBuffer<'_>) -> picopb::decode::Result<Self> {
+        let mut message = Self::default();
+        while !buf.is_empty() {
+            let (field, wire_type) = buf.read_tag()?;
+            match field.0 {
+                1 => {
+                    message.error = Some(picopb::decode::read_field(buf, field, wire_type)?);
+                }
+                2 => {
+                    message.supported_features = Some(picopb::decode::read_field(buf, field, wire_type)?);
+                }
+                15 => {
+                    picopb::decode::read_repeated(buf, field, wire_type, |value| {
+                        message.file.push(value);
+                        Ok(())
+                    })?;
+                }
+                _ => buf.skip_unknown(field, wire_type)?,
+            }
+        }
+        Ok(message)
+    }
+}
+impl picopb::decode::FromWire for CodeGeneratorResponse {
+    fn wire_type() -> WireType {
+        WireType::Len
+    }
+    fn read(buf: &mut picopb::decode::DecodeBuffer<'_>) -> picopb::decode::Result<Self> {
+        <Self as picopb::decode::DecodeMessage>::decode_from(&mut buf.read_sub_buffer()?)
+    }
+}
+impl Randomize for CodeGeneratorResponse {
+    fn randomized<R: picopb::randomizer::Rng + ?Sized>(rng: &mut R) -> Self {
+        Self {
+            error: randomized(rng),
+            supported_features: randomized(rng),
+            file: randomized(rng),
+        }
+    }
+}
+impl picopb::text_format::ToText for CodeGeneratorResponse {
+    fn write_text<W: std::fmt::Write>(&self, out: &mut W) -> std::fmt::Result {
+        let mut writer = picopb::text_format::FieldWriter::new(out);
+        if let Some(value_error) = &self.error {
+            writer.field(self.fields().error.identifier, value_error)?;
+        }
+        if let Some(value_supported_features) = &self.supported_features {
+            writer.field(self.fields().supported_features.identifier, value_supported_features)?;
+        }
+        for value_file in self.file.iter() {
+            writer.message(self.fields().file.identifier, value_file)?;
+        }
+        Ok(())
+    }
+}
+impl picopb::text_format::FromText for CodeGeneratorResponse {
+    fn from_text(text: &picopb::text_format::TextValue) -> picopb::text_format::Result<Self> {
+        let mut message = Self::default();
+        for (name, value) in text.as_message()? {
+            match name.as_str() {
+                "error" => {
+                    message.error = Some(picopb::text_format::FromText::from_text(value)?);
+                }
+                "supported_features" => {
+                    message.supported_features = Some(picopb::text_format::FromText::from_text(value)?);
+                }
+                "file" => {
+                    picopb::text_format::read_repeated(value, |value| {
+                        message.file.push(value);
+                        Ok(())
+                    })?;
+                }
+                _ => return Err(picopb::text_format::TextFormatError::UnknownField(name.clone())),
+            }
+        }
+        Ok(message)
+    }
+}
+impl CodeGeneratorResponse {
+    pub const DESCRIPTOR: picopb::reflect::MessageDescriptor = picopb::reflect::MessageDescriptor {
+        name: "CodeGeneratorResponse",
+        fields: &[
+            CODE_GENERATOR_RESPONSE_FIELDS.error,
+            CODE_GENERATOR_RESPONSE_FIELDS.supported_features,
+            CODE_GENERATOR_RESPONSE_FIELDS.file,
+        ],
+    };
+}
+impl picopb::reflect::Reflect for CodeGeneratorResponse {
+    fn descriptor(&self) -> &'static picopb::reflect::MessageDescriptor {
+        &Self::DESCRIPTOR
+    }
+    fn get(&self, number: u32) -> Option<Vec<picopb::reflect::FieldValue<'_>>> {
+        match number {
+            1 => Some(self.error.iter().map(picopb::reflect::ReflectField::to_value).collect()),
+            2 => Some(self.supported_features.iter().map(picopb::reflect::ReflectField::to_value).collect()),
+            15 => Some(self.file.iter().map(picopb::reflect::ReflectField::to_value).collect()),
+            _ => None,
+        }
+    }
+    fn set(&mut self, number: u32, values: &[picopb::reflect::FieldValue]) -> picopb::reflect::Result<()> {
+        match number {
+            1 => picopb::reflect::set_optional(&mut self.error, values),
+            2 => picopb::reflect::set_optional(&mut self.supported_features, values),
+            15 => picopb::reflect::set_repeated(&mut self.file, values),
+            _ => Err(picopb::reflect::ReflectError::UnknownField(number.to_string())),
+        }
+    }
+}
+impl picopb::reflect::ReflectField for CodeGeneratorResponse {
+    fn to_value(&self) -> picopb::reflect::FieldValue<'_> {
+        picopb::reflect::FieldValue::Message(self)
+    }
+    fn from_value(value: &picopb::reflect::FieldValue) -> picopb::reflect::Result<Self> {
+        let mut message = Self::default();
+        picopb::reflect::copy_fields(&mut message, value.as_message()?)?;
+        Ok(message)
+    }
+}
+#[derive(Default, Debug)]
+pub struct CodeGeneratorResponseFile {
+    pub name: Option<String>,
+    pub content: Option<String>,
+}
+#[derive(Debug)]
+pub struct CodeGeneratorResponseFileFieldsType<'a> {
+    pub name: picopb::common::ConstMessageField<'a>,
+    pub content: picopb::common::ConstMessageField<'a>,
+}
+const CODE_GENERATOR_RESPONSE_FILE_FIELDS: CodeGeneratorResponseFileFieldsType = CodeGeneratorResponseFileFieldsType {
+    name: picopb::common::ConstMessageField {
+        qualifier: picopb::common::FieldQualifier::Optional,
+        field_type: picopb::common::FieldType::UnboundedString,
+        identifier: "name",
+        json_name: "name",
+        ordinal: picopb::common::Field(1),
+    },
+    content: picopb::common::ConstMessageField {
+        qualifier: picopb::common::FieldQualifier::Optional,
+        field_type: picopb::common::FieldType::UnboundedString,
+        identifier: "content",
+        json_name: "content",
+        ordinal: picopb::common::Field(15),
+    },
+};
+impl CodeGeneratorResponseFile {
+    fn fields(&self) -> CodeGeneratorResponseFileFieldsType<'static> {
+        CODE_GENERATOR_RESPONSE_FILE_FIELDS
+    }
+}
+// CodeGeneratorResponseFile has no MAX_ENCODED_LEN, unbounded: CodeGeneratorResponseFile.name
+impl picopb::wiretypes::WireTyped for &CodeGeneratorResponseFile {
+    fn wiretype(&self) -> WireType {
+        WireType::Len
+    }
+}
+impl picopb::encode::ToWire for &CodeGeneratorResponseFile {
+    fn append<W: picopb::encode::EncodeSink>(&self, buf: &mut W) -> std::io::Result<usize> {
+        let mut total_size = 0;
+        if let Some(value_name) = &self.name {
+            total_size += buf.encode(value_name.as_str(), self.fields().name.ordinal)?;
+        }
+        if let Some(value_content) = &self.content {
+            total_size += buf.encode(value_content.as_str(), self.fields().content.ordinal)?;
+        }
+        Ok(total_size)
+    }
+    fn precalculate_size(&self) -> usize {
+        let mut total_size = 0;
+        if let Some(value_name) = &self.name {
+            total_size += value_name.as_str().precalculate_field_size(self.fields().name.ordinal);
+        }
+        if let Some(value_content) = &self.content {
+            total_size += value_content.as_str().precalculate_field_size(self.fields().content.ordinal);
+        }
+        total_size
+    }
+}
+impl picopb::decode::DecodeMessage for CodeGeneratorResponseFile {
+    fn decode_from(buf: &mut picopb::decode::DecodeBuffer<'_>) -> picopb::decode::Result<Self> {
+        let mut message = Self::default();
+        while !buf.is_empty() {
+            let (field, wire_type) = buf.read_tag()?;
+            match field.0 {
+                1 => {
+                    message.name = Some(picopb::decode::read_field(buf, field, wire_type)?);
+                }
+                15 => {
+                    message.content = Some(picopb::decode::read_field(buf, field, wire_type)?);
+                }
+                _ => buf.skip_unknown(field, wire_type)?,
+            }
+        }
+        Ok(message)
+    }
+}
+impl picopb::decode::FromWire for CodeGeneratorResponseFile {
+    fn wire_type() -> WireType {
+        WireType::Len
+    }
+    fn read(buf: &mut picopb::decode::DecodeBuffer<'_>) -> picopb::decode::Result<Self> {
+        <Self as picopb::decode::DecodeMessage>::decode_from(&mut buf.read_sub_buffer()?)
+    }
+}
+impl Randomize for CodeGeneratorResponseFile {
+    fn randomized<R: picopb::randomizer::Rng + ?Sized>(rng: &mut R) -> Self {
+        Self {
+            name: randomized(rng),
+            content: randomized(rng),
+        }
+    }
+}
+impl picopb::text_format::ToText for CodeGeneratorResponseFile {
+    fn write_text<W: std::fmt::Write>(&self, out: &mut W) -> std::fmt::Result {
+        let mut writer = picopb::text_format::FieldWriter::new(out);
+        if let Some(value_name) = &self.name {
+            writer.field(self.fields().name.identifier, value_name)?;
+        }
+        if let Some(value_content) = &self.content {
+            writer.field(self.fields().content.identifier, value_content)?;
+        }
+        Ok(())
+    }
+}
+impl picopb::text_format::FromText for CodeGeneratorResponseFile {
+    fn from_text(text: &picopb::text_format::TextValue) -> picopb::text_format::Result<Self> {
+        let mut message = Self::default();
+        for (name, value) in text.as_message()? {
+            match name.as_str() {
+                "name" => {
+                    message.name = Some(picopb::text_format::FromText::from_text(value)?);
+                }
+                "content" => {
+                    message.content = Some(picopb::text_format::FromText::from_text(value)?);
+                }
+                _ => return Err(picopb::text_format::TextFormatError::UnknownField(name.clone())),
+            }
+        }
+        Ok(message)
+    }
+}
+impl CodeGeneratorResponseFile {
+    pub const DESCRIPTOR: picopb::reflect::MessageDescriptor = picopb::reflect::MessageDescriptor {
+        name: "CodeGeneratorResponseFile",
+        fields: &[
+            CODE_GENERATOR_RESPONSE_FILE_FIELDS.name,
+            CODE_GENERATOR_RESPONSE_FILE_FIELDS.content,
+        ],
+    };
+}
+impl picopb::reflect::Reflect for CodeGeneratorResponseFile {
+    fn descriptor(&self) -> &'static picopb::reflect::MessageDescriptor {
+        &Self::DESCRIPTOR
+    }
+    fn get(&self, number: u32) -> Option<Vec<picopb::reflect::FieldValue<'_>>> {
+        match number {
+            1 => Some(self.name.iter().map(picopb::reflect::ReflectField::to_value).collect()),
+            15 => Some(self.content.iter().map(picopb::reflect::ReflectField::to_value).collect()),
+            _ => None,
+        }
+    }
+    fn set(&mut self, number: u32, values: &[picopb::reflect::FieldValue]) -> picopb::reflect::Result<()> {
+        match number {
+            1 => picopb::reflect::set_optional(&mut self.name, values),
+            15 => picopb::reflect::set_optional(&mut self.content, values),
+            _ => Err(picopb::reflect::ReflectError::UnknownField(number.to_string())),
+        }
+    }
+}
+impl picopb::reflect::ReflectField for CodeGeneratorResponseFile {
+    fn to_value(&self) -> picopb::reflect::FieldValue<'_> {
+        picopb::reflect::FieldValue::Message(self)
+    }
+    fn from_value(value: &picopb::reflect::FieldValue) -> picopb::reflect::Result<Self> {
+        let mut message = Self::default();
+        picopb::reflect::copy_fields(&mut message, value.as_message()?)?;
+        Ok(message)
+    }
+}
+#[derive(Default, Debug)]
+pub struct DescriptorProto {
+    pub name: Option<String>,
+    pub field: Vec<FieldDescriptorProto>,
+    pub nested_type: Vec<DescriptorProto>,
+    pub enum_type: Vec<EnumDescriptorProto>,
+    pub options: Option<MessageOptions>,
+}
+#[derive(Debug)]
+pub struct DescriptorProtoFieldsType<'a> {
+    pub name: picopb::common::ConstMessageField<'a>,
+    pub field: picopb::common::ConstMessageField<'a>,
+    pub nested_type: picopb::common::ConstMessageField<'a>,
+    pub enum_type: picopb::common::ConstMessageField<'a>,
+    pub options: picopb::common::ConstMessageField<'a>,
+}
+const DESCRIPTOR_PROTO_FIELDS: DescriptorProtoFieldsType = DescriptorProtoFieldsType {
+    name: picopb::common::ConstMessageField {
+        qualifier: picopb::common::FieldQualifier::Optional,
+        field_type: picopb::common::FieldType::UnboundedString,
+        identifier: "name",
+        json_name: "name",
+        ordinal: picopb::common::Field(1),
+    },
+    field: picopb::common::ConstMessageField {
+        qualifier: picopb::common::FieldQualifier::RepeatedUnbounded,
+        field_type: picopb::common::FieldType::MessageType("FieldDescriptorProto"),
+        identifier: "field",
+        json_name: "field",
+        ordinal: picopb::common::Field(2),
+    },
+    nested_type: picopb::common::ConstMessageField {
+        qualifier: picopb::common::FieldQualifier::RepeatedUnbounded,
+        field_type: picopb::common::FieldType::MessageType("DescriptorProto"),
+        identifier: "nested_type",
+        json_name: "nestedType",
+        ordinal: picopb::common::Field(3),
+    },
+    enum_type: picopb::common::ConstMessageField {
+        qualifier: picopb::common::FieldQualifier::RepeatedUnbounded,
+        field_type: picopb::common::FieldType::MessageType("EnumDescriptorProto"),
+        identifier: "enum_type",
+        json_name: "enumType",
+        ordinal: picopb::common::Field(4),
+    },
+    options: picopb::common::ConstMessageField {
+        qualifier: picopb::common::FieldQualifier::Optional,
+        field_type: picopb::common::FieldType::MessageType("MessageOptions"),
+        identifier: "options",
+        json_name: "options",
+        ordinal: picopb::common::Field(7),
+    },
+};
+impl DescriptorProto {
+    fn fields(&self) -> DescriptorProtoFieldsType<'static> {
+        DESCRIPTOR_PROTO_FIELDS
+    }
+}
+// DescriptorProto has no MAX_ENCODED_LEN, unbounded: DescriptorProto.name
+impl picopb::wiretypes::WireTyped for &DescriptorProto {
+    fn wiretype(&self) -> WireType {
+        WireType::Len
+    }
+}
+impl picopb::encode::ToWire for &DescriptorProto {
+    fn append<W: picopb::encode::EncodeSink>(&self, buf: &mut W) -> std::io::Result<usize> {
+        let mut total_size = 0;
+        if let Some(value_name) = &self.name {
+            total_size += buf.encode(value_name.as_str(), self.fields().name.ordinal)?;
+        }
+        for value_field in self.field.iter() {
+            total_size += buf.encode_sub_message(value_field, self.fields().field.ordinal)?;
+        }
+        for value_nested_type in self.nested_type.iter() {
+            total_size += buf.encode_sub_message(value_nested_type, self.fields().nested_type.ordinal)?;
+        }
+        for value_enum_type in self.enum_type.iter() {
+            total_size += buf.encode_sub_message(value_enum_type, self.fields().enum_type.ordinal)?;
+        }
+        if let Some(value_options) = &self.options {
+            total_size += buf.encode_sub_message(value_options, self.fields().options.ordinal)?;
+        }
+        Ok(total_size)
+    }
+    fn precalculate_size(&self) -> usize {
+        let mut total_size = 0;
+        if let Some(value_name) = &self.name {
+            total_size += value_name.as_str().precalculate_field_size(self.fields().name.ordinal);
+        }
+        for value_field in self.field.iter() {
+            total_size += value_field.precalculate_sub_message_size(self.fields().field.ordinal);
+        }
+        for value_nested_type in self.nested_type.iter() {
+            total_size += value_nested_type.precalculate_sub_message_size(self.fields().nested_type.ordinal);
+        }
+        for value_enum_type in self.enum_type.iter() {
+            total_size += value_enum_type.precalculate_sub_message_size(self.fields().enum_type.ordinal);
+        }
+        if let Some(value_options) = &self.options {
+            total_size += value_options.precalculate_sub_message_size(self.fields().options.ordinal);
+        }
+        total_size
+    }
+}
+impl picopb::decode::DecodeMessage for DescriptorProto {
+    fn decode_from(buf: &mut picopb::decode::DecodeBuffer<'_>) -> picopb::decode::Result<Self> {
+        let mut message = Self::default();
+        while !buf.is_empty() {
+            let (field, wire_type) = buf.read_tag()?;
+            match field.0 {
+                1 => {
+                    message.name = Some(picopb::decode::read_field(buf, field, wire_type)?);
+                }
+                2 => {
+                    picopb::decode::read_repeated(buf, field, wire_type, |value| {
+                        message.field.push(value);
+                        Ok(())
+                    })?;
+                }
+                3 => {
+                    picopb::decode::read_repeated(buf, field, wire_type, |value| {
+                        message.nested_type.push(value);
+                        Ok(())
+                    })?;
+                }
+                4 => {
+                    picopb::decode::read_repeated(buf, field, wire_type, |value| {
+                        message.enum_type.push(value);
+                        Ok(())
+                    })?;
+                }
+                7 => {
+                    message.options = Some(picopb::decode::read_field(buf, field, wire_type)?);
+                }
+                _ => buf.skip_unknown(field, wire_type)?,
+            }
+        }
+        Ok(message)
+    }
+}
+impl picopb::decode::FromWire for DescriptorProto {
+    fn wire_type() -> WireType {
+        WireType::Len
+    }
+    fn read(buf: &mut picopb::decode::DecodeBuffer<'_>) -> picopb::decode::Result<Self> {
+        <Self as picopb::decode::DecodeMessage>::decode_from(&mut buf.read_sub_buffer()?)
+    }
+}
+impl Randomize for DescriptorProto {
+    fn randomized<R: picopb::randomizer::Rng + ?Sized>(rng: &mut R) -> Self {
+        Self {
+            name: randomized(rng),
+            field: randomized(rng),
+            nested_type: randomized(rng),
+            enum_type: randomized(rng),
+            options: randomized(rng),
+        }
+    }
+}
+impl picopb::text_format::ToText for DescriptorProto {
+    fn write_text<W: std::fmt::Write>(&self, out: &mut W) -> std::fmt::Result {
+        let mut writer = picopb::text_format::FieldWriter::new(out);
+        if let Some(value_name) = &self.name {
+            writer.field(self.fields().name.identifier, value_name)?;
+        }
+        for value_field in self.field.iter() {
+            writer.message(self.fields().field.identifier, value_field)?;
+        }
+        for value_nested_type in self.nested_type.iter() {
+            writer.message(self.fields().nested_type.identifier, value_nested_type)?;
+        }
+        for value_enum_type in self.enum_type.iter() {
+            writer.message(self.fields().enum_type.identifier, value_enum_type)?;
+        }
+        if let Some(value_options) = &self.options {
+            writer.message(self.fields().options.identifier, value_options)?;
+        }
+        Ok(())
+    }
+}
+impl picopb::text_format::FromText for DescriptorProto {
+    fn from_text(text: &picopb::text_format::TextValue) -> picopb::text_format::Result<Self> {
+        let mut message = Self::default();
+        for (name, value) in text.as_message()? {
+            match name.as_str() {
+                "name" => {
+                    message.name = Some(picopb::text_format::FromText::from_text(value)?);
+                }
+                "field" => {
+                    picopb::text_format::read_repeated(value, |value| {
+                        message.field.push(value);
+                        Ok(())
+                    })?;
+                }
+                "nested_type" => {
+                    picopb::text_format::read_repeated(value, |value| {
+                        message.nested_type.push(value);
+                        Ok(())
+                    })?;
+                }
+                "enum_type" => {
+                    picopb::text_format::read_repeated(value, |value| {
+                        message.enum_type.push(value);
+                        Ok(())
+                    })?;
+                }
+                "options" => {
+                    message.options = Some(picopb::text_format::FromText::from_text(value)?);
+                }
+                _ => return Err(picopb::text_format::TextFormatError::UnknownField(name.clone())),
+            }
+        }
+        Ok(message)
+    }
+}
+impl DescriptorProto {
+    pub const DESCRIPTOR: picopb::reflect::MessageDescriptor = picopb::reflect::MessageDescriptor {
+        name: "DescriptorProto",
+        fields: &[
+            DESCRIPTOR_PROTO_FIELDS.name,
+            DESCRIPTOR_PROTO_FIELDS.field,
+            DESCRIPTOR_PROTO_FIELDS.nested_type,
+            DESCRIPTOR_PROTO_FIELDS.enum_type,
+            DESCRIPTOR_PROTO_FIELDS.options,
+        ],
+    };
+}
+impl picopb::reflect::Reflect for DescriptorProto {
+    fn descriptor(&self) -> &'static picopb::reflect::MessageDescriptor {
+        &Self::DESCRIPTOR
+    }
+    fn get(&self, number: u32) -> Option<Vec<picopb::reflect::FieldValue<'_>>> {
+        match number {
+            1 => Some(self.name.iter().map(picopb::reflect::ReflectField::to_value).collect()),
+            2 => Some(self.field.iter().map(picopb::reflect::ReflectField::to_value).collect()),
+            3 => Some(self.nested_type.iter().map(picopb::reflect::ReflectField::to_value).collect()),
+            4 => Some(self.enum_type.iter().map(picopb::reflect::ReflectField::to_value).collect()),
+            7 => Some(self.options.iter().map(picopb::reflect::ReflectField::to_value).collect()),
+            _ => None,
+        }
+    }
+    fn set(&mut self, number: u32, values: &[picopb::reflect::FieldValue]) -> picopb::reflect::Result<()> {
+        match number {
+            1 => picopb::reflect::set_optional(&mut self.name, values),
+            2 => picopb::reflect::set_repeated(&mut self.field, values),
+            3 => picopb::reflect::set_repeated(&mut self.nested_type, values),
+            4 => picopb::reflect::set_repeated(&mut self.enum_type, values),
+            7 => picopb::reflect::set_optional(&mut self.options, values),
+            _ => Err(picopb::reflect::ReflectError::UnknownField(number.to_string())),
+        }
+    }
+}
+impl picopb::reflect::ReflectField for DescriptorProto {
+    fn to_value(&self) -> picopb::reflect::FieldValue<'_> {
+        picopb::reflect::FieldValue::Message(self)
+    }
+    fn from_value(value: &picopb::reflect::FieldValue) -> picopb::reflect::Result<Self> {
+        let mut message = Self::default();
+        picopb::reflect::copy_fields(&mut message, value.as_message()?)?;
+        Ok(message)
+    }
+}
+#[derive(Default, Debug)]
+pub struct EnumDescriptorProto {
+    pub name: Option<String>,
+    pub value: Vec<EnumValueDescriptorProto>,
+    pub options: Option<EnumOptions>,
+}
+#[derive(Debug)]
+pub struct EnumDescriptorProtoFieldsType<'a> {
+    pub name: picopb::common::ConstMessageField<'a>,
+    pub value: picopb::common::ConstMessageField<'a>,
+    pub options: picopb::common::ConstMessageField<'a>,
+}
+const ENUM_DESCRIPTOR_PROTO_FIELDS: EnumDescriptorProtoFieldsType = EnumDescriptorProtoFieldsType {
+    name: picopb::common::ConstMessageField {
+        qualifier: picopb::common::FieldQualifier::Optional,
+        field_type: picopb::common::FieldType::UnboundedString,
+        identifier: "name",
+        json_name: "name",
+        ordinal: picopb::common::Field(1),
+    },
+    value: picopb::common::ConstMessageField {
+        qualifier: picopb::common::FieldQualifier::RepeatedUnbounded,
+        field_type: picopb::common::FieldType::MessageType("EnumValueDescriptorProto"),
+        identifier: "value",
+        json_name: "value",
+        ordinal: picopb::common::Field(2),
+    },
+    options: picopb::common::ConstMessageField {
+        qualifier: picopb::common::FieldQualifier::Optional,
+        field_type: picopb::common::FieldType::MessageType("EnumOptions"),
+        identifier: "options",
+        json_name: "options",
+        ordinal: picopb::common::Field(3),
+    },
+};
+impl EnumDescriptorProto {
+    fn fields(&self) -> EnumDescriptorProtoFieldsType<'static> {
+        ENUM_DESCRIPTOR_PROTO_FIELDS
+    }
+}
+// EnumDescriptorProto has no MAX_ENCODED_LEN, unbounded: EnumDescriptorProto.name
+impl picopb::wiretypes::WireTyped for &EnumDescriptorProto {
+    fn wiretype(&self) -> WireType {
+        WireType::Len
+    }
+}
+impl picopb::encode::ToWire for &EnumDescriptorProto {
+    fn append<W: picopb::encode::EncodeSink>(&self, buf: &mut W) -> std::io::Result<usize> {
+        let mut total_size = 0;
+        if let Some(value_name) = &self.name {
+            total_size += buf.encode(value_name.as_str(), self.fields().name.ordinal)?;
+        }
+        for value_value in self.value.iter() {
+            total_size += buf.encode_sub_message(value_value, self.fields().value.ordinal)?;
+        }
+        if let Some(value_options) = &self.options {
+            total_size += buf.encode_sub_message(value_options, self.fields().options.ordinal)?;
+        }
+        Ok(total_size)
+    }
+    fn precalculate_size(&self) -> usize {
+        let mut total_size = 0;
+        if let Some(value_name) = &self.name {
+            total_size += value_name.as_str().precalculate_field_size(self.fields().name.ordinal);
+        }
+        for value_value in self.value.iter() {
+            total_size += value_value.precalculate_sub_message_size(self.fields().value.ordinal);
+        }
+        if let Some(value_options) = &self.options {
+            total_size += value_options.precalculate_sub_message_size(self.fields().options.ordinal);
+        }
+        total_size
+    }
+}
+impl picopb::decode::DecodeMessage for EnumDescriptorProto {
+    fn decode_from(buf: &mut picopb::decode::DecodeBuffer<'_>) -> picopb::decode::Result<Self> {
+        let mut message = Self::default();
+        while !buf.is_empty() {
+            let (field, wire_type) = buf.read_tag()?;
+            match field.0 {
+                1 => {
+                    message.name = Some(picopb::decode::read_field(buf, field, wire_type)?);
+                }
+                2 => {
+                    picopb::decode::read_repeated(buf, field, wire_type, |value| {
+                        message.value.push(value);
+                        Ok(())
+                    })?;
+                }
+                3 => {
+                    message.options = Some(picopb::decode::read_field(buf, field, wire_type)?);
+                }
+                _ => buf.skip_unknown(field, wire_type)?,
+            }
+        }
+        Ok(message)
+    }
+}
+impl picopb::decode::FromWire for EnumDescriptorProto {
+    fn wire_type() -> WireType {
+        WireType::Len
+    }
+    fn read(buf: &mut picopb::decode::DecodeBuffer<'_>) -> picopb::decode::Result<Self> {
+        <Self as picopb::decode::DecodeMessage>::decode_from(&mut buf.read_sub_buffer()?)
+    }
+}
+impl Randomize for EnumDescriptorProto {
+    fn randomized<R: picopb::randomizer::Rng + ?Sized>(rng: &mut R) -> Self {
+        Self {
+            name: randomized(rng),
+            value: randomized(rng),
+            options: randomized(rng),
+        }
+    }
+}
+impl picopb::text_format::ToText for EnumDescriptorProto {
+    fn write_text<W: std::fmt::Write>(&self, out: &mut W) -> std::fmt::Result {
+        let mut writer = picopb::text_format::FieldWriter::new(out);
+        if let Some(value_name) = &self.name {
+            writer.field(self.fields().name.identifier, value_name)?;
+        }
+        for value_value in self.value.iter() {
+            writer.message(self.fields().value.identifier, value_value)?;
+        }
+        if let Some(value_options) = &self.options {
+            writer.message(self.fields().options.identifier, value_options)?;
+        }
+        Ok(())
+    }
+}
+impl picopb::text_format::FromText for EnumDescriptorProto {
+    fn from_text(text: &picopb::text_format::TextValue) -> picopb::text_format::Result<Self> {
+        let mut message = Self::default();
+        for (name, value) in text.as_message()? {
+            match name.as_str() {
+                "name" => {
+                    message.name = Some(picopb::text_format::FromText::from_text(value)?);
+                }
+                "value" => {
+                    picopb::text_format::read_repeated(value, |value| {
+                        message.value.push(value);
+                        Ok(())
+                    })?;
+                }
+                "options" => {
+                    message.options = Some(picopb::text_format::FromText::from_text(value)?);
+                }
+                _ => return Err(picopb::text_format::TextFormatError::UnknownField(name.clone())),
+            }
+        }
+        Ok(message)
+    }
+}
+impl EnumDescriptorProto {
+    pub const DESCRIPTOR: picopb::reflect::MessageDescriptor = picopb::reflect::MessageDescriptor {
+        name: "EnumDescriptorProto",
+        fields: &[
+            ENUM_DESCRIPTOR_PROTO_FIELDS.name,
+            ENUM_DESCRIPTOR_PROTO_FIELDS.value,
+            ENUM_DESCRIPTOR_PROTO_FIELDS.options,
+        ],
+    };
+}
+impl picopb::reflect::Reflect for EnumDescriptorProto {
+    fn descriptor(&self) -> &'static picopb::reflect::MessageDescriptor {
+        &Self::DESCRIPTOR
+    }
+    fn get(&self, number: u32) -> Option<Vec<picopb::reflect::FieldValue<'_>>> {
+        match number {
+            1 => Some(self.name.iter().map(picopb::reflect::ReflectField::to_value).collect()),
+            2 => Some(self.value.iter().map(picopb::reflect::ReflectField::to_value).collect()),
+            3 => Some(self.options.iter().map(picopb::reflect::ReflectField::to_value).collect()),
+            _ => None,
+        }
+    }
+    fn set(&mut self, number: u32, values: &[picopb::reflect::FieldValue]) -> picopb::reflect::Result<()> {
+        match number {
+            1 => picopb::reflect::set_optional(&mut self.name, values),
+            2 => picopb::reflect::set_repeated(&mut self.value, values),
+            3 => picopb::reflect::set_optional(&mut self.options, values),
+            _ => Err(picopb::reflect::ReflectError::UnknownField(number.to_string())),
+        }
+    }
+}
+impl picopb::reflect::ReflectField for EnumDescriptorProto {
+    fn to_value(&self) -> picopb::reflect::FieldValue<'_> {
+        picopb::reflect::FieldValue::Message(self)
+    }
+    fn from_value(value: &picopb::reflect::FieldValue) -> picopb::reflect::Result<Self> {
+        let mut message = Self::default();
+        picopb::reflect::copy_fields(&mut message, value.as_message()?)?;
+        Ok(message)
+    }
+}
+#[derive(Default, Debug)]
+pub struct EnumOptions {
+    pub allow_alias: Option<bool>,
+}
+#[derive(Debug)]
+pub struct EnumOptionsFieldsType<'a> {
+    pub allow_alias: picopb::common::ConstMessageField<'a>,
+}
+const ENUM_OPTIONS_FIELDS: EnumOptionsFieldsType = EnumOptionsFieldsType {
+    allow_alias: picopb::common::ConstMessageField {
+        qualifier: picopb::common::FieldQualifier::Optional,
+        field_type: picopb::common::FieldType::Bool,
+        identifier: "allow_alias",
+        json_name: "allowAlias",
+        ordinal: picopb::common::Field(2),
+    },
+};
+impl EnumOptions {
+    fn fields(&self) -> EnumOptionsFieldsType<'static> {
+        ENUM_OPTIONS_FIELDS
+    }
+}
+impl EnumOptions {
+    pub const MAX_ENCODED_LEN: usize = 2;
+}
+impl picopb::wiretypes::WireTyped for &EnumOptions {
+    fn wiretype(&self) -> WireType {
+        WireType::Len
+    }
+}
+impl picopb::encode::ToWire for &EnumOptions {
+    fn append<W: picopb::encode::EncodeSink>(&self, buf: &mut W) -> std::io::Result<usize> {
+        let mut total_size = 0;
+        if let Some(value_allow_alias) = &self.allow_alias {
+            total_size += buf.encode(*value_allow_alias, self.fields().allow_alias.ordinal)?;
+        }
+        Ok(total_size)
+    }
+    fn precalculate_size(&self) -> usize {
+        let mut total_size = 0;
+        if let Some(value_allow_alias) = &self.allow_alias {
+            total_size += (*value_allow_alias).precalculate_field_size(self.fields().allow_alias.ordinal);
+        }
+        total_size
+    }
+}
+impl picopb::decode::DecodeMessage for EnumOptions {
+    fn decode_from(buf: &mut picopb::decode::DecodeBuffer<'_>) -> picopb::decode::Result<Self> {
+        let mut message = Self::default();
+        while !buf.is_empty() {
+            let (field, wire_type) = buf.read_tag()?;
+            match field.0 {
+                2 => {
+                    message.allow_alias = Some(picopb::decode::read_field(buf, field, wire_type)?);
+                }
+                _ => buf.skip_unknown(field, wire_type)?,
+            }
+        }
+        Ok(message)
+    }
+}
+impl picopb::decode::FromWire for EnumOptions {
+    fn wire_type() -> WireType {
+        WireType::Len
+    }
+    fn read(buf: &mut picopb::decode::DecodeBuffer<'_>) -> picopb::decode::Result<Self> {
+        <Self as picopb::decode::DecodeMessage>::decode_from(&mut buf.read_sub_buffer()?)
+    }
+}
+impl Randomize for EnumOptions {
+    fn randomized<R: picopb::randomizer::Rng + ?Sized>(rng: &mut R) -> Self {
+        Self {
+            allow_alias: randomized(rng),
+        }
+    }
+}
+impl picopb::text_format::ToText for EnumOptions {
+    fn write_text<W: std::fmt::Write>(&self, out: &mut W) -> std::fmt::Result {
+        let mut writer = picopb::text_format::FieldWriter::new(out);
+        if let Some(value_allow_alias) = &self.allow_alias {
+            writer.field(self.fields().allow_alias.identifier, value_allow_alias)?;
+        }
+        Ok(())
+    }
+}
+impl picopb::text_format::FromText for EnumOptions {
+    fn from_text(text: &picopb::text_format::TextValue) -> picopb::text_format::Result<Self> {
+        let mut message = Self::default();
+        for (name, value) in text.as_message()? {
+            match name.as_str() {
+                "allow_alias" => {
+                    message.allow_alias = Some(picopb::text_format::FromText::from_text(value)?);
+                }
+                _ => return Err(picopb::text_format::TextFormatError::UnknownField(name.clone())),
+            }
+        }
+        Ok(message)
+    }
+}
+impl EnumOptions {
+    pub const DESCRIPTOR: picopb::reflect::MessageDescriptor = picopb::reflect::MessageDescriptor {
+        name: "EnumOptions",
+        fields: &[
+            ENUM_OPTIONS_FIELDS.allow_alias,
+        ],
+    };
+}
+impl picopb::reflect::Reflect for EnumOptions {
+    fn descriptor(&self) -> &'static picopb::reflect::MessageDescriptor {
+        &Self::DESCRIPTOR
+    }
+    fn get(&self, number: u32) -> Option<Vec<picopb::reflect::FieldValue<'_>>> {
+        match number {
+            2 => Some(self.allow_alias.iter().map(picopb::reflect::ReflectField::to_value).collect()),
+            _ => None,
+        }
+    }
+    fn set(&mut self, number: u32, values: &[picopb::reflect::FieldValue]) -> picopb::reflect::Result<()> {
+        match number {
+            2 => picopb::reflect::set_optional(&mut self.allow_alias, values),
+            _ => Err(picopb::reflect::ReflectError::UnknownField(number.to_string())),
+        }
+    }
+}
+impl picopb::reflect::ReflectField for EnumOptions {
+    fn to_value(&self) -> picopb::reflect::FieldValue<'_> {
+        picopb::reflect::FieldValue::Message(self)
+    }
+    fn from_value(value: &picopb::reflect::FieldValue) -> picopb::reflect::Result<Self> {
+        let mut message = Self::default();
+        picopb::reflect::copy_fields(&mut message, value.as_message()?)?;
+        Ok(message)
+    }
+}
+#[derive(Default, Debug)]
+pub struct EnumValueDescriptorProto {
+    pub name: Option<String>,
+    pub number: Option<i32>,
+}
+#[derive(Debug)]
+pub struct EnumValueDescriptorProtoFieldsType<'a> {
+    pub name: picopb::common::ConstMessageField<'a>,
+    pub number: picopb::common::ConstMessageField<'a>,
+}
+const ENUM_VALUE_DESCRIPTOR_PROTO_FIELDS: EnumValueDescriptorProtoFieldsType = EnumValueDescriptorProtoFieldsType {
+    name: picopb::common::ConstMessageField {
+        qualifier: picopb::common::FieldQualifier::Optional,
+        field_type: picopb::common::FieldType::UnboundedString,
+        identifier: "name",
+        json_name: "name",
+        ordinal: picopb::common::Field(1),
+    },
+    number: picopb::common::ConstMessageField {
+        qualifier: picopb::common::FieldQualifier::Optional,
+        field_type: picopb::common::FieldType::Int32,
+        identifier: "number",
+        json_name: "number",
+        ordinal: picopb::common::Field(2),
+    },
+};
+impl EnumValueDescriptorProto {
+    fn fields(&self) -> EnumValueDescriptorProtoFieldsType<'static> {
+        ENUM_VALUE_DESCRIPTOR_PROTO_FIELDS
+    }
+}
+// EnumValueDescriptorProto has no MAX_ENCODED_LEN, unbounded: EnumValueDescriptorProto.name
+impl picopb::wiretypes::WireTyped for &EnumValueDescriptorProto {
+    fn wiretype(&self) -> WireType {
+        WireType::Len
+    }
+}
+impl picopb::encode::ToWire for &EnumValueDescriptorProto {
+    fn append<W: picopb::encode::EncodeSink>(&self, buf: &mut W) -> std::io::Result<usize> {
+        let mut total_size = 0;
+        if let Some(value_name) = &self.name {
+            total_size += buf.encode(value_name.as_str(), self.fields().name.ordinal)?;
+        }
+        if let Some(value_number) = &self.number {
+            total_size += buf.encode(*value_number, self.fields().number.ordinal)?;
+        }
+        Ok(total_size)
+    }
+    fn precalculate_size(&self) -> usize {
+        let mut total_size = 0;
+        if let Some(value_name) = &self.name {
+            total_size += value_name.as_str().precalculate_field_size(self.fields().name.ordinal);
+        }
+        if let Some(value_number) = &self.number {
+            total_size += (*value_number).precalculate_field_size(self.fields().number.ordinal);
+        }
+        total_size
+    }
+}
+impl picopb::decode::DecodeMessage for EnumValueDescriptorProto {
+    fn decode_from(buf: &mut picopb::decode::DecodeBuffer<'_>) -> picopb::decode::Result<Self> {
+        let mut message = Self::default();
+        while !buf.is_empty() {
+            let (field, wire_type) = buf.read_tag()?;
+            match field.0 {
+                1 => {
+                    message.name = Some(picopb::decode::read_field(buf, field, wire_type)?);
+                }
+                2 => {
+                    message.number = Some(picopb::decode::read_field(buf, field, wire_type)?);
+                }
+                _ => buf.skip_unknown(field, wire_type)?,
+            }
+        }
+        Ok(message)
+    }
+}
+impl picopb::decode::FromWire for EnumValueDescriptorProto {
+    fn wire_type() -> WireType {
+        WireType::Len
+    }
+    fn read(buf: &mut picopb::decode::DecodeBuffer<'_>) -> picopb::decode::Result<Self> {
+        <Self as picopb::decode::DecodeMessage>::decode_from(&mut buf.read_sub_buffer()?)
+    }
+}
+impl Randomize for EnumValueDescriptorProto {
+    fn randomized<R: picopb::randomizer::Rng + ?Sized>(rng: &mut R) -> Self {
+        Self {
+            name: randomized(rng),
+            number: randomized(rng),
+        }
+    }
+}
+impl picopb::text_format::ToText for EnumValueDescriptorProto {
+    fn write_text<W: std::fmt::Write>(&self, out: &mut W) -> std::fmt::Result {
+        let mut writer = picopb::text_format::FieldWriter::new(out);
+        if let Some(value_name) = &self.name {
+            writer.field(self.fields().name.identifier, value_name)?;
+        }
+        if let Some(value_number) = &self.number {
+            writer.field(self.fields().number.identifier, value_number)?;
+        }
+        Ok(())
+    }
+}
+impl picopb::text_format::FromText for EnumValueDescriptorProto {
+    fn from_text(text: &picopb::text_format::TextValue) -> picopb::text_format::Result<Self> {
+        let mut message = Self::default();
+        for (name, value) in text.as_message()? {
+            match name.as_str() {
+                "name" => {
+                    message.name = Some(picopb::text_format::FromText::from_text(value)?);
+                }
+                "number" => {
+                    message.number = Some(picopb::text_format::FromText::from_text(value)?);
+                }
+                _ => return Err(picopb::text_format::TextFormatError::UnknownField(name.clone())),
+            }
+        }
+        Ok(message)
+    }
+}
+impl EnumValueDescriptorProto {
+    pub const DESCRIPTOR: picopb::reflect::MessageDescriptor = picopb::reflect::MessageDescriptor {
+        name: "EnumValueDescriptorProto",
+        fields: &[
+            ENUM_VALUE_DESCRIPTOR_PROTO_FIELDS.name,
+            ENUM_VALUE_DESCRIPTOR_PROTO_FIELDS.number,
+        ],
+    };
+}
+impl picopb::reflect::Reflect for EnumValueDescriptorProto {
+    fn descriptor(&self) -> &'static picopb::reflect::MessageDescriptor {
+        &Self::DESCRIPTOR
+    }
+    fn get(&self, number: u32) -> Option<Vec<picopb::reflect::FieldValue<'_>>> {
+        match number {
+            1 => Some(self.name.iter().map(picopb::reflect::ReflectField::to_value).collect()),
+            2 => Some(self.number.iter().map(picopb::reflect::ReflectField::to_value).collect()),
+            _ => None,
+        }
+    }
+    fn set(&mut self, number: u32, values: &[picopb::reflect::FieldValue]) -> picopb::reflect::Result<()> {
+        match number {
+            1 => picopb::reflect::set_optional(&mut self.name, values),
+            2 => picopb::reflect::set_optional(&mut self.number, values),
+            _ => Err(picopb::reflect::ReflectError::UnknownField(number.to_string())),
+        }
+    }
+}
+impl picopb::reflect::ReflectField for EnumValueDescriptorProto {
+    fn to_value(&self) -> picopb::reflect::FieldValue<'_> {
+        picopb::reflect::FieldValue::Message(self)
+    }
+    fn from_value(value: &picopb::reflect::FieldValue) -> picopb::reflect::Result<Self> {
+        let mut message = Self::default();
+        picopb::reflect::copy_fields(&mut message, value.as_message()?)?;
+        Ok(message)
+    }
+}
+#[derive(Default, Debug)]
+pub struct FieldDescriptorProto {
+    pub name: Option<String>,
+    pub number: Option<i32>,
+    pub label: Option<i32>,
+    pub field_type: Option<i32>,
+    pub type_name: Option<String>,
+    pub options: Option<FieldOptions>,
+    pub oneof_index: Option<i32>,
+    pub json_name: Option<String>,
+}
+#[derive(Debug)]
+pub struct FieldDescriptorProtoFieldsType<'a> {
+    pub name: picopb::common::ConstMessageField<'a>,
+    pub number: picopb::common::ConstMessageField<'a>,
+    pub label: picopb::common::ConstMessageField<'a>,
+    pub field_type: picopb::common::ConstMessageField<'a>,
+    pub type_name: picopb::common::ConstMessageField<'a>,
+    pub options: picopb::common::ConstMessageField<'a>,
+    pub oneof_index: picopb::common::ConstMessageField<'a>,
+    pub json_name: picopb::common::ConstMessageField<'a>,
+}
+const FIELD_DESCRIPTOR_PROTO_FIELDS: FieldDescriptorProtoFieldsType = FieldDescriptorProtoFieldsType {
+    name: picopb::common::ConstMessageField {
+        qualifier: picopb::common::FieldQualifier::Optional,
+        field_type: picopb::common::FieldType::UnboundedString,
+        identifier: "name",
+        json_name: "name",
+        ordinal: picopb::common::Field(1),
+    },
+    number: picopb::common::ConstMessageField {
+        qualifier: picopb::common::FieldQualifier::Optional,
+        field_type: picopb::common::FieldType::Int32,
+        identifier: "number",
+        json_name: "number",
+        ordinal: picopb::common::Field(3),
+    },
+    label: picopb::common::ConstMessageField {
+        qualifier: picopb::common::FieldQualifier::Optional,
+        field_type: picopb::common::FieldType::Int32,
+        identifier: "label",
+        json_name: "label",
+        ordinal: picopb::common::Field(4),
+    },
+    field_type: picopb::common::ConstMessageField {
+        qualifier: picopb::common::FieldQualifier::Optional,
+        field_type: picopb::common::FieldType::Int32,
+        identifier: "field_type",
+        json_name: "fieldType",
+        ordinal: picopb::common::Field(5),
+    },
+    type_name: picopb::common::ConstMessageField {
+        qualifier: picopb::common::FieldQualifier::Optional,
+        field_type: picopb::common::FieldType::UnboundedString,
+        identifier: "type_name",
+        json_name: "typeName",
+        ordinal: picopb::common::Field(6),
+    },
+    options: picopb::common::ConstMessageField {
+        qualifier: picopb::common::FieldQualifier::Optional,
+        field_type: picopb::common::FieldType::MessageType("FieldOptions"),
+        identifier: "options",
+        json_name: "options",
+        ordinal: picopb::common::Field(8),
+    },
+    oneof_index: picopb::common::ConstMessageField {
+        qualifier: picopb::common::FieldQualifier::Optional,
+        field_type: picopb::common::FieldType::Int32,
+        identifier: "oneof_index",
+        json_name: "oneofIndex",
+        ordinal: picopb::common::Field(9),
+    },
+    json_name: picopb::common::ConstMessageField {
+        qualifier: picopb::common::FieldQualifier::Optional,
+        field_type: picopb::common::FieldType::UnboundedString,
+        identifier: "json_name",
+        json_name: "jsonName",
+        ordinal: picopb::common::Field(10),
+    },
+};
+impl FieldDescriptorProto {
+    fn fields(&self) -> FieldDescriptorProtoFieldsType<'static> {
+        FIELD_DESCRIPTOR_PROTO_FIELDS
+    }
+}
+// FieldDescriptorProto has no MAX_ENCODED_LEN, unbounded: FieldDescriptorProto.name
+impl picopb::wiretypes::WireTyped for &FieldDescriptorProto {
+    fn wiretype(&self) -> WireType {
+        WireType::Len
+    }
+}
+impl picopb::encode::ToWire for &FieldDescriptorProto {
+    fn append<W: picopb::encode::EncodeSink>(&self, buf: &mut W) -> std::io::Result<usize> {
+        let mut total_size = 0;
+        if let Some(value_name) = &self.name {
+            total_size += buf.encode(value_name.as_str(), self.fields().name.ordinal)?;
+        }
+        if let Some(value_number) = &self.number {
+            total_size += buf.encode(*value_number, self.fields().number.ordinal)?;
+        }
+        if let Some(value_label) = &self.label {
+            total_size += buf.encode(*value_label, self.fields().label.ordinal)?;
+        }
+        if let Some(value_field_type) = &self.field_type {
+            total_size += buf.encode(*value_field_type, self.fields().field_type.ordinal)?;
+        }
+        if let Some(value_type_name) = &self.type_name {
+            total_size += buf.encode(value_type_name.as_str(), self.fields().type_name.ordinal)?;
+        }
+        if let Some(value_options) = &self.options {
+            total_size += buf.encode_sub_message(value_options, self.fields().options.ordinal)?;
+        }
+        if let Some(value_oneof_index) = &self.oneof_index {
+            total_size += buf.encode(*value_oneof_index, self.fields().oneof_index.ordinal)?;
+        }
+        if let Some(value_json_name) = &self.json_name {
+            total_size += buf.encode(value_json_name.as_str(), self.fields().json_name.ordinal)?;
+        }
+        Ok(total_size)
+    }
+    fn precalculate_size(&self) -> usize {
+        let mut total_size = 0;
+        if let Some(value_name) = &self.name {
+            total_size += value_name.as_str().precalculate_field_size(self.fields().name.ordinal);
+        }
+        if let Some(value_number) = &self.number {
+            total_size += (*value_number).precalculate_field_size(self.fields().number.ordinal);
+        }
+        if let Some(value_label) = &self.label {
+            total_size += (*value_label).precalculate_field_size(self.fields().label.ordinal);
+        }
+        if let Some(value_field_type) = &self.field_type {
+            total_size += (*value_field_type).precalculate_field_size(self.fields().field_type.ordinal);
+        }
+        if let Some(value_type_name) = &self.type_name {
+            total_size += value_type_name.as_str().precalculate_field_size(self.fields().type_name.ordinal);
+        }
+        if let Some(value_options) = &self.options {
+            total_size += value_options.precalculate_sub_message_size(self.fields().options.ordinal);
+        }
+        if let Some(value_oneof_index) = &self.oneof_index {
+            total_size += (*value_oneof_index).precalculate_field_size(self.fields().oneof_index.ordinal);
+        }
+        if let Some(value_json_name) = &self.json_name {
+            total_size += value_json_name.as_str().precalculate_field_size(self.fields().json_name.ordinal);
+        }
+        total_size
+    }
+}
+impl picopb::decode::DecodeMessage for FieldDescriptorProto {
+    fn decode_from(buf: &mut picopb::decode::DecodeBuffer<'_>) -> picopb::decode::Result<Self> {
+        let mut message = Self::default();
+        while !buf.is_empty() {
+            let (field, wire_type) = buf.read_tag()?;
+            match field.0 {
+                1 => {
+                    message.name = Some(picopb::decode::read_field(buf, field, wire_type)?);
+                }
+                3 => {
+                    message.number = Some(picopb::decode::read_field(buf, field, wire_type)?);
+                }
+                4 => {
+                    message.label = Some(picopb::decode::read_field(buf, field, wire_type)?);
+                }
+                5 => {
+                    message.field_type = Some(picopb::decode::read_field(buf, field, wire_type)?);
+                }
+                6 => {
+                    message.type_name = Some(picopb::decode::read_field(buf, field, wire_type)?);
+                }
+                8 => {
+                    message.options = Some(picopb::decode::read_field(buf, field, wire_type)?);
+                }
+                9 => {
+                    message.oneof_index = Some(picopb::decode::read_field(buf, field, wire_type)?);
+                }
+                10 => {
+                    message.json_name = Some(picopb::decode::read_field(buf, field, wire_type)?);
+                }
+                _ => buf.skip_unknown(field, wire_type)?,
+            }
+        }
+        Ok(message)
+    }
+}
+impl picopb::decode::FromWire for FieldDescriptorProto {
+    fn wire_type() -> WireType {
+        WireType::Len
+    }
+    fn read(buf: &mut picopb::decode::DecodeBuffer<'_>) -> picopb::decode::Result<Self> {
+        <Self as picopb::decode::DecodeMessage>::decode_from(&mut buf.read_sub_buffer()?)
+    }
+}
+impl Randomize for FieldDescriptorProto {
+    fn randomized<R: picopb::randomizer::Rng + ?Sized>(rng: &mut R) -> Self {
+        Self {
+            name: randomized(rng),
+            number: randomized(rng),
+            label: randomized(rng),
+            field_type: randomized(rng),
+            type_name: randomized(rng),
+            options: randomized(rng),
+            oneof_index: randomized(rng),
+            json_name: randomized(rng),
+        }
+    }
+}
+impl picopb::text_format::ToText for FieldDescriptorProto {
+    fn write_text<W: std::fmt::Write>(&self, out: &mut W) -> std::fmt::Result {
+        let mut writer = picopb::text_format::FieldWriter::new(out);
+        if let Some(value_name) = &self.name {
+            writer.field(self.fields().name.identifier, value_name)?;
+        }
+        if let Some(value_number) = &self.number {
+            writer.field(self.fields().number.identifier, value_number)?;
+        }
+        if let Some(value_label) = &self.label {
+            writer.field(self.fields().label.identifier, value_label)?;
+        }
+        if let Some(value_field_type) = &self.field_type {
+            writer.field(self.fields().field_type.identifier, value_field_type)?;
+        }
+        if let Some(value_type_name) = &self.type_name {
+            writer.field(self.fields().type_name.identifier, value_type_name)?;
+        }
+        if let Some(value_options) = &self.options {
+            writer.message(self.fields().options.identifier, value_options)?;
+        }
+        if let Some(value_oneof_index) = &self.oneof_index {
+            writer.field(self.fields().oneof_index.identifier, value_oneof_index)?;
+        }
+        if let Some(value_json_name) = &self.json_name {
+            writer.field(self.fields().json_name.identifier, value_json_name)?;
+        }
+        Ok(())
+    }
+}
+impl picopb::text_format::FromText for FieldDescriptorProto {
+    fn from_text(text: &picopb::text_format::TextValue) -> picopb::text_format::Result<Self> {
+        let mut message = Self::default();
+        for (name, value) in text.as_message()? {
+            match name.as_str() {
+                "name" => {
+                    message.name = Some(picopb::text_format::FromText::from_text(value)?);
+                }
+                "number" => {
+                    message.number = Some(picopb::text_format::FromText::from_text(value)?);
+                }
+                "label" => {
+                    message.label = Some(picopb::text_format::FromText::from_text(value)?);
+                }
+                "field_type" => {
+                    message.field_type = Some(picopb::text_format::FromText::from_text(value)?);
+                }
+                "type_name" => {
+                    message.type_name = Some(picopb::text_format::FromText::from_text(value)?);
+                }
+                "options" => {
+                    message.options = Some(picopb::text_format::FromText::from_text(value)?);
+                }
+                "oneof_index" => {
+                    message.oneof_index = Some(picopb::text_format::FromText::from_text(value)?);
+                }
+                "json_name" => {
+                    message.json_name = Some(picopb::text_format::FromText::from_text(value)?);
+                }
+                _ => return Err(picopb::text_format::TextFormatError::UnknownField(name.clone())),
+            }
+        }
+        Ok(message)
+    }
+}
+impl FieldDescriptorProto {
+    pub const DESCRIPTOR: picopb::reflect::MessageDescriptor = picopb::reflect::MessageDescriptor {
+        name: "FieldDescriptorProto",
+        fields: &[
+            FIELD_DESCRIPTOR_PROTO_FIELDS.name,
+            FIELD_DESCRIPTOR_PROTO_FIELDS.number,
+            FIELD_DESCRIPTOR_PROTO_FIELDS.label,
+            FIELD_DESCRIPTOR_PROTO_FIELDS.field_type,
+            FIELD_DESCRIPTOR_PROTO_FIELDS.type_name,
+            FIELD_DESCRIPTOR_PROTO_FIELDS.options,
+            FIELD_DESCRIPTOR_PROTO_FIELDS.oneof_index,
+            FIELD_DESCRIPTOR_PROTO_FIELDS.json_name,
+        ],
+    };
+}
+impl picopb::reflect::Reflect for FieldDescriptorProto {
+    fn descriptor(&self) -> &'static picopb::reflect::MessageDescriptor {
+        &Self::DESCRIPTOR
+    }
+    fn get(&self, number: u32) -> Option<Vec<picopb::reflect::FieldValue<'_>>> {
+        match number {
+            1 => Some(self.name.iter().map(picopb::reflect::ReflectField::to_value).collect()),
+            3 => Some(self.number.iter().map(picopb::reflect::ReflectField::to_value).collect()),
+            4 => Some(self.label.iter().map(picopb::reflect::ReflectField::to_value).collect()),
+            5 => Some(self.field_type.iter().map(picopb::reflect::ReflectField::to_value).collect()),
+            6 => Some(self.type_name.iter().map(picopb::reflect::ReflectField::to_value).collect()),
+            8 => Some(self.options.iter().map(picopb::reflect::ReflectField::to_value).collect()),
+            9 => Some(self.oneof_index.iter().map(picopb::reflect::ReflectField::to_value).collect()),
+            10 => Some(self.json_name.iter().map(picopb::reflect::ReflectField::to_value).collect()),
+            _ => None,
+        }
+    }
+    fn set(&mut self, number: u32, values: &[picopb::reflect::FieldValue]) -> picopb::reflect::Result<()> {
+        match number {
+            1 => picopb::reflect::set_optional(&mut self.name, values),
+            3 => picopb::reflect::set_optional(&mut self.number, values),
+            4 => picopb::reflect::set_optional(&mut self.label, values),
+            5 => picopb::reflect::set_optional(&mut self.field_type, values),
+            6 => picopb::reflect::set_optional(&mut self.type_name, values),
+            8 => picopb::reflect::set_optional(&mut self.options, values),
+            9 => picopb::reflect::set_optional(&mut self.oneof_index, values),
+            10 => picopb::reflect::set_optional(&mut self.json_name, values),
+            _ => Err(picopb::reflect::ReflectError::UnknownField(number.to_string())),
+        }
+    }
+}
+impl picopb::reflect::ReflectField for FieldDescriptorProto {
+    fn to_value(&self) -> picopb::reflect::FieldValue<'_> {
+        picopb::reflect::FieldValue::Message(self)
+    }
+    fn from_value(value: &picopb::reflect::FieldValue) -> picopb::reflect::Result<Self> {
+        let mut message = Self::default();
+        picopb::reflect::copy_fields(&mut message, value.as_message()?)?;
+        Ok(message)
+    }
+}
+#[derive(Default, Debug)]
+pub struct FieldOptions {
+    pub packed: Option<bool>,
+    pub nanopb: Option<NanoPBOptions>,
+}
+#[derive(Debug)]
+pub struct FieldOptionsFieldsType<'a> {
+    pub packed: picopb::common::ConstMessageField<'a>,
+    pub nanopb: picopb::common::ConstMessageField<'a>,
+}
+const FIELD_OPTIONS_FIELDS: FieldOptionsFieldsType = FieldOptionsFieldsType {
+    packed: picopb::common::ConstMessageField {
+        qualifier: picopb::common::FieldQualifier::Optional,
+        field_type: picopb::common::FieldType::Bool,
+        identifier: "packed",
+        json_name: "packed",
+        ordinal: picopb::common::Field(2),
+    },
+    nanopb: picopb::common::ConstMessageField {
+        qualifier: picopb::common::FieldQualifier::Optional,
+        field_type: picopb::common::FieldType::MessageType("NanoPBOptions"),
+        identifier: "nanopb",
+        json_name: "nanopb",
+        ordinal: picopb::common::Field(1010),
+    },
+};
+impl FieldOptions {
+    fn fields(&self) -> FieldOptionsFieldsType<'static> {
+        FIELD_OPTIONS_FIELDS
+    }
+}
+impl FieldOptions {
+    pub const MAX_ENCODED_LEN: usize = 44;
+}
+impl picopb::wiretypes::WireTyped for &FieldOptions {
+    fn wiretype(&self) -> WireType {
+        WireType::Len
+    }
+}
+impl picopb::encode::ToWire for &FieldOptions {
+    fn append<W: picopb::encode::EncodeSink>(&self, buf: &mut W) -> std::io::Result<usize> {
+        let mut total_size = 0;
+        if let Some(value_packed) = &self.packed {
+            total_size += buf.encode(*value_packed, self.fields().packed.ordinal)?;
+        }
+        if let Some(value_nanopb) = &self.nanopb {
+            total_size += buf.encode_sub_message(value_nanopb, self.fields().nanopb.ordinal)?;
+        }
+        Ok(total_size)
+    }
+    fn precalculate_size(&self) -> usize {
+        let mut total_size = 0;
+        if let Some(value_packed) = &self.packed {
+            total_size += (*value_packed).precalculate_field_size(self.fields().packed.ordinal);
+        }
+        if let Some(value_nanopb) = &self.nanopb {
+            total_size += value_nanopb.precalculate_sub_message_size(self.fields().nanopb.ordinal);
+        }
+        total_size
+    }
+}
+impl picopb::decode::DecodeMessage for FieldOptions {
+    fn decode_from(buf: &mut picopb::decode::DecodeBuffer<'_>) -> picopb::decode::Result<Self> {
+        let mut message = Self::default();
+        while !buf.is_empty() {
+            let (field, wire_type) = buf.read_tag()?;
+            match field.0 {
+                2 => {
+                    message.packed = Some(picopb::decode::read_field(buf, field, wire_type)?);
+                }
+                1010 => {
+                    message.nanopb = Some(picopb::decode::read_field(buf, field, wire_type)?);
+                }
+                _ => buf.skip_unknown(field, wire_type)?,
+            }
+        }
+        Ok(message)
+    }
+}
+impl picopb::decode::FromWire for FieldOptions {
+    fn wire_type() -> WireType {
+        WireType::Len
+    }
+    fn read(buf: &mut picopb::decode::DecodeBuffer<'_>) -> picopb::decode::Result<Self> {
+        <Self as picopb::decode::DecodeMessage>::decode_from(&mut buf.read_sub_buffer()?)
+    }
+}
+impl Randomize for FieldOptions {
+    fn randomized<R: picopb::randomizer::Rng + ?Sized>(rng: &mut R) -> Self {
+        Self {
+            packed: randomized(rng),
+            nanopb: randomized(rng),
+        }
+    }
+}
+impl picopb::text_format::ToText for FieldOptions {
+    fn write_text<W: std::fmt::Write>(&self, out: &mut W) -> std::fmt::Result {
+        let mut writer = picopb::text_format::FieldWriter::new(out);
+        if let Some(value_packed) = &self.packed {
+            writer.field(self.fields().packed.identifier, value_packed)?;
+        }
+        if let Some(value_nanopb) = &self.nanopb {
+            writer.message(self.fields().nanopb.identifier, value_nanopb)?;
+        }
+        Ok(())
+    }
+}
+impl picopb::text_format::FromText for FieldOptions {
+    fn from_text(text: &picopb::text_format::TextValue) -> picopb::text_format::Result<Self> {
+        let mut message = Self::default();
+        for (name, value) in text.as_message()? {
+            match name.as_str() {
+                "packed" => {
+                    message.packed = Some(picopb::text_format::FromText::from_text(value)?);
+                }
+                "nanopb" => {
+                    message.nanopb = Some(picopb::text_format::FromText::from_text(value)?);
+                }
+                _ => return Err(picopb::text_format::TextFormatError::UnknownField(name.clone())),
+            }
+        }
+        Ok(message)
+    }
+}
+impl FieldOptions {
+    pub const DESCRIPTOR: picopb::reflect::MessageDescriptor = picopb::reflect::MessageDescriptor {
+        name: "FieldOptions",
+        fields: &[
+            FIELD_OPTIONS_FIELDS.packed,
+            FIELD_OPTIONS_FIELDS.nanopb,
+        ],
+    };
+}
+impl picopb::reflect::Reflect for FieldOptions {
+    fn descriptor(&self) -> &'static picopb::reflect::MessageDescriptor {
+        &Self::DESCRIPTOR
+    }
+    fn get(&self, number: u32) -> Option<Vec<picopb::reflect::FieldValue<'_>>> {
+        match number {
+            2 => Some(self.packed.iter().map(picopb::reflect::ReflectField::to_value).collect()),
+            1010 => Some(self.nanopb.iter().map(picopb::reflect::ReflectField::to_value).collect()),
+            _ => None,
+        }
+    }
+    fn set(&mut self, number: u32, values: &[picopb::reflect::FieldValue]) -> picopb::reflect::Result<()> {
+        match number {
+            2 => picopb::reflect::set_optional(&mut self.packed, values),
+            1010 => picopb::reflect::set_optional(&mut self.nanopb, values),
+            _ => Err(picopb::reflect::ReflectError::UnknownField(number.to_string())),
+        }
+    }
+}
+impl picopb::reflect::ReflectField for FieldOptions {
+    fn to_value(&self) -> picopb::reflect::FieldValue<'_> {
+        picopb::reflect::FieldValue::Message(self)
+    }
+    fn from_value(value: &picopb::reflect::FieldValue) -> picopb::reflect::Result<Self> {
+        let mut message = Self::default();
+        picopb::reflect::copy_fields(&mut message, value.as_message()?)?;
+        Ok(message)
+    }
+}
+#[derive(Default, Debug)]
+pub struct FileDescriptorProto {
+    pub name: Option<String>,
+    pub package: Option<String>,
+    pub dependency: Vec<String>,
+    pub message_type: Vec<DescriptorProto>,
+    pub enum_type: Vec<EnumDescriptorProto>,
+    pub syntax: Option<String>,
+}
+#[derive(Debug)]
+pub struct FileDescriptorProtoFieldsType<'a> {
+    pub name: picopb::common::ConstMessageField<'a>,
+    pub package: picopb::common::ConstMessageField<'a>,
+    pub dependency: picopb::common::ConstMessageField<'a>,
+    pub message_type: picopb::common::ConstMessageField<'a>,
+    pub enum_type: picopb::common::ConstMessageField<'a>,
+    pub syntax: picopb::common::ConstMessageField<'a>,
+}
+const FILE_DESCRIPTOR_PROTO_FIELDS: FileDescriptorProtoFieldsType = FileDescriptorProtoFieldsType {
+    name: picopb::common::ConstMessageField {
+        qualifier: picopb::common::FieldQualifier::Optional,
+        field_type: picopb::common::FieldType::UnboundedString,
+        identifier: "name",
+        json_name: "name",
+        ordinal: picopb::common::Field(1),
+    },
+    package: picopb::common::ConstMessageField {
+        qualifier: picopb::common::FieldQualifier::Optional,
+        field_type: picopb::common::FieldType::UnboundedString,
+        identifier: "package",
+        json_name: "package",
+        ordinal: picopb::common::Field(2),
+    },
+    dependency: picopb::common::ConstMessageField {
+        qualifier: picopb::common::FieldQualifier::RepeatedUnbounded,
+        field_type: picopb::common::FieldType::UnboundedString,
+        identifier: "dependency",
+        json_name: "dependency",
+        ordinal: picopb::common::Field(3),
+    },
+    message_type: picopb::common::ConstMessageField {
+        qualifier: picopb::common::FieldQualifier::RepeatedUnbounded,
+        field_type: picopb::common::FieldType::MessageType("DescriptorProto"),
+        identifier: "message_type",
+        json_name: "messageType",
+        ordinal: picopb::common::Field(4),
+    },
+    enum_type: picopb::common::ConstMessageField {
+        qualifier: picopb::common::FieldQualifier::RepeatedUnbounded,
+        field_type: picopb::common::FieldType::MessageType("EnumDescriptorProto"),
+        identifier: "enum_type",
+        json_name: "enumType",
+        ordinal: picopb::common::Field(5),
+    },
+    syntax: picopb::common::ConstMessageField {
+        qualifier: picopb::common::FieldQualifier::Optional,
+        field_type: picopb::common::FieldType::UnboundedString,
+        identifier: "syntax",
+        json_name: "syntax",
+        ordinal: picopb::common::Field(12),
+    },
+};
+impl FileDescriptorProto {
+    fn fields(&self) -> FileDescriptorProtoFieldsType<'static> {
+        FILE_DESCRIPTOR_PROTO_FIELDS
+    }
+}
+// FileDescriptorProto has no MAX_ENCODED_LEN, unbounded: FileDescriptorProto.name
+impl picopb::wiretypes::WireTyped for &FileDescriptorProto {
+    fn wiretype(&self) -> WireType {
+        WireType::Len
+    }
+}
+impl picopb::encode::ToWire for &FileDescriptorProto {
+    fn append<W: picopb::encode::EncodeSink>(&self, buf: &mut W) -> std::io::Result<usize> {
+        let mut total_size = 0;
+        if let Some(value_name) = &self.name {
+            total_size += buf.encode(value_name.as_str(), self.fields().name.ordinal)?;
+        }
+        if let Some(value_package) = &self.package {
+            total_size += buf.encode(value_package.as_str(), self.fields().package.ordinal)?;
+        }
+        for value_dependency in self.dependency.iter() {
+            total_size += buf.encode(value_dependency.as_str(), self.fields().dependency.ordinal)?;
+        }
+        for value_message_type in self.message_type.iter() {
+            total_size += buf.encode_sub_message(value_message_type, self.fields().message_type.ordinal)?;
+        }
+        for value_enum_type in self.enum_type.iter() {
+            total_size += buf.encode_sub_message(value_enum_type, self.fields().enum_type.ordinal)?;
+        }
+        if let Some(value_syntax) = &self.syntax {
+            total_size += buf.encode(value_syntax.as_str(), self.fields().syntax.ordinal)?;
+        }
+        Ok(total_size)
+    }
+    fn precalculate_size(&self) -> usize {
+        let mut total_size = 0;
+        if let Some(value_name) = &self.name {
+            total_size += value_name.as_str().precalculate_field_size(self.fields().name.ordinal);
+        }
+        if let Some(value_package) = &self.package {
+            total_size += value_package.as_str().precalculate_field_size(self.fields().package.ordinal);
+        }
+        for value_dependency in self.dependency.iter() {
+            total_size += value_dependency.as_str().precalculate_field_size(self.fields().dependency.ordinal);
+        }
+        for value_message_type in self.message_type.iter() {
+            total_size += value_message_type.precalculate_sub_message_size(self.fields().message_type.ordinal);
+        }
+        for value_enum_type in self.enum_type.iter() {
+            total_size += value_enum_type.precalculate_sub_message_size(self.fields().enum_type.ordinal);
+        }
+        if let Some(value_syntax) = &self.syntax {
+            total_size += value_syntax.as_str().precalculate_field_size(self.fields().syntax.ordinal);
+        }
+        total_size
+    }
+}
+impl picopb::decode::DecodeMessage for FileDescriptorProto {
+    fn decode_from(buf: &mut picopb::decode::DecodeBuffer<'_>) -> picopb::decode::Result<Self> {
+        let mut message = Self::default();
+        while !buf.is_empty() {
+            let (field, wire_type) = buf.read_tag()?;
+            match field.0 {
+                1 => {
+                    message.name = Some(picopb::decode::read_field(buf, field, wire_type)?);
+                }
+                2 => {
+                    message.package = Some(picopb::decode::read_field(buf, field, wire_type)?);
+                }
+                3 => {
+                    picopb::decode::read_repeated(buf, field, wire_type, |value| {
+                        message.dependency.push(value);
+                        Ok(())
+                    })?;
+                }
+                4 => {
+                    picopb::decode::read_repeated(buf, field, wire_type, |value| {
+                        message.message_type.push(value);
+                        Ok(())
+                    })?;
+                }
+                5 => {
+                    picopb::decode::read_repeated(buf, field, wire_type, |value| {
+                        message.enum_type.push(value);
+                        Ok(())
+                    })?;
+                }
+                12 => {
+                    message.syntax = Some(picopb::decode::read_field(buf, field, wire_type)?);
+                }
+                _ => buf.skip_unknown(field, wire_type)?,
+            }
+        }
+        Ok(message)
+    }
+}
+impl picopb::decode::FromWire for FileDescriptorProto {
+    fn wire_type() -> WireType {
+        WireType::Len
+    }
+    fn read(buf: &mut picopb::decode::DecodeBuffer<'_>) -> picopb::decode::Result<Self> {
+        <Self as picopb::decode::DecodeMessage>::decode_from(&mut buf.read_sub_buffer()?)
+    }
+}
+impl Randomize for FileDescriptorProto {
+    fn randomized<R: picopb::randomizer::Rng + ?Sized>(rng: &mut R) -> Self {
+        Self {
+            name: randomized(rng),
+            package: randomized(rng),
+            dependency: randomized(rng),
+            message_type: randomized(rng),
+            enum_type: randomized(rng),
+            syntax: randomized(rng),
+        }
+    }
+}
+impl picopb::text_format::ToText for FileDescriptorProto {
+    fn write_text<W: std::fmt::Write>(&self, out: &mut W) -> std::fmt::Result {
+        let mut writer = picopb::text_format::FieldWriter::new(out);
+        if let Some(value_name) = &self.name {
+            writer.field(self.fields().name.identifier, value_name)?;
+        }
+        if let Some(value_package) = &self.package {
+            writer.field(self.fields().package.identifier, value_package)?;
+        }
+        for value_dependency in self.dependency.iter() {
+            writer.field(self.fields().dependency.identifier, value_dependency)?;
+        }
+        for value_message_type in self.message_type.iter() {
+            writer.message(self.fields().message_type.identifier, value_message_type)?;
+        }
+        for value_enum_type in self.enum_type.iter() {
+            writer.message(self.fields().enum_type.identifier, value_enum_type)?;
+        }
+        if let Some(value_syntax) = &self.syntax {
+            writer.field(self.fields().syntax.identifier, value_syntax)?;
+        }
+        Ok(())
+    }
+}
+impl picopb::text_format::FromText for FileDescriptorProto {
+    fn from_text(text: &picopb::text_format::TextValue) -> picopb::text_format::Result<Self> {
+        let mut message = Self::default();
+        for (name, value) in text.as_message()? {
+            match name.as_str() {
+                "name" => {
+                    message.name = Some(picopb::text_format::FromText::from_text(value)?);
+                }
+                "package" => {
+                    message.package = Some(picopb::text_format::FromText::from_text(value)?);
+                }
+                "dependency" => {
+                    picopb::text_format::read_repeated(value, |value| {
+                        message.dependency.push(value);
+                        Ok(())
+                    })?;
+                }
+                "message_type" => {
+                    picopb::text_format::read_repeated(value, |value| {
+                        message.message_type.push(value);
+                        Ok(())
+                    })?;
+                }
+                "enum_type" => {
+                    picopb::text_format::read_repeated(value, |value| {
+                        message.enum_type.push(value);
+                        Ok(())
+                    })?;
+                }
+                "syntax" => {
+                    message.syntax = Some(picopb::text_format::FromText::from_text(value)?);
+                }
+                _ => return Err(picopb::text_format::TextFormatError::UnknownField(name.clone())),
+            }
+        }
+        Ok(message)
+    }
+}
+impl FileDescriptorProto {
+    pub const DESCRIPTOR: picopb::reflect::MessageDescriptor = picopb::reflect::MessageDescriptor {
+        name: "FileDescriptorProto",
+        fields: &[
+            FILE_DESCRIPTOR_PROTO_FIELDS.name,
+            FILE_DESCRIPTOR_PROTO_FIELDS.package,
+            FILE_DESCRIPTOR_PROTO_FIELDS.dependency,
+            FILE_DESCRIPTOR_PROTO_FIELDS.message_type,
+            FILE_DESCRIPTOR_PROTO_FIELDS.enum_type,
+            FILE_DESCRIPTOR_PROTO_FIELDS.syntax,
+        ],
+    };
+}
+impl picopb::reflect::Reflect for FileDescriptorProto {
+    fn descriptor(&self) -> &'static picopb::reflect::MessageDescriptor {
+        &Self::DESCRIPTOR
+    }
+    fn get(&self, number: u32) -> Option<Vec<picopb::reflect::FieldValue<'_>>> {
+        match number {
+            1 => Some(self.name.iter().map(picopb::reflect::ReflectField::to_value).collect()),
+            2 => Some(self.package.iter().map(picopb::reflect::ReflectField::to_value).collect()),
+            3 => Some(self.dependency.iter().map(picopb::reflect::ReflectField::to_value).collect()),
+            4 => Some(self.message_type.iter().map(picopb::reflect::ReflectField::to_value).collect()),
+            5 => Some(self.enum_type.iter().map(picopb::reflect::ReflectField::to_value).collect()),
+            12 => Some(self.syntax.iter().map(picopb::reflect::ReflectField::to_value).collect()),
+            _ => None,
+        }
+    }
+    fn set(&mut self, number: u32, values: &[picopb::reflect::FieldValue]) -> picopb::reflect::Result<()> {
+        match number {
+            1 => picopb::reflect::set_optional(&mut self.name, values),
+            2 => picopb::reflect::set_optional(&mut self.package, values),
+            3 => picopb::reflect::set_repeated(&mut self.dependency, values),
+            4 => picopb::reflect::set_repeated(&mut self.message_type, values),
+            5 => picopb::reflect::set_repeated(&mut self.enum_type, values),
+            12 => picopb::reflect::set_optional(&mut self.syntax, values),
+            _ => Err(picopb::reflect::ReflectError::UnknownField(number.to_string())),
+        }
+    }
+}
+impl picopb::reflect::ReflectField for FileDescriptorProto {
+    fn to_value(&self) -> picopb::reflect::FieldValue<'_> {
+        picopb::reflect::FieldValue::Message(self)
+    }
+    fn from_value(value: &picopb::reflect::FieldValue) -> picopb::reflect::Result<Self> {
+        let mut message = Self::default();
+        picopb::reflect::copy_fields(&mut message, value.as_message()?)?;
+        Ok(message)
+    }
+}
+#[derive(Default, Debug)]
+pub struct FileDescriptorSet {
+    pub file: Vec<FileDescriptorProto>,
+}
+#[derive(Debug)]
+pub struct FileDescriptorSetFieldsType<'a> {
+    pub file: picopb::common::ConstMessageField<'a>,
+}
+const FILE_DESCRIPTOR_SET_FIELDS: FileDescriptorSetFieldsType = FileDescriptorSetFieldsType {
+    file: picopb::common::ConstMessageField {
+        qualifier: picopb::common::FieldQualifier::RepeatedUnbounded,
+        field_type: picopb::common::FieldType::MessageType("FileDescriptorProto"),
+        identifier: "file",
+        json_name: "file",
+        ordinal: picopb::common::Field(1),
+    },
+};
+impl FileDescriptorSet {
+    fn fields(&self) -> FileDescriptorSetFieldsType<'static> {
+        FILE_DESCRIPTOR_SET_FIELDS
+    }
+}
+// FileDescriptorSet has no MAX_ENCODED_LEN, unbounded: FileDescriptorProto.name
+impl picopb::wiretypes::WireTyped for &FileDescriptorSet {
+    fn wiretype(&self) -> WireType {
+        WireType::Len
+    }
+}
+impl picopb::encode::ToWire for &FileDescriptorSet {
+    fn append<W: picopb::encode::EncodeSink>(&self, buf: &mut W) -> std::io::Result<usize> {
+        let mut total_size = 0;
+        for value_file in self.file.iter() {
+            total_size += buf.encode_sub_message(value_file, self.fields().file.ordinal)?;
+        }
+        Ok(total_size)
+    }
+    fn precalculate_size(&self) -> usize {
+        let mut total_size = 0;
+        for value_file in self.file.iter() {
+            total_size += value_file.precalculate_sub_message_size(self.fields().file.ordinal);
+        }
+        total_size
+    }
+}
+impl picopb::decode::DecodeMessage for FileDescriptorSet {
+    fn decode_from(buf: &mut picopb::decode::DecodeBuffer<'_>) -> picopb::decode::Result<Self> {
+        let mut message = Self::default();
+        while !buf.is_empty() {
+            let (field, wire_type) = buf.read_tag()?;
+            match field.0 {
+                1 => {
+                    picopb::decode::read_repeated(buf, field, wire_type, |value| {
+                        message.file.push(value);
+                        Ok(())
+                    })?;
+                }
+                _ => buf.skip_unknown(field, wire_type)?,
+            }
+        }
+        Ok(message)
+    }
+}
+impl picopb::decode::FromWire for FileDescriptorSet {
+    fn wire_type() -> WireType {
+        WireType::Len
+    }
+    fn read(buf: &mut picopb::decode::DecodeBuffer<'_>) -> picopb::decode::Result<Self> {
+        <Self as picopb::decode::DecodeMessage>::decode_from(&mut buf.read_sub_buffer()?)
+    }
+}
+impl Randomize for FileDescriptorSet {
+    fn randomized<R: picopb::randomizer::Rng + ?Sized>(rng: &mut R) -> Self {
+        Self {
+            file: randomized(rng),
+        }
+    }
+}
+impl picopb::text_format::ToText for FileDescriptorSet {
+    fn write_text<W: std::fmt::Write>(&self, out: &mut W) -> std::fmt::Result {
+        let mut writer = picopb::text_format::FieldWriter::new(out);
+        for value_file in self.file.iter() {
+            writer.message(self.fields().file.identifier, value_file)?;
+        }
+        Ok(())
+    }
+}
+impl picopb::text_format::FromText for FileDescriptorSet {
+    fn from_text(text: &picopb::text_format::TextValue) -> picopb::text_format::Result<Self> {
+        let mut message = Self::default();
+        for (name, value) in text.as_message()? {
+            match name.as_str() {
+                "file" => {
+                    picopb::text_format::read_repeated(value, |value| {
+                        message.file.push(value);
+                        Ok(())
+                    })?;
+                }
+                _ => return Err(picopb::text_format::TextFormatError::UnknownField(name.clone())),
+            }
+        }
+        Ok(message)
+    }
+}
+impl FileDescriptorSet {
+    pub const DESCRIPTOR: picopb::reflect::MessageDescriptor = picopb::reflect::MessageDescriptor {
+        name: "FileDescriptorSet",
+        fields: &[
+            FILE_DESCRIPTOR_SET_FIELDS.file,
+        ],
+    };
+}
+impl picopb::reflect::Reflect for FileDescriptorSet {
+    fn descriptor(&self) -> &'static picopb::reflect::MessageDescriptor {
+        &Self::DESCRIPTOR
+    }
+    fn get(&self, number: u32) -> Option<Vec<picopb::reflect::FieldValue<'_>>> {
+        match number {
+            1 => Some(self.file.iter().map(picopb::reflect::ReflectField::to_value).collect()),
+            _ => None,
+        }
+    }
+    fn set(&mut self, number: u32, values: &[picopb::reflect::FieldValue]) -> picopb::reflect::Result<()> {
+        match number {
+            1 => picopb::reflect::set_repeated(&mut self.file, values),
+            _ => Err(picopb::reflect::ReflectError::UnknownField(number.to_string())),
+        }
+    }
+}
+impl picopb::reflect::ReflectField for FileDescriptorSet {
+    fn to_value(&self) -> picopb::reflect::FieldValue<'_> {
+        picopb::reflect::FieldValue::Message(self)
+    }
+    fn from_value(value: &picopb::reflect::FieldValue) -> picopb::reflect::Result<Self> {
+        let mut message = Self::default();
+        picopb::reflect::copy_fields(&mut message, value.as_message()?)?;
+        Ok(message)
+    }
+}
+#[derive(Default, Debug)]
+pub struct MessageOptions {
+    pub nanopb_msgopt: Option<NanoPBOptions>,
+}
+#[derive(Debug)]
+pub struct MessageOptionsFieldsType<'a> {
+    pub nanopb_msgopt: picopb::common::ConstMessageField<'a>,
+}
+const MESSAGE_OPTIONS_FIELDS: MessageOptionsFieldsType = MessageOptionsFieldsType {
+    nanopb_msgopt: picopb::common::ConstMessageField {
+        qualifier: picopb::common::FieldQualifier::Optional,
+        field_type: picopb::common::FieldType::MessageType("NanoPBOptions"),
+        identifier: "nanopb_msgopt",
+        json_name: "nanopbMsgopt",
+        ordinal: picopb::common::Field(1010),
+    },
+};
+impl MessageOptions {
+    fn fields(&self) -> MessageOptionsFieldsType<'static> {
+        MESSAGE_OPTIONS_FIELDS
+    }
+}
+impl MessageOptions {
+    pub const MAX_ENCODED_LEN: usize = 42;
+}
+impl picopb::wiretypes::WireTyped for &MessageOptions {
+    fn wiretype(&self) -> WireType {
+        WireType::Len
+    }
+}
+impl picopb::encode::ToWire for &MessageOptions {
+    fn append<W: picopb::encode::EncodeSink>(&self, buf: &mut W) -> std::io::Result<usize> {
+        let mut total_size = 0;
+        if let Some(value_nanopb_msgopt) = &self.nanopb_msgopt {
+            total_size += buf.encode_sub_message(value_nanopb_msgopt, self.fields().nanopb_msgopt.ordinal)?;
+        }
+        Ok(total_size)
+    }
+    fn precalculate_size(&self) -> usize {
+        let mut total_size = 0;
+        if let Some(value_nanopb_msgopt) = &self.nanopb_msgopt {
+            total_size += value_nanopb_msgopt.precalculate_sub_message_size(self.fields().nanopb_msgopt.ordinal);
+        }
+        total_size
+    }
+}
+impl picopb::decode::DecodeMessage for MessageOptions {
+    fn decode_from(buf: &mut picopb::decode::DecodeBuffer<'_>) -> picopb::decode::Result<Self> {
+        let mut message = Self::default();
+        while !buf.is_empty() {
+            let (field, wire_type) = buf.read_tag()?;
+            match field.0 {
+                1010 => {
+                    message.nanopb_msgopt = Some(picopb::decode::read_field(buf, field, wire_type)?);
+                }
+                _ => buf.skip_unknown(field, wire_type)?,
+            }
+        }
+        Ok(message)
+    }
+}
+impl picopb::decode::FromWire for MessageOptions {
+    fn wire_type() -> WireType {
+        WireType::Len
+    }
+    fn read(buf: &mut picopb::decode::DecodeBuffer<'_>) -> picopb::decode::Result<Self> {
+        <Self as picopb::decode::DecodeMessage>::decode_from(&mut buf.read_sub_buffer()?)
+    }
+}
+impl Randomize for MessageOptions {
+    fn randomized<R: picopb::randomizer::Rng + ?Sized>(rng: &mut R) -> Self {
+        Self {
+            nanopb_msgopt: randomized(rng),
+        }
+    }
+}
+impl picopb::text_format::ToText for MessageOptions {
+    fn write_text<W: std::fmt::Write>(&self, out: &mut W) -> std::fmt::Result {
+        let mut writer = picopb::text_format::FieldWriter::new(out);
+        if let Some(value_nanopb_msgopt) = &self.nanopb_msgopt {
+            writer.message(self.fields().nanopb_msgopt.identifier, value_nanopb_msgopt)?;
+        }
+        Ok(())
+    }
+}
+impl picopb::text_format::FromText for MessageOptions {
+    fn from_text(text: &picopb::text_format::TextValue) -> picopb::text_format::Result<Self> {
+        let mut message = Self::default();
+        for (name, value) in text.as_message()? {
+            match name.as_str() {
+                "nanopb_msgopt" => {
+                    message.nanopb_msgopt = Some(picopb::text_format::FromText::from_text(value)?);
+                }
+                _ => return Err(picopb::text_format::TextFormatError::UnknownField(name.clone())),
+            }
+        }
+        Ok(message)
+    }
+}
+impl MessageOptions {
+    pub const DESCRIPTOR: picopb::reflect::MessageDescriptor = picopb::reflect::MessageDescriptor {
+        name: "MessageOptions",
+        fields: &[
+            MESSAGE_OPTIONS_FIELDS.nanopb_msgopt,
+        ],
+    };
+}
+impl picopb::reflect::Reflect for MessageOptions {
+    fn descriptor(&self) -> &'static picopb::reflect::MessageDescriptor {
+        &Self::DESCRIPTOR
+    }
+    fn get(&self, number: u32) -> Option<Vec<picopb::reflect::FieldValue<'_>>> {
+        match number {
+            1010 => Some(self.nanopb_msgopt.iter().map(picopb::reflect::ReflectField::to_value).collect()),
+            _ => None,
+        }
+    }
+    fn set(&mut self, number: u32, values: &[picopb::reflect::FieldValue]) -> picopb::reflect::Result<()> {
+        match number {
+            1010 => picopb::reflect::set_optional(&mut self.nanopb_msgopt, values),
+            _ => Err(picopb::reflect::ReflectError::UnknownField(number.to_string())),
+        }
+    }
+}
+impl picopb::reflect::ReflectField for MessageOptions {
+    fn to_value(&self) -> picopb::reflect::FieldValue<'_> {
+        picopb::reflect::FieldValue::Message(self)
+    }
+    fn from_value(value: &picopb::reflect::FieldValue) -> picopb::reflect::Result<Self> {
+        let mut message = Self::default();
+        picopb::reflect::copy_fields(&mut message, value.as_message()?)?;
+        Ok(message)
+    }
+}
+#[derive(Default, Debug)]
+pub struct NanoPBOptions {
+    pub max_size: Option<i32>,
+    pub max_count: Option<i32>,
+    pub msgid: Option<u32>,
+    pub max_length: Option<i32>,
+}
+#[derive(Debug)]
+pub struct NanoPBOptionsFieldsType<'a> {
+    pub max_size: picopb::common::ConstMessageField<'a>,
+    pub max_count: picopb::common::ConstMessageField<'a>,
+    pub msgid: picopb::common::ConstMessageField<'a>,
+    pub max_length: picopb::common::ConstMessageField<'a>,
+}
+const NANO_PB_OPTIONS_FIELDS: NanoPBOptionsFieldsType = NanoPBOptionsFieldsType {
+    max_size: picopb::common::ConstMessageField {
+        qualifier: picopb::common::FieldQualifier::Optional,
+        field_type: picopb::common::FieldType::Int32,
+        identifier: "max_size",
+        json_name: "maxSize",
+        ordinal: picopb::common::Field(1),
+    },
+    max_count: picopb::common::ConstMessageField {
+        qualifier: picopb::common::FieldQualifier::Optional,
+        field_type: picopb::common::FieldType::Int32,
+        identifier: "max_count",
+        json_name: "maxCount",
+        ordinal: picopb::common::Field(2),
+    },
+    msgid: picopb::common::ConstMessageField {
+        qualifier: picopb::common::FieldQualifier::Optional,
+        field_type: picopb::common::FieldType::Uint32,
+        identifier: "msgid",
+        json_name: "msgid",
+        ordinal: picopb::common::Field(9),
+    },
+    max_length: picopb::common::ConstMessageField {
+        qualifier: picopb::common::FieldQualifier::Optional,
+        field_type: picopb::common::FieldType::Int32,
+        identifier: "max_length",
+        json_name: "maxLength",
+        ordinal: picopb::common::Field(14),
+    },
+};
+impl NanoPBOptions {
+    fn fields(&self) -> NanoPBOptionsFieldsType<'static> {
+        NANO_PB_OPTIONS_FIELDS
+    }
+}
+impl NanoPBOptions {
+    pub const MAX_ENCODED_LEN: usize = 39;
+}
+impl picopb::wiretypes::WireTyped for &NanoPBOptions {
+    fn wiretype(&self) -> WireType {
+        WireType::Len
+    }
+}
+impl picopb::encode::ToWire for &NanoPBOptions {
+    fn append<W: picopb::encode::EncodeSink>(&self, buf: &mut W) -> std::io::Result<usize> {
+        let mut total_size = 0;
+        if let Some(value_max_size) = &self.max_size {
+            total_size += buf.encode(*value_max_size, self.fields().max_size.ordinal)?;
+        }
+        if let Some(value_max_count) = &self.max_count {
+            total_size += buf.encode(*value_max_count, self.fields().max_count.ordinal)?;
+        }
+        if let Some(value_msgid) = &self.msgid {
+            total_size += buf.encode(*value_msgid, self.fields().msgid.ordinal)?;
+        }
+        if let Some(value_max_length) = &self.max_length {
+            total_size += buf.encode(*value_max_length, self.fields().max_length.ordinal)?;
+        }
+        Ok(total_size)
+    }
+    fn precalculate_size(&self) -> usize {
+        let mut total_size = 0;
+        if let Some(value_max_size) = &self.max_size {
+            total_size += (*value_max_size).precalculate_field_size(self.fields().max_size.ordinal);
+        }
+        if let Some(value_max_count) = &self.max_count {
+            total_size += (*value_max_count).precalculate_field_size(self.fields().max_count.ordinal);
+        }
+        if let Some(value_msgid) = &self.msgid {
+            total_size += (*value_msgid).precalculate_field_size(self.fields().msgid.ordinal);
+        }
+        if let Some(value_max_length) = &self.max_length {
+            total_size += (*value_max_length).precalculate_field_size(self.fields().max_length.ordinal);
+        }
+        total_size
+    }
+}
+impl picopb::decode::DecodeMessage for NanoPBOptions {
+    fn decode_from(buf: &mut picopb::decode::DecodeBuffer<'_>) -> picopb::decode::Result<Self> {
+        let mut message = Self::default();
+        while !buf.is_empty() {
+            let (field, wire_type) = buf.read_tag()?;
+            match field.0 {
+                1 => {
+                    message.max_size = Some(picopb::decode::read_field(buf, field, wire_type)?);
+                }
+                2 => {
+                    message.max_count = Some(picopb::decode::read_field(buf, field, wire_type)?);
+                }
+                9 => {
+                    message.msgid = Some(picopb::decode::read_field(buf, field, wire_type)?);
+                }
+                14 => {
+                    message.max_length = Some(picopb::decode::read_field(buf, field, wire_type)?);
+                }
+                _ => buf.skip_unknown(field, wire_type)?,
+            }
+        }
+        Ok(message)
+    }
+}
+impl picopb::decode::FromWire for NanoPBOptions {
+    fn wire_type() -> WireType {
+        WireType::Len
+    }
+    fn read(buf: &mut picopb::decode::DecodeBuffer<'_>) -> picopb::decode::Result<Self> {
+        <Self as picopb::decode::DecodeMessage>::decode_from(&mut buf.read_sub_buffer()?)
+    }
+}
+impl Randomize for NanoPBOptions {
+    fn randomized<R: picopb::randomizer::Rng + ?Sized>(rng: &mut R) -> Self {
+        Self {
+            max_size: randomized(rng),
+            max_count: randomized(rng),
+            msgid: randomized(rng),
+            max_length: randomized(rng),
+        }
+    }
+}
+impl picopb::text_format::ToText for NanoPBOptions {
+    fn write_text<W: std::fmt::Write>(&self, out: &mut W) -> std::fmt::Result {
+        let mut writer = picopb::text_format::FieldWriter::new(out);
+        if let Some(value_max_size) = &self.max_size {
+            writer.field(self.fields().max_size.identifier, value_max_size)?;
+        }
+        if let Some(value_max_count) = &self.max_count {
+            writer.field(self.fields().max_count.identifier, value_max_count)?;
+        }
+        if let Some(value_msgid) = &self.msgid {
+            writer.field(self.fields().msgid.identifier, value_msgid)?;
+        }
+        if let Some(value_max_length) = &self.max_length {
+            writer.field(self.fields().max_length.identifier, value_max_length)?;
+        }
+        Ok(())
+    }
+}
+impl picopb::text_format::FromText for NanoPBOptions {
+    fn from_text(text: &picopb::text_format::TextValue) -> picopb::text_format::Result<Self> {
+        let mut message = Self::default();
+        for (name, value) in text.as_message()? {
+            match name.as_str() {
+                "max_size" => {
+                    message.max_size = Some(picopb::text_format::FromText::from_text(value)?);
+                }
+                "max_count" => {
+                    message.max_count = Some(picopb::text_format::FromText::from_text(value)?);
+                }
+                "msgid" => {
+                    message.msgid = Some(picopb::text_format::FromText::from_text(value)?);
+                }
+                "max_length" => {
+                    message.max_length = Some(picopb::text_format::FromText::from_text(value)?);
+                }
+                _ => return Err(picopb::text_format::TextFormatError::UnknownField(name.clone())),
+            }
+        }
+        Ok(message)
+    }
+}
+impl NanoPBOptions {
+    pub const DESCRIPTOR: picopb::reflect::MessageDescriptor = picopb::reflect::MessageDescriptor {
+        name: "NanoPBOptions",
+        fields: &[
+            NANO_PB_OPTIONS_FIELDS.max_size,
+            NANO_PB_OPTIONS_FIELDS.max_count,
+            NANO_PB_OPTIONS_FIELDS.msgid,
+            NANO_PB_OPTIONS_FIELDS.max_length,
+        ],
+    };
+}
+impl picopb::reflect::Reflect for NanoPBOptions {
+    fn descriptor(&self) -> &'static picopb::reflect::MessageDescriptor {
+        &Self::DESCRIPTOR
+    }
+    fn get(&self, number: u32) -> Option<Vec<picopb::reflect::FieldValue<'_>>> {
+        match number {
+            1 => Some(self.max_size.iter().map(picopb::reflect::ReflectField::to_value).collect()),
+            2 => Some(self.max_count.iter().map(picopb::reflect::ReflectField::to_value).collect()),
+            9 => Some(self.msgid.iter().map(picopb::reflect::ReflectField::to_value).collect()),
+            14 => Some(self.max_length.iter().map(picopb::reflect::ReflectField::to_value).collect()),
+            _ => None,
+        }
+    }
+    fn set(&mut self, number: u32, values: &[picopb::reflect::FieldValue]) -> picopb::reflect::Result<()> {
+        match number {
+            1 => picopb::reflect::set_optional(&mut self.max_size, values),
+            2 => picopb::reflect::set_optional(&mut self.max_count, values),
+            9 => picopb::reflect::set_optional(&mut self.msgid, values),
+            14 => picopb::reflect::set_optional(&mut self.max_length, values),
+            _ => Err(picopb::reflect::ReflectError::UnknownField(number.to_string())),
+        }
+    }
+}
+impl picopb::reflect::ReflectField for NanoPBOptions {
+    fn to_value(&self) -> picopb::reflect::FieldValue<'_> {
+        picopb::reflect::FieldValue::Message(self)
+    }
+    fn from_value(value: &picopb::reflect::FieldValue) -> picopb::reflect::Result<Self> {
+        let mut message = Self::default();
+        picopb::reflect::copy_fields(&mut message, value.as_message()?)?;
+        Ok(message)
+    }
+}
@@ -0,0 +1,262 @@
+use picopb::common::*;
+use picopb::encode::{EncodeSink, ToWire};
+use picopb::wiretypes::{WireType, WireTyped};
+use picopb::randomizer::{randomized, Randomize};
+use std::ops::Deref;
+#[allow(clippy::enum_variant_names)]
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub enum Color {
+    #[default]
+    ColorUnknown,
+    ColorRed,
+    ColorGreen,
+}
+impl TryFrom<i32> for Color {
+    type Error = String;
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Color::ColorUnknown),
+            1 => Ok(Color::ColorRed),
+            2 => Ok(Color::ColorGreen),
+            _ => Err(format!("invalid ordinal value: {} for enum Color", value)),
+        }
+    }
+}
+impl From<Color> for i32 {
+    fn from(value: Color) -> Self {
+        match value {
+            Color::ColorUnknown => 0,
+            Color::ColorRed => 1,
+            Color::ColorGreen => 2,
+        }
+    }
+}
+impl picopb::wiretypes::WireTyped for Color {
+    fn wiretype(&self) -> WireType {
+        WireType::VarInt
+    }
+}
+impl picopb::encode::ToWire for Color {
+    fn append<W: picopb::encode::EncodeSink>(&self, buf: &mut W) -> std::io::Result<usize> {
+        i32::from(*self).append(buf)
+    }
+    fn precalculate_size(&self) -> usize {
+        i32::from(*self).precalculate_size()
+    }
+}
+impl picopb::decode::FromWire for Color {
+    fn wire_type() -> WireType {
+        WireType::VarInt
+    }
+    fn read(buf: &mut picopb::decode::DecodeBuffer<'_>) -> picopb::decode::Result<Self> {
+        let value = buf.read_varint()? as i64;
+        Self::try_from(value as i32).map_err(|_| picopb::decode::DecodeError::InvalidEnumValue(value))
+    }
+}
+impl picopb::text_format::ToText for Color {
+    fn write_text<W: std::fmt::Write>(&self, out: &mut W) -> std::fmt::Result {
+        let name = match self {
+            Color::ColorUnknown => "COLOR_UNKNOWN",
+            Color::ColorRed => "COLOR_RED",
+            Color::ColorGreen => "COLOR_GREEN",
+        };
+        out.write_str(name)
+    }
+}
+impl picopb::text_format::FromText for Color {
+    fn from_text(text: &picopb::text_format::TextValue) -> picopb::text_format::Result<Self> {
+        match text.as_literal()? {
+            "COLOR_UNKNOWN" => Ok(Self::ColorUnknown),
+            "COLOR_RED" => Ok(Self::ColorRed),
+            "COLOR_GREEN" => Ok(Self::ColorGreen),
+            literal => {
+                let invalid = || picopb::text_format::TextFormatError::InvalidEnumValue(literal.to_string());
+                let value: i32 = picopb::text_format::FromText::from_text(text).map_err(|_| invalid())?;
+                Self::try_from(value).map_err(|_| invalid())
+            }
+        }
+    }
+}
+impl picopb::reflect::ReflectField for Color {
+    fn to_value(&self) -> picopb::reflect::FieldValue<'_> {
+        picopb::reflect::FieldValue::Enum(i32::from(*self))
+    }
+    fn from_value(value: &picopb::reflect::FieldValue) -> picopb::reflect::Result<Self> {
+        match value {
+            picopb::reflect::FieldValue::Enum(value) => Self::try_from(*value).map_err(|_| picopb::reflect::ReflectError::InvalidEnumValue(*value)),
+            _ => Err(picopb::reflect::ReflectError::UnexpectedValue("enum")),
+        }
+    }
+}
+impl Randomize for Color {
+    fn randomized<R: picopb::randomizer::Rng + ?Sized>(rng: &mut R) -> Self {
+        const VALUES: [Color; 3] = [Color::ColorUnknown, Color::ColorRed, Color::ColorGreen];
+        VALUES[rng.gen_range(0..VALUES.len())]
+    }
+}
+#[derive(Default, Debug)]
+pub struct Pixel {
+    pub color: Color,
+    pub x: Option<i32>,
+}
+#[derive(Debug)]
+pub struct PixelFieldsType<'a> {
+    pub color: picopb::common::ConstMessageField<'a>,
+    pub x: picopb::common::ConstMessageField<'a>,
+}
+const PIXEL_FIELDS: PixelFieldsType = PixelFieldsType {
+    color: picopb::common::ConstMessageField {
+        qualifier: picopb::common::FieldQualifier::Required,
+        field_type: picopb::common::FieldType::EnumType("Color"),
+        identifier: "color",
+        json_name: "color",
+        ordinal: picopb::common::Field(1),
+    },
+    x: picopb::common::ConstMessageField {
+        qualifier: picopb::common::FieldQualifier::Optional,
+        field_type: picopb::common::FieldType::Int32,
+        identifier: "x",
+        json_name: "x",
+        ordinal: picopb::common::Field(2),
+    },
+};
+impl Pixel {
+    fn fields(&self) -> PixelFieldsType<'static> {
+        PIXEL_FIELDS
+    }
+}
+impl Pixel {
+    pub const MAX_ENCODED_LEN: usize = 22;
+}
+impl picopb::wiretypes::WireTyped for &Pixel {
+    fn wiretype(&self) -> WireType {
+        WireType::Len
+    }
+}
+impl picopb::encode::ToWire for &Pixel {
+    fn append<W: picopb::encode::EncodeSink>(&self, buf: &mut W) -> std::io::Result<usize> {
+        let mut total_size = 0;
+        total_size += buf.encode(self.color, self.fields().color.ordinal)?;
+        if let Some(value_x) = &self.x {
+            total_size += buf.encode(*value_x, self.fields().x.ordinal)?;
+        }
+        Ok(total_size)
+    }
+    fn precalculate_size(&self) -> usize {
+        let mut total_size = 0;
+        total_size += self.color.precalculate_field_size(self.fields().color.ordinal);
+        if let Some(value_x) = &self.x {
+            total_size += (*value_x).precalculate_field_size(self.fields().x.ordinal);
+        }
+        total_size
+    }
+}
+impl picopb::decode::DecodeMessage for Pixel {
+    fn decode_from(buf: &mut picopb::decode::DecodeBuffer<'_>) -> picopb::decode::Result<Self> {
+        let mut message = Self::default();
+        let mut has_color = false;
+        while !buf.is_empty() {
+            let (field, wire_type) = buf.read_tag()?;
+            match field.0 {
+                1 => {
+                    message.color = picopb::decode::read_field(buf, field, wire_type)?;
+                    has_color = true;
+                }
+                2 => {
+                    message.x = Some(picopb::decode::read_field(buf, field, wire_type)?);
+                }
+                _ => buf.skip_unknown(field, wire_type)?,
+            }
+        }
+        if !has_color {
+            return Err(picopb::decode::DecodeError::MissingRequiredField("color"));
+        }
+        Ok(message)
+    }
+}
+impl picopb::decode::FromWire for Pixel {
+    fn wire_type() -> WireType {
+        WireType::Len
+    }
+    fn read(buf: &mut picopb::decode::DecodeBuffer<'_>) -> picopb::decode::Result<Self> {
+        <Self as picopb::decode::DecodeMessage>::decode_from(&mut buf.read_sub_buffer()?)
+    }
+}
+impl Randomize for Pixel {
+    fn randomized<R: picopb::randomizer::Rng + ?Sized>(rng: &mut R) -> Self {
+        Self {
+            color: randomized(rng),
+            x: randomized(rng),
+        }
+    }
+}
+impl picopb::text_format::ToText for Pixel {
+    fn write_text<W: std::fmt::Write>(&self, out: &mut W) -> std::fmt::Result {
+        let mut writer = picopb::text_format::FieldWriter::new(out);
+        writer.field(self.fields().color.identifier, &self.color)?;
+        if let Some(value_x) = &self.x {
+            writer.field(self.fields().x.identifier, value_x)?;
+        }
+        Ok(())
+    }
+}
+impl picopb::text_format::FromText for Pixel {
+    fn from_text(text: &picopb::text_format::TextValue) -> picopb::text_format::Result<Self> {
+        let mut message = Self::default();
+        let mut has_color = false;
+        for (name, value) in text.as_message()? {
+            match name.as_str() {
+                "color" => {
+                    message.color = picopb::text_format::FromText::from_text(value)?;
+                    has_color = true;
+                }
+                "x" => {
+                    message.x = Some(picopb::text_format::FromText::from_text(value)?);
+                }
+                _ => return Err(picopb::text_format::TextFormatError::UnknownField(name.clone())),
+            }
+        }
+        if !has_color {
+            return Err(picopb::text_format::TextFormatError::MissingRequiredField("color"));
+        }
+        Ok(message)
+    }
+}
+impl Pixel {
+    pub const DESCRIPTOR: picopb::reflect::MessageDescriptor = picopb::reflect::MessageDescriptor {
+        name: "Pixel",
+        fields: &[
+            PIXEL_FIELDS.color,
+            PIXEL_FIELDS.x,
+        ],
+    };
+}
+impl picopb::reflect::Reflect for Pixel {
+    fn descriptor(&self) -> &'static picopb::reflect::MessageDescriptor {
+        &Self::DESCRIPTOR
+    }
+    fn get(&self, number: u32) -> Option<Vec<picopb::reflect::FieldValue<'_>>> {
+        match number {
+            1 => Some(vec![picopb::reflect::ReflectField::to_value(&self.color)]),
+            2 => Some(self.x.iter().map(picopb::reflect::ReflectField::to_value).collect()),
+            _ => None,
+        }
+    }
+    fn set(&mut self, number: u32, values: &[picopb::reflect::FieldValue]) -> picopb::reflect::Result<()> {
+        match number {
+            1 => picopb::reflect::set_required(&mut self.color, values),
+            2 => picopb::reflect::set_optional(&mut self.x, values),
+            _ => Err(picopb::reflect::ReflectError::UnknownField(number.to_string())),
+        }
+    }
+}
+impl picopb::reflect::ReflectField for Pixel {
+    fn to_value(&self) -> picopb::reflect::FieldValue<'_> {
+        picopb::reflect::FieldValue::Message(self)
+    }
+    fn from_value(value: &picopb::reflect::FieldValue) -> picopb::reflect::Result<Self> {
+        let mut message = Self::default();
+        picopb::reflect::copy_fields(&mut message, value.as_message()?)?;
+        Ok(message)
+    }
+}
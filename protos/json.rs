@@ -0,0 +1,717 @@
+use picopb::common::*;
+use picopb::encode::{EncodeSink, ToWire};
+use picopb::wiretypes::{WireType, WireTyped};
+use picopb::randomizer::{randomized, Randomize};
+use std::ops::Deref;
+#[allow(clippy::enum_variant_names)]
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub enum Status {
+    #[default]
+    StatusOk,
+    StatusDegraded,
+    StatusFailed,
+}
+impl TryFrom<i32> for Status {
+    type Error = String;
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Status::StatusOk),
+            1 => Ok(Status::StatusDegraded),
+            -1 => Ok(Status::StatusFailed),
+            _ => Err(format!("invalid ordinal value: {} for enum Status", value)),
+        }
+    }
+}
+impl From<Status> for i32 {
+    fn from(value: Status) -> Self {
+        match value {
+            Status::StatusOk => 0,
+            Status::StatusDegraded => 1,
+            Status::StatusFailed => -1,
+        }
+    }
+}
+impl picopb::wiretypes::WireTyped for Status {
+    fn wiretype(&self) -> WireType {
+        WireType::VarInt
+    }
+}
+impl picopb::encode::ToWire for Status {
+    fn append<W: picopb::encode::EncodeSink>(&self, buf: &mut W) -> std::io::Result<usize> {
+        i32::from(*self).append(buf)
+    }
+    fn precalculate_size(&self) -> usize {
+        i32::from(*self).precalculate_size()
+    }
+}
+impl picopb::decode::FromWire for Status {
+    fn wire_type() -> WireType {
+        WireType::VarInt
+    }
+    fn read(buf: &mut picopb::decode::DecodeBuffer<'_>) -> picopb::decode::Result<Self> {
+        let value = buf.read_varint()? as i64;
+        Self::try_from(value as i32).map_err(|_| picopb::decode::DecodeError::InvalidEnumValue(value))
+    }
+}
+impl picopb::text_format::ToText for Status {
+    fn write_text<W: std::fmt::Write>(&self, out: &mut W) -> std::fmt::Result {
+        let name = match self {
+            Status::StatusOk => "STATUS_OK",
+            Status::StatusDegraded => "STATUS_DEGRADED",
+            Status::StatusFailed => "STATUS_FAILED",
+        };
+        out.write_str(name)
+    }
+}
+impl picopb::text_format::FromText for Status {
+    fn from_text(text: &picopb::text_format::TextValue) -> picopb::text_format::Result<Self> {
+        match text.as_literal()? {
+            "STATUS_OK" => Ok(Self::StatusOk),
+            "STATUS_DEGRADED" => Ok(Self::StatusDegraded),
+            "STATUS_FAILED" => Ok(Self::StatusFailed),
+            literal => {
+                let invalid = || picopb::text_format::TextFormatError::InvalidEnumValue(literal.to_string());
+                let value: i32 = picopb::text_format::FromText::from_text(text).map_err(|_| invalid())?;
+                Self::try_from(value).map_err(|_| invalid())
+            }
+        }
+    }
+}
+impl picopb::reflect::ReflectField for Status {
+    fn to_value(&self) -> picopb::reflect::FieldValue<'_> {
+        picopb::reflect::FieldValue::Enum(i32::from(*self))
+    }
+    fn from_value(value: &picopb::reflect::FieldValue) -> picopb::reflect::Result<Self> {
+        match value {
+            picopb::reflect::FieldValue::Enum(value) => Self::try_from(*value).map_err(|_| picopb::reflect::ReflectError::InvalidEnumValue(*value)),
+            _ => Err(picopb::reflect::ReflectError::UnexpectedValue("enum")),
+        }
+    }
+}
+impl Randomize for Status {
+    fn randomized<R: picopb::randomizer::Rng + ?Sized>(rng: &mut R) -> Self {
+        const VALUES: [Status; 3] = [Status::StatusOk, Status::StatusDegraded, Status::StatusFailed];
+        VALUES[rng.gen_range(0..VALUES.len())]
+    }
+}
+impl picopb::json::ToJson for Status {
+    fn write_json<W: std::fmt::Write>(&self, out: &mut W) -> std::fmt::Result {
+        let name = match self {
+            Status::StatusOk => "STATUS_OK",
+            Status::StatusDegraded => "STATUS_DEGRADED",
+            Status::StatusFailed => "STATUS_FAILED",
+        };
+        picopb::json::write_string(out, name)
+    }
+}
+impl picopb::json::FromJson for Status {
+    fn from_json(json: &picopb::json::JsonValue) -> picopb::json::Result<Self> {
+        match json {
+            picopb::json::JsonValue::String(name) => match name.as_str() {
+                "STATUS_OK" => Ok(Self::StatusOk),
+                "STATUS_DEGRADED" => Ok(Self::StatusDegraded),
+                "STATUS_FAILED" => Ok(Self::StatusFailed),
+                _ => Err(picopb::json::JsonError::InvalidEnumValue(name.clone())),
+            },
+            number => {
+                let value: i32 = picopb::json::FromJson::from_json(number)?;
+                Self::try_from(value).map_err(|_| picopb::json::JsonError::InvalidEnumValue(value.to_string()))
+            }
+        }
+    }
+}
+#[derive(Default, Debug)]
+pub struct Sample {
+    pub value: i32,
+    pub delta: Option<i32>,
+}
+#[derive(Debug)]
+pub struct SampleFieldsType<'a> {
+    pub value: picopb::common::ConstMessageField<'a>,
+    pub delta: picopb::common::ConstMessageField<'a>,
+}
+const SAMPLE_FIELDS: SampleFieldsType = SampleFieldsType {
+    value: picopb::common::ConstMessageField {
+        qualifier: picopb::common::FieldQualifier::Required,
+        field_type: picopb::common::FieldType::Int32,
+        identifier: "value",
+        json_name: "value",
+        ordinal: picopb::common::Field(1),
+    },
+    delta: picopb::common::ConstMessageField {
+        qualifier: picopb::common::FieldQualifier::Optional,
+        field_type: picopb::common::FieldType::Int32,
+        identifier: "delta",
+        json_name: "delta",
+        ordinal: picopb::common::Field(2),
+    },
+};
+impl Sample {
+    fn fields(&self) -> SampleFieldsType<'static> {
+        SAMPLE_FIELDS
+    }
+}
+impl Sample {
+    pub const MAX_ENCODED_LEN: usize = 22;
+}
+impl picopb::wiretypes::WireTyped for &Sample {
+    fn wiretype(&self) -> WireType {
+        WireType::Len
+    }
+}
+impl picopb::encode::ToWire for &Sample {
+    fn append<W: picopb::encode::EncodeSink>(&self, buf: &mut W) -> std::io::Result<usize> {
+        let mut total_size = 0;
+        total_size += buf.encode(self.value, self.fields().value.ordinal)?;
+        if let Some(value_delta) = &self.delta {
+            total_size += buf.encode(*value_delta, self.fields().delta.ordinal)?;
+        }
+        Ok(total_size)
+    }
+    fn precalculate_size(&self) -> usize {
+        let mut total_size = 0;
+        total_size += self.value.precalculate_field_size(self.fields().value.ordinal);
+        if let Some(value_delta) = &self.delta {
+            total_size += (*value_delta).precalculate_field_size(self.fields().delta.ordinal);
+        }
+        total_size
+    }
+}
+impl picopb::decode::DecodeMessage for Sample {
+    fn decode_from(buf: &mut picopb::decode::DecodeBuffer<'_>) -> picopb::decode::Result<Self> {
+        let mut message = Self::default();
+        let mut has_value = false;
+        while !buf.is_empty() {
+            let (field, wire_type) = buf.read_tag()?;
+            match field.0 {
+                1 => {
+                    message.value = picopb::decode::read_field(buf, field, wire_type)?;
+                    has_value = true;
+                }
+                2 => {
+                    message.delta = Some(picopb::decode::read_field(buf, field, wire_type)?);
+                }
+                _ => buf.skip_unknown(field, wire_type)?,
+            }
+        }
+        if !has_value {
+            return Err(picopb::decode::DecodeError::MissingRequiredField("value"));
+        }
+        Ok(message)
+    }
+}
+impl picopb::decode::FromWire for Sample {
+    fn wire_type() -> WireType {
+        WireType::Len
+    }
+    fn read(buf: &mut picopb::decode::DecodeBuffer<'_>) -> picopb::decode::Result<Self> {
+        <Self as picopb::decode::DecodeMessage>::decode_from(&mut buf.read_sub_buffer()?)
+    }
+}
+impl Randomize for Sample {
+    fn randomized<R: picopb::randomizer::Rng + ?Sized>(rng: &mut R) -> Self {
+        Self {
+            value: randomized(rng),
+            delta: randomized(rng),
+        }
+    }
+}
+impl picopb::text_format::ToText for Sample {
+    fn write_text<W: std::fmt::Write>(&self, out: &mut W) -> std::fmt::Result {
+        let mut writer = picopb::text_format::FieldWriter::new(out);
+        writer.field(self.fields().value.identifier, &self.value)?;
+        if let Some(value_delta) = &self.delta {
+            writer.field(self.fields().delta.identifier, value_delta)?;
+        }
+        Ok(())
+    }
+}
+impl picopb::text_format::FromText for Sample {
+    fn from_text(text: &picopb::text_format::TextValue) -> picopb::text_format::Result<Self> {
+        let mut message = Self::default();
+        let mut has_value = false;
+        for (name, value) in text.as_message()? {
+            match name.as_str() {
+                "value" => {
+                    message.value = picopb::text_format::FromText::from_text(value)?;
+                    has_value = true;
+                }
+                "delta" => {
+                    message.delta = Some(picopb::text_format::FromText::from_text(value)?);
+                }
+                _ => return Err(picopb::text_format::TextFormatError::UnknownField(name.clone())),
+            }
+        }
+        if !has_value {
+            return Err(picopb::text_format::TextFormatError::MissingRequiredField("value"));
+        }
+        Ok(message)
+    }
+}
+impl Sample {
+    pub const DESCRIPTOR: picopb::reflect::MessageDescriptor = picopb::reflect::MessageDescriptor {
+        name: "Sample",
+        fields: &[
+            SAMPLE_FIELDS.value,
+            SAMPLE_FIELDS.delta,
+        ],
+    };
+}
+impl picopb::reflect::Reflect for Sample {
+    fn descriptor(&self) -> &'static picopb::reflect::MessageDescriptor {
+        &Self::DESCRIPTOR
+    }
+    fn get(&self, number: u32) -> Option<Vec<picopb::reflect::FieldValue<'_>>> {
+        match number {
+            1 => Some(vec![picopb::reflect::ReflectField::to_value(&self.value)]),
+            2 => Some(self.delta.iter().map(picopb::reflect::ReflectField::to_value).collect()),
+            _ => None,
+        }
+    }
+    fn set(&mut self, number: u32, values: &[picopb::reflect::FieldValue]) -> picopb::reflect::Result<()> {
+        match number {
+            1 => picopb::reflect::set_required(&mut self.value, values),
+            2 => picopb::reflect::set_optional(&mut self.delta, values),
+            _ => Err(picopb::reflect::ReflectError::UnknownField(number.to_string())),
+        }
+    }
+}
+impl picopb::reflect::ReflectField for Sample {
+    fn to_value(&self) -> picopb::reflect::FieldValue<'_> {
+        picopb::reflect::FieldValue::Message(self)
+    }
+    fn from_value(value: &picopb::reflect::FieldValue) -> picopb::reflect::Result<Self> {
+        let mut message = Self::default();
+        picopb::reflect::copy_fields(&mut message, value.as_message()?)?;
+        Ok(message)
+    }
+}
+impl picopb::json::ToJson for Sample {
+    fn write_json<W: std::fmt::Write>(&self, out: &mut W) -> std::fmt::Result {
+        let mut object = picopb::json::ObjectWriter::new(out)?;
+        object.field(self.fields().value.json_name, &self.value)?;
+        if let Some(value_delta) = &self.delta {
+            object.field(self.fields().delta.json_name, value_delta)?;
+        }
+        object.finish()
+    }
+}
+impl picopb::json::FromJson for Sample {
+    fn from_json(json: &picopb::json::JsonValue) -> picopb::json::Result<Self> {
+        let mut message = Self::default();
+        let mut has_value = false;
+        for (name, value) in json.as_object()? {
+            if *value == picopb::json::JsonValue::Null {
+                continue;
+            }
+            match name.as_str() {
+                "value" => {
+                    message.value = picopb::json::FromJson::from_json(value)?;
+                    has_value = true;
+                }
+                "delta" => {
+                    message.delta = Some(picopb::json::FromJson::from_json(value)?);
+                }
+                _ => return Err(picopb::json::JsonError::UnknownField(name.clone())),
+            }
+        }
+        if !has_value {
+            return Err(picopb::json::JsonError::MissingRequiredField("value"));
+        }
+        Ok(message)
+    }
+}
+#[derive(Debug)]
+pub struct Telemetry {
+    pub device_id: u32,
+    pub uptime_ms: Option<i64>,
+    pub firmware_version: Option<String>,
+    pub payload: Option<bytes::Bytes>,
+    pub counters: picopb::common::Packed<Vec<u64>>,
+    pub samples: Vec<Sample>,
+    pub labels: Vec<String>,
+}
+impl Default for Telemetry {
+    fn default() -> Self {
+        Self {
+            device_id: Default::default(),
+            uptime_ms: Default::default(),
+            firmware_version: Default::default(),
+            payload: Default::default(),
+            counters: picopb::common::Packed(Vec::new()),
+            samples: Default::default(),
+            labels: Default::default(),
+        }
+    }
+}
+#[derive(Debug)]
+pub struct TelemetryFieldsType<'a> {
+    pub device_id: picopb::common::ConstMessageField<'a>,
+    pub uptime_ms: picopb::common::ConstMessageField<'a>,
+    pub firmware_version: picopb::common::ConstMessageField<'a>,
+    pub payload: picopb::common::ConstMessageField<'a>,
+    pub counters: picopb::common::ConstMessageField<'a>,
+    pub samples: picopb::common::ConstMessageField<'a>,
+    pub labels: picopb::common::ConstMessageField<'a>,
+}
+const TELEMETRY_FIELDS: TelemetryFieldsType = TelemetryFieldsType {
+    device_id: picopb::common::ConstMessageField {
+        qualifier: picopb::common::FieldQualifier::Required,
+        field_type: picopb::common::FieldType::Uint32,
+        identifier: "device_id",
+        json_name: "deviceId",
+        ordinal: picopb::common::Field(1),
+    },
+    uptime_ms: picopb::common::ConstMessageField {
+        qualifier: picopb::common::FieldQualifier::Optional,
+        field_type: picopb::common::FieldType::Int64,
+        identifier: "uptime_ms",
+        json_name: "uptimeMs",
+        ordinal: picopb::common::Field(2),
+    },
+    firmware_version: picopb::common::ConstMessageField {
+        qualifier: picopb::common::FieldQualifier::Optional,
+        field_type: picopb::common::FieldType::UnboundedString,
+        identifier: "firmware_version",
+        json_name: "fw",
+        ordinal: picopb::common::Field(3),
+    },
+    payload: picopb::common::ConstMessageField {
+        qualifier: picopb::common::FieldQualifier::Optional,
+        field_type: picopb::common::FieldType::UnboundedBytes,
+        identifier: "payload",
+        json_name: "payload",
+        ordinal: picopb::common::Field(4),
+    },
+    counters: picopb::common::ConstMessageField {
+        qualifier: picopb::common::FieldQualifier::PackedRepeatedUnbounded,
+        field_type: picopb::common::FieldType::Uint64,
+        identifier: "counters",
+        json_name: "counters",
+        ordinal: picopb::common::Field(5),
+    },
+    samples: picopb::common::ConstMessageField {
+        qualifier: picopb::common::FieldQualifier::RepeatedUnbounded,
+        field_type: picopb::common::FieldType::MessageType("Sample"),
+        identifier: "samples",
+        json_name: "samples",
+        ordinal: picopb::common::Field(6),
+    },
+    labels: picopb::common::ConstMessageField {
+        qualifier: picopb::common::FieldQualifier::RepeatedUnbounded,
+        field_type: picopb::common::FieldType::UnboundedString,
+        identifier: "labels",
+        json_name: "labels",
+        ordinal: picopb::common::Field(7),
+    },
+};
+impl Telemetry {
+    fn fields(&self) -> TelemetryFieldsType<'static> {
+        TELEMETRY_FIELDS
+    }
+}
+// Telemetry has no MAX_ENCODED_LEN, unbounded: Telemetry.firmware_version
+impl picopb::wiretypes::WireTyped for &Telemetry {
+    fn wiretype(&self) -> WireType {
+        WireType::Len
+    }
+}
+impl picopb::encode::ToWire for &Telemetry {
+    fn append<W: picopb::encode::EncodeSink>(&self, buf: &mut W) -> std::io::Result<usize> {
+        let mut total_size = 0;
+        total_size += buf.encode(self.device_id, self.fields().device_id.ordinal)?;
+        if let Some(value_uptime_ms) = &self.uptime_ms {
+            total_size += buf.encode(*value_uptime_ms, self.fields().uptime_ms.ordinal)?;
+        }
+        if let Some(value_firmware_version) = &self.firmware_version {
+            total_size += buf.encode(value_firmware_version.as_str(), self.fields().firmware_version.ordinal)?;
+        }
+        if let Some(value_payload) = &self.payload {
+            total_size += buf.encode(value_payload.deref(), self.fields().payload.ordinal)?;
+        }
+        if !self.counters.0.is_empty() {
+            total_size += buf.encode(picopb::common::Packed(self.counters.0.as_slice()), self.fields().counters.ordinal)?;
+        }
+        for value_samples in self.samples.iter() {
+            total_size += buf.encode_sub_message(value_samples, self.fields().samples.ordinal)?;
+        }
+        for value_labels in self.labels.iter() {
+            total_size += buf.encode(value_labels.as_str(), self.fields().labels.ordinal)?;
+        }
+        Ok(total_size)
+    }
+    fn precalculate_size(&self) -> usize {
+        let mut total_size = 0;
+        total_size += self.device_id.precalculate_field_size(self.fields().device_id.ordinal);
+        if let Some(value_uptime_ms) = &self.uptime_ms {
+            total_size += (*value_uptime_ms).precalculate_field_size(self.fields().uptime_ms.ordinal);
+        }
+        if let Some(value_firmware_version) = &self.firmware_version {
+            total_size += value_firmware_version.as_str().precalculate_field_size(self.fields().firmware_version.ordinal);
+        }
+        if let Some(value_payload) = &self.payload {
+            total_size += value_payload.deref().precalculate_field_size(self.fields().payload.ordinal);
+        }
+        if !self.counters.0.is_empty() {
+            total_size += picopb::common::Packed(self.counters.0.as_slice()).precalculate_field_size(self.fields().counters.ordinal);
+        }
+        for value_samples in self.samples.iter() {
+            total_size += value_samples.precalculate_sub_message_size(self.fields().samples.ordinal);
+        }
+        for value_labels in self.labels.iter() {
+            total_size += value_labels.as_str().precalculate_field_size(self.fields().labels.ordinal);
+        }
+        total_size
+    }
+}
+impl picopb::decode::DecodeMessage for Telemetry {
+    fn decode_from(buf: &mut picopb::decode::DecodeBuffer<'_>) -> picopb::decode::Result<Self> {
+        let mut message = Self::default();
+        let mut has_device_id = false;
+        while !buf.is_empty() {
+            let (field, wire_type) = buf.read_tag()?;
+            match field.0 {
+                1 => {
+                    message.device_id = picopb::decode::read_field(buf, field, wire_type)?;
+                    has_device_id = true;
+                }
+                2 => {
+                    message.uptime_ms = Some(picopb::decode::read_field(buf, field, wire_type)?);
+                }
+                3 => {
+                    message.firmware_version = Some(picopb::decode::read_field(buf, field, wire_type)?);
+                }
+                4 => {
+                    message.payload = Some(picopb::decode::read_field(buf, field, wire_type)?);
+                }
+                5 => {
+                    picopb::decode::read_repeated(buf, field, wire_type, |value| {
+                        message.counters.0.push(value);
+                        Ok(())
+                    })?;
+                }
+                6 => {
+                    picopb::decode::read_repeated(buf, field, wire_type, |value| {
+                        message.samples.push(value);
+                        Ok(())
+                    })?;
+                }
+                7 => {
+                    picopb::decode::read_repeated(buf, field, wire_type, |value| {
+                        message.labels.push(value);
+                        Ok(())
+                    })?;
+                }
+                _ => buf.skip_unknown(field, wire_type)?,
+            }
+        }
+        if !has_device_id {
+            return Err(picopb::decode::DecodeError::MissingRequiredField("device_id"));
+        }
+        Ok(message)
+    }
+}
+impl picopb::decode::FromWire for Telemetry {
+    fn wire_type() -> WireType {
+        WireType::Len
+    }
+    fn read(buf: &mut picopb::decode::DecodeBuffer<'_>) -> picopb::decode::Result<Self> {
+        <Self as picopb::decode::DecodeMessage>::decode_from(&mut buf.read_sub_buffer()?)
+    }
+}
+impl Randomize for Telemetry {
+    fn randomized<R: picopb::randomizer::Rng + ?Sized>(rng: &mut R) -> Self {
+        Self {
+            device_id: randomized(rng),
+            uptime_ms: randomized(rng),
+            firmware_version: randomized(rng),
+            payload: randomized(rng),
+            counters: randomized(rng),
+            samples: randomized(rng),
+            labels: randomized(rng),
+        }
+    }
+}
+impl picopb::text_format::ToText for Telemetry {
+    fn write_text<W: std::fmt::Write>(&self, out: &mut W) -> std::fmt::Result {
+        let mut writer = picopb::text_format::FieldWriter::new(out);
+        writer.field(self.fields().device_id.identifier, &self.device_id)?;
+        if let Some(value_uptime_ms) = &self.uptime_ms {
+            writer.field(self.fields().uptime_ms.identifier, value_uptime_ms)?;
+        }
+        if let Some(value_firmware_version) = &self.firmware_version {
+            writer.field(self.fields().firmware_version.identifier, value_firmware_version)?;
+        }
+        if let Some(value_payload) = &self.payload {
+            writer.field(self.fields().payload.identifier, value_payload)?;
+        }
+        for value_counters in self.counters.0.iter() {
+            writer.field(self.fields().counters.identifier, value_counters)?;
+        }
+        for value_samples in self.samples.iter() {
+            writer.message(self.fields().samples.identifier, value_samples)?;
+        }
+        for value_labels in self.labels.iter() {
+            writer.field(self.fields().labels.identifier, value_labels)?;
+        }
+        Ok(())
+    }
+}
+impl picopb::text_format::FromText for Telemetry {
+    fn from_text(text: &picopb::text_format::TextValue) -> picopb::text_format::Result<Self> {
+        let mut message = Self::default();
+        let mut has_device_id = false;
+        for (name, value) in text.as_message()? {
+            match name.as_str() {
+                "device_id" => {
+                    message.device_id = picopb::text_format::FromText::from_text(value)?;
+                    has_device_id = true;
+                }
+                "uptime_ms" => {
+                    message.uptime_ms = Some(picopb::text_format::FromText::from_text(value)?);
+                }
+                "firmware_version" => {
+                    message.firmware_version = Some(picopb::text_format::FromText::from_text(value)?);
+                }
+                "payload" => {
+                    message.payload = Some(picopb::text_format::FromText::from_text(value)?);
+                }
+                "counters" => {
+                    picopb::text_format::read_repeated(value, |value| {
+                        message.counters.0.push(value);
+                        Ok(())
+                    })?;
+                }
+                "samples" => {
+                    picopb::text_format::read_repeated(value, |value| {
+                        message.samples.push(value);
+                        Ok(())
+                    })?;
+                }
+                "labels" => {
+                    picopb::text_format::read_repeated(value, |value| {
+                        message.labels.push(value);
+                        Ok(())
+                    })?;
+                }
+                _ => return Err(picopb::text_format::TextFormatError::UnknownField(name.clone())),
+            }
+        }
+        if !has_device_id {
+            return Err(picopb::text_format::TextFormatError::MissingRequiredField("device_id"));
+        }
+        Ok(message)
+    }
+}
+impl Telemetry {
+    pub const DESCRIPTOR: picopb::reflect::MessageDescriptor = picopb::reflect::MessageDescriptor {
+        name: "Telemetry",
+        fields: &[
+            TELEMETRY_FIELDS.device_id,
+            TELEMETRY_FIELDS.uptime_ms,
+            TELEMETRY_FIELDS.firmware_version,
+            TELEMETRY_FIELDS.payload,
+            TELEMETRY_FIELDS.counters,
+            TELEMETRY_FIELDS.samples,
+            TELEMETRY_FIELDS.labels,
+        ],
+    };
+}
+impl picopb::reflect::Reflect for Telemetry {
+    fn descriptor(&self) -> &'static picopb::reflect::MessageDescriptor {
+        &Self::DESCRIPTOR
+    }
+    fn get(&self, number: u32) -> Option<Vec<picopb::reflect::FieldValue<'_>>> {
+        match number {
+            1 => Some(vec![picopb::reflect::ReflectField::to_value(&self.device_id)]),
+            2 => Some(self.uptime_ms.iter().map(picopb::reflect::ReflectField::to_value).collect()),
+            3 => Some(self.firmware_version.iter().map(picopb::reflect::ReflectField::to_value).collect()),
+            4 => Some(self.payload.iter().map(picopb::reflect::ReflectField::to_value).collect()),
+            5 => Some(self.counters.0.iter().map(picopb::reflect::ReflectField::to_value).collect()),
+            6 => Some(self.samples.iter().map(picopb::reflect::ReflectField::to_value).collect()),
+            7 => Some(self.labels.iter().map(picopb::reflect::ReflectField::to_value).collect()),
+            _ => None,
+        }
+    }
+    fn set(&mut self, number: u32, values: &[picopb::reflect::FieldValue]) -> picopb::reflect::Result<()> {
+        match number {
+            1 => picopb::reflect::set_required(&mut self.device_id, values),
+            2 => picopb::reflect::set_optional(&mut self.uptime_ms, values),
+            3 => picopb::reflect::set_optional(&mut self.firmware_version, values),
+            4 => picopb::reflect::set_optional(&mut self.payload, values),
+            5 => picopb::reflect::set_repeated(&mut self.counters.0, values),
+            6 => picopb::reflect::set_repeated(&mut self.samples, values),
+            7 => picopb::reflect::set_repeated(&mut self.labels, values),
+            _ => Err(picopb::reflect::ReflectError::UnknownField(number.to_string())),
+        }
+    }
+}
+impl picopb::reflect::ReflectField for Telemetry {
+    fn to_value(&self) -> picopb::reflect::FieldValue<'_> {
+        picopb::reflect::FieldValue::Message(self)
+    }
+    fn from_value(value: &picopb::reflect::FieldValue) -> picopb::reflect::Result<Self> {
+        let mut message = Self::default();
+        picopb::reflect::copy_fields(&mut message, value.as_message()?)?;
+        Ok(message)
+    }
+}
+impl picopb::json::ToJson for Telemetry {
+    fn write_json<W: std::fmt::Write>(&self, out: &mut W) -> std::fmt::Result {
+        let mut object = picopb::json::ObjectWriter::new(out)?;
+        object.field(self.fields().device_id.json_name, &self.device_id)?;
+        if let Some(value_uptime_ms) = &self.uptime_ms {
+            object.field(self.fields().uptime_ms.json_name, value_uptime_ms)?;
+        }
+        if let Some(value_firmware_version) = &self.firmware_version {
+            object.field(self.fields().firmware_version.json_name, value_firmware_version)?;
+        }
+        if let Some(value_payload) = &self.payload {
+            object.field(self.fields().payload.json_name, value_payload)?;
+        }
+        object.repeated(self.fields().counters.json_name, self.counters.0.as_slice())?;
+        object.repeated(self.fields().samples.json_name, self.samples.as_slice())?;
+        object.repeated(self.fields().labels.json_name, self.labels.as_slice())?;
+        object.finish()
+    }
+}
+impl picopb::json::FromJson for Telemetry {
+    fn from_json(json: &picopb::json::JsonValue) -> picopb::json::Result<Self> {
+        let mut message = Self::default();
+        let mut has_device_id = false;
+        for (name, value) in json.as_object()? {
+            if *value == picopb::json::JsonValue::Null {
+                continue;
+            }
+            match name.as_str() {
+                "deviceId" | "device_id" => {
+                    message.device_id = picopb::json::FromJson::from_json(value)?;
+                    has_device_id = true;
+                }
+                "uptimeMs" | "uptime_ms" => {
+                    message.uptime_ms = Some(picopb::json::FromJson::from_json(value)?);
+                }
+                "fw" | "firmware_version" => {
+                    message.firmware_version = Some(picopb::json::FromJson::from_json(value)?);
+                }
+                "payload" => {
+                    message.payload = Some(picopb::json::FromJson::from_json(value)?);
+                }
+                "counters" => {
+                    message.counters = picopb::common::Packed(picopb::json::read_vec(value)?);
+                }
+                "samples" => {
+                    message.samples = picopb::json::read_vec(value)?;
+                }
+                "labels" => {
+                    message.labels = picopb::json::read_vec(value)?;
+                }
+                _ => return Err(picopb::json::JsonError::UnknownField(name.clone())),
+            }
+        }
+        if !has_device_id {
+            return Err(picopb::json::JsonError::MissingRequiredField("device_id"));
+        }
+        Ok(message)
+    }
+}
@@ -14,12 +14,14 @@ const MESSAGE_WITH_OPTIONAL_FIELD_FIELDS: MessageWithOptionalFieldFieldsType = M
         qualifier: picopb::common::FieldQualifier::Optional,
         field_type: picopb::common::FieldType::UnboundedString,
         identifier: "d",
+        json_name: "d",
         ordinal: picopb::common::Field(4),
     },
     e: picopb::common::ConstMessageField {
         qualifier: picopb::common::FieldQualifier::RepeatedUnbounded,
         field_type: picopb::common::FieldType::Int32,
         identifier: "e",
+        json_name: "e",
         ordinal: picopb::common::Field(5),
     },
 };
@@ -16,18 +16,21 @@ const MESSAGE_WITH_OPTIONAL_FIELD_FIELDS: MessageWithOptionalFieldFieldsType = M
         qualifier: picopb::common::FieldQualifier::Optional,
         field_type: picopb::common::FieldType::UnboundedString,
         identifier: "a",
+        json_name: "a",
         ordinal: picopb::common::Field(1),
     },
     b: picopb::common::ConstMessageField {
         qualifier: picopb::common::FieldQualifier::RepeatedUnbounded,
         field_type: picopb::common::FieldType::UnboundedString,
         identifier: "b",
+        json_name: "b",
         ordinal: picopb::common::Field(4),
     },
     e: picopb::common::ConstMessageField {
         qualifier: picopb::common::FieldQualifier::RepeatedUnbounded,
         field_type: picopb::common::FieldType::Int32,
         identifier: "e",
+        json_name: "e",
         ordinal: picopb::common::Field(5),
     },
 };
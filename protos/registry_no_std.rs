@@ -0,0 +1,939 @@
+use picopb::common::*;
+use picopb::encode::{EncodeSink, ToWire};
+use picopb::wiretypes::{WireType, WireTyped};
+use picopb::randomizer::{randomized, Randomize};
+#[allow(unused_imports)]
+use alloc::{format, string::{String, ToString}, vec, vec::Vec};
+use core::ops::Deref;
+#[allow(clippy::enum_variant_names)]
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub enum Unit {
+    #[default]
+    UnitUnknown,
+    UnitCelsius,
+    UnitKelvin,
+    UnitInvalid,
+}
+impl TryFrom<i32> for Unit {
+    type Error = String;
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Unit::UnitUnknown),
+            1 => Ok(Unit::UnitCelsius),
+            2 => Ok(Unit::UnitKelvin),
+            -1 => Ok(Unit::UnitInvalid),
+            _ => Err(format!("invalid ordinal value: {} for enum Unit", value)),
+        }
+    }
+}
+impl From<Unit> for i32 {
+    fn from(value: Unit) -> Self {
+        match value {
+            Unit::UnitUnknown => 0,
+            Unit::UnitCelsius => 1,
+            Unit::UnitKelvin => 2,
+            Unit::UnitInvalid => -1,
+        }
+    }
+}
+impl picopb::wiretypes::WireTyped for Unit {
+    fn wiretype(&self) -> WireType {
+        WireType::VarInt
+    }
+}
+impl picopb::encode::ToWire for Unit {
+    fn append<W: picopb::encode::EncodeSink>(&self, buf: &mut W) -> picopb::encode::SinkResult<usize> {
+        i32::from(*self).append(buf)
+    }
+    fn precalculate_size(&self) -> usize {
+        i32::from(*self).precalculate_size()
+    }
+}
+impl picopb::decode::FromWire for Unit {
+    fn wire_type() -> WireType {
+        WireType::VarInt
+    }
+    fn read(buf: &mut picopb::decode::DecodeBuffer<'_>) -> picopb::decode::Result<Self> {
+        let value = buf.read_varint()? as i64;
+        Self::try_from(value as i32).map_err(|_| picopb::decode::DecodeError::InvalidEnumValue(value))
+    }
+}
+impl picopb::text_format::ToText for Unit {
+    fn write_text<W: core::fmt::Write>(&self, out: &mut W) -> core::fmt::Result {
+        let name = match self {
+            Unit::UnitUnknown => "UNIT_UNKNOWN",
+            Unit::UnitCelsius => "UNIT_CELSIUS",
+            Unit::UnitKelvin => "UNIT_KELVIN",
+            Unit::UnitInvalid => "UNIT_INVALID",
+        };
+        out.write_str(name)
+    }
+}
+impl picopb::text_format::FromText for Unit {
+    fn from_text(text: &picopb::text_format::TextValue) -> picopb::text_format::Result<Self> {
+        match text.as_literal()? {
+            "UNIT_UNKNOWN" => Ok(Self::UnitUnknown),
+            "UNIT_CELSIUS" => Ok(Self::UnitCelsius),
+            "UNIT_KELVIN" => Ok(Self::UnitKelvin),
+            "UNIT_INVALID" => Ok(Self::UnitInvalid),
+            literal => {
+                let invalid = || picopb::text_format::TextFormatError::InvalidEnumValue(literal.to_string());
+                let value: i32 = picopb::text_format::FromText::from_text(text).map_err(|_| invalid())?;
+                Self::try_from(value).map_err(|_| invalid())
+            }
+        }
+    }
+}
+impl picopb::reflect::ReflectField for Unit {
+    fn to_value(&self) -> picopb::reflect::FieldValue<'_> {
+        picopb::reflect::FieldValue::Enum(i32::from(*self))
+    }
+    fn from_value(value: &picopb::reflect::FieldValue) -> picopb::reflect::Result<Self> {
+        match value {
+            picopb::reflect::FieldValue::Enum(value) => Self::try_from(*value).map_err(|_| picopb::reflect::ReflectError::InvalidEnumValue(*value)),
+            _ => Err(picopb::reflect::ReflectError::UnexpectedValue("enum")),
+        }
+    }
+}
+impl Randomize for Unit {
+    fn randomized<R: picopb::randomizer::Rng + ?Sized>(rng: &mut R) -> Self {
+        const VALUES: [Unit; 4] = [Unit::UnitUnknown, Unit::UnitCelsius, Unit::UnitKelvin, Unit::UnitInvalid];
+        VALUES[rng.gen_range(0..VALUES.len())]
+    }
+}
+#[derive(Debug)]
+pub struct Batch {
+    pub samples: picopb::common::Packed<Vec<i32>>,
+    pub tags: Vec<String>,
+    pub locations: Vec<Location>,
+    pub counters: Vec<u32>,
+}
+impl Default for Batch {
+    fn default() -> Self {
+        Self {
+            samples: picopb::common::Packed(Vec::new()),
+            tags: Default::default(),
+            locations: Default::default(),
+            counters: Default::default(),
+        }
+    }
+}
+#[derive(Debug)]
+pub struct BatchFieldsType<'a> {
+    pub samples: picopb::common::ConstMessageField<'a>,
+    pub tags: picopb::common::ConstMessageField<'a>,
+    pub locations: picopb::common::ConstMessageField<'a>,
+    pub counters: picopb::common::ConstMessageField<'a>,
+}
+const BATCH_FIELDS: BatchFieldsType = BatchFieldsType {
+    samples: picopb::common::ConstMessageField {
+        qualifier: picopb::common::FieldQualifier::PackedRepeatedUnbounded,
+        field_type: picopb::common::FieldType::Int32,
+        identifier: "samples",
+        json_name: "samples",
+        ordinal: picopb::common::Field(1),
+    },
+    tags: picopb::common::ConstMessageField {
+        qualifier: picopb::common::FieldQualifier::RepeatedUnbounded,
+        field_type: picopb::common::FieldType::UnboundedString,
+        identifier: "tags",
+        json_name: "tags",
+        ordinal: picopb::common::Field(2),
+    },
+    locations: picopb::common::ConstMessageField {
+        qualifier: picopb::common::FieldQualifier::RepeatedUnbounded,
+        field_type: picopb::common::FieldType::MessageType("Location"),
+        identifier: "locations",
+        json_name: "locations",
+        ordinal: picopb::common::Field(3),
+    },
+    counters: picopb::common::ConstMessageField {
+        qualifier: picopb::common::FieldQualifier::RepeatedUnbounded,
+        field_type: picopb::common::FieldType::Uint32,
+        identifier: "counters",
+        json_name: "counters",
+        ordinal: picopb::common::Field(4),
+    },
+};
+impl Batch {
+    fn fields(&self) -> BatchFieldsType<'static> {
+        BATCH_FIELDS
+    }
+}
+impl Batch {
+    pub const MSGID: u32 = 3;
+}
+// Batch has no MAX_ENCODED_LEN, unbounded: Batch.samples
+impl picopb::wiretypes::WireTyped for &Batch {
+    fn wiretype(&self) -> WireType {
+        WireType::Len
+    }
+}
+impl picopb::encode::ToWire for &Batch {
+    fn append<W: picopb::encode::EncodeSink>(&self, buf: &mut W) -> picopb::encode::SinkResult<usize> {
+        let mut total_size = 0;
+        if !self.samples.0.is_empty() {
+            total_size += buf.encode(picopb::common::Packed(self.samples.0.as_slice()), self.fields().samples.ordinal)?;
+        }
+        for value_tags in self.tags.iter() {
+            total_size += buf.encode(value_tags.as_str(), self.fields().tags.ordinal)?;
+        }
+        for value_locations in self.locations.iter() {
+            total_size += buf.encode_sub_message(value_locations, self.fields().locations.ordinal)?;
+        }
+        for value_counters in self.counters.iter() {
+            total_size += buf.encode(*value_counters, self.fields().counters.ordinal)?;
+        }
+        Ok(total_size)
+    }
+    fn precalculate_size(&self) -> usize {
+        let mut total_size = 0;
+        if !self.samples.0.is_empty() {
+            total_size += picopb::common::Packed(self.samples.0.as_slice()).precalculate_field_size(self.fields().samples.ordinal);
+        }
+        for value_tags in self.tags.iter() {
+            total_size += value_tags.as_str().precalculate_field_size(self.fields().tags.ordinal);
+        }
+        for value_locations in self.locations.iter() {
+            total_size += value_locations.precalculate_sub_message_size(self.fields().locations.ordinal);
+        }
+        for value_counters in self.counters.iter() {
+            total_size += (*value_counters).precalculate_field_size(self.fields().counters.ordinal);
+        }
+        total_size
+    }
+}
+impl picopb::decode::DecodeMessage for Batch {
+    fn decode_from(buf: &mut picopb::decode::DecodeBuffer<'_>) -> picopb::decode::Result<Self> {
+        let mut message = Self::default();
+        while !buf.is_empty() {
+            let (field, wire_type) = buf.read_tag()?;
+            match field.0 {
+                1 => {
+                    picopb::decode::read_repeated(buf, field, wire_type, |value| {
+                        message.samples.0.push(value);
+                        Ok(())
+                    })?;
+                }
+                2 => {
+                    picopb::decode::read_repeated(buf, field, wire_type, |value| {
+                        message.tags.push(value);
+                        Ok(())
+                    })?;
+                }
+                3 => {
+                    picopb::decode::read_repeated(buf, field, wire_type, |value| {
+                        message.locations.push(value);
+                        Ok(())
+                    })?;
+                }
+                4 => {
+                    picopb::decode::read_repeated(buf, field, wire_type, |value| {
+                        message.counters.push(value);
+                        Ok(())
+                    })?;
+                }
+                _ => buf.skip_unknown(field, wire_type)?,
+            }
+        }
+        Ok(message)
+    }
+}
+impl picopb::decode::FromWire for Batch {
+    fn wire_type() -> WireType {
+        WireType::Len
+    }
+    fn read(buf: &mut picopb::decode::DecodeBuffer<'_>) -> picopb::decode::Result<Self> {
+        <Self as picopb::decode::DecodeMessage>::decode_from(&mut buf.read_sub_buffer()?)
+    }
+}
+impl Randomize for Batch {
+    fn randomized<R: picopb::randomizer::Rng + ?Sized>(rng: &mut R) -> Self {
+        Self {
+            samples: randomized(rng),
+            tags: randomized(rng),
+            locations: randomized(rng),
+            counters: randomized(rng),
+        }
+    }
+}
+impl picopb::text_format::ToText for Batch {
+    fn write_text<W: core::fmt::Write>(&self, out: &mut W) -> core::fmt::Result {
+        let mut writer = picopb::text_format::FieldWriter::new(out);
+        for value_samples in self.samples.0.iter() {
+            writer.field(self.fields().samples.identifier, value_samples)?;
+        }
+        for value_tags in self.tags.iter() {
+            writer.field(self.fields().tags.identifier, value_tags)?;
+        }
+        for value_locations in self.locations.iter() {
+            writer.message(self.fields().locations.identifier, value_locations)?;
+        }
+        for value_counters in self.counters.iter() {
+            writer.field(self.fields().counters.identifier, value_counters)?;
+        }
+        Ok(())
+    }
+}
+impl picopb::text_format::FromText for Batch {
+    fn from_text(text: &picopb::text_format::TextValue) -> picopb::text_format::Result<Self> {
+        let mut message = Self::default();
+        for (name, value) in text.as_message()? {
+            match name.as_str() {
+                "samples" => {
+                    picopb::text_format::read_repeated(value, |value| {
+                        message.samples.0.push(value);
+                        Ok(())
+                    })?;
+                }
+                "tags" => {
+                    picopb::text_format::read_repeated(value, |value| {
+                        message.tags.push(value);
+                        Ok(())
+                    })?;
+                }
+                "locations" => {
+                    picopb::text_format::read_repeated(value, |value| {
+                        message.locations.push(value);
+                        Ok(())
+                    })?;
+                }
+                "counters" => {
+                    picopb::text_format::read_repeated(value, |value| {
+                        message.counters.push(value);
+                        Ok(())
+                    })?;
+                }
+                _ => return Err(picopb::text_format::TextFormatError::UnknownField(name.clone())),
+            }
+        }
+        Ok(message)
+    }
+}
+impl Batch {
+    pub const DESCRIPTOR: picopb::reflect::MessageDescriptor = picopb::reflect::MessageDescriptor {
+        name: "Batch",
+        fields: &[
+            BATCH_FIELDS.samples,
+            BATCH_FIELDS.tags,
+            BATCH_FIELDS.locations,
+            BATCH_FIELDS.counters,
+        ],
+    };
+}
+impl picopb::reflect::Reflect for Batch {
+    fn descriptor(&self) -> &'static picopb::reflect::MessageDescriptor {
+        &Self::DESCRIPTOR
+    }
+    fn get(&self, number: u32) -> Option<Vec<picopb::reflect::FieldValue<'_>>> {
+        match number {
+            1 => Some(self.samples.0.iter().map(picopb::reflect::ReflectField::to_value).collect()),
+            2 => Some(self.tags.iter().map(picopb::reflect::ReflectField::to_value).collect()),
+            3 => Some(self.locations.iter().map(picopb::reflect::ReflectField::to_value).collect()),
+            4 => Some(self.counters.iter().map(picopb::reflect::ReflectField::to_value).collect()),
+            _ => None,
+        }
+    }
+    fn set(&mut self, number: u32, values: &[picopb::reflect::FieldValue]) -> picopb::reflect::Result<()> {
+        match number {
+            1 => picopb::reflect::set_repeated(&mut self.samples.0, values),
+            2 => picopb::reflect::set_repeated(&mut self.tags, values),
+            3 => picopb::reflect::set_repeated(&mut self.locations, values),
+            4 => picopb::reflect::set_repeated(&mut self.counters, values),
+            _ => Err(picopb::reflect::ReflectError::UnknownField(number.to_string())),
+        }
+    }
+}
+impl picopb::reflect::ReflectField for Batch {
+    fn to_value(&self) -> picopb::reflect::FieldValue<'_> {
+        picopb::reflect::FieldValue::Message(self)
+    }
+    fn from_value(value: &picopb::reflect::FieldValue) -> picopb::reflect::Result<Self> {
+        let mut message = Self::default();
+        picopb::reflect::copy_fields(&mut message, value.as_message()?)?;
+        Ok(message)
+    }
+}
+#[derive(Default, Debug)]
+pub struct Location {
+    pub latitude: i32,
+    pub longitude: i32,
+    pub label: Option<bytes::Bytes>,
+}
+#[derive(Debug)]
+pub struct LocationFieldsType<'a> {
+    pub latitude: picopb::common::ConstMessageField<'a>,
+    pub longitude: picopb::common::ConstMessageField<'a>,
+    pub label: picopb::common::ConstMessageField<'a>,
+}
+const LOCATION_FIELDS: LocationFieldsType = LocationFieldsType {
+    latitude: picopb::common::ConstMessageField {
+        qualifier: picopb::common::FieldQualifier::Required,
+        field_type: picopb::common::FieldType::Int32,
+        identifier: "latitude",
+        json_name: "latitude",
+        ordinal: picopb::common::Field(1),
+    },
+    longitude: picopb::common::ConstMessageField {
+        qualifier: picopb::common::FieldQualifier::Required,
+        field_type: picopb::common::FieldType::Int32,
+        identifier: "longitude",
+        json_name: "longitude",
+        ordinal: picopb::common::Field(2),
+    },
+    label: picopb::common::ConstMessageField {
+        qualifier: picopb::common::FieldQualifier::Optional,
+        field_type: picopb::common::FieldType::UnboundedBytes,
+        identifier: "label",
+        json_name: "label",
+        ordinal: picopb::common::Field(3),
+    },
+};
+impl Location {
+    fn fields(&self) -> LocationFieldsType<'static> {
+        LOCATION_FIELDS
+    }
+}
+// Location has no MAX_ENCODED_LEN, unbounded: Location.label
+impl picopb::wiretypes::WireTyped for &Location {
+    fn wiretype(&self) -> WireType {
+        WireType::Len
+    }
+}
+impl picopb::encode::ToWire for &Location {
+    fn append<W: picopb::encode::EncodeSink>(&self, buf: &mut W) -> picopb::encode::SinkResult<usize> {
+        let mut total_size = 0;
+        total_size += buf.encode(self.latitude, self.fields().latitude.ordinal)?;
+        total_size += buf.encode(self.longitude, self.fields().longitude.ordinal)?;
+        if let Some(value_label) = &self.label {
+            total_size += buf.encode(value_label.deref(), self.fields().label.ordinal)?;
+        }
+        Ok(total_size)
+    }
+    fn precalculate_size(&self) -> usize {
+        let mut total_size = 0;
+        total_size += self.latitude.precalculate_field_size(self.fields().latitude.ordinal);
+        total_size += self.longitude.precalculate_field_size(self.fields().longitude.ordinal);
+        if let Some(value_label) = &self.label {
+            total_size += value_label.deref().precalculate_field_size(self.fields().label.ordinal);
+        }
+        total_size
+    }
+}
+impl picopb::decode::DecodeMessage for Location {
+    fn decode_from(buf: &mut picopb::decode::DecodeBuffer<'_>) -> picopb::decode::Result<Self> {
+        let mut message = Self::default();
+        let mut has_latitude = false;
+        let mut has_longitude = false;
+        while !buf.is_empty() {
+            let (field, wire_type) = buf.read_tag()?;
+            match field.0 {
+                1 => {
+                    message.latitude = picopb::decode::read_field(buf, field, wire_type)?;
+                    has_latitude = true;
+                }
+                2 => {
+                    message.longitude = picopb::decode::read_field(buf, field, wire_type)?;
+                    has_longitude = true;
+                }
+                3 => {
+                    message.label = Some(picopb::decode::read_field(buf, field, wire_type)?);
+                }
+                _ => buf.skip_unknown(field, wire_type)?,
+            }
+        }
+        if !has_latitude {
+            return Err(picopb::decode::DecodeError::MissingRequiredField("latitude"));
+        }
+        if !has_longitude {
+            return Err(picopb::decode::DecodeError::MissingRequiredField("longitude"));
+        }
+        Ok(message)
+    }
+}
+impl picopb::decode::FromWire for Location {
+    fn wire_type() -> WireType {
+        WireType::Len
+    }
+    fn read(buf: &mut picopb::decode::DecodeBuffer<'_>) -> picopb::decode::Result<Self> {
+        <Self as picopb::decode::DecodeMessage>::decode_from(&mut buf.read_sub_buffer()?)
+    }
+}
+impl Randomize for Location {
+    fn randomized<R: picopb::randomizer::Rng + ?Sized>(rng: &mut R) -> Self {
+        Self {
+            latitude: randomized(rng),
+            longitude: randomized(rng),
+            label: randomized(rng),
+        }
+    }
+}
+impl picopb::text_format::ToText for Location {
+    fn write_text<W: core::fmt::Write>(&self, out: &mut W) -> core::fmt::Result {
+        let mut writer = picopb::text_format::FieldWriter::new(out);
+        writer.field(self.fields().latitude.identifier, &self.latitude)?;
+        writer.field(self.fields().longitude.identifier, &self.longitude)?;
+        if let Some(value_label) = &self.label {
+            writer.field(self.fields().label.identifier, value_label)?;
+        }
+        Ok(())
+    }
+}
+impl picopb::text_format::FromText for Location {
+    fn from_text(text: &picopb::text_format::TextValue) -> picopb::text_format::Result<Self> {
+        let mut message = Self::default();
+        let mut has_latitude = false;
+        let mut has_longitude = false;
+        for (name, value) in text.as_message()? {
+            match name.as_str() {
+                "latitude" => {
+                    message.latitude = picopb::text_format::FromText::from_text(value)?;
+                    has_latitude = true;
+                }
+                "longitude" => {
+                    message.longitude = picopb::text_format::FromText::from_text(value)?;
+                    has_longitude = true;
+                }
+                "label" => {
+                    message.label = Some(picopb::text_format::FromText::from_text(value)?);
+                }
+                _ => return Err(picopb::text_format::TextFormatError::UnknownField(name.clone())),
+            }
+        }
+        if !has_latitude {
+            return Err(picopb::text_format::TextFormatError::MissingRequiredField("latitude"));
+        }
+        if !has_longitude {
+            return Err(picopb::text_format::TextFormatError::MissingRequiredField("longitude"));
+        }
+        Ok(message)
+    }
+}
+impl Location {
+    pub const DESCRIPTOR: picopb::reflect::MessageDescriptor = picopb::reflect::MessageDescriptor {
+        name: "Location",
+        fields: &[
+            LOCATION_FIELDS.latitude,
+            LOCATION_FIELDS.longitude,
+            LOCATION_FIELDS.label,
+        ],
+    };
+}
+impl picopb::reflect::Reflect for Location {
+    fn descriptor(&self) -> &'static picopb::reflect::MessageDescriptor {
+        &Self::DESCRIPTOR
+    }
+    fn get(&self, number: u32) -> Option<Vec<picopb::reflect::FieldValue<'_>>> {
+        match number {
+            1 => Some(vec![picopb::reflect::ReflectField::to_value(&self.latitude)]),
+            2 => Some(vec![picopb::reflect::ReflectField::to_value(&self.longitude)]),
+            3 => Some(self.label.iter().map(picopb::reflect::ReflectField::to_value).collect()),
+            _ => None,
+        }
+    }
+    fn set(&mut self, number: u32, values: &[picopb::reflect::FieldValue]) -> picopb::reflect::Result<()> {
+        match number {
+            1 => picopb::reflect::set_required(&mut self.latitude, values),
+            2 => picopb::reflect::set_required(&mut self.longitude, values),
+            3 => picopb::reflect::set_optional(&mut self.label, values),
+            _ => Err(picopb::reflect::ReflectError::UnknownField(number.to_string())),
+        }
+    }
+}
+impl picopb::reflect::ReflectField for Location {
+    fn to_value(&self) -> picopb::reflect::FieldValue<'_> {
+        picopb::reflect::FieldValue::Message(self)
+    }
+    fn from_value(value: &picopb::reflect::FieldValue) -> picopb::reflect::Result<Self> {
+        let mut message = Self::default();
+        picopb::reflect::copy_fields(&mut message, value.as_message()?)?;
+        Ok(message)
+    }
+}
+#[derive(Default, Debug)]
+pub struct Ping {
+    pub sequence: i32,
+}
+#[derive(Debug)]
+pub struct PingFieldsType<'a> {
+    pub sequence: picopb::common::ConstMessageField<'a>,
+}
+const PING_FIELDS: PingFieldsType = PingFieldsType {
+    sequence: picopb::common::ConstMessageField {
+        qualifier: picopb::common::FieldQualifier::Required,
+        field_type: picopb::common::FieldType::Int32,
+        identifier: "sequence",
+        json_name: "sequence",
+        ordinal: picopb::common::Field(1),
+    },
+};
+impl Ping {
+    fn fields(&self) -> PingFieldsType<'static> {
+        PING_FIELDS
+    }
+}
+impl Ping {
+    pub const MSGID: u32 = 1;
+}
+impl Ping {
+    pub const MAX_ENCODED_LEN: usize = 11;
+}
+impl picopb::wiretypes::WireTyped for &Ping {
+    fn wiretype(&self) -> WireType {
+        WireType::Len
+    }
+}
+impl picopb::encode::ToWire for &Ping {
+    fn append<W: picopb::encode::EncodeSink>(&self, buf: &mut W) -> picopb::encode::SinkResult<usize> {
+        let mut total_size = 0;
+        total_size += buf.encode(self.sequence, self.fields().sequence.ordinal)?;
+        Ok(total_size)
+    }
+    fn precalculate_size(&self) -> usize {
+        let mut total_size = 0;
+        total_size += self.sequence.precalculate_field_size(self.fields().sequence.ordinal);
+        total_size
+    }
+}
+impl picopb::decode::DecodeMessage for Ping {
+    fn decode_from(buf: &mut picopb::decode::DecodeBuffer<'_>) -> picopb::decode::Result<Self> {
+        let mut message = Self::default();
+        let mut has_sequence = false;
+        while !buf.is_empty() {
+            let (field, wire_type) = buf.read_tag()?;
+            match field.0 {
+                1 => {
+                    message.sequence = picopb::decode::read_field(buf, field, wire_type)?;
+                    has_sequence = true;
+                }
+                _ => buf.skip_unknown(field, wire_type)?,
+            }
+        }
+        if !has_sequence {
+            return Err(picopb::decode::DecodeError::MissingRequiredField("sequence"));
+        }
+        Ok(message)
+    }
+}
+impl picopb::decode::FromWire for Ping {
+    fn wire_type() -> WireType {
+        WireType::Len
+    }
+    fn read(buf: &mut picopb::decode::DecodeBuffer<'_>) -> picopb::decode::Result<Self> {
+        <Self as picopb::decode::DecodeMessage>::decode_from(&mut buf.read_sub_buffer()?)
+    }
+}
+impl Randomize for Ping {
+    fn randomized<R: picopb::randomizer::Rng + ?Sized>(rng: &mut R) -> Self {
+        Self {
+            sequence: randomized(rng),
+        }
+    }
+}
+impl picopb::text_format::ToText for Ping {
+    fn write_text<W: core::fmt::Write>(&self, out: &mut W) -> core::fmt::Result {
+        let mut writer = picopb::text_format::FieldWriter::new(out);
+        writer.field(self.fields().sequence.identifier, &self.sequence)?;
+        Ok(())
+    }
+}
+impl picopb::text_format::FromText for Ping {
+    fn from_text(text: &picopb::text_format::TextValue) -> picopb::text_format::Result<Self> {
+        let mut message = Self::default();
+        let mut has_sequence = false;
+        for (name, value) in text.as_message()? {
+            match name.as_str() {
+                "sequence" => {
+                    message.sequence = picopb::text_format::FromText::from_text(value)?;
+                    has_sequence = true;
+                }
+                _ => return Err(picopb::text_format::TextFormatError::UnknownField(name.clone())),
+            }
+        }
+        if !has_sequence {
+            return Err(picopb::text_format::TextFormatError::MissingRequiredField("sequence"));
+        }
+        Ok(message)
+    }
+}
+impl Ping {
+    pub const DESCRIPTOR: picopb::reflect::MessageDescriptor = picopb::reflect::MessageDescriptor {
+        name: "Ping",
+        fields: &[
+            PING_FIELDS.sequence,
+        ],
+    };
+}
+impl picopb::reflect::Reflect for Ping {
+    fn descriptor(&self) -> &'static picopb::reflect::MessageDescriptor {
+        &Self::DESCRIPTOR
+    }
+    fn get(&self, number: u32) -> Option<Vec<picopb::reflect::FieldValue<'_>>> {
+        match number {
+            1 => Some(vec![picopb::reflect::ReflectField::to_value(&self.sequence)]),
+            _ => None,
+        }
+    }
+    fn set(&mut self, number: u32, values: &[picopb::reflect::FieldValue]) -> picopb::reflect::Result<()> {
+        match number {
+            1 => picopb::reflect::set_required(&mut self.sequence, values),
+            _ => Err(picopb::reflect::ReflectError::UnknownField(number.to_string())),
+        }
+    }
+}
+impl picopb::reflect::ReflectField for Ping {
+    fn to_value(&self) -> picopb::reflect::FieldValue<'_> {
+        picopb::reflect::FieldValue::Message(self)
+    }
+    fn from_value(value: &picopb::reflect::FieldValue) -> picopb::reflect::Result<Self> {
+        let mut message = Self::default();
+        picopb::reflect::copy_fields(&mut message, value.as_message()?)?;
+        Ok(message)
+    }
+}
+#[derive(Default, Debug)]
+pub struct Reading {
+    pub sensor: String,
+    pub value: Option<i32>,
+    pub location: Option<Location>,
+}
+#[derive(Debug)]
+pub struct ReadingFieldsType<'a> {
+    pub sensor: picopb::common::ConstMessageField<'a>,
+    pub value: picopb::common::ConstMessageField<'a>,
+    pub location: picopb::common::ConstMessageField<'a>,
+}
+const READING_FIELDS: ReadingFieldsType = ReadingFieldsType {
+    sensor: picopb::common::ConstMessageField {
+        qualifier: picopb::common::FieldQualifier::Required,
+        field_type: picopb::common::FieldType::UnboundedString,
+        identifier: "sensor",
+        json_name: "sensor",
+        ordinal: picopb::common::Field(1),
+    },
+    value: picopb::common::ConstMessageField {
+        qualifier: picopb::common::FieldQualifier::Optional,
+        field_type: picopb::common::FieldType::Int32,
+        identifier: "value",
+        json_name: "value",
+        ordinal: picopb::common::Field(2),
+    },
+    location: picopb::common::ConstMessageField {
+        qualifier: picopb::common::FieldQualifier::Optional,
+        field_type: picopb::common::FieldType::MessageType("Location"),
+        identifier: "location",
+        json_name: "location",
+        ordinal: picopb::common::Field(3),
+    },
+};
+impl Reading {
+    fn fields(&self) -> ReadingFieldsType<'static> {
+        READING_FIELDS
+    }
+}
+impl Reading {
+    pub const MSGID: u32 = 2;
+}
+// Reading has no MAX_ENCODED_LEN, unbounded: Reading.sensor
+impl picopb::wiretypes::WireTyped for &Reading {
+    fn wiretype(&self) -> WireType {
+        WireType::Len
+    }
+}
+impl picopb::encode::ToWire for &Reading {
+    fn append<W: picopb::encode::EncodeSink>(&self, buf: &mut W) -> picopb::encode::SinkResult<usize> {
+        let mut total_size = 0;
+        total_size += buf.encode(self.sensor.as_str(), self.fields().sensor.ordinal)?;
+        if let Some(value_value) = &self.value {
+            total_size += buf.encode(*value_value, self.fields().value.ordinal)?;
+        }
+        if let Some(value_location) = &self.location {
+            total_size += buf.encode_sub_message(value_location, self.fields().location.ordinal)?;
+        }
+        Ok(total_size)
+    }
+    fn precalculate_size(&self) -> usize {
+        let mut total_size = 0;
+        total_size += self.sensor.as_str().precalculate_field_size(self.fields().sensor.ordinal);
+        if let Some(value_value) = &self.value {
+            total_size += (*value_value).precalculate_field_size(self.fields().value.ordinal);
+        }
+        if let Some(value_location) = &self.location {
+            total_size += value_location.precalculate_sub_message_size(self.fields().location.ordinal);
+        }
+        total_size
+    }
+}
+impl picopb::decode::DecodeMessage for Reading {
+    fn decode_from(buf: &mut picopb::decode::DecodeBuffer<'_>) -> picopb::decode::Result<Self> {
+        let mut message = Self::default();
+        let mut has_sensor = false;
+        while !buf.is_empty() {
+            let (field, wire_type) = buf.read_tag()?;
+            match field.0 {
+                1 => {
+                    message.sensor = picopb::decode::read_field(buf, field, wire_type)?;
+                    has_sensor = true;
+                }
+                2 => {
+                    message.value = Some(picopb::decode::read_field(buf, field, wire_type)?);
+                }
+                3 => {
+                    message.location = Some(picopb::decode::read_field(buf, field, wire_type)?);
+                }
+                _ => buf.skip_unknown(field, wire_type)?,
+            }
+        }
+        if !has_sensor {
+            return Err(picopb::decode::DecodeError::MissingRequiredField("sensor"));
+        }
+        Ok(message)
+    }
+}
+impl picopb::decode::FromWire for Reading {
+    fn wire_type() -> WireType {
+        WireType::Len
+    }
+    fn read(buf: &mut picopb::decode::DecodeBuffer<'_>) -> picopb::decode::Result<Self> {
+        <Self as picopb::decode::DecodeMessage>::decode_from(&mut buf.read_sub_buffer()?)
+    }
+}
+impl Randomize for Reading {
+    fn randomized<R: picopb::randomizer::Rng + ?Sized>(rng: &mut R) -> Self {
+        Self {
+            sensor: randomized(rng),
+            value: randomized(rng),
+            location: randomized(rng),
+        }
+    }
+}
+impl picopb::text_format::ToText for Reading {
+    fn write_text<W: core::fmt::Write>(&self, out: &mut W) -> core::fmt::Result {
+        let mut writer = picopb::text_format::FieldWriter::new(out);
+        writer.field(self.fields().sensor.identifier, &self.sensor)?;
+        if let Some(value_value) = &self.value {
+            writer.field(self.fields().value.identifier, value_value)?;
+        }
+        if let Some(value_location) = &self.location {
+            writer.message(self.fields().location.identifier, value_location)?;
+        }
+        Ok(())
+    }
+}
+impl picopb::text_format::FromText for Reading {
+    fn from_text(text: &picopb::text_format::TextValue) -> picopb::text_format::Result<Self> {
+        let mut message = Self::default();
+        let mut has_sensor = false;
+        for (name, value) in text.as_message()? {
+            match name.as_str() {
+                "sensor" => {
+                    message.sensor = picopb::text_format::FromText::from_text(value)?;
+                    has_sensor = true;
+                }
+                "value" => {
+                    message.value = Some(picopb::text_format::FromText::from_text(value)?);
+                }
+                "location" => {
+                    message.location = Some(picopb::text_format::FromText::from_text(value)?);
+                }
+                _ => return Err(picopb::text_format::TextFormatError::UnknownField(name.clone())),
+            }
+        }
+        if !has_sensor {
+            return Err(picopb::text_format::TextFormatError::MissingRequiredField("sensor"));
+        }
+        Ok(message)
+    }
+}
+impl Reading {
+    pub const DESCRIPTOR: picopb::reflect::MessageDescriptor = picopb::reflect::MessageDescriptor {
+        name: "Reading",
+        fields: &[
+            READING_FIELDS.sensor,
+            READING_FIELDS.value,
+            READING_FIELDS.location,
+        ],
+    };
+}
+impl picopb::reflect::Reflect for Reading {
+    fn descriptor(&self) -> &'static picopb::reflect::MessageDescriptor {
+        &Self::DESCRIPTOR
+    }
+    fn get(&self, number: u32) -> Option<Vec<picopb::reflect::FieldValue<'_>>> {
+        match number {
+            1 => Some(vec![picopb::reflect::ReflectField::to_value(&self.sensor)]),
+            2 => Some(self.value.iter().map(picopb::reflect::ReflectField::to_value).collect()),
+            3 => Some(self.location.iter().map(picopb::reflect::ReflectField::to_value).collect()),
+            _ => None,
+        }
+    }
+    fn set(&mut self, number: u32, values: &[picopb::reflect::FieldValue]) -> picopb::reflect::Result<()> {
+        match number {
+            1 => picopb::reflect::set_required(&mut self.sensor, values),
+            2 => picopb::reflect::set_optional(&mut self.value, values),
+            3 => picopb::reflect::set_optional(&mut self.location, values),
+            _ => Err(picopb::reflect::ReflectError::UnknownField(number.to_string())),
+        }
+    }
+}
+impl picopb::reflect::ReflectField for Reading {
+    fn to_value(&self) -> picopb::reflect::FieldValue<'_> {
+        picopb::reflect::FieldValue::Message(self)
+    }
+    fn from_value(value: &picopb::reflect::FieldValue) -> picopb::reflect::Result<Self> {
+        let mut message = Self::default();
+        picopb::reflect::copy_fields(&mut message, value.as_message()?)?;
+        Ok(message)
+    }
+}
+#[derive(Debug)]
+pub enum AnyMessage {
+    Batch(Batch),
+    Ping(Ping),
+    Reading(Reading),
+}
+impl From<Batch> for AnyMessage {
+    fn from(value: Batch) -> Self {
+        Self::Batch(value)
+    }
+}
+impl From<Ping> for AnyMessage {
+    fn from(value: Ping) -> Self {
+        Self::Ping(value)
+    }
+}
+impl From<Reading> for AnyMessage {
+    fn from(value: Reading) -> Self {
+        Self::Reading(value)
+    }
+}
+impl AnyMessage {
+    pub fn id(&self) -> u32 {
+        match self {
+            Self::Batch(_) => Batch::MSGID,
+            Self::Ping(_) => Ping::MSGID,
+            Self::Reading(_) => Reading::MSGID,
+        }
+    }
+    pub fn decode_any(id: u32, bytes: &[u8]) -> picopb::decode::Result<Self> {
+        match id {
+            Batch::MSGID => Ok(Self::Batch(<Batch as picopb::decode::DecodeMessage>::decode(bytes)?)),
+            Ping::MSGID => Ok(Self::Ping(<Ping as picopb::decode::DecodeMessage>::decode(bytes)?)),
+            Reading::MSGID => Ok(Self::Reading(<Reading as picopb::decode::DecodeMessage>::decode(bytes)?)),
+            _ => Err(picopb::decode::DecodeError::UnknownMessageId(id)),
+        }
+    }
+    /// Decodes a message written by `encode_with_id`, a varint message id followed by the message
+    pub fn decode_with_id(bytes: &[u8]) -> picopb::decode::Result<Self> {
+        let mut buf = picopb::decode::DecodeBuffer::from_slice(bytes);
+        let id = u32::try_from(buf.read_varint()?).map_err(|_| picopb::decode::DecodeError::VarintTooLong)?;
+        Self::decode_any(id, buf.remaining())
+    }
+    pub fn encode_with_id<W: picopb::encode::EncodeSink>(&self, buf: &mut W) -> picopb::encode::SinkResult<usize> {
+        match self {
+            Self::Batch(message) => picopb::encode::encode_with_id(buf, Batch::MSGID, message),
+            Self::Ping(message) => picopb::encode::encode_with_id(buf, Ping::MSGID, message),
+            Self::Reading(message) => picopb::encode::encode_with_id(buf, Reading::MSGID, message),
+        }
+    }
+}
@@ -0,0 +1,722 @@
+use picopb::common::*;
+use picopb::encode::{EncodeSink, ToWire};
+use picopb::wiretypes::{WireType, WireTyped};
+use picopb::randomizer::{randomized, Randomize};
+use std::ops::Deref;
+#[derive(Default, Debug)]
+pub struct Position {
+    pub x: i32,
+    pub unknown_fields: Vec<u8>,
+}
+#[derive(Debug)]
+pub struct PositionFieldsType<'a> {
+    pub x: picopb::common::ConstMessageField<'a>,
+}
+const POSITION_FIELDS: PositionFieldsType = PositionFieldsType {
+    x: picopb::common::ConstMessageField {
+        qualifier: picopb::common::FieldQualifier::Required,
+        field_type: picopb::common::FieldType::Int32,
+        identifier: "x",
+        json_name: "x",
+        ordinal: picopb::common::Field(1),
+    },
+};
+impl Position {
+    fn fields(&self) -> PositionFieldsType<'static> {
+        POSITION_FIELDS
+    }
+}
+// Position has no MAX_ENCODED_LEN, unbounded: Position.unknown_fields
+impl picopb::wiretypes::WireTyped for &Position {
+    fn wiretype(&self) -> WireType {
+        WireType::Len
+    }
+}
+impl picopb::encode::ToWire for &Position {
+    fn append<W: picopb::encode::EncodeSink>(&self, buf: &mut W) -> std::io::Result<usize> {
+        let mut total_size = 0;
+        total_size += buf.encode(self.x, self.fields().x.ordinal)?;
+        total_size += buf.write_bytes(self.unknown_fields.as_bytes())?;
+        Ok(total_size)
+    }
+    fn precalculate_size(&self) -> usize {
+        let mut total_size = 0;
+        total_size += self.x.precalculate_field_size(self.fields().x.ordinal);
+        total_size += self.unknown_fields.as_bytes().len();
+        total_size
+    }
+}
+impl picopb::decode::DecodeMessage for Position {
+    fn decode_from(buf: &mut picopb::decode::DecodeBuffer<'_>) -> picopb::decode::Result<Self> {
+        let mut message = Self::default();
+        let mut has_x = false;
+        while !buf.is_empty() {
+            let (field, wire_type) = buf.read_tag()?;
+            match field.0 {
+                1 => {
+                    message.x = picopb::decode::read_field(buf, field, wire_type)?;
+                    has_x = true;
+                }
+                _ => buf.read_unknown(field, wire_type, &mut message.unknown_fields)?,
+            }
+        }
+        if !has_x {
+            return Err(picopb::decode::DecodeError::MissingRequiredField("x"));
+        }
+        Ok(message)
+    }
+}
+impl picopb::decode::FromWire for Position {
+    fn wire_type() -> WireType {
+        WireType::Len
+    }
+    fn read(buf: &mut picopb::decode::DecodeBuffer<'_>) -> picopb::decode::Result<Self> {
+        <Self as picopb::decode::DecodeMessage>::decode_from(&mut buf.read_sub_buffer()?)
+    }
+}
+impl Randomize for Position {
+    fn randomized<R: picopb::randomizer::Rng + ?Sized>(rng: &mut R) -> Self {
+        Self {
+            x: randomized(rng),
+            unknown_fields: Default::default(),
+        }
+    }
+}
+impl picopb::text_format::ToText for Position {
+    fn write_text<W: std::fmt::Write>(&self, out: &mut W) -> std::fmt::Result {
+        let mut writer = picopb::text_format::FieldWriter::new(out);
+        writer.field(self.fields().x.identifier, &self.x)?;
+        Ok(())
+    }
+}
+impl picopb::text_format::FromText for Position {
+    fn from_text(text: &picopb::text_format::TextValue) -> picopb::text_format::Result<Self> {
+        let mut message = Self::default();
+        let mut has_x = false;
+        for (name, value) in text.as_message()? {
+            match name.as_str() {
+                "x" => {
+                    message.x = picopb::text_format::FromText::from_text(value)?;
+                    has_x = true;
+                }
+                _ => return Err(picopb::text_format::TextFormatError::UnknownField(name.clone())),
+            }
+        }
+        if !has_x {
+            return Err(picopb::text_format::TextFormatError::MissingRequiredField("x"));
+        }
+        Ok(message)
+    }
+}
+impl Position {
+    pub const DESCRIPTOR: picopb::reflect::MessageDescriptor = picopb::reflect::MessageDescriptor {
+        name: "Position",
+        fields: &[
+            POSITION_FIELDS.x,
+        ],
+    };
+}
+impl picopb::reflect::Reflect for Position {
+    fn descriptor(&self) -> &'static picopb::reflect::MessageDescriptor {
+        &Self::DESCRIPTOR
+    }
+    fn get(&self, number: u32) -> Option<Vec<picopb::reflect::FieldValue<'_>>> {
+        match number {
+            1 => Some(vec![picopb::reflect::ReflectField::to_value(&self.x)]),
+            _ => None,
+        }
+    }
+    fn set(&mut self, number: u32, values: &[picopb::reflect::FieldValue]) -> picopb::reflect::Result<()> {
+        match number {
+            1 => picopb::reflect::set_required(&mut self.x, values),
+            _ => Err(picopb::reflect::ReflectError::UnknownField(number.to_string())),
+        }
+    }
+}
+impl picopb::reflect::ReflectField for Position {
+    fn to_value(&self) -> picopb::reflect::FieldValue<'_> {
+        picopb::reflect::FieldValue::Message(self)
+    }
+    fn from_value(value: &picopb::reflect::FieldValue) -> picopb::reflect::Result<Self> {
+        let mut message = Self::default();
+        picopb::reflect::copy_fields(&mut message, value.as_message()?)?;
+        Ok(message)
+    }
+}
+#[derive(Default, Debug)]
+pub struct PositionV2 {
+    pub x: i32,
+    pub y: Option<i32>,
+    pub unknown_fields: Vec<u8>,
+}
+#[derive(Debug)]
+pub struct PositionV2FieldsType<'a> {
+    pub x: picopb::common::ConstMessageField<'a>,
+    pub y: picopb::common::ConstMessageField<'a>,
+}
+const POSITION_V_2_FIELDS: PositionV2FieldsType = PositionV2FieldsType {
+    x: picopb::common::ConstMessageField {
+        qualifier: picopb::common::FieldQualifier::Required,
+        field_type: picopb::common::FieldType::Int32,
+        identifier: "x",
+        json_name: "x",
+        ordinal: picopb::common::Field(1),
+    },
+    y: picopb::common::ConstMessageField {
+        qualifier: picopb::common::FieldQualifier::Optional,
+        field_type: picopb::common::FieldType::Int32,
+        identifier: "y",
+        json_name: "y",
+        ordinal: picopb::common::Field(2),
+    },
+};
+impl PositionV2 {
+    fn fields(&self) -> PositionV2FieldsType<'static> {
+        POSITION_V_2_FIELDS
+    }
+}
+// PositionV2 has no MAX_ENCODED_LEN, unbounded: PositionV2.unknown_fields
+impl picopb::wiretypes::WireTyped for &PositionV2 {
+    fn wiretype(&self) -> WireType {
+        WireType::Len
+    }
+}
+impl picopb::encode::ToWire for &PositionV2 {
+    fn append<W: picopb::encode::EncodeSink>(&self, buf: &mut W) -> std::io::Result<usize> {
+        let mut total_size = 0;
+        total_size += buf.encode(self.x, self.fields().x.ordinal)?;
+        if let Some(value_y) = &self.y {
+            total_size += buf.encode(*value_y, self.fields().y.ordinal)?;
+        }
+        total_size += buf.write_bytes(self.unknown_fields.as_bytes())?;
+        Ok(total_size)
+    }
+    fn precalculate_size(&self) -> usize {
+        let mut total_size = 0;
+        total_size += self.x.precalculate_field_size(self.fields().x.ordinal);
+        if let Some(value_y) = &self.y {
+            total_size += (*value_y).precalculate_field_size(self.fields().y.ordinal);
+        }
+        total_size += self.unknown_fields.as_bytes().len();
+        total_size
+    }
+}
+impl picopb::decode::DecodeMessage for PositionV2 {
+    fn decode_from(buf: &mut picopb::decode::DecodeBuffer<'_>) -> picopb::decode::Result<Self> {
+        let mut message = Self::default();
+        let mut has_x = false;
+        while !buf.is_empty() {
+            let (field, wire_type) = buf.read_tag()?;
+            match field.0 {
+                1 => {
+                    message.x = picopb::decode::read_field(buf, field, wire_type)?;
+                    has_x = true;
+                }
+                2 => {
+                    message.y = Some(picopb::decode::read_field(buf, field, wire_type)?);
+                }
+                _ => buf.read_unknown(field, wire_type, &mut message.unknown_fields)?,
+            }
+        }
+        if !has_x {
+            return Err(picopb::decode::DecodeError::MissingRequiredField("x"));
+        }
+        Ok(message)
+    }
+}
+impl picopb::decode::FromWire for PositionV2 {
+    fn wire_type() -> WireType {
+        WireType::Len
+    }
+    fn read(buf: &mut picopb::decode::DecodeBuffer<'_>) -> picopb::decode::Result<Self> {
+        <Self as picopb::decode::DecodeMessage>::decode_from(&mut buf.read_sub_buffer()?)
+    }
+}
+impl Randomize for PositionV2 {
+    fn randomized<R: picopb::randomizer::Rng + ?Sized>(rng: &mut R) -> Self {
+        Self {
+            x: randomized(rng),
+            y: randomized(rng),
+            unknown_fields: Default::default(),
+        }
+    }
+}
+impl picopb::text_format::ToText for PositionV2 {
+    fn write_text<W: std::fmt::Write>(&self, out: &mut W) -> std::fmt::Result {
+        let mut writer = picopb::text_format::FieldWriter::new(out);
+        writer.field(self.fields().x.identifier, &self.x)?;
+        if let Some(value_y) = &self.y {
+            writer.field(self.fields().y.identifier, value_y)?;
+        }
+        Ok(())
+    }
+}
+impl picopb::text_format::FromText for PositionV2 {
+    fn from_text(text: &picopb::text_format::TextValue) -> picopb::text_format::Result<Self> {
+        let mut message = Self::default();
+        let mut has_x = false;
+        for (name, value) in text.as_message()? {
+            match name.as_str() {
+                "x" => {
+                    message.x = picopb::text_format::FromText::from_text(value)?;
+                    has_x = true;
+                }
+                "y" => {
+                    message.y = Some(picopb::text_format::FromText::from_text(value)?);
+                }
+                _ => return Err(picopb::text_format::TextFormatError::UnknownField(name.clone())),
+            }
+        }
+        if !has_x {
+            return Err(picopb::text_format::TextFormatError::MissingRequiredField("x"));
+        }
+        Ok(message)
+    }
+}
+impl PositionV2 {
+    pub const DESCRIPTOR: picopb::reflect::MessageDescriptor = picopb::reflect::MessageDescriptor {
+        name: "PositionV2",
+        fields: &[
+            POSITION_V_2_FIELDS.x,
+            POSITION_V_2_FIELDS.y,
+        ],
+    };
+}
+impl picopb::reflect::Reflect for PositionV2 {
+    fn descriptor(&self) -> &'static picopb::reflect::MessageDescriptor {
+        &Self::DESCRIPTOR
+    }
+    fn get(&self, number: u32) -> Option<Vec<picopb::reflect::FieldValue<'_>>> {
+        match number {
+            1 => Some(vec![picopb::reflect::ReflectField::to_value(&self.x)]),
+            2 => Some(self.y.iter().map(picopb::reflect::ReflectField::to_value).collect()),
+            _ => None,
+        }
+    }
+    fn set(&mut self, number: u32, values: &[picopb::reflect::FieldValue]) -> picopb::reflect::Result<()> {
+        match number {
+            1 => picopb::reflect::set_required(&mut self.x, values),
+            2 => picopb::reflect::set_optional(&mut self.y, values),
+            _ => Err(picopb::reflect::ReflectError::UnknownField(number.to_string())),
+        }
+    }
+}
+impl picopb::reflect::ReflectField for PositionV2 {
+    fn to_value(&self) -> picopb::reflect::FieldValue<'_> {
+        picopb::reflect::FieldValue::Message(self)
+    }
+    fn from_value(value: &picopb::reflect::FieldValue) -> picopb::reflect::Result<Self> {
+        let mut message = Self::default();
+        picopb::reflect::copy_fields(&mut message, value.as_message()?)?;
+        Ok(message)
+    }
+}
+#[derive(Default, Debug)]
+pub struct Sensor {
+    pub id: u32,
+    pub position: Option<Position>,
+    pub unknown_fields: Vec<u8>,
+}
+#[derive(Debug)]
+pub struct SensorFieldsType<'a> {
+    pub id: picopb::common::ConstMessageField<'a>,
+    pub position: picopb::common::ConstMessageField<'a>,
+}
+const SENSOR_FIELDS: SensorFieldsType = SensorFieldsType {
+    id: picopb::common::ConstMessageField {
+        qualifier: picopb::common::FieldQualifier::Required,
+        field_type: picopb::common::FieldType::Uint32,
+        identifier: "id",
+        json_name: "id",
+        ordinal: picopb::common::Field(1),
+    },
+    position: picopb::common::ConstMessageField {
+        qualifier: picopb::common::FieldQualifier::Optional,
+        field_type: picopb::common::FieldType::MessageType("Position"),
+        identifier: "position",
+        json_name: "position",
+        ordinal: picopb::common::Field(2),
+    },
+};
+impl Sensor {
+    fn fields(&self) -> SensorFieldsType<'static> {
+        SENSOR_FIELDS
+    }
+}
+// Sensor has no MAX_ENCODED_LEN, unbounded: Sensor.unknown_fields
+impl picopb::wiretypes::WireTyped for &Sensor {
+    fn wiretype(&self) -> WireType {
+        WireType::Len
+    }
+}
+impl picopb::encode::ToWire for &Sensor {
+    fn append<W: picopb::encode::EncodeSink>(&self, buf: &mut W) -> std::io::Result<usize> {
+        let mut total_size = 0;
+        total_size += buf.encode(self.id, self.fields().id.ordinal)?;
+        if let Some(value_position) = &self.position {
+            total_size += buf.encode_sub_message(value_position, self.fields().position.ordinal)?;
+        }
+        total_size += buf.write_bytes(self.unknown_fields.as_bytes())?;
+        Ok(total_size)
+    }
+    fn precalculate_size(&self) -> usize {
+        let mut total_size = 0;
+        total_size += self.id.precalculate_field_size(self.fields().id.ordinal);
+        if let Some(value_position) = &self.position {
+            total_size += value_position.precalculate_sub_message_size(self.fields().position.ordinal);
+        }
+        total_size += self.unknown_fields.as_bytes().len();
+        total_size
+    }
+}
+impl picopb::decode::DecodeMessage for Sensor {
+    fn decode_from(buf: &mut picopb::decode::DecodeBuffer<'_>) -> picopb::decode::Result<Self> {
+        let mut message = Self::default();
+        let mut has_id = false;
+        while !buf.is_empty() {
+            let (field, wire_type) = buf.read_tag()?;
+            match field.0 {
+                1 => {
+                    message.id = picopb::decode::read_field(buf, field, wire_type)?;
+                    has_id = true;
+                }
+                2 => {
+                    message.position = Some(picopb::decode::read_field(buf, field, wire_type)?);
+                }
+                _ => buf.read_unknown(field, wire_type, &mut message.unknown_fields)?,
+            }
+        }
+        if !has_id {
+            return Err(picopb::decode::DecodeError::MissingRequiredField("id"));
+        }
+        Ok(message)
+    }
+}
+impl picopb::decode::FromWire for Sensor {
+    fn wire_type() -> WireType {
+        WireType::Len
+    }
+    fn read(buf: &mut picopb::decode::DecodeBuffer<'_>) -> picopb::decode::Result<Self> {
+        <Self as picopb::decode::DecodeMessage>::decode_from(&mut buf.read_sub_buffer()?)
+    }
+}
+impl Randomize for Sensor {
+    fn randomized<R: picopb::randomizer::Rng + ?Sized>(rng: &mut R) -> Self {
+        Self {
+            id: randomized(rng),
+            position: randomized(rng),
+            unknown_fields: Default::default(),
+        }
+    }
+}
+impl picopb::text_format::ToText for Sensor {
+    fn write_text<W: std::fmt::Write>(&self, out: &mut W) -> std::fmt::Result {
+        let mut writer = picopb::text_format::FieldWriter::new(out);
+        writer.field(self.fields().id.identifier, &self.id)?;
+        if let Some(value_position) = &self.position {
+            writer.message(self.fields().position.identifier, value_position)?;
+        }
+        Ok(())
+    }
+}
+impl picopb::text_format::FromText for Sensor {
+    fn from_text(text: &picopb::text_format::TextValue) -> picopb::text_format::Result<Self> {
+        let mut message = Self::default();
+        let mut has_id = false;
+        for (name, value) in text.as_message()? {
+            match name.as_str() {
+                "id" => {
+                    message.id = picopb::text_format::FromText::from_text(value)?;
+                    has_id = true;
+                }
+                "position" => {
+                    message.position = Some(picopb::text_format::FromText::from_text(value)?);
+                }
+                _ => return Err(picopb::text_format::TextFormatError::UnknownField(name.clone())),
+            }
+        }
+        if !has_id {
+            return Err(picopb::text_format::TextFormatError::MissingRequiredField("id"));
+        }
+        Ok(message)
+    }
+}
+impl Sensor {
+    pub const DESCRIPTOR: picopb::reflect::MessageDescriptor = picopb::reflect::MessageDescriptor {
+        name: "Sensor",
+        fields: &[
+            SENSOR_FIELDS.id,
+            SENSOR_FIELDS.position,
+        ],
+    };
+}
+impl picopb::reflect::Reflect for Sensor {
+    fn descriptor(&self) -> &'static picopb::reflect::MessageDescriptor {
+        &Self::DESCRIPTOR
+    }
+    fn get(&self, number: u32) -> Option<Vec<picopb::reflect::FieldValue<'_>>> {
+        match number {
+            1 => Some(vec![picopb::reflect::ReflectField::to_value(&self.id)]),
+            2 => Some(self.position.iter().map(picopb::reflect::ReflectField::to_value).collect()),
+            _ => None,
+        }
+    }
+    fn set(&mut self, number: u32, values: &[picopb::reflect::FieldValue]) -> picopb::reflect::Result<()> {
+        match number {
+            1 => picopb::reflect::set_required(&mut self.id, values),
+            2 => picopb::reflect::set_optional(&mut self.position, values),
+            _ => Err(picopb::reflect::ReflectError::UnknownField(number.to_string())),
+        }
+    }
+}
+impl picopb::reflect::ReflectField for Sensor {
+    fn to_value(&self) -> picopb::reflect::FieldValue<'_> {
+        picopb::reflect::FieldValue::Message(self)
+    }
+    fn from_value(value: &picopb::reflect::FieldValue) -> picopb::reflect::Result<Self> {
+        let mut message = Self::default();
+        picopb::reflect::copy_fields(&mut message, value.as_message()?)?;
+        Ok(message)
+    }
+}
+#[derive(Debug)]
+pub struct SensorV2 {
+    pub id: u32,
+    pub position: Option<PositionV2>,
+    pub name: Option<String>,
+    pub offsets: picopb::common::Packed<Vec<i64>>,
+    pub unknown_fields: Vec<u8>,
+}
+impl Default for SensorV2 {
+    fn default() -> Self {
+        Self {
+            id: Default::default(),
+            position: Default::default(),
+            name: Default::default(),
+            offsets: picopb::common::Packed(Vec::new()),
+            unknown_fields: Default::default(),
+        }
+    }
+}
+#[derive(Debug)]
+pub struct SensorV2FieldsType<'a> {
+    pub id: picopb::common::ConstMessageField<'a>,
+    pub position: picopb::common::ConstMessageField<'a>,
+    pub name: picopb::common::ConstMessageField<'a>,
+    pub offsets: picopb::common::ConstMessageField<'a>,
+}
+const SENSOR_V_2_FIELDS: SensorV2FieldsType = SensorV2FieldsType {
+    id: picopb::common::ConstMessageField {
+        qualifier: picopb::common::FieldQualifier::Required,
+        field_type: picopb::common::FieldType::Uint32,
+        identifier: "id",
+        json_name: "id",
+        ordinal: picopb::common::Field(1),
+    },
+    position: picopb::common::ConstMessageField {
+        qualifier: picopb::common::FieldQualifier::Optional,
+        field_type: picopb::common::FieldType::MessageType("PositionV2"),
+        identifier: "position",
+        json_name: "position",
+        ordinal: picopb::common::Field(2),
+    },
+    name: picopb::common::ConstMessageField {
+        qualifier: picopb::common::FieldQualifier::Optional,
+        field_type: picopb::common::FieldType::UnboundedString,
+        identifier: "name",
+        json_name: "name",
+        ordinal: picopb::common::Field(3),
+    },
+    offsets: picopb::common::ConstMessageField {
+        qualifier: picopb::common::FieldQualifier::PackedRepeatedUnbounded,
+        field_type: picopb::common::FieldType::Int64,
+        identifier: "offsets",
+        json_name: "offsets",
+        ordinal: picopb::common::Field(4),
+    },
+};
+impl SensorV2 {
+    fn fields(&self) -> SensorV2FieldsType<'static> {
+        SENSOR_V_2_FIELDS
+    }
+}
+// SensorV2 has no MAX_ENCODED_LEN, unbounded: SensorV2.unknown_fields
+impl picopb::wiretypes::WireTyped for &SensorV2 {
+    fn wiretype(&self) -> WireType {
+        WireType::Len
+    }
+}
+impl picopb::encode::ToWire for &SensorV2 {
+    fn append<W: picopb::encode::EncodeSink>(&self, buf: &mut W) -> std::io::Result<usize> {
+        let mut total_size = 0;
+        total_size += buf.encode(self.id, self.fields().id.ordinal)?;
+        if let Some(value_position) = &self.position {
+            total_size += buf.encode_sub_message(value_position, self.fields().position.ordinal)?;
+        }
+        if let Some(value_name) = &self.name {
+            total_size += buf.encode(value_name.as_str(), self.fields().name.ordinal)?;
+        }
+        if !self.offsets.0.is_empty() {
+            total_size += buf.encode(picopb::common::Packed(self.offsets.0.as_slice()), self.fields().offsets.ordinal)?;
+        }
+        total_size += buf.write_bytes(self.unknown_fields.as_bytes())?;
+        Ok(total_size)
+    }
+    fn precalculate_size(&self) -> usize {
+        let mut total_size = 0;
+        total_size += self.id.precalculate_field_size(self.fields().id.ordinal);
+        if let Some(value_position) = &self.position {
+            total_size += value_position.precalculate_sub_message_size(self.fields().position.ordinal);
+        }
+        if let Some(value_name) = &self.name {
+            total_size += value_name.as_str().precalculate_field_size(self.fields().name.ordinal);
+        }
+        if !self.offsets.0.is_empty() {
+            total_size += picopb::common::Packed(self.offsets.0.as_slice()).precalculate_field_size(self.fields().offsets.ordinal);
+        }
+        total_size += self.unknown_fields.as_bytes().len();
+        total_size
+    }
+}
+impl picopb::decode::DecodeMessage for SensorV2 {
+    fn decode_from(buf: &mut picopb::decode::DecodeBuffer<'_>) -> picopb::decode::Result<Self> {
+        let mut message = Self::default();
+        let mut has_id = false;
+        while !buf.is_empty() {
+            let (field, wire_type) = buf.read_tag()?;
+            match field.0 {
+                1 => {
+                    message.id = picopb::decode::read_field(buf, field, wire_type)?;
+                    has_id = true;
+                }
+                2 => {
+                    message.position = Some(picopb::decode::read_field(buf, field, wire_type)?);
+                }
+                3 => {
+                    message.name = Some(picopb::decode::read_field(buf, field, wire_type)?);
+                }
+                4 => {
+                    picopb::decode::read_repeated(buf, field, wire_type, |value| {
+                        message.offsets.0.push(value);
+                        Ok(())
+                    })?;
+                }
+                _ => buf.read_unknown(field, wire_type, &mut message.unknown_fields)?,
+            }
+        }
+        if !has_id {
+            return Err(picopb::decode::DecodeError::MissingRequiredField("id"));
+        }
+        Ok(message)
+    }
+}
+impl picopb::decode::FromWire for SensorV2 {
+    fn wire_type() -> WireType {
+        WireType::Len
+    }
+    fn read(buf: &mut picopb::decode::DecodeBuffer<'_>) -> picopb::decode::Result<Self> {
+        <Self as picopb::decode::DecodeMessage>::decode_from(&mut buf.read_sub_buffer()?)
+    }
+}
+impl Randomize for SensorV2 {
+    fn randomized<R: picopb::randomizer::Rng + ?Sized>(rng: &mut R) -> Self {
+        Self {
+            id: randomized(rng),
+            position: randomized(rng),
+            name: randomized(rng),
+            offsets: randomized(rng),
+            unknown_fields: Default::default(),
+        }
+    }
+}
+impl picopb::text_format::ToText for SensorV2 {
+    fn write_text<W: std::fmt::Write>(&self, out: &mut W) -> std::fmt::Result {
+        let mut writer = picopb::text_format::FieldWriter::new(out);
+        writer.field(self.fields().id.identifier, &self.id)?;
+        if let Some(value_position) = &self.position {
+            writer.message(self.fields().position.identifier, value_position)?;
+        }
+        if let Some(value_name) = &self.name {
+            writer.field(self.fields().name.identifier, value_name)?;
+        }
+        for value_offsets in self.offsets.0.iter() {
+            writer.field(self.fields().offsets.identifier, value_offsets)?;
+        }
+        Ok(())
+    }
+}
+impl picopb::text_format::FromText for SensorV2 {
+    fn from_text(text: &picopb::text_format::TextValue) -> picopb::text_format::Result<Self> {
+        let mut message = Self::default();
+        let mut has_id = false;
+        for (name, value) in text.as_message()? {
+            match name.as_str() {
+                "id" => {
+                    message.id = picopb::text_format::FromText::from_text(value)?;
+                    has_id = true;
+                }
+                "position" => {
+                    message.position = Some(picopb::text_format::FromText::from_text(value)?);
+                }
+                "name" => {
+                    message.name = Some(picopb::text_format::FromText::from_text(value)?);
+                }
+                "offsets" => {
+                    picopb::text_format::read_repeated(value, |value| {
+                        message.offsets.0.push(value);
+                        Ok(())
+                    })?;
+                }
+                _ => return Err(picopb::text_format::TextFormatError::UnknownField(name.clone())),
+            }
+        }
+        if !has_id {
+            return Err(picopb::text_format::TextFormatError::MissingRequiredField("id"));
+        }
+        Ok(message)
+    }
+}
+impl SensorV2 {
+    pub const DESCRIPTOR: picopb::reflect::MessageDescriptor = picopb::reflect::MessageDescriptor {
+        name: "SensorV2",
+        fields: &[
+            SENSOR_V_2_FIELDS.id,
+            SENSOR_V_2_FIELDS.position,
+            SENSOR_V_2_FIELDS.name,
+            SENSOR_V_2_FIELDS.offsets,
+        ],
+    };
+}
+impl picopb::reflect::Reflect for SensorV2 {
+    fn descriptor(&self) -> &'static picopb::reflect::MessageDescriptor {
+        &Self::DESCRIPTOR
+    }
+    fn get(&self, number: u32) -> Option<Vec<picopb::reflect::FieldValue<'_>>> {
+        match number {
+            1 => Some(vec![picopb::reflect::ReflectField::to_value(&self.id)]),
+            2 => Some(self.position.iter().map(picopb::reflect::ReflectField::to_value).collect()),
+            3 => Some(self.name.iter().map(picopb::reflect::ReflectField::to_value).collect()),
+            4 => Some(self.offsets.0.iter().map(picopb::reflect::ReflectField::to_value).collect()),
+            _ => None,
+        }
+    }
+    fn set(&mut self, number: u32, values: &[picopb::reflect::FieldValue]) -> picopb::reflect::Result<()> {
+        match number {
+            1 => picopb::reflect::set_required(&mut self.id, values),
+            2 => picopb::reflect::set_optional(&mut self.position, values),
+            3 => picopb::reflect::set_optional(&mut self.name, values),
+            4 => picopb::reflect::set_repeated(&mut self.offsets.0, values),
+            _ => Err(picopb::reflect::ReflectError::UnknownField(number.to_string())),
+        }
+    }
+}
+impl picopb::reflect::ReflectField for SensorV2 {
+    fn to_value(&self) -> picopb::reflect::FieldValue<'_> {
+        picopb::reflect::FieldValue::Message(self)
+    }
+    fn from_value(value: &picopb::reflect::FieldValue) -> picopb::reflect::Result<Self> {
+        let mut message = Self::default();
+        picopb::reflect::copy_fields(&mut message, value.as_message()?)?;
+        Ok(message)
+    }
+}
@@ -0,0 +1,15 @@
+use picopb::lsp::serve;
+use std::process::ExitCode;
+
+/// Runs the language server on stdin and stdout, as started by editors
+fn main() -> ExitCode {
+    match serve(std::io::stdin().lock(), std::io::stdout().lock()) {
+        Ok(true) => ExitCode::SUCCESS,
+        // exit without shutdown, the protocol asks for exit code 1
+        Ok(false) => ExitCode::FAILURE,
+        Err(err) => {
+            eprintln!("picopb-lsp: {}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
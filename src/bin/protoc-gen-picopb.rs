@@ -0,0 +1,34 @@
+use picopb::decode::DecodeMessage;
+use picopb::descriptor::proto::CodeGeneratorRequest;
+use picopb::encode::ToWire;
+use picopb::plugin::generate_response;
+use std::io::{Read, Write};
+use std::process::ExitCode;
+
+/// Reads a `CodeGeneratorRequest` from stdin and writes the
+/// `CodeGeneratorResponse` to stdout, as protoc runs its plugins
+fn main() -> ExitCode {
+    let mut input = Vec::new();
+    if let Err(err) = std::io::stdin().read_to_end(&mut input) {
+        eprintln!("protoc-gen-picopb: failed to read the request: {}", err);
+        return ExitCode::FAILURE;
+    }
+    let request = match CodeGeneratorRequest::decode(&input) {
+        Ok(request) => request,
+        Err(err) => {
+            eprintln!("protoc-gen-picopb: invalid request: {:?}", err);
+            return ExitCode::FAILURE;
+        }
+    };
+    let mut output = Vec::new();
+    (&generate_response(&request))
+        .append(&mut output)
+        .expect("writing to a Vec does not fail");
+    match std::io::stdout().write_all(&output) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("protoc-gen-picopb: failed to write the response: {}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
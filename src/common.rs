@@ -1,5 +1,5 @@
 use std::collections::BTreeMap;
-
+use std::fmt;
 
 #[derive(Debug, Clone)]
 pub enum FieldType<'a> {
@@ -78,7 +78,7 @@ impl FieldQualifier {
             Self::RepeatedUnbounded => "picopb::common::FieldQualifier::RepeatedUnbounded".into(),
             Self::Repeated(len) => format!("picopb::common::FieldQualifier::Repeated({})", len), 
             Self::PackedRepeated(len) => format!("picopb::common::FieldQualifier::PackedRepeated({})", len), 
-            Self::PackedRepeatedUnbounded => format!("picopb::common::FieldQualifier::PackedRepeatedUnbounded"), 
+            Self::PackedRepeatedUnbounded => "picopb::common::FieldQualifier::PackedRepeatedUnbounded".into(), 
         }   
     }
 }
@@ -90,23 +90,71 @@ pub enum FieldOption {
     Packed(bool)
 }
 
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct FieldOptions {
     pub max_size: Option<usize>,
     pub max_len: Option<usize>,
     pub packed: bool,
 }
 
-impl Default for FieldOptions {
-    fn default() -> Self {
-        Self {
-            max_size: None,
-            max_len: None,
-            packed: false,
+/// A single component of an option name, `(nanopb)` is an extension
+/// while `max_size` is a simple name.
+#[derive(Debug, Clone, PartialEq)]
+pub enum OptionNamePart {
+    Simple(String),
+    Extension(String),
+}
+
+/// OptionName is the full name of an option
+/// Example: (nanopb).max_size
+#[derive(Debug, Clone, PartialEq)]
+pub struct OptionName(pub Vec<OptionNamePart>);
+
+impl fmt::Display for OptionName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, part) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, ".")?;
+            }
+            match part {
+                OptionNamePart::Simple(name) => write!(f, "{}", name)?,
+                OptionNamePart::Extension(name) => write!(f, "({})", name)?,
+            }
         }
+        Ok(())
     }
 }
 
+/// OptionValue is the value assigned to an option, aggregates
+/// (`{ a: 1 b { c: 2 } }`) are kept as a tree of named values.
+#[derive(Debug, Clone, PartialEq)]
+pub enum OptionValue {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    String(String),
+    Identifier(String),
+    Aggregate(Vec<(String, OptionValue)>),
+}
+
+/// ProtoOption is a generic option as written in the proto definition,
+/// used for file, message, field, enum and enum value options.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProtoOption {
+    pub name: OptionName,
+    pub value: OptionValue,
+}
+
+/// Looks up the value of the option with the given name, if it is set multiple
+/// times the last value is returned.
+pub fn find_option<'o>(options: &'o [ProtoOption], name: &str) -> Option<&'o OptionValue> {
+    options
+        .iter()
+        .rev()
+        .find(|option| option.name.to_string() == name)
+        .map(|option| &option.value)
+}
+
 impl FieldQualifier {
     pub fn from_str(s: &str, options: &FieldOptions) -> Self {
         // TODO: how should max_len and max_size be handled?
@@ -137,7 +185,7 @@ impl FieldQualifier {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Field(pub u32);
 
 #[derive(Debug, Clone)]
@@ -146,6 +194,21 @@ pub struct MessageField<'a> {
     pub field_type: FieldType<'a>,
     pub identifier: Identifier,
     pub ordinal: Field,
+    pub options: Vec<ProtoOption>,
+}
+
+impl MessageField<'_> {
+    /// The `json_name` option if set, otherwise the lowerCamelCase name of the field
+    pub fn json_name(&self) -> String {
+        match find_option(&self.options, "json_name") {
+            Some(OptionValue::String(name)) => name.clone(),
+            _ => crate::json::json_name(&self.identifier),
+        }
+    }
+
+    pub fn is_repeated(&self) -> bool {
+        !matches!(self.qualifier, FieldQualifier::Required | FieldQualifier::Optional)
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -153,6 +216,8 @@ pub struct ConstMessageField<'a> {
     pub qualifier: FieldQualifier,
     pub field_type: FieldType<'a>,
     pub identifier: &'static str,
+    /// The lowerCamelCase name, or the `json_name` option, used by the JSON mapping
+    pub json_name: &'static str,
     pub ordinal: Field,
 }
 
@@ -160,23 +225,116 @@ pub struct ConstMessageField<'a> {
 pub struct MessageType<'a> {
     pub identifier: String,
     pub fields: BTreeMap<Ordinal, MessageField<'a>>,
+    /// Set by the `(nanopb_msgopt).msgid` option
+    pub msgid: Option<u32>,
+    pub options: Vec<ProtoOption>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct EnumType {
     pub identifier: String,
-    pub pairs: BTreeMap<String, Ordinal>,
+    /// Enum values are signed 32 bit integers
+    pub pairs: BTreeMap<String, i32>,
+    /// The value names in declaration order, in proto2 the first one is the default
+    pub declared: Vec<String>,
+    /// Set by `option allow_alias = true;`, allows several values to share an ordinal
+    pub allow_alias: bool,
+    pub options: Vec<ProtoOption>,
+    pub value_options: BTreeMap<String, Vec<ProtoOption>>,
 }
 
-#[derive(Debug)]
+impl EnumType {
+    /// The names and numbers of the values in declaration order, the first
+    /// one is the default and the first of several aliases is the one decoded
+    pub fn values(&self) -> impl Iterator<Item = (&str, i32)> + '_ {
+        self.declared
+            .iter()
+            .filter_map(|name| Some((name.as_str(), *self.pairs.get(name)?)))
+    }
+}
+
+#[derive(Debug, Clone)]
 pub enum Version {
     Proto2,
     Unknown,
 }
 
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 /// Packed is used to encode the fact that the type should
 /// be encoded and decoded using the [packed=true] option
 /// in the type system.
-pub struct Packed<T>(pub T);
\ No newline at end of file
+pub struct Packed<T>(pub T);
+
+/// How generated messages store fields that are not part of the schema
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum UnknownFieldStorage {
+    /// unknown fields are dropped when decoding
+    #[default]
+    None,
+    /// unknown fields are kept in a `Vec<u8>`
+    Unbounded,
+    /// unknown fields are kept in a `BoundedUnknownFields` of this many bytes
+    Bounded(usize),
+}
+
+/// Holds the encoded fields (tag and value) that a message does not know
+/// about, they are written back as-is when the message is encoded
+pub trait UnknownFields {
+    /// appends an encoded field, returns false if there is no room for it
+    fn push_field(&mut self, field: &[u8]) -> bool;
+
+    fn as_bytes(&self) -> &[u8];
+}
+
+impl UnknownFields for Vec<u8> {
+    fn push_field(&mut self, field: &[u8]) -> bool {
+        self.extend_from_slice(field);
+        true
+    }
+
+    fn as_bytes(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+/// Unknown field storage of at most N bytes for builds without allocation
+#[derive(Clone, Copy)]
+pub struct BoundedUnknownFields<const N: usize> {
+    bytes: [u8; N],
+    len: usize,
+}
+
+impl<const N: usize> Default for BoundedUnknownFields<N> {
+    fn default() -> Self {
+        Self {
+            bytes: [0; N],
+            len: 0,
+        }
+    }
+}
+
+impl<const N: usize> fmt::Debug for BoundedUnknownFields<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("BoundedUnknownFields")
+            .field(&self.as_bytes())
+            .finish()
+    }
+}
+
+impl<const N: usize> UnknownFields for BoundedUnknownFields<N> {
+    fn push_field(&mut self, field: &[u8]) -> bool {
+        match self.bytes.get_mut(self.len..self.len + field.len()) {
+            Some(slot) => {
+                slot.copy_from_slice(field);
+                self.len += field.len();
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn as_bytes(&self) -> &[u8] {
+        &self.bytes[..self.len]
+    }
+}
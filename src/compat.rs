@@ -0,0 +1,370 @@
+use crate::common::{EnumType, FieldQualifier, FieldType, MessageField, MessageType};
+use crate::parser::ProtoParser;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// This module compares two versions of a schema and reports the changes
+/// that affect peers still using the other version. Messages and enums are
+/// matched by name, fields by number and then by name, enum values by name.
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChangeKind {
+    PackageChanged(Option<String>, Option<String>),
+    MessageRemoved,
+    EnumRemoved,
+    MsgidChanged(Option<u32>, Option<u32>),
+    /// Holds whether the field was required
+    FieldRemoved(bool),
+    RequiredFieldAdded,
+    /// The field with this name has a new number, holds the old and the new number
+    FieldRenumbered(u32, u32),
+    /// The number is used by a field with another name, holds the new name
+    FieldRenamed(String),
+    /// The number is used by another field of an incompatible type, holds its name
+    FieldNumberReused(String),
+    TypeChanged(String, String),
+    CardinalityChanged(&'static str, &'static str),
+    /// Holds whether the field is packed now
+    PackedChanged(bool),
+    /// The `max_size` of string or bytes values, `None` is unbounded
+    BoundsChanged(Option<usize>, Option<usize>),
+    /// The `max_size` of a repeated field, the number of values
+    CountChanged(Option<usize>, Option<usize>),
+    JsonNameChanged(String, String),
+    EnumValueRemoved(i32),
+    EnumValueAdded(i32),
+    /// The number of the value has another name now, holds it
+    EnumValueRenamed(String),
+    EnumValueRenumbered(i32, i32),
+}
+
+/// How far a change reaches, ordered from the least to the most severe
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// Only generated code, or the names used by the text format and JSON, change
+    Source,
+    /// The wire format stays compatible, but some values of one version can
+    /// not be represented by the other, like a value beyond a smaller
+    /// `max_size` or an enum value the other side does not know
+    Values,
+    /// Data encoded with one version can not be decoded with the other
+    Wire,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Source => "source",
+            Self::Values => "values",
+            Self::Wire => "wire",
+        })
+    }
+}
+
+/// A change between the old and the new schema
+#[derive(Debug, Clone, PartialEq)]
+pub struct Change {
+    pub severity: Severity,
+    /// What changed: `package`, `Message`, `Message.field`, `Enum` or `Enum.VALUE`
+    pub path: String,
+    pub kind: ChangeKind,
+}
+
+fn bound(bound: &Option<usize>) -> String {
+    match bound {
+        Some(bound) => bound.to_string(),
+        None => "unbounded".into(),
+    }
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}: ", self.severity, self.path)?;
+        match &self.kind {
+            ChangeKind::PackageChanged(old, new) => {
+                write!(f, "package changed from {:?} to {:?}", old, new)
+            }
+            ChangeKind::MessageRemoved => write!(f, "message removed"),
+            ChangeKind::EnumRemoved => write!(f, "enum removed"),
+            ChangeKind::MsgidChanged(old, new) => {
+                write!(f, "msgid changed from {:?} to {:?}", old, new)
+            }
+            ChangeKind::FieldRemoved(true) => write!(f, "required field removed"),
+            ChangeKind::FieldRemoved(false) => write!(f, "field removed"),
+            ChangeKind::RequiredFieldAdded => write!(f, "required field added"),
+            ChangeKind::FieldRenumbered(old, new) => {
+                write!(f, "field number changed from {} to {}", old, new)
+            }
+            ChangeKind::FieldRenamed(name) => write!(f, "field renamed to {}", name),
+            ChangeKind::FieldNumberReused(name) => {
+                write!(f, "field number reused by {}", name)
+            }
+            ChangeKind::TypeChanged(old, new) => {
+                write!(f, "type changed from {} to {}", old, new)
+            }
+            ChangeKind::CardinalityChanged(old, new) => {
+                write!(f, "changed from {} to {}", old, new)
+            }
+            ChangeKind::PackedChanged(true) => write!(f, "packed now"),
+            ChangeKind::PackedChanged(false) => write!(f, "not packed anymore"),
+            ChangeKind::BoundsChanged(old, new) => {
+                write!(f, "max_size changed from {} to {}", bound(old), bound(new))
+            }
+            ChangeKind::CountChanged(old, new) => {
+                write!(f, "max count changed from {} to {}", bound(old), bound(new))
+            }
+            ChangeKind::JsonNameChanged(old, new) => {
+                write!(f, "JSON name changed from {} to {}", old, new)
+            }
+            ChangeKind::EnumValueRemoved(number) => write!(f, "value {} removed", number),
+            ChangeKind::EnumValueAdded(number) => write!(f, "value {} added", number),
+            ChangeKind::EnumValueRenamed(name) => write!(f, "value renamed to {}", name),
+            ChangeKind::EnumValueRenumbered(old, new) => {
+                write!(f, "value changed from {} to {}", old, new)
+            }
+        }
+    }
+}
+
+/// The type of a field without its bounds, and whether it is length delimited
+fn type_name(field_type: &FieldType) -> (String, bool) {
+    match field_type {
+        FieldType::Bool => ("bool".into(), false),
+        FieldType::Int32 => ("int32".into(), false),
+        FieldType::Int64 => ("int64".into(), false),
+        FieldType::Uint32 => ("uint32".into(), false),
+        FieldType::Uint64 => ("uint64".into(), false),
+        FieldType::EnumType(name) => (format!("enum {}", name), false),
+        FieldType::UnboundedString | FieldType::String(_) => ("string".into(), true),
+        FieldType::UnboundedBytes | FieldType::Bytes(_) => ("bytes".into(), true),
+        FieldType::MessageType(name) => (format!("message {}", name), true),
+    }
+}
+
+fn cardinality(qualifier: &FieldQualifier) -> &'static str {
+    match qualifier {
+        FieldQualifier::Required => "required",
+        FieldQualifier::Optional => "optional",
+        _ => "repeated",
+    }
+}
+
+fn is_packed(qualifier: &FieldQualifier) -> bool {
+    matches!(
+        qualifier,
+        FieldQualifier::PackedRepeated(_) | FieldQualifier::PackedRepeatedUnbounded
+    )
+}
+
+/// The bound of string and bytes values
+fn bounds(field_type: &FieldType) -> Option<usize> {
+    match field_type {
+        FieldType::String(limit) | FieldType::Bytes(limit) => Some(*limit),
+        _ => None,
+    }
+}
+
+/// The bound of the number of values of repeated fields
+fn count(qualifier: &FieldQualifier) -> Option<usize> {
+    match qualifier {
+        FieldQualifier::Repeated(limit) | FieldQualifier::PackedRepeated(limit) => Some(*limit),
+        _ => None,
+    }
+}
+
+struct Comparison {
+    changes: Vec<Change>,
+}
+
+impl Comparison {
+    fn push(&mut self, severity: Severity, path: String, kind: ChangeKind) {
+        self.changes.push(Change {
+            severity,
+            path,
+            kind,
+        });
+    }
+
+    fn messages(&mut self, old: &ProtoParser, new: &ProtoParser) {
+        let old_types: BTreeMap<_, _> = old.message_types.iter().collect();
+        for (name, old_type) in old_types {
+            match new.message_types.get(name) {
+                Some(new_type) => self.message(old_type, new_type),
+                None => self.push(Severity::Source, name.clone(), ChangeKind::MessageRemoved),
+            }
+        }
+    }
+
+    fn message(&mut self, old: &MessageType, new: &MessageType) {
+        let name = &old.identifier;
+        if old.msgid != new.msgid {
+            // receivers dispatch on the msgid, a new one is only a source change
+            let severity = match old.msgid {
+                Some(_) => Severity::Wire,
+                None => Severity::Source,
+            };
+            let kind = ChangeKind::MsgidChanged(old.msgid, new.msgid);
+            self.push(severity, name.clone(), kind);
+        }
+        let find_by_name = |message: &'_ MessageType<'_>, identifier: &str| {
+            message
+                .fields
+                .values()
+                .find(|field| field.identifier == identifier)
+                .map(|field| field.ordinal.0)
+        };
+        for (number, old_field) in old.fields.iter() {
+            let path = format!("{}.{}", name, old_field.identifier);
+            let renumbered = find_by_name(new, &old_field.identifier).filter(|n| n != number);
+            if let Some(new_number) = renumbered {
+                let kind = ChangeKind::FieldRenumbered(*number, new_number);
+                self.push(Severity::Wire, path.clone(), kind);
+            }
+            // a field moved to this number from another one is compared at its old number
+            let new_field = new.fields.get(number).filter(|new_field| {
+                find_by_name(old, &new_field.identifier).is_none_or(|n| n == *number)
+            });
+            match new_field {
+                Some(new_field) => self.field(&path, old_field, new_field),
+                None if renumbered.is_some() => {}
+                None => {
+                    let required = matches!(old_field.qualifier, FieldQualifier::Required);
+                    let severity = match required {
+                        true => Severity::Wire,
+                        false => Severity::Source,
+                    };
+                    self.push(severity, path, ChangeKind::FieldRemoved(required));
+                }
+            }
+        }
+        for (number, new_field) in new.fields.iter() {
+            let is_new = !old.fields.contains_key(number)
+                && find_by_name(old, &new_field.identifier).is_none();
+            if is_new && matches!(new_field.qualifier, FieldQualifier::Required) {
+                let path = format!("{}.{}", name, new_field.identifier);
+                self.push(Severity::Wire, path, ChangeKind::RequiredFieldAdded);
+            }
+        }
+    }
+
+    /// Compares the fields with the same number
+    fn field(&mut self, path: &str, old: &MessageField, new: &MessageField) {
+        let (old_type, old_len) = type_name(&old.field_type);
+        let (new_type, new_len) = type_name(&new.field_type);
+        let is_message =
+            |field: &MessageField| matches!(field.field_type, FieldType::MessageType(_));
+        let type_severity = if old_type == new_type {
+            None
+        } else if old_len == new_len && !is_message(old) && !is_message(new) {
+            // varints are read as each other, as are strings and bytes
+            Some(Severity::Values)
+        } else {
+            Some(Severity::Wire)
+        };
+        if old.identifier != new.identifier {
+            if type_severity == Some(Severity::Wire) {
+                let kind = ChangeKind::FieldNumberReused(new.identifier.clone());
+                self.push(Severity::Wire, path.into(), kind);
+                return;
+            }
+            let kind = ChangeKind::FieldRenamed(new.identifier.clone());
+            self.push(Severity::Source, path.into(), kind);
+        }
+        if let Some(severity) = type_severity {
+            let kind = ChangeKind::TypeChanged(old_type, new_type);
+            self.push(severity, path.into(), kind);
+        }
+        let (old_cardinality, new_cardinality) =
+            (cardinality(&old.qualifier), cardinality(&new.qualifier));
+        if old_cardinality != new_cardinality {
+            let kind = ChangeKind::CardinalityChanged(old_cardinality, new_cardinality);
+            self.push(Severity::Wire, path.into(), kind);
+        } else if is_packed(&old.qualifier) != is_packed(&new.qualifier) {
+            // parsers accept repeated scalars both packed and unpacked
+            let kind = ChangeKind::PackedChanged(is_packed(&new.qualifier));
+            self.push(Severity::Source, path.into(), kind);
+        }
+        let (old_bounds, new_bounds) = (bounds(&old.field_type), bounds(&new.field_type));
+        if old_bounds != new_bounds && old_len && new_len {
+            let kind = ChangeKind::BoundsChanged(old_bounds, new_bounds);
+            self.push(Severity::Values, path.into(), kind);
+        }
+        let (old_count, new_count) = (count(&old.qualifier), count(&new.qualifier));
+        if old_count != new_count && old_cardinality == new_cardinality {
+            let kind = ChangeKind::CountChanged(old_count, new_count);
+            self.push(Severity::Values, path.into(), kind);
+        }
+        if old.json_name() != new.json_name() && old.identifier == new.identifier {
+            let kind = ChangeKind::JsonNameChanged(old.json_name(), new.json_name());
+            self.push(Severity::Source, path.into(), kind);
+        }
+    }
+
+    fn enums(&mut self, old: &ProtoParser, new: &ProtoParser) {
+        let old_types: BTreeMap<_, _> = old.enum_types.iter().collect();
+        for (name, old_type) in old_types {
+            match new.enum_types.get(name) {
+                Some(new_type) => self.enum_type(old_type, new_type),
+                None => self.push(Severity::Source, name.clone(), ChangeKind::EnumRemoved),
+            }
+        }
+    }
+
+    fn enum_type(&mut self, old: &EnumType, new: &EnumType) {
+        let name_of = |enum_type: &EnumType, number: i32| {
+            enum_type
+                .pairs
+                .iter()
+                .find(|(_, value)| **value == number)
+                .map(|(name, _)| name.clone())
+        };
+        for (value_name, number) in old.pairs.iter() {
+            let path = format!("{}.{}", old.identifier, value_name);
+            match (new.pairs.get(value_name), name_of(new, *number)) {
+                (Some(new_number), _) if new_number == number => {}
+                (Some(new_number), _) => {
+                    let kind = ChangeKind::EnumValueRenumbered(*number, *new_number);
+                    self.push(Severity::Wire, path, kind);
+                }
+                (None, Some(new_name)) => self.push(
+                    Severity::Source,
+                    path,
+                    ChangeKind::EnumValueRenamed(new_name),
+                ),
+                (None, None) => {
+                    self.push(Severity::Wire, path, ChangeKind::EnumValueRemoved(*number))
+                }
+            }
+        }
+        // generated enums reject values they do not know
+        for (value_name, number) in new.pairs.iter() {
+            if !old.pairs.contains_key(value_name) && name_of(old, *number).is_none() {
+                let path = format!("{}.{}", new.identifier, value_name);
+                self.push(Severity::Values, path, ChangeKind::EnumValueAdded(*number));
+            }
+        }
+    }
+}
+
+/// Compares the old and the new version of a schema. Changes are listed
+/// by message and enum name, compatible additions are not reported
+pub fn compare(old: &ProtoParser, new: &ProtoParser) -> Vec<Change> {
+    let mut comparison = Comparison {
+        changes: Vec::new(),
+    };
+    if old.package != new.package {
+        let kind = ChangeKind::PackageChanged(old.package.clone(), new.package.clone());
+        comparison.push(Severity::Source, "package".into(), kind);
+    }
+    comparison.messages(old, new);
+    comparison.enums(old, new);
+    comparison.changes
+}
+
+/// The changes at least as severe as the strictness
+pub fn breaking_changes(old: &ProtoParser, new: &ProtoParser, strictness: Severity) -> Vec<Change> {
+    compare(old, new)
+        .into_iter()
+        .filter(|change| change.severity >= strictness)
+        .collect()
+}
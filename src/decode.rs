@@ -1 +1,550 @@
+use crate::{
+    common::{Field, UnknownFields},
+    wiretypes::{WireType, WireTypeError},
+};
 
+use arraystring::{prelude::Capacity, ArrayString};
+use arrayvec::ArrayVec;
+use std::{io::Read, marker::PhantomData};
+
+/// This module contains the decoder for the protocol buffers wire format
+/// Decoding operates on a borrowed byte slice, values are read one field
+/// at a time using the tag to dispatch to the correct field.
+
+#[derive(Debug, PartialEq)]
+pub enum DecodeError {
+    UnexpectedEndOfBuffer,
+    VarintTooLong,
+    InvalidWireType(u32),
+    InvalidFieldNumber(u32),
+    UnexpectedWireType(Field, WireType),
+    UnexpectedEndGroup,
+    InvalidUtf8,
+    CapacityExceeded,
+    InvalidEnumValue(i64),
+    MissingRequiredField(&'static str),
+    UnknownMessageId(u32),
+    /// The field is not part of the message and the policy is `UnknownFieldPolicy::Error`
+    UnknownField(Field),
+    /// A length delimited record starting at the offset ends before its length prefix says
+    TruncatedRecord(usize),
+    /// Groups are nested deeper than `MAX_GROUP_DEPTH`
+    GroupTooDeep,
+    Io(std::io::ErrorKind),
+}
+
+impl From<WireTypeError> for DecodeError {
+    fn from(value: WireTypeError) -> Self {
+        match value {
+            WireTypeError::InvalidTag(id) => Self::InvalidWireType(id),
+        }
+    }
+}
+
+pub type Result<T> = std::result::Result<T, DecodeError>;
+
+/// Skipped groups nested deeper than this fail with `DecodeError::GroupTooDeep`,
+/// so that hostile input cannot overflow the stack
+pub const MAX_GROUP_DEPTH: usize = 64;
+
+/// Messages decoded or parsed at runtime, by `dynamic::DynamicMessage` and the
+/// JSON and text format parsers, may nest at most this many sub-messages
+pub const MAX_MESSAGE_DEPTH: usize = 100;
+
+/// What the decoder does with fields that are not part of the message
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum UnknownFieldPolicy {
+    /// fail with `DecodeError::UnknownField`
+    Error,
+    /// drop the field
+    Skip,
+    /// keep the field in the message's unknown fields, messages generated
+    /// without unknown field storage drop it
+    #[default]
+    Preserve,
+}
+
+#[derive(Debug)]
+pub struct DecodeBuffer<'a> {
+    buffer: &'a [u8],
+    position: usize,
+    /// start of the most recently read tag
+    tag_position: usize,
+    policy: UnknownFieldPolicy,
+}
+
+impl<'a> DecodeBuffer<'a> {
+    pub fn from_slice(buffer: &'a [u8]) -> DecodeBuffer<'a> {
+        DecodeBuffer {
+            buffer,
+            position: 0,
+            tag_position: 0,
+            policy: UnknownFieldPolicy::default(),
+        }
+    }
+
+    pub fn with_policy(mut self, policy: UnknownFieldPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    pub fn policy(&self) -> UnknownFieldPolicy {
+        self.policy
+    }
+
+    /// the number of bytes read so far
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// the bytes that have not been read yet
+    pub fn remaining(&self) -> &'a [u8] {
+        &self.buffer[self.position..]
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.position >= self.buffer.len()
+    }
+
+    pub fn read_byte(&mut self) -> Result<u8> {
+        let byte = *self
+            .buffer
+            .get(self.position)
+            .ok_or(DecodeError::UnexpectedEndOfBuffer)?;
+        self.position += 1;
+        Ok(byte)
+    }
+
+    /// reads the next `len` bytes
+    pub fn read_slice(&mut self, len: usize) -> Result<&'a [u8]> {
+        let end = self
+            .position
+            .checked_add(len)
+            .ok_or(DecodeError::UnexpectedEndOfBuffer)?;
+        let slice = self
+            .buffer
+            .get(self.position..end)
+            .ok_or(DecodeError::UnexpectedEndOfBuffer)?;
+        self.position = end;
+        Ok(slice)
+    }
+
+    /// reads a base 128 varint of at most 10 bytes
+    pub fn read_varint(&mut self) -> Result<u64> {
+        let mut value: u64 = 0;
+        for i in 0..10 {
+            let byte = self.read_byte()?;
+            value |= ((byte & 0x7f) as u64) << (7 * i);
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(DecodeError::VarintTooLong)
+    }
+
+    pub fn read_fixed32(&mut self) -> Result<u32> {
+        let bytes = self.read_slice(4)?;
+        Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    pub fn read_fixed64(&mut self) -> Result<u64> {
+        let bytes = self.read_slice(8)?;
+        let mut value = [0; 8];
+        value.copy_from_slice(bytes);
+        Ok(u64::from_le_bytes(value))
+    }
+
+    /// reads the length prefix followed by that many bytes
+    pub fn read_len_delimited(&mut self) -> Result<&'a [u8]> {
+        let len = self.read_varint()?;
+        let len = usize::try_from(len).map_err(|_| DecodeError::UnexpectedEndOfBuffer)?;
+        self.read_slice(len)
+    }
+
+    /// reads a length delimited record as a buffer with the same unknown field policy
+    pub fn read_sub_buffer(&mut self) -> Result<DecodeBuffer<'a>> {
+        Ok(DecodeBuffer::from_slice(self.read_len_delimited()?).with_policy(self.policy))
+    }
+
+    /// reads a tag and splits it into field number and wire type
+    pub fn read_tag(&mut self) -> Result<(Field, WireType)> {
+        self.tag_position = self.position;
+        let tag = self.read_varint()?;
+        let tag = u32::try_from(tag).map_err(|_| DecodeError::InvalidFieldNumber(u32::MAX))?;
+        let wire_type = WireType::try_from(tag & 0x07)?;
+        let field = tag >> 3;
+        if field == 0 {
+            return Err(DecodeError::InvalidFieldNumber(field));
+        }
+        Ok((Field(field), wire_type))
+    }
+
+    /// skips the value of a field with the given wire type
+    pub fn skip_value(&mut self, field: Field, wire_type: WireType) -> Result<()> {
+        self.skip_nested_value(field, wire_type, 0)
+    }
+
+    /// skips the value, `depth` is the number of groups it is nested in
+    fn skip_nested_value(&mut self, field: Field, wire_type: WireType, depth: usize) -> Result<()> {
+        match wire_type {
+            WireType::VarInt => self.read_varint().map(|_| ()),
+            WireType::I64 => self.read_slice(8).map(|_| ()),
+            WireType::I32 => self.read_slice(4).map(|_| ()),
+            WireType::Len => self.read_len_delimited().map(|_| ()),
+            WireType::SGroup if depth >= MAX_GROUP_DEPTH => Err(DecodeError::GroupTooDeep),
+            WireType::SGroup => loop {
+                let (inner_field, inner_wire_type) = self.read_tag()?;
+                if inner_wire_type == WireType::EGroup {
+                    if inner_field != field {
+                        return Err(DecodeError::UnexpectedEndGroup);
+                    }
+                    return Ok(());
+                }
+                self.skip_nested_value(inner_field, inner_wire_type, depth + 1)?;
+            },
+            WireType::EGroup => Err(DecodeError::UnexpectedEndGroup),
+        }
+    }
+
+    /// skips a field the message does not know about, unless the policy is to error
+    pub fn skip_unknown(&mut self, field: Field, wire_type: WireType) -> Result<()> {
+        if self.policy == UnknownFieldPolicy::Error {
+            return Err(DecodeError::UnknownField(field));
+        }
+        self.skip_value(field, wire_type)
+    }
+
+    /// like `skip_unknown`, but with `UnknownFieldPolicy::Preserve` the tag and
+    /// value are copied as-is into `unknown_fields`
+    pub fn read_unknown(
+        &mut self,
+        field: Field,
+        wire_type: WireType,
+        unknown_fields: &mut impl UnknownFields,
+    ) -> Result<()> {
+        let start = self.tag_position;
+        self.skip_unknown(field, wire_type)?;
+        if self.policy == UnknownFieldPolicy::Preserve
+            && !unknown_fields.push_field(&self.buffer[start..self.position])
+        {
+            return Err(DecodeError::CapacityExceeded);
+        }
+        Ok(())
+    }
+}
+
+/// The FromWire trait decodes a single value (without its tag)
+/// from the protocol buffers wire format
+pub trait FromWire: Sized {
+    /// the wire type values of this type are encoded with
+    fn wire_type() -> WireType;
+
+    fn read(buf: &mut DecodeBuffer<'_>) -> Result<Self>;
+}
+
+/// The DecodeMessage trait is implemented by generated messages
+pub trait DecodeMessage: Sized {
+    /// decodes the fields of the message until the buffer is empty
+    fn decode_from(buf: &mut DecodeBuffer<'_>) -> Result<Self>;
+
+    fn decode(bytes: &[u8]) -> Result<Self> {
+        Self::decode_from(&mut DecodeBuffer::from_slice(bytes))
+    }
+
+    fn decode_with_policy(bytes: &[u8], policy: UnknownFieldPolicy) -> Result<Self> {
+        Self::decode_from(&mut DecodeBuffer::from_slice(bytes).with_policy(policy))
+    }
+}
+
+/// Reads the value of a field after checking that the wire type matches
+pub fn read_field<T: FromWire>(
+    buf: &mut DecodeBuffer<'_>,
+    field: Field,
+    wire_type: WireType,
+) -> Result<T> {
+    if wire_type != T::wire_type() {
+        return Err(DecodeError::UnexpectedWireType(field, wire_type));
+    }
+    T::read(buf)
+}
+
+/// Reads the values of a repeated field. Scalars may be written either packed,
+/// as a single length delimited record, or with one tag per value and parsers
+/// must accept both
+pub fn read_repeated<T: FromWire>(
+    buf: &mut DecodeBuffer<'_>,
+    field: Field,
+    wire_type: WireType,
+    mut push: impl FnMut(T) -> Result<()>,
+) -> Result<()> {
+    if wire_type != WireType::Len || T::wire_type() == WireType::Len {
+        return push(read_field(buf, field, wire_type)?);
+    }
+    let mut packed = DecodeBuffer::from_slice(buf.read_len_delimited()?);
+    while !packed.is_empty() {
+        push(T::read(&mut packed)?)?;
+    }
+    Ok(())
+}
+
+/// Stores the next value of a bounded repeated field
+pub fn push_bounded<T, const N: usize>(values: &mut ArrayVec<T, N>, value: T) -> Result<()> {
+    values
+        .try_push(value)
+        .map_err(|_| DecodeError::CapacityExceeded)
+}
+
+impl FromWire for bool {
+    fn wire_type() -> WireType {
+        WireType::VarInt
+    }
+
+    fn read(buf: &mut DecodeBuffer<'_>) -> Result<Self> {
+        Ok(buf.read_varint()? != 0)
+    }
+}
+
+impl FromWire for i32 {
+    fn wire_type() -> WireType {
+        WireType::VarInt
+    }
+
+    fn read(buf: &mut DecodeBuffer<'_>) -> Result<Self> {
+        // negative values are sign extended to 64 bits, truncate
+        Ok(buf.read_varint()? as i32)
+    }
+}
+
+impl FromWire for i64 {
+    fn wire_type() -> WireType {
+        WireType::VarInt
+    }
+
+    fn read(buf: &mut DecodeBuffer<'_>) -> Result<Self> {
+        Ok(buf.read_varint()? as i64)
+    }
+}
+
+impl FromWire for u32 {
+    fn wire_type() -> WireType {
+        WireType::VarInt
+    }
+
+    fn read(buf: &mut DecodeBuffer<'_>) -> Result<Self> {
+        Ok(buf.read_varint()? as u32)
+    }
+}
+
+impl FromWire for u64 {
+    fn wire_type() -> WireType {
+        WireType::VarInt
+    }
+
+    fn read(buf: &mut DecodeBuffer<'_>) -> Result<Self> {
+        buf.read_varint()
+    }
+}
+
+impl FromWire for String {
+    fn wire_type() -> WireType {
+        WireType::Len
+    }
+
+    fn read(buf: &mut DecodeBuffer<'_>) -> Result<Self> {
+        let bytes = buf.read_len_delimited()?;
+        std::str::from_utf8(bytes)
+            .map(|s| s.to_string())
+            .map_err(|_| DecodeError::InvalidUtf8)
+    }
+}
+
+impl<SIZE: Capacity> FromWire for ArrayString<SIZE> {
+    fn wire_type() -> WireType {
+        WireType::Len
+    }
+
+    fn read(buf: &mut DecodeBuffer<'_>) -> Result<Self> {
+        let bytes = buf.read_len_delimited()?;
+        let s = std::str::from_utf8(bytes).map_err(|_| DecodeError::InvalidUtf8)?;
+        ArrayString::try_from_str(s).map_err(|_| DecodeError::CapacityExceeded)
+    }
+}
+
+impl FromWire for bytes::Bytes {
+    fn wire_type() -> WireType {
+        WireType::Len
+    }
+
+    fn read(buf: &mut DecodeBuffer<'_>) -> Result<Self> {
+        Ok(bytes::Bytes::copy_from_slice(buf.read_len_delimited()?))
+    }
+}
+
+/// Fixed size bytes, shorter values are padded with zeros
+impl<const N: usize> FromWire for [u8; N] {
+    fn wire_type() -> WireType {
+        WireType::Len
+    }
+
+    fn read(buf: &mut DecodeBuffer<'_>) -> Result<Self> {
+        let bytes = buf.read_len_delimited()?;
+        let mut value = [0; N];
+        value
+            .get_mut(..bytes.len())
+            .ok_or(DecodeError::CapacityExceeded)?
+            .copy_from_slice(bytes);
+        Ok(value)
+    }
+}
+
+/// Iterator over varint length prefixed messages in a byte slice,
+/// the same framing as `parseDelimitedFrom` in the Java library.
+pub struct DelimitedIter<'a, M> {
+    buf: DecodeBuffer<'a>,
+    message: PhantomData<M>,
+}
+
+/// Decodes successive length delimited messages from the bytes
+pub fn decode_delimited<M: DecodeMessage>(bytes: &[u8]) -> DelimitedIter<'_, M> {
+    DelimitedIter {
+        buf: DecodeBuffer::from_slice(bytes),
+        message: PhantomData,
+    }
+}
+
+impl<'a, M> DelimitedIter<'a, M> {
+    /// the number of bytes consumed so far
+    pub fn position(&self) -> usize {
+        self.buf.position()
+    }
+}
+
+impl<M: DecodeMessage> Iterator for DelimitedIter<'_, M> {
+    type Item = Result<M>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.buf.is_empty() {
+            return None;
+        }
+        let offset = self.buf.position();
+        let record = match self.buf.read_len_delimited() {
+            Ok(record) => record,
+            Err(DecodeError::UnexpectedEndOfBuffer) => {
+                // nothing more can be read after a truncated record
+                self.buf.position = self.buf.buffer.len();
+                return Some(Err(DecodeError::TruncatedRecord(offset)));
+            }
+            Err(err) => {
+                self.buf.position = self.buf.buffer.len();
+                return Some(Err(err));
+            }
+        };
+        Some(M::decode(record))
+    }
+}
+
+/// Reads varint length prefixed messages from a reader, like `DelimitedIter`
+/// but for sockets, files and other streams.
+pub struct DelimitedReader<R, M> {
+    reader: R,
+    record: Vec<u8>,
+    position: usize,
+    done: bool,
+    message: PhantomData<M>,
+}
+
+impl<R: Read, M: DecodeMessage> DelimitedReader<R, M> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            record: Vec::new(),
+            position: 0,
+            done: false,
+            message: PhantomData,
+        }
+    }
+
+    /// the number of bytes read from the reader so far
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    fn read_byte(&mut self) -> std::io::Result<Option<u8>> {
+        let mut byte = [0; 1];
+        loop {
+            match self.reader.read(&mut byte) {
+                Ok(0) => return Ok(None),
+                Ok(_) => {
+                    self.position += 1;
+                    return Ok(Some(byte[0]));
+                }
+                Err(err) if err.kind() == std::io::ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err),
+            }
+        }
+    }
+
+    /// reads the length prefix, returns None at the end of the stream
+    fn read_len(&mut self, offset: usize) -> Result<Option<usize>> {
+        let mut len: u64 = 0;
+        for i in 0..10 {
+            let byte = match self
+                .read_byte()
+                .map_err(|err| DecodeError::Io(err.kind()))?
+            {
+                Some(byte) => byte,
+                None if i == 0 => return Ok(None),
+                None => return Err(DecodeError::TruncatedRecord(offset)),
+            };
+            len |= ((byte & 0x7f) as u64) << (7 * i);
+            if byte & 0x80 == 0 {
+                return usize::try_from(len)
+                    .map(Some)
+                    .map_err(|_| DecodeError::TruncatedRecord(offset));
+            }
+        }
+        Err(DecodeError::VarintTooLong)
+    }
+
+    /// reads the next record into the record buffer, returns false at the end of the stream
+    fn read_record(&mut self) -> Result<bool> {
+        let offset = self.position;
+        let len = match self.read_len(offset)? {
+            Some(len) => len,
+            None => return Ok(false),
+        };
+        // the buffer grows with the bytes read, never to an untrusted length prefix
+        self.record.clear();
+        let read = (&mut self.reader)
+            .take(len as u64)
+            .read_to_end(&mut self.record)
+            .map_err(|err| DecodeError::Io(err.kind()))?;
+        self.position += read;
+        match read == len {
+            true => Ok(true),
+            false => Err(DecodeError::TruncatedRecord(offset)),
+        }
+    }
+}
+
+impl<R: Read, M: DecodeMessage> Iterator for DelimitedReader<R, M> {
+    type Item = Result<M>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        match self.read_record() {
+            Ok(true) => Some(M::decode(&self.record)),
+            Ok(false) => {
+                self.done = true;
+                None
+            }
+            Err(err) => {
+                // framing is lost after a truncated or unreadable record
+                self.done = true;
+                Some(Err(err))
+            }
+        }
+    }
+}
@@ -0,0 +1,517 @@
+use crate::common::{
+    find_option, EnumType, Field, FieldOptions, FieldQualifier, FieldType, MessageField,
+    MessageType, OptionName, OptionNamePart, OptionValue, ProtoOption, Version,
+};
+use crate::decode::{DecodeError, DecodeMessage};
+use crate::encode::ToWire;
+use crate::parser::{ParseConfig, ProtoParser};
+use proto::{
+    DescriptorProto, EnumDescriptorProto, EnumOptions, EnumValueDescriptorProto,
+    FieldDescriptorProto, FileDescriptorProto, FileDescriptorSet, MessageOptions, NanoPBOptions,
+};
+use std::collections::{BTreeMap, HashSet};
+
+/// This module reads the binary descriptors protoc produces (`protoc -o`)
+/// into the same `MessageType` and `EnumType` model as `parser::parse`, so
+/// code can be generated for schemas picopb's own parser does not cover,
+/// and writes parsed schemas back as descriptors for tools that read them.
+
+#[derive(Debug, PartialEq)]
+pub enum DescriptorError {
+    Decode(DecodeError),
+    /// Only proto2 is supported, holds the `syntax` of the file
+    UnsupportedSyntax(String),
+    /// A descriptor lacks a value picopb needs, holds the path of the value
+    MissingValue(String),
+    /// Holds the field and its `FieldDescriptorProto.Type`, floats, fixed and zigzag
+    /// encoded integers and groups are not supported
+    UnsupportedFieldType(String, i32),
+    /// Holds the field and its `FieldDescriptorProto.Label`
+    UnsupportedLabel(String, i32),
+    /// Fields in a oneof are not supported
+    UnsupportedOneof(String),
+    /// Two types have the same name once nested types are moved to the top level
+    DuplicateType(String),
+    /// The option of the field has a value picopb can not use, like a negative size
+    InvalidOption(String),
+}
+
+impl From<DecodeError> for DescriptorError {
+    fn from(value: DecodeError) -> Self {
+        Self::Decode(value)
+    }
+}
+
+pub type Result<T> = std::result::Result<T, DescriptorError>;
+
+/// DescriptorConfig changes how descriptors are converted
+#[derive(Debug, Default, Clone, Copy)]
+pub struct DescriptorConfig {
+    /// The `(nanopb).max_size` of string, bytes and repeated fields that do not set one
+    pub default_max_size: Option<usize>,
+}
+
+/// The subset of `google/protobuf/descriptor.proto` picopb uses,
+/// generated from `protos/descriptor.proto`
+pub mod proto {
+    #![allow(dead_code, unused_imports)]
+    include!("../protos/descriptor.rs");
+}
+
+/// `FieldDescriptorProto.Type` values of the scalar types picopb supports
+const SCALAR_TYPES: [(i32, &str); 7] = [
+    (3, "int64"),
+    (4, "uint64"),
+    (5, "int32"),
+    (8, "bool"),
+    (9, "string"),
+    (12, "bytes"),
+    (13, "uint32"),
+];
+const TYPE_MESSAGE: i32 = 11;
+const TYPE_ENUM: i32 = 14;
+
+/// `FieldDescriptorProto.Label` values
+const LABELS: [(i32, &str); 3] = [(1, "optional"), (2, "required"), (3, "repeated")];
+const LABEL_OPTIONAL: i32 = 1;
+const LABEL_REQUIRED: i32 = 2;
+const LABEL_REPEATED: i32 = 3;
+
+/// Decodes a `FileDescriptorSet`, as written by `protoc -o`
+pub fn decode_descriptor_set(bytes: &[u8]) -> Result<FileDescriptorSet> {
+    Ok(FileDescriptorSet::decode(bytes)?)
+}
+
+/// Converts every file in the set into a single schema
+pub fn schema_from_descriptor_set(set: &FileDescriptorSet) -> Result<ProtoParser<'_>> {
+    schema_from_files(set.file.iter())
+}
+
+/// Converts the files into a single schema. Nested types are declared at the
+/// top level under their own name, as picopb does not support nesting. The
+/// package is the one of the last file, protoc lists imported files before
+/// the files that import them
+pub fn schema_from_files<'a>(
+    files: impl IntoIterator<Item = &'a FileDescriptorProto>,
+) -> Result<ProtoParser<'a>> {
+    schema_from_files_with_config(files, DescriptorConfig::default())
+}
+
+pub fn schema_from_files_with_config<'a>(
+    files: impl IntoIterator<Item = &'a FileDescriptorProto>,
+    config: DescriptorConfig,
+) -> Result<ProtoParser<'a>> {
+    let files: Vec<&FileDescriptorProto> = files.into_iter().collect();
+    let names: HashSet<&str> = files
+        .iter()
+        .filter_map(|file| file.name.as_deref())
+        .collect();
+    let mut schema = ProtoParser::with_config(ParseConfig::default());
+    schema.version = Version::Proto2;
+    // the fully qualified name of every type by its flattened name
+    let mut full_names = BTreeMap::new();
+    for file in files.iter() {
+        match file.syntax.as_deref() {
+            None | Some("") | Some("proto2") => {}
+            Some(syntax) => return Err(DescriptorError::UnsupportedSyntax(syntax.into())),
+        }
+        if file.package.is_some() {
+            schema.package = file.package.clone();
+        }
+        // only imports of files outside the set are left unresolved
+        for dependency in file.dependency.iter() {
+            if !names.contains(dependency.as_str()) && !schema.imports.contains(dependency) {
+                schema.imports.push(dependency.clone());
+            }
+        }
+        let scope = match file.package.as_deref() {
+            Some(package) if !package.is_empty() => format!(".{}", package),
+            _ => String::new(),
+        };
+        for enum_type in file.enum_type.iter() {
+            add_enum(&mut schema, enum_type, &scope, &mut full_names)?;
+        }
+        for message_type in file.message_type.iter() {
+            add_message(&mut schema, message_type, config, &scope, &mut full_names)?;
+        }
+    }
+    for file in files {
+        for message_type in file.message_type.iter() {
+            check_references(message_type, &full_names)?;
+        }
+    }
+    Ok(schema)
+}
+
+/// Fails if a field refers to a type outside the set that has the flattened
+/// name of a type in the set, `.other.Inner` when `.sensors.Reading.Inner` is
+/// declared as `Inner`
+fn check_references(
+    descriptor: &DescriptorProto,
+    full_names: &BTreeMap<String, String>,
+) -> Result<()> {
+    for field in descriptor.field.iter() {
+        let Some(type_name) = field.type_name.as_deref() else {
+            continue;
+        };
+        let Some(name) = type_name
+            .strip_prefix('.')
+            .and_then(|name| name.rsplit('.').next())
+        else {
+            continue;
+        };
+        if full_names
+            .get(name)
+            .is_some_and(|full_name| full_name != type_name)
+        {
+            return Err(DescriptorError::DuplicateType(name.into()));
+        }
+    }
+    for nested_type in descriptor.nested_type.iter() {
+        check_references(nested_type, full_names)?;
+    }
+    Ok(())
+}
+
+fn required<T>(value: &Option<T>, path: impl Fn() -> String) -> Result<&T> {
+    value
+        .as_ref()
+        .ok_or_else(|| DescriptorError::MissingValue(path()))
+}
+
+/// An option as the parser would have read it, `(extension).name` or `name`
+fn option(extension: Option<&str>, name: &str, value: OptionValue) -> ProtoOption {
+    let mut parts = Vec::new();
+    if let Some(extension) = extension {
+        parts.push(OptionNamePart::Extension(extension.into()));
+    }
+    parts.push(OptionNamePart::Simple(name.into()));
+    ProtoOption {
+        name: OptionName(parts),
+        value,
+    }
+}
+
+fn is_declared(schema: &ProtoParser, identifier: &str) -> bool {
+    schema.message_types.contains_key(identifier) || schema.enum_types.contains_key(identifier)
+}
+
+/// `scope` is the fully qualified name of the package or message the type
+/// is declared in, `.sensors.Reading`
+fn add_enum(
+    schema: &mut ProtoParser,
+    descriptor: &EnumDescriptorProto,
+    scope: &str,
+    full_names: &mut BTreeMap<String, String>,
+) -> Result<()> {
+    let identifier = required(&descriptor.name, || "enum.name".into())?.clone();
+    if is_declared(schema, &identifier) {
+        return Err(DescriptorError::DuplicateType(identifier));
+    }
+    full_names.insert(identifier.clone(), format!("{}.{}", scope, identifier));
+    let mut enum_type = EnumType {
+        identifier: identifier.clone(),
+        pairs: BTreeMap::new(),
+        declared: Vec::new(),
+        allow_alias: false,
+        options: Vec::new(),
+        value_options: BTreeMap::new(),
+    };
+    let allow_alias = descriptor
+        .options
+        .as_ref()
+        .and_then(|options| options.allow_alias);
+    if let Some(allow_alias) = allow_alias {
+        enum_type.allow_alias = allow_alias;
+        enum_type
+            .options
+            .push(option(None, "allow_alias", OptionValue::Bool(allow_alias)));
+    }
+    for value in descriptor.value.iter() {
+        let name = required(&value.name, || format!("{}.value.name", identifier))?;
+        let number = required(&value.number, || format!("{}.{}.number", identifier, name))?;
+        if !enum_type.pairs.contains_key(name) {
+            enum_type.declared.push(name.clone());
+        }
+        enum_type.pairs.insert(name.clone(), *number);
+    }
+    schema.enum_types.insert(identifier, enum_type);
+    Ok(())
+}
+
+fn add_message<'a>(
+    schema: &mut ProtoParser<'a>,
+    descriptor: &'a DescriptorProto,
+    config: DescriptorConfig,
+    scope: &str,
+    full_names: &mut BTreeMap<String, String>,
+) -> Result<()> {
+    let identifier = required(&descriptor.name, || "message.name".into())?.clone();
+    if is_declared(schema, &identifier) {
+        return Err(DescriptorError::DuplicateType(identifier));
+    }
+    let full_name = format!("{}.{}", scope, identifier);
+    full_names.insert(identifier.clone(), full_name.clone());
+    let mut message_type = MessageType {
+        identifier: identifier.clone(),
+        fields: BTreeMap::new(),
+        msgid: None,
+        options: Vec::new(),
+    };
+    let msgid = descriptor
+        .options
+        .as_ref()
+        .and_then(|options| options.nanopb_msgopt.as_ref())
+        .and_then(|nanopb| nanopb.msgid);
+    if let Some(msgid) = msgid {
+        message_type.msgid = Some(msgid);
+        message_type.options.push(option(
+            Some("nanopb_msgopt"),
+            "msgid",
+            OptionValue::Int(msgid.into()),
+        ));
+    }
+    for field in descriptor.field.iter() {
+        let field = message_field(&identifier, field, config)?;
+        message_type.fields.insert(field.ordinal.0, field);
+    }
+    schema.message_types.insert(identifier, message_type);
+    for nested_type in descriptor.nested_type.iter() {
+        add_message(schema, nested_type, config, &full_name, full_names)?;
+    }
+    for enum_type in descriptor.enum_type.iter() {
+        add_enum(schema, enum_type, &full_name, full_names)?;
+    }
+    Ok(())
+}
+
+/// A size option of the field, negative sizes are invalid
+fn size_option(path: &str, value: Option<i32>) -> Result<Option<usize>> {
+    value
+        .map(|value| {
+            usize::try_from(value).map_err(|_| DescriptorError::InvalidOption(path.into()))
+        })
+        .transpose()
+}
+
+fn message_field<'a>(
+    message: &str,
+    descriptor: &'a FieldDescriptorProto,
+    config: DescriptorConfig,
+) -> Result<MessageField<'a>> {
+    let identifier = required(&descriptor.name, || format!("{}.field.name", message))?.clone();
+    let path = format!("{}.{}", message, identifier);
+    if descriptor.oneof_index.is_some() {
+        return Err(DescriptorError::UnsupportedOneof(path));
+    }
+    let number = *required(&descriptor.number, || format!("{}.number", path))?;
+    let ordinal =
+        u32::try_from(number).map_err(|_| DescriptorError::InvalidOption(path.clone()))?;
+    let label = *required(&descriptor.label, || format!("{}.label", path))?;
+    let label = LABELS
+        .iter()
+        .find(|(value, _)| *value == label)
+        .map(|(_, label)| *label)
+        .ok_or_else(|| DescriptorError::UnsupportedLabel(path.clone(), label))?;
+
+    let mut field_options = FieldOptions::default();
+    let mut options = Vec::new();
+    if let Some(descriptor_options) = &descriptor.options {
+        if let Some(packed) = descriptor_options.packed {
+            field_options.packed = packed;
+            options.push(option(None, "packed", OptionValue::Bool(packed)));
+        }
+        if let Some(nanopb) = &descriptor_options.nanopb {
+            // picopb bounds repeated fields with max_size, nanopb with max_count
+            let max_size = match (nanopb.max_size, label) {
+                (None, "repeated") => nanopb.max_count,
+                (max_size, _) => max_size,
+            };
+            field_options.max_size = size_option(&path, max_size)?;
+            field_options.max_len = size_option(&path, nanopb.max_length)?;
+            if let Some(max_size) = field_options.max_size {
+                let value = OptionValue::Int(max_size as i64);
+                options.push(option(Some("nanopb"), "max_size", value));
+            }
+            if let Some(max_len) = field_options.max_len {
+                let value = OptionValue::Int(max_len as i64);
+                options.push(option(Some("nanopb"), "max_len", value));
+            }
+        }
+    }
+    if field_options.max_size.is_none() && field_options.max_len.is_none() {
+        field_options.max_size = config.default_max_size;
+    }
+    // protoc always sets json_name, only a name that differs from the default is an option
+    if let Some(json_name) = &descriptor.json_name {
+        if *json_name != crate::json::json_name(&identifier) {
+            let value = OptionValue::String(json_name.clone());
+            options.push(option(None, "json_name", value));
+        }
+    }
+
+    let field_type = *required(&descriptor.field_type, || format!("{}.type", path))?;
+    let type_name = || {
+        descriptor
+            .type_name
+            .as_deref()
+            .and_then(|type_name| type_name.rsplit('.').next())
+            .filter(|type_name| !type_name.is_empty())
+            .ok_or_else(|| DescriptorError::MissingValue(format!("{}.type_name", path)))
+    };
+    let field_type = match field_type {
+        TYPE_MESSAGE => FieldType::MessageType(type_name()?),
+        TYPE_ENUM => FieldType::EnumType(type_name()?),
+        field_type => match SCALAR_TYPES.iter().find(|(value, _)| *value == field_type) {
+            Some((_, name)) => FieldType::from_str(name, field_options.max_size),
+            None => return Err(DescriptorError::UnsupportedFieldType(path, field_type)),
+        },
+    };
+
+    Ok(MessageField {
+        qualifier: FieldQualifier::from_str(label, &field_options),
+        field_type,
+        identifier,
+        ordinal: Field(ordinal),
+        options,
+    })
+}
+
+/// Encodes a `FileDescriptorSet` as `protoc -o` writes it
+pub fn encode_descriptor_set(set: &FileDescriptorSet) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    set.append(&mut bytes)
+        .expect("writing to a Vec does not fail");
+    bytes
+}
+
+/// Describes the schema as protoc would describe the file `name`. Types are
+/// listed by name, and of the options only those picopb understands are kept:
+/// `packed`, `json_name`, `allow_alias`, `(nanopb).max_size`, `(nanopb).max_len`
+/// and `(nanopb_msgopt).msgid`
+pub fn file_descriptor_from_schema(schema: &ProtoParser, name: &str) -> FileDescriptorProto {
+    let mut message_types: Vec<&MessageType> = schema.message_types.values().collect();
+    message_types.sort_by(|a, b| a.identifier.cmp(&b.identifier));
+    let mut enum_types: Vec<&EnumType> = schema.enum_types.values().collect();
+    enum_types.sort_by(|a, b| a.identifier.cmp(&b.identifier));
+    FileDescriptorProto {
+        name: Some(name.into()),
+        package: schema.package.clone(),
+        dependency: schema.imports.clone(),
+        message_type: message_types
+            .into_iter()
+            .map(|message_type| message_descriptor(schema, message_type))
+            .collect(),
+        enum_type: enum_types.into_iter().map(enum_descriptor).collect(),
+        syntax: match schema.version {
+            Version::Proto2 => Some("proto2".into()),
+            Version::Unknown => None,
+        },
+    }
+}
+
+/// The values are in declaration order, as the first one is the default
+fn enum_descriptor(enum_type: &EnumType) -> EnumDescriptorProto {
+    EnumDescriptorProto {
+        name: Some(enum_type.identifier.clone()),
+        value: enum_type
+            .declared
+            .iter()
+            .map(|name| EnumValueDescriptorProto {
+                name: Some(name.clone()),
+                number: enum_type.pairs.get(name).copied(),
+            })
+            .collect(),
+        options: enum_type.allow_alias.then_some(EnumOptions {
+            allow_alias: Some(true),
+        }),
+    }
+}
+
+fn message_descriptor(schema: &ProtoParser, message_type: &MessageType) -> DescriptorProto {
+    DescriptorProto {
+        name: Some(message_type.identifier.clone()),
+        field: message_type
+            .fields
+            .values()
+            .map(|field| field_descriptor(schema, field))
+            .collect(),
+        options: message_type.msgid.map(|msgid| MessageOptions {
+            nanopb_msgopt: Some(NanoPBOptions {
+                msgid: Some(msgid),
+                ..Default::default()
+            }),
+        }),
+        ..Default::default()
+    }
+}
+
+/// The value of a size option, sizes that do not fit a descriptor are left out
+fn size_value(options: &[ProtoOption], name: &str) -> Option<i32> {
+    match find_option(options, name) {
+        Some(OptionValue::Int(value)) => i32::try_from(*value).ok(),
+        _ => None,
+    }
+}
+
+fn field_descriptor(schema: &ProtoParser, field: &MessageField) -> FieldDescriptorProto {
+    let (label, packed) = match field.qualifier {
+        FieldQualifier::Optional => (LABEL_OPTIONAL, false),
+        FieldQualifier::Required => (LABEL_REQUIRED, false),
+        FieldQualifier::Repeated(_) | FieldQualifier::RepeatedUnbounded => (LABEL_REPEATED, false),
+        FieldQualifier::PackedRepeated(_) | FieldQualifier::PackedRepeatedUnbounded => {
+            (LABEL_REPEATED, true)
+        }
+    };
+    let qualified = |type_name: &str| match &schema.package {
+        Some(package) => format!(".{}.{}", package, type_name),
+        None => format!(".{}", type_name),
+    };
+    let (field_type, type_name) = match field.field_type {
+        FieldType::MessageType(type_name) => (TYPE_MESSAGE, Some(qualified(type_name))),
+        FieldType::EnumType(type_name) => (TYPE_ENUM, Some(qualified(type_name))),
+        ref scalar => {
+            let name = match scalar {
+                FieldType::UnboundedString | FieldType::String(_) => "string",
+                FieldType::UnboundedBytes | FieldType::Bytes(_) => "bytes",
+                FieldType::Bool => "bool",
+                FieldType::Int32 => "int32",
+                FieldType::Int64 => "int64",
+                FieldType::Uint32 => "uint32",
+                FieldType::Uint64 => "uint64",
+                FieldType::MessageType(_) | FieldType::EnumType(_) => unreachable!(),
+            };
+            let (value, _) = SCALAR_TYPES
+                .iter()
+                .find(|(_, scalar)| *scalar == name)
+                .expect("every scalar type has a descriptor type");
+            (*value, None)
+        }
+    };
+
+    // nanopb bounds repeated fields with max_count, picopb with max_size
+    let max_size = size_value(&field.options, "(nanopb).max_size");
+    let nanopb = NanoPBOptions {
+        max_size: max_size.filter(|_| label != LABEL_REPEATED),
+        max_count: max_size.filter(|_| label == LABEL_REPEATED),
+        max_length: size_value(&field.options, "(nanopb).max_len"),
+        ..Default::default()
+    };
+    let has_nanopb =
+        nanopb.max_size.is_some() || nanopb.max_count.is_some() || nanopb.max_length.is_some();
+    let options = (packed || has_nanopb).then(|| proto::FieldOptions {
+        packed: packed.then_some(true),
+        nanopb: has_nanopb.then_some(nanopb),
+    });
+
+    FieldDescriptorProto {
+        name: Some(field.identifier.clone()),
+        number: i32::try_from(field.ordinal.0).ok(),
+        label: Some(label),
+        field_type: Some(field_type),
+        type_name,
+        options,
+        oneof_index: None,
+        json_name: Some(field.json_name()),
+    }
+}
@@ -0,0 +1,758 @@
+use crate::common::{Field, FieldQualifier, FieldType, MessageField, MessageType, Packed};
+use crate::decode::{DecodeBuffer, DecodeError, FromWire, MAX_MESSAGE_DEPTH};
+use crate::encode::{precalculate_prefix_size, write_prefix, EncodeSink, ToWire};
+use crate::json::{self, FromJson, JsonError, JsonValue, ObjectWriter, ToJson};
+use crate::parser::ProtoParser;
+use crate::text_format::{self, FieldWriter, FromText, TextFormatError, TextValue, ToText};
+use crate::wiretypes::{WireType, WireTyped};
+use std::collections::BTreeMap;
+use std::fmt::{self, Write};
+
+/// This module interprets a parsed schema at runtime, so messages can be
+/// built, encoded, decoded and converted to and from the text format and
+/// JSON without generated code.
+
+#[derive(Debug, PartialEq)]
+pub enum DynamicError {
+    UnknownMessageType(String),
+    /// Holds the number or name of the field
+    UnknownField(String),
+    MissingRequiredField(String),
+    /// A value does not have the type of the field, or a singular field was
+    /// given several values, holds the field
+    InvalidValue(String),
+    /// A value, or the number of values, exceeds the bounds of the field
+    CapacityExceeded(String),
+    /// Sub-messages are nested deeper than `decode::MAX_MESSAGE_DEPTH`, when
+    /// decoding or converting from the text format or JSON
+    MessageTooDeep,
+    Decode(DecodeError),
+    Encode(std::io::ErrorKind),
+    Json(JsonError),
+    TextFormat(TextFormatError),
+}
+
+impl From<DecodeError> for DynamicError {
+    fn from(value: DecodeError) -> Self {
+        Self::Decode(value)
+    }
+}
+
+impl From<JsonError> for DynamicError {
+    fn from(value: JsonError) -> Self {
+        Self::Json(value)
+    }
+}
+
+impl From<TextFormatError> for DynamicError {
+    fn from(value: TextFormatError) -> Self {
+        Self::TextFormat(value)
+    }
+}
+
+pub type Result<T> = std::result::Result<T, DynamicError>;
+
+/// The value of a single field, repeated fields hold one value per element
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value<'p> {
+    Bool(bool),
+    Int32(i32),
+    Int64(i64),
+    Uint32(u32),
+    Uint64(u64),
+    String(String),
+    Bytes(Vec<u8>),
+    /// The number of the enum value, kept as-is when it has no name
+    Enum(i32),
+    Message(DynamicMessage<'p>),
+}
+
+/// A message of a type from a parsed schema
+#[derive(Debug, Clone)]
+pub struct DynamicMessage<'p> {
+    schema: &'p ProtoParser<'p>,
+    message_type: &'p MessageType<'p>,
+    /// values by field number, in the order they were set or decoded
+    fields: BTreeMap<u32, Vec<Value<'p>>>,
+}
+
+impl PartialEq for DynamicMessage<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.message_type.identifier == other.message_type.identifier && self.fields == other.fields
+    }
+}
+
+fn find_message_type<'p>(schema: &'p ProtoParser<'p>, name: &str) -> Result<&'p MessageType<'p>> {
+    schema
+        .message_types
+        .get(name)
+        .ok_or_else(|| DynamicError::UnknownMessageType(name.to_string()))
+}
+
+/// Whether the value fits the `(nanopb).max_size` of string and bytes fields
+fn value_fits(field_type: &FieldType, value: &Value) -> bool {
+    match (field_type, value) {
+        (FieldType::String(limit), Value::String(value)) => value.len() <= *limit,
+        (FieldType::Bytes(limit), Value::Bytes(value)) => value.len() <= *limit,
+        _ => true,
+    }
+}
+
+/// Whether the number of values fits the field
+fn count_fits(qualifier: &FieldQualifier, count: usize) -> bool {
+    match qualifier {
+        FieldQualifier::Required | FieldQualifier::Optional => count <= 1,
+        FieldQualifier::Repeated(limit) | FieldQualifier::PackedRepeated(limit) => count <= *limit,
+        FieldQualifier::RepeatedUnbounded | FieldQualifier::PackedRepeatedUnbounded => true,
+    }
+}
+
+/// The wire type values of the field type are encoded with
+fn wire_type_of(field_type: &FieldType) -> WireType {
+    match field_type {
+        FieldType::UnboundedString
+        | FieldType::String(_)
+        | FieldType::UnboundedBytes
+        | FieldType::Bytes(_)
+        | FieldType::MessageType(_) => WireType::Len,
+        _ => WireType::VarInt,
+    }
+}
+
+impl<'p> DynamicMessage<'p> {
+    /// Creates an empty message of the named type
+    pub fn new(schema: &'p ProtoParser<'p>, name: &str) -> Result<Self> {
+        Ok(Self::of_type(schema, find_message_type(schema, name)?))
+    }
+
+    fn of_type(schema: &'p ProtoParser<'p>, message_type: &'p MessageType<'p>) -> Self {
+        Self {
+            schema,
+            message_type,
+            fields: BTreeMap::new(),
+        }
+    }
+
+    pub fn message_type(&self) -> &'p MessageType<'p> {
+        self.message_type
+    }
+
+    fn field_by_name(&self, name: &str) -> Option<&'p MessageField<'p>> {
+        self.message_type
+            .fields
+            .values()
+            .find(|field| field.identifier == name)
+    }
+
+    fn sub_message_type(&self, field_type: &FieldType) -> Result<&'p MessageType<'p>> {
+        match field_type {
+            FieldType::MessageType(name) => find_message_type(self.schema, name),
+            _ => Err(DynamicError::UnknownMessageType(format!(
+                "{:?}",
+                field_type
+            ))),
+        }
+    }
+
+    /// Adds the value to a repeated field, or replaces the value of a singular one
+    fn push_value(&mut self, field: &MessageField, value: Value<'p>) -> Result<()> {
+        let values = self.fields.entry(field.ordinal.0).or_default();
+        if !field.is_repeated() {
+            values.clear();
+        }
+        if !value_fits(&field.field_type, &value) || !count_fits(&field.qualifier, values.len() + 1)
+        {
+            return Err(DynamicError::CapacityExceeded(field.identifier.clone()));
+        }
+        values.push(value);
+        Ok(())
+    }
+
+    /// The values of the field, empty if it is not set, `None` if the
+    /// message type has no such field
+    pub fn get(&self, number: u32) -> Option<&[Value<'p>]> {
+        self.message_type.fields.get(&number)?;
+        Some(self.fields.get(&number).map_or(&[], Vec::as_slice))
+    }
+
+    pub fn get_by_name(&self, name: &str) -> Option<&[Value<'p>]> {
+        self.get(self.field_by_name(name)?.ordinal.0)
+    }
+
+    /// Replaces the values of the field, no values clear it. The values must
+    /// have the type of the field and fit its bounds, message values must be
+    /// of the field's message type. Enum values are not checked against the
+    /// enum, the same as for decoded messages
+    pub fn set(&mut self, number: u32, values: Vec<Value<'p>>) -> Result<()> {
+        let field = self
+            .message_type
+            .fields
+            .get(&number)
+            .ok_or_else(|| DynamicError::UnknownField(number.to_string()))?;
+        let invalid = || DynamicError::InvalidValue(field.identifier.clone());
+        if !field.is_repeated() && values.len() > 1 {
+            return Err(invalid());
+        }
+        for value in values.iter() {
+            let matches = match (&field.field_type, value) {
+                (FieldType::Bool, Value::Bool(_))
+                | (FieldType::Int32, Value::Int32(_))
+                | (FieldType::Int64, Value::Int64(_))
+                | (FieldType::Uint32, Value::Uint32(_))
+                | (FieldType::Uint64, Value::Uint64(_))
+                | (FieldType::EnumType(_), Value::Enum(_))
+                | (FieldType::UnboundedString | FieldType::String(_), Value::String(_))
+                | (FieldType::UnboundedBytes | FieldType::Bytes(_), Value::Bytes(_)) => true,
+                (FieldType::MessageType(name), Value::Message(message)) => {
+                    message.message_type.identifier == *name
+                }
+                _ => false,
+            };
+            if !matches {
+                return Err(invalid());
+            }
+        }
+        if !values
+            .iter()
+            .all(|value| value_fits(&field.field_type, value))
+            || !count_fits(&field.qualifier, values.len())
+        {
+            return Err(DynamicError::CapacityExceeded(field.identifier.clone()));
+        }
+        match values.is_empty() {
+            true => self.fields.remove(&number),
+            false => self.fields.insert(number, values),
+        };
+        Ok(())
+    }
+
+    pub fn set_by_name(&mut self, name: &str, values: Vec<Value<'p>>) -> Result<()> {
+        let field = self
+            .field_by_name(name)
+            .ok_or_else(|| DynamicError::UnknownField(name.to_string()))?;
+        self.set(field.ordinal.0, values)
+    }
+
+    /// The fields that are set and their values, in field number order
+    pub fn fields(&self) -> impl Iterator<Item = (&'p MessageField<'p>, &[Value<'p>])> + '_ {
+        self.fields
+            .iter()
+            .map(|(ordinal, values)| (&self.message_type.fields[ordinal], values.as_slice()))
+    }
+
+    /// Encodes the message after checking that the required fields of it and
+    /// its sub messages are set, returns the number of bytes written
+    pub fn encode<W: EncodeSink>(&self, buf: &mut W) -> Result<usize> {
+        self.check_initialized()?;
+        self.append(buf)
+            .map_err(|err| DynamicError::Encode(err.kind()))
+    }
+
+    fn check_initialized(&self) -> Result<()> {
+        self.check_required()?;
+        for value in self.fields.values().flatten() {
+            if let Value::Message(message) = value {
+                message.check_initialized()?;
+            }
+        }
+        Ok(())
+    }
+
+    fn check_required(&self) -> Result<()> {
+        for (ordinal, field) in self.message_type.fields.iter() {
+            let is_set = self
+                .fields
+                .get(ordinal)
+                .is_some_and(|values| !values.is_empty());
+            if matches!(field.qualifier, FieldQualifier::Required) && !is_set {
+                return Err(DynamicError::MissingRequiredField(field.identifier.clone()));
+            }
+        }
+        Ok(())
+    }
+
+    /// Decodes a message of the named type
+    pub fn decode(schema: &'p ProtoParser<'p>, name: &str, bytes: &[u8]) -> Result<Self> {
+        let message_type = find_message_type(schema, name)?;
+        Self::decode_from(schema, message_type, &mut DecodeBuffer::from_slice(bytes))
+    }
+
+    /// Decodes the fields of the message until the buffer is empty, unknown
+    /// fields are handled according to the buffer's policy
+    pub fn decode_from(
+        schema: &'p ProtoParser<'p>,
+        message_type: &'p MessageType<'p>,
+        buf: &mut DecodeBuffer<'_>,
+    ) -> Result<Self> {
+        Self::decode_nested(schema, message_type, buf, 0)
+    }
+
+    /// Decodes a message that is `depth` sub-messages deep
+    fn decode_nested(
+        schema: &'p ProtoParser<'p>,
+        message_type: &'p MessageType<'p>,
+        buf: &mut DecodeBuffer<'_>,
+        depth: usize,
+    ) -> Result<Self> {
+        if depth > MAX_MESSAGE_DEPTH {
+            return Err(DynamicError::MessageTooDeep);
+        }
+        let mut message = Self::of_type(schema, message_type);
+        while !buf.is_empty() {
+            let (ordinal, wire_type) = buf.read_tag()?;
+            let field = match message_type.fields.get(&ordinal.0) {
+                Some(field) => field,
+                None => {
+                    buf.skip_unknown(ordinal, wire_type)?;
+                    continue;
+                }
+            };
+            let expected = wire_type_of(&field.field_type);
+            // repeated scalars are accepted both packed and unpacked
+            if field.is_repeated() && wire_type == WireType::Len && expected != WireType::Len {
+                let mut packed = buf.read_sub_buffer()?;
+                while !packed.is_empty() {
+                    let value = message.read_value(field, &mut packed, depth)?;
+                    message.push_value(field, value)?;
+                }
+                continue;
+            }
+            if wire_type != expected {
+                return Err(DecodeError::UnexpectedWireType(ordinal, wire_type).into());
+            }
+            let value = message.read_value(field, buf, depth)?;
+            message.push_value(field, value)?;
+        }
+        message.check_required()?;
+        Ok(message)
+    }
+
+    fn read_value(
+        &self,
+        field: &MessageField,
+        buf: &mut DecodeBuffer<'_>,
+        depth: usize,
+    ) -> Result<Value<'p>> {
+        Ok(match &field.field_type {
+            FieldType::Bool => Value::Bool(bool::read(buf)?),
+            FieldType::Int32 => Value::Int32(i32::read(buf)?),
+            FieldType::Int64 => Value::Int64(i64::read(buf)?),
+            FieldType::Uint32 => Value::Uint32(u32::read(buf)?),
+            FieldType::Uint64 => Value::Uint64(u64::read(buf)?),
+            FieldType::EnumType(_) => Value::Enum(i32::read(buf)?),
+            FieldType::UnboundedString | FieldType::String(_) => Value::String(String::read(buf)?),
+            FieldType::UnboundedBytes | FieldType::Bytes(_) => {
+                Value::Bytes(buf.read_len_delimited()?.to_vec())
+            }
+            FieldType::MessageType(_) => {
+                let message_type = self.sub_message_type(&field.field_type)?;
+                let mut sub_buffer = buf.read_sub_buffer()?;
+                Value::Message(Self::decode_nested(
+                    self.schema,
+                    message_type,
+                    &mut sub_buffer,
+                    depth + 1,
+                )?)
+            }
+        })
+    }
+
+    /// The name of the enum value, if the field is an enum and the value has one
+    fn enum_name(&self, field: &MessageField, value: &Value) -> Option<&'p str> {
+        match (&field.field_type, value) {
+            (FieldType::EnumType(name), Value::Enum(number)) => self
+                .schema
+                .enum_types
+                .get(*name)?
+                .values()
+                .find(|(_, value)| value == number)
+                .map(|(name, _)| name),
+            _ => None,
+        }
+    }
+
+    /// The number of the named enum value of the field's enum type
+    fn enum_number(&self, field: &MessageField, name: &str) -> Option<i32> {
+        match &field.field_type {
+            FieldType::EnumType(enum_name) => self
+                .schema
+                .enum_types
+                .get(*enum_name)?
+                .pairs
+                .get(name)
+                .copied(),
+            _ => None,
+        }
+    }
+
+    /// The value of the named field's enum type with the given name
+    pub fn enum_value(&self, field: &str, name: &str) -> Result<Value<'p>> {
+        let field = self
+            .field_by_name(field)
+            .ok_or_else(|| DynamicError::UnknownField(field.to_string()))?;
+        self.enum_number(field, name)
+            .map(Value::Enum)
+            .ok_or_else(|| DynamicError::InvalidValue(field.identifier.clone()))
+    }
+
+    /// Parses a message of the named type from the text format
+    pub fn from_text_format(schema: &'p ProtoParser<'p>, name: &str, input: &str) -> Result<Self> {
+        let message_type = find_message_type(schema, name)?;
+        Self::from_text(schema, message_type, &text_format::parse(input)?)
+    }
+
+    pub fn from_text(
+        schema: &'p ProtoParser<'p>,
+        message_type: &'p MessageType<'p>,
+        text: &TextValue,
+    ) -> Result<Self> {
+        Self::from_text_nested(schema, message_type, text, 0)
+    }
+
+    /// Converts a message that is `depth` sub-messages deep
+    fn from_text_nested(
+        schema: &'p ProtoParser<'p>,
+        message_type: &'p MessageType<'p>,
+        text: &TextValue,
+        depth: usize,
+    ) -> Result<Self> {
+        if depth > MAX_MESSAGE_DEPTH {
+            return Err(DynamicError::MessageTooDeep);
+        }
+        let mut message = Self::of_type(schema, message_type);
+        for (name, value) in text.as_message()? {
+            let field = message
+                .field_by_name(name)
+                .ok_or_else(|| TextFormatError::UnknownField(name.clone()))?;
+            match value {
+                TextValue::List(values) => {
+                    for value in values {
+                        let value = message.text_value(field, value, depth)?;
+                        message.push_value(field, value)?;
+                    }
+                }
+                value => {
+                    let value = message.text_value(field, value, depth)?;
+                    message.push_value(field, value)?;
+                }
+            }
+        }
+        message.check_required()?;
+        Ok(message)
+    }
+
+    fn text_value(
+        &self,
+        field: &MessageField,
+        text: &TextValue,
+        depth: usize,
+    ) -> Result<Value<'p>> {
+        Ok(match &field.field_type {
+            FieldType::Bool => Value::Bool(bool::from_text(text)?),
+            FieldType::Int32 => Value::Int32(i32::from_text(text)?),
+            FieldType::Int64 => Value::Int64(i64::from_text(text)?),
+            FieldType::Uint32 => Value::Uint32(u32::from_text(text)?),
+            FieldType::Uint64 => Value::Uint64(u64::from_text(text)?),
+            FieldType::EnumType(_) => {
+                let literal = text.as_literal()?;
+                match self.enum_number(field, literal) {
+                    Some(number) => Value::Enum(number),
+                    None => Value::Enum(
+                        i32::from_text(text)
+                            .map_err(|_| TextFormatError::InvalidEnumValue(literal.to_string()))?,
+                    ),
+                }
+            }
+            FieldType::UnboundedString | FieldType::String(_) => {
+                Value::String(String::from_text(text)?)
+            }
+            FieldType::UnboundedBytes | FieldType::Bytes(_) => {
+                Value::Bytes(text.as_bytes()?.to_vec())
+            }
+            FieldType::MessageType(_) => {
+                let message_type = self.sub_message_type(&field.field_type)?;
+                Value::Message(Self::from_text_nested(
+                    self.schema,
+                    message_type,
+                    text,
+                    depth + 1,
+                )?)
+            }
+        })
+    }
+
+    /// Parses a message of the named type from JSON
+    pub fn from_json_str(schema: &'p ProtoParser<'p>, name: &str, input: &str) -> Result<Self> {
+        let message_type = find_message_type(schema, name)?;
+        Self::from_json(schema, message_type, &json::parse(input)?)
+    }
+
+    /// Fields are matched by their JSON name or their field name
+    pub fn from_json(
+        schema: &'p ProtoParser<'p>,
+        message_type: &'p MessageType<'p>,
+        json: &JsonValue,
+    ) -> Result<Self> {
+        Self::from_json_nested(schema, message_type, json, 0)
+    }
+
+    /// Converts a message that is `depth` sub-messages deep
+    fn from_json_nested(
+        schema: &'p ProtoParser<'p>,
+        message_type: &'p MessageType<'p>,
+        json: &JsonValue,
+        depth: usize,
+    ) -> Result<Self> {
+        if depth > MAX_MESSAGE_DEPTH {
+            return Err(DynamicError::MessageTooDeep);
+        }
+        let mut message = Self::of_type(schema, message_type);
+        for (name, value) in json.as_object()? {
+            let field = message_type
+                .fields
+                .values()
+                .find(|field| field.json_name() == *name || field.identifier == *name)
+                .ok_or_else(|| JsonError::UnknownField(name.clone()))?;
+            match value {
+                // null is the same as leaving the field out
+                JsonValue::Null => {}
+                JsonValue::Array(values) if field.is_repeated() => {
+                    for value in values {
+                        let value = message.json_value(field, value, depth)?;
+                        message.push_value(field, value)?;
+                    }
+                }
+                value => {
+                    let value = message.json_value(field, value, depth)?;
+                    message.push_value(field, value)?;
+                }
+            }
+        }
+        message.check_required()?;
+        Ok(message)
+    }
+
+    fn json_value(
+        &self,
+        field: &MessageField,
+        json: &JsonValue,
+        depth: usize,
+    ) -> Result<Value<'p>> {
+        Ok(match &field.field_type {
+            FieldType::Bool => Value::Bool(bool::from_json(json)?),
+            FieldType::Int32 => Value::Int32(i32::from_json(json)?),
+            FieldType::Int64 => Value::Int64(i64::from_json(json)?),
+            FieldType::Uint32 => Value::Uint32(u32::from_json(json)?),
+            FieldType::Uint64 => Value::Uint64(u64::from_json(json)?),
+            FieldType::EnumType(_) => match json {
+                JsonValue::String(name) => Value::Enum(
+                    self.enum_number(field, name)
+                        .ok_or_else(|| JsonError::InvalidEnumValue(name.clone()))?,
+                ),
+                number => Value::Enum(i32::from_json(number)?),
+            },
+            FieldType::UnboundedString | FieldType::String(_) => {
+                Value::String(String::from_json(json)?)
+            }
+            FieldType::UnboundedBytes | FieldType::Bytes(_) => {
+                Value::Bytes(json::read_base64(json.as_str()?)?)
+            }
+            FieldType::MessageType(_) => {
+                let message_type = self.sub_message_type(&field.field_type)?;
+                Value::Message(Self::from_json_nested(
+                    self.schema,
+                    message_type,
+                    json,
+                    depth + 1,
+                )?)
+            }
+        })
+    }
+}
+
+impl WireTyped for Value<'_> {
+    fn wiretype(&self) -> WireType {
+        match self {
+            Self::String(_) | Self::Bytes(_) | Self::Message(_) => WireType::Len,
+            _ => WireType::VarInt,
+        }
+    }
+}
+
+/// Messages are written with their length prefix, the same as strings and bytes
+impl ToWire for Value<'_> {
+    fn append<W: EncodeSink>(&self, buf: &mut W) -> std::io::Result<usize> {
+        match self {
+            Self::Bool(value) => value.append(buf),
+            Self::Int32(value) | Self::Enum(value) => value.append(buf),
+            Self::Int64(value) => value.append(buf),
+            Self::Uint32(value) => value.append(buf),
+            Self::Uint64(value) => value.append(buf),
+            Self::String(value) => value.as_bytes().append(buf),
+            Self::Bytes(value) => value.as_slice().append(buf),
+            Self::Message(message) => {
+                let count = write_prefix(buf, message.precalculate_size())?;
+                Ok(count + message.append(buf)?)
+            }
+        }
+    }
+
+    fn precalculate_size(&self) -> usize {
+        match self {
+            Self::Bool(value) => value.precalculate_size(),
+            Self::Int32(value) | Self::Enum(value) => value.precalculate_size(),
+            Self::Int64(value) => value.precalculate_size(),
+            Self::Uint32(value) => value.precalculate_size(),
+            Self::Uint64(value) => value.precalculate_size(),
+            Self::String(value) => value.as_bytes().precalculate_size(),
+            Self::Bytes(value) => value.as_slice().precalculate_size(),
+            Self::Message(message) => {
+                let size = message.precalculate_size();
+                precalculate_prefix_size(size) + size
+            }
+        }
+    }
+}
+
+fn is_packed(field: &MessageField) -> bool {
+    matches!(
+        field.qualifier,
+        FieldQualifier::PackedRepeated(_) | FieldQualifier::PackedRepeatedUnbounded
+    )
+}
+
+impl WireTyped for &DynamicMessage<'_> {
+    fn wiretype(&self) -> WireType {
+        WireType::Len
+    }
+}
+
+/// Fields are written in field number order, packed fields as a single record
+impl ToWire for &DynamicMessage<'_> {
+    fn append<W: EncodeSink>(&self, buf: &mut W) -> std::io::Result<usize> {
+        let mut total_size = 0;
+        for (ordinal, values) in self.fields.iter() {
+            let field = &self.message_type.fields[ordinal];
+            if is_packed(field) {
+                if !values.is_empty() {
+                    let packed = Packed(values.as_slice());
+                    total_size += packed.write_tag(buf, Field(*ordinal))?;
+                    total_size += packed.append(buf)?;
+                }
+                continue;
+            }
+            for value in values {
+                total_size += value.write_tag(buf, Field(*ordinal))?;
+                total_size += value.append(buf)?;
+            }
+        }
+        Ok(total_size)
+    }
+
+    fn precalculate_size(&self) -> usize {
+        let mut total_size = 0;
+        for (ordinal, values) in self.fields.iter() {
+            let field = &self.message_type.fields[ordinal];
+            if is_packed(field) {
+                if !values.is_empty() {
+                    total_size +=
+                        Packed(values.as_slice()).precalculate_field_size(Field(*ordinal));
+                }
+                continue;
+            }
+            for value in values {
+                total_size += value.precalculate_field_size(Field(*ordinal));
+            }
+        }
+        total_size
+    }
+}
+
+/// Writes text that is already in the text format, enum names
+struct Literal<'a>(&'a str);
+
+impl ToText for Literal<'_> {
+    fn write_text<W: Write>(&self, out: &mut W) -> fmt::Result {
+        out.write_str(self.0)
+    }
+}
+
+/// Enums are written by their number, messages write their fields
+impl ToText for Value<'_> {
+    fn write_text<W: Write>(&self, out: &mut W) -> fmt::Result {
+        match self {
+            Self::Bool(value) => value.write_text(out),
+            Self::Int32(value) | Self::Enum(value) => value.write_text(out),
+            Self::Int64(value) => value.write_text(out),
+            Self::Uint32(value) => value.write_text(out),
+            Self::Uint64(value) => value.write_text(out),
+            Self::String(value) => value.write_text(out),
+            Self::Bytes(value) => text_format::write_bytes(out, value),
+            Self::Message(message) => message.write_text(out),
+        }
+    }
+}
+
+impl ToText for DynamicMessage<'_> {
+    fn write_text<W: Write>(&self, out: &mut W) -> fmt::Result {
+        let mut writer = FieldWriter::new(out);
+        for (ordinal, values) in self.fields.iter() {
+            let field = &self.message_type.fields[ordinal];
+            for value in values {
+                match (value, self.enum_name(field, value)) {
+                    (Value::Message(message), _) => writer.message(&field.identifier, message)?,
+                    (_, Some(name)) => writer.field(&field.identifier, &Literal(name))?,
+                    (value, None) => writer.field(&field.identifier, value)?,
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Enums are written by their number, see `DynamicMessage` for their names
+impl ToJson for Value<'_> {
+    fn write_json<W: Write>(&self, out: &mut W) -> fmt::Result {
+        match self {
+            Self::Bool(value) => value.write_json(out),
+            Self::Int32(value) | Self::Enum(value) => value.write_json(out),
+            Self::Int64(value) => value.write_json(out),
+            Self::Uint32(value) => value.write_json(out),
+            Self::Uint64(value) => value.write_json(out),
+            Self::String(value) => value.write_json(out),
+            Self::Bytes(value) => json::write_base64(out, value),
+            Self::Message(message) => message.write_json(out),
+        }
+    }
+}
+
+/// A field value that knows the name of its enum value
+struct JsonField<'v, 'p>(&'v Value<'p>, Option<&'p str>);
+
+impl ToJson for JsonField<'_, '_> {
+    fn write_json<W: Write>(&self, out: &mut W) -> fmt::Result {
+        match self.1 {
+            Some(name) => json::write_string(out, name),
+            None => self.0.write_json(out),
+        }
+    }
+}
+
+impl ToJson for DynamicMessage<'_> {
+    fn write_json<W: Write>(&self, out: &mut W) -> fmt::Result {
+        let mut object = ObjectWriter::new(out)?;
+        for (ordinal, values) in self.fields.iter() {
+            let field = &self.message_type.fields[ordinal];
+            let values: Vec<JsonField> = values
+                .iter()
+                .map(|value| JsonField(value, self.enum_name(field, value)))
+                .collect();
+            match (field.is_repeated(), values.first()) {
+                (true, _) => object.repeated(&field.json_name(), &values)?,
+                (false, Some(value)) => object.field(&field.json_name(), value)?,
+                (false, None) => {}
+            }
+        }
+        object.finish()
+    }
+}
@@ -1,12 +1,12 @@
 use std::io::Write;
 
+use bytes::BufMut;
+
 use crate::{
     common::{Field, Packed},
     wiretypes::{ToVarint, WireTyped},
 };
 
-use leb128;
-
 #[derive(Debug)]
 pub enum EncodeError {
     BufferOutOfSpace,
@@ -14,6 +14,74 @@ pub enum EncodeError {
 
 pub type Result<T> = std::result::Result<T, EncodeError>;
 
+fn out_of_space() -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::OutOfMemory, "out of memory")
+}
+
+/// The result of writing to an `EncodeSink`, generated `no_std` code refers
+/// to it instead of `std::io::Result`. It is still `std::io::Result`, as the
+/// runtime needs `std`
+pub type SinkResult<T> = std::io::Result<T>;
+
+/// The EncodeSink trait is the output encoded messages are written to.
+///
+/// Implemented for `EncodeBuffer`, `Vec<u8>`, `bytes::BytesMut` and
+/// `CountingSink`, and through the `IoSink` and `BufMutSink` wrappers for
+/// any `std::io::Write` or `bytes::BufMut`.
+pub trait EncodeSink {
+    /// writes all of the bytes or fails, returns the number of bytes written
+    fn write_bytes(&mut self, bytes: &[u8]) -> std::io::Result<usize>;
+
+    fn encode_tag(&mut self, encodable: impl ToWire, field: Field) -> std::io::Result<usize>
+    where
+        Self: Sized,
+    {
+        encodable.write_tag(self, field)
+    }
+
+    fn encode_value(&mut self, encodable: impl ToWire) -> std::io::Result<usize>
+    where
+        Self: Sized,
+    {
+        encodable.append(self)
+    }
+
+    fn encode(&mut self, encodable: impl ToWire + Copy, field: Field) -> std::io::Result<usize>
+    where
+        Self: Sized,
+    {
+        let tag_size = self.encode_tag(encodable, field)?;
+        Ok(tag_size + self.encode_value(encodable)?)
+    }
+
+    /// encodes a message as a length delimited field
+    fn encode_sub_message(&mut self, encodable: impl ToWire, field: Field) -> std::io::Result<usize>
+    where
+        Self: Sized,
+    {
+        let mut count = encodable.write_tag(self, field)?;
+        count += write_prefix(self, encodable.precalculate_size())?;
+        count += encodable.append(self)?;
+        Ok(count)
+    }
+
+    /// encodes the message prefixed with its length as a varint,
+    /// the same framing as `writeDelimitedTo` in the Java library
+    fn encode_delimited(&mut self, message: impl ToWire) -> std::io::Result<usize>
+    where
+        Self: Sized,
+    {
+        let prefix_size = write_prefix(self, message.precalculate_size())?;
+        Ok(prefix_size + message.append(self)?)
+    }
+}
+
+impl<S: EncodeSink + ?Sized> EncodeSink for &mut S {
+    fn write_bytes(&mut self, bytes: &[u8]) -> std::io::Result<usize> {
+        (**self).write_bytes(bytes)
+    }
+}
+
 #[derive(Debug)]
 pub struct EncodeBuffer<'a> {
     buffer: &'a mut [u8],
@@ -24,11 +92,8 @@ impl Write for EncodeBuffer<'_> {
     fn write(&mut self, append_bytes: &[u8]) -> std::io::Result<usize> {
         let count = append_bytes.len();
         let new_len = self.len + count;
-        if new_len >= self.buffer.len() {
-            return Err(std::io::Error::new(
-                std::io::ErrorKind::OutOfMemory,
-                "out of memory",
-            ));
+        if new_len > self.buffer.len() {
+            return Err(out_of_space());
         }
         self.buffer[self.len..new_len].copy_from_slice(append_bytes);
         self.len = new_len;
@@ -40,6 +105,12 @@ impl Write for EncodeBuffer<'_> {
     }
 }
 
+impl EncodeSink for EncodeBuffer<'_> {
+    fn write_bytes(&mut self, bytes: &[u8]) -> std::io::Result<usize> {
+        self.write(bytes)
+    }
+}
+
 impl EncodeBuffer<'_> {
     pub fn from_static(static_buffer: &mut [u8]) -> EncodeBuffer<'_> {
         EncodeBuffer {
@@ -51,50 +122,96 @@ impl EncodeBuffer<'_> {
     pub fn as_slice(&self) -> &[u8] {
         &self.buffer[..self.len]
     }
+}
 
-    pub fn encode_tag(&mut self, encodable: impl ToWire, field: Field) -> std::io::Result<usize> {
-        encodable.write_tag(self, field)
+impl EncodeSink for Vec<u8> {
+    fn write_bytes(&mut self, bytes: &[u8]) -> std::io::Result<usize> {
+        self.extend_from_slice(bytes);
+        Ok(bytes.len())
     }
+}
 
-    pub fn encode_value(&mut self, encodable: impl ToWire) -> std::io::Result<usize> {
-        encodable.append(self)
+impl EncodeSink for bytes::BytesMut {
+    fn write_bytes(&mut self, bytes: &[u8]) -> std::io::Result<usize> {
+        self.extend_from_slice(bytes);
+        Ok(bytes.len())
     }
+}
 
-    pub fn encode_sub_message(&mut self, encodable: impl ToWire, field: Field) -> std::io::Result<usize> {
-        encodable.append(self)
+/// Streams the encoded bytes to any `std::io::Write`, such as a socket or file
+#[derive(Debug)]
+pub struct IoSink<W: Write>(pub W);
+
+impl<W: Write> EncodeSink for IoSink<W> {
+    fn write_bytes(&mut self, bytes: &[u8]) -> std::io::Result<usize> {
+        self.0.write_all(bytes)?;
+        Ok(bytes.len())
+    }
+}
+
+/// Writes the encoded bytes to any `bytes::BufMut`, fails instead of
+/// panicking when the remaining capacity is too small
+#[derive(Debug)]
+pub struct BufMutSink<B: BufMut>(pub B);
+
+impl<B: BufMut> EncodeSink for BufMutSink<B> {
+    fn write_bytes(&mut self, bytes: &[u8]) -> std::io::Result<usize> {
+        if self.0.remaining_mut() < bytes.len() {
+            return Err(out_of_space());
+        }
+        self.0.put_slice(bytes);
+        Ok(bytes.len())
     }
+}
+
+/// Discards the encoded bytes and only counts them
+#[derive(Debug, Default)]
+pub struct CountingSink {
+    pub count: usize,
+}
 
-    pub fn encode(
-        &mut self,
-        encodable: impl ToWire + Copy,
-        field: Field,
-    ) -> std::io::Result<usize> {
-        self.encode_tag(encodable, field)?;
-        self.encode_value(encodable)
+impl EncodeSink for CountingSink {
+    fn write_bytes(&mut self, bytes: &[u8]) -> std::io::Result<usize> {
+        self.count += bytes.len();
+        Ok(bytes.len())
     }
 }
 
 /// The ToWire trait encodes the type in the protocol buffers wire format
 pub trait ToWire: WireTyped {
     /// encodes the tag for the type and returns the bytes written
-    fn write_tag(&self, buf: &mut EncodeBuffer, field: Field) -> std::io::Result<usize> {
+    fn write_tag<W: EncodeSink>(&self, buf: &mut W, field: Field) -> std::io::Result<usize> {
         let tag = self.tag(field);
         let (bytes, count) = tag.encode();
-        buf.write(&bytes[0..count])
+        buf.write_bytes(&bytes[0..count])
     }
 
-    /// encodes to the end of the encode buffer and returns the number of bytes written
-    fn append(&self, buf: &mut EncodeBuffer) -> std::io::Result<usize>;
+    /// encodes to the end of the sink and returns the number of bytes written
+    fn append<W: EncodeSink>(&self, buf: &mut W) -> std::io::Result<usize>;
 
     /// precalculates the number of bytes required to encode this
     /// will be called before encode_append in some cases
     fn precalculate_size(&self) -> usize;
+
+    /// precalculates the number of bytes required to encode this as a field, including the tag
+    fn precalculate_field_size(&self, field: Field) -> usize {
+        let (_, tag_size) = self.tag(field).encode();
+        tag_size + self.precalculate_size()
+    }
+
+    /// precalculates the number of bytes required to encode this as
+    /// a sub message field, including the tag and length prefix
+    fn precalculate_sub_message_size(&self, field: Field) -> usize {
+        let size = self.precalculate_size();
+        let (_, tag_size) = self.tag(field).encode();
+        tag_size + precalculate_prefix_size(size) + size
+    }
 }
 
 /// Writes the length prefix. Used for length encoded types.
-pub fn write_prefix(buf: &mut EncodeBuffer, len: usize) -> std::io::Result<usize> {
+pub fn write_prefix<W: EncodeSink>(buf: &mut W, len: usize) -> std::io::Result<usize> {
     let (bytes, count) = (len as u64).to_varint_encoding();
-    buf.write(&bytes[..count])
+    buf.write_bytes(&bytes[..count])
 }
 
 pub fn precalculate_prefix_size(len: usize) -> usize {
@@ -102,13 +219,26 @@ pub fn precalculate_prefix_size(len: usize) -> usize {
     count
 }
 
+/// Writes the message id as a varint followed by the message,
+/// used to multiplex several message types over a single link.
+pub fn encode_with_id<W: EncodeSink>(
+    buf: &mut W,
+    id: u32,
+    message: impl ToWire,
+) -> std::io::Result<usize> {
+    let (bytes, count) = id.to_varint_encoding();
+    buf.write_bytes(&bytes[..count])?;
+    Ok(count + message.append(buf)?)
+}
+
+/// The elements are encoded without tags, prefixed by their total length
 impl<T> ToWire for Packed<&[T]>
 where
     T: ToWire,
 {
-    fn append(&self, buf: &mut EncodeBuffer) -> std::io::Result<usize> {
-        write_prefix(buf, self.precalculate_size())?;
-        let mut count = 0;
+    fn append<W: EncodeSink>(&self, buf: &mut W) -> std::io::Result<usize> {
+        let payload_size: usize = self.0.iter().map(|t| t.precalculate_size()).sum();
+        let mut count = write_prefix(buf, payload_size)?;
         for t in self.0.iter() {
             count += t.append(buf)?;
         }
@@ -116,83 +246,112 @@ where
     }
 
     fn precalculate_size(&self) -> usize {
-        self.0.iter().map(|t| t.precalculate_size()).sum()
+        let payload_size: usize = self.0.iter().map(|t| t.precalculate_size()).sum();
+        precalculate_prefix_size(payload_size) + payload_size
     }
 }
 
 impl ToWire for &String {
-    fn append(&self, buf: &mut EncodeBuffer) -> std::io::Result<usize> {
-        write_prefix(buf, self.len())?;
-        buf.write(self.as_bytes())
+    fn append<W: EncodeSink>(&self, buf: &mut W) -> std::io::Result<usize> {
+        let prefix_size = write_prefix(buf, self.len())?;
+        Ok(prefix_size + buf.write_bytes(self.as_bytes())?)
     }
 
     fn precalculate_size(&self) -> usize {
-        self.as_bytes().len()
+        precalculate_prefix_size(self.len()) + self.len()
     }
 }
 
 impl ToWire for &[u8] {
-    fn append(&self, buf: &mut EncodeBuffer) -> std::io::Result<usize> {
-        write_prefix(buf, self.len())?;
-        buf.write(self)
+    fn append<W: EncodeSink>(&self, buf: &mut W) -> std::io::Result<usize> {
+        let prefix_size = write_prefix(buf, self.len())?;
+        Ok(prefix_size + buf.write_bytes(self)?)
     }
 
     fn precalculate_size(&self) -> usize {
-        self.len()
+        precalculate_prefix_size(self.len()) + self.len()
     }
 }
 
-impl ToWire for i32
-where
-    i32: ToVarint,
-{
-    fn append(&self, buf: &mut EncodeBuffer) -> std::io::Result<usize> {
-        leb128::write::signed(buf, *self as i64)
+/// Writes the varint encoding of the value
+fn append_varint<W: EncodeSink>(buf: &mut W, value: impl ToVarint) -> std::io::Result<usize> {
+    let (bytes, count) = value.to_varint_encoding();
+    buf.write_bytes(&bytes[..count])
+}
+
+fn precalculate_varint_size(value: impl ToVarint) -> usize {
+    let (_, count) = value.to_varint_encoding();
+    count
+}
+
+impl ToWire for i32 {
+    fn append<W: EncodeSink>(&self, buf: &mut W) -> std::io::Result<usize> {
+        append_varint(buf, *self)
     }
 
     fn precalculate_size(&self) -> usize {
-        let (_, size) = self.to_varint_encoding();
-        size
+        precalculate_varint_size(*self)
     }
 }
 
-// TODO: These should be autogenerated instead
-impl ToWire for &str {
-    fn append(&self, buf: &mut EncodeBuffer) -> std::io::Result<usize> {
-        write_prefix(buf, self.len())?;
-        buf.write(self.as_bytes())
+impl ToWire for i64 {
+    fn append<W: EncodeSink>(&self, buf: &mut W) -> std::io::Result<usize> {
+        append_varint(buf, *self)
     }
 
     fn precalculate_size(&self) -> usize {
-        self.len()
+        precalculate_varint_size(*self)
     }
 }
 
-impl<T> ToWire for &[T]
-where
-    T: ToWire,
-{
-    fn append(&self, buf: &mut EncodeBuffer) -> std::io::Result<usize> {
-        write_prefix(buf, self.len())?;
-        let mut total_size = 0;
-        for element in self.iter() {
-            total_size += element.append(buf)?;
-        }
-        Ok(total_size)
+impl ToWire for u32 {
+    fn append<W: EncodeSink>(&self, buf: &mut W) -> std::io::Result<usize> {
+        append_varint(buf, *self)
+    }
+
+    fn precalculate_size(&self) -> usize {
+        precalculate_varint_size(*self)
+    }
+}
+
+impl ToWire for u64 {
+    fn append<W: EncodeSink>(&self, buf: &mut W) -> std::io::Result<usize> {
+        append_varint(buf, *self)
+    }
+
+    fn precalculate_size(&self) -> usize {
+        precalculate_varint_size(*self)
+    }
+}
+
+impl ToWire for bool {
+    fn append<W: EncodeSink>(&self, buf: &mut W) -> std::io::Result<usize> {
+        append_varint(buf, *self)
+    }
+
+    fn precalculate_size(&self) -> usize {
+        precalculate_varint_size(*self)
+    }
+}
+
+// TODO: These should be autogenerated instead
+impl ToWire for &str {
+    fn append<W: EncodeSink>(&self, buf: &mut W) -> std::io::Result<usize> {
+        let prefix_size = write_prefix(buf, self.len())?;
+        Ok(prefix_size + buf.write_bytes(self.as_bytes())?)
     }
 
     fn precalculate_size(&self) -> usize {
-        self.len()
+        precalculate_prefix_size(self.len()) + self.len()
     }
 }
 
 impl ToWire for String {
-    fn append(&self, buf: &mut EncodeBuffer) -> std::io::Result<usize> {
-        write_prefix(buf, self.len())?;
+    fn append<W: EncodeSink>(&self, buf: &mut W) -> std::io::Result<usize> {
         self.as_str().append(buf)
     }
 
     fn precalculate_size(&self) -> usize {
-        self.len()
+        self.as_str().precalculate_size()
     }
 }
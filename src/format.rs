@@ -0,0 +1,412 @@
+use crate::parser::{ParserError, PicoPBParser, Rule};
+use pest::iterators::Pair;
+use pest::Parser;
+
+/// This module formats .proto files in a canonical style: one statement per
+/// line indented by four spaces, `name = value` options separated by `, `,
+/// at most one blank line in a row and one around every message and enum.
+/// Comments are kept, comments inside a statement are moved above it.
+/// Options after the terminator, as written for earlier versions of picopb,
+/// are moved before it.
+
+#[derive(Debug, Clone, Copy)]
+enum Trivia<'i> {
+    Comment(&'i str),
+    /// Consecutive line breaks, with only whitespace between them
+    Newlines(usize),
+    /// Punctuation or a keyword, written from the parse tree instead
+    Other(usize, char),
+}
+
+const INDENT: &str = "    ";
+/// Statements with options longer than this are written one option per line
+const MAX_WIDTH: usize = 100;
+
+/// The comments and line breaks in text between two tokens. Strings are
+/// always tokens, so the text can not contain a quoted `//`
+fn trivia(text: &str) -> Vec<Trivia<'_>> {
+    let mut result = Vec::new();
+    let mut offset = 0;
+    while let Some(c) = text[offset..].chars().next() {
+        let rest = &text[offset..];
+        if rest.starts_with("//") {
+            let len = rest.find('\n').unwrap_or(rest.len());
+            result.push(Trivia::Comment(rest[..len].trim_end()));
+            offset += len;
+        } else if rest.starts_with("/*") {
+            let len = rest.find("*/").map_or(rest.len(), |end| end + 2);
+            result.push(Trivia::Comment(&rest[..len]));
+            offset += len;
+        } else {
+            match (c, result.last_mut()) {
+                ('\n', Some(Trivia::Newlines(count))) => *count += 1,
+                ('\n', _) => result.push(Trivia::Newlines(1)),
+                _ if c.is_whitespace() => {}
+                _ => result.push(Trivia::Other(offset, c)),
+            }
+            offset += c.len_utf8();
+        }
+    }
+    result
+}
+
+/// The offset of the first, or last, occurrence of the punctuation outside comments
+fn find_other(text: &str, wanted: char, last: bool) -> Option<usize> {
+    let mut found = trivia(text).into_iter().filter_map(|trivia| match trivia {
+        Trivia::Other(offset, c) if c == wanted => Some(offset),
+        _ => None,
+    });
+    match last {
+        true => found.next_back(),
+        false => found.next(),
+    }
+}
+
+fn option_name_text(name: Pair<Rule>) -> String {
+    let parts: Vec<String> = name
+        .into_inner()
+        .filter_map(|part| part.into_inner().next())
+        .map(|part| match part.as_rule() {
+            Rule::extension_name => format!("({})", part.into_inner().as_str()),
+            _ => part.as_str().to_string(),
+        })
+        .collect();
+    parts.join(".")
+}
+
+/// Aggregates are written on one line, `{ max_size: 10, inner { value: true } }`
+fn aggregate_text(aggregate: Pair<Rule>) -> String {
+    let fields: Vec<String> = aggregate
+        .into_inner()
+        .map(|field| {
+            let mut inner = field.into_inner();
+            let identifier = inner.next().map_or("", |identifier| identifier.as_str());
+            let value = inner.next().expect("aggregate fields have a value");
+            let value = match value.as_rule() {
+                Rule::constant => value.into_inner().next().expect("constants have a value"),
+                _ => value,
+            };
+            match value.as_rule() {
+                Rule::aggregate => format!("{} {}", identifier, aggregate_text(value)),
+                _ => format!("{}: {}", identifier, value.as_str()),
+            }
+        })
+        .collect();
+    match fields.is_empty() {
+        true => "{}".into(),
+        false => format!("{{ {} }}", fields.join(", ")),
+    }
+}
+
+fn constant_text(constant: Pair<Rule>) -> String {
+    let value = constant
+        .into_inner()
+        .next()
+        .expect("constants have a value");
+    match value.as_rule() {
+        Rule::aggregate => aggregate_text(value),
+        _ => value.as_str().to_string(),
+    }
+}
+
+/// `name = value`, of an option in a list or an option statement
+fn option_text(option: Pair<Rule>) -> String {
+    let mut inner = option.into_inner();
+    let name = inner.next().expect("options have a name");
+    let value = inner.next().expect("options have a value");
+    format!("{} = {}", option_name_text(name), constant_text(value))
+}
+
+fn range_text(range: Pair<Rule>) -> String {
+    let bounds: Vec<&str> = range.into_inner().map(|bound| bound.as_str()).collect();
+    bounds.join(" to ")
+}
+
+fn tokens<'i>(statement: &Pair<'i, Rule>) -> impl Iterator<Item = Pair<'i, Rule>> {
+    statement
+        .clone()
+        .into_inner()
+        .flatten()
+        .filter(|pair| pair.clone().into_inner().next().is_none())
+}
+
+/// Where the statement ends. Its span also covers the whitespace and
+/// comments skipped while looking for the optional legacy options
+fn end_of(statement: &Pair<'_, Rule>) -> usize {
+    let span = statement.as_span();
+    let last = tokens(statement)
+        .last()
+        .map_or(span.start(), |token| token.as_span().end());
+    let rest = trivia(&span.get_input()[last..span.end()]);
+    match rest
+        .iter()
+        .rev()
+        .find(|trivia| matches!(trivia, Trivia::Other(..)))
+    {
+        Some(Trivia::Other(offset, c)) => last + offset + c.len_utf8(),
+        _ => last,
+    }
+}
+
+/// The comments between the tokens of a statement
+fn inner_comments<'i>(statement: &Pair<'i, Rule>) -> Vec<&'i str> {
+    let input = statement.as_span().get_input();
+    let mut comments = Vec::new();
+    let mut start = statement.as_span().start();
+    for token in tokens(statement) {
+        comments.extend(comments_in(&input[start..token.as_span().start()]));
+        start = token.as_span().end();
+    }
+    comments.extend(comments_in(&input[start..end_of(statement)]));
+    comments
+}
+
+fn comments_in(text: &str) -> impl Iterator<Item = &str> {
+    trivia(text).into_iter().filter_map(|trivia| match trivia {
+        Trivia::Comment(comment) => Some(comment),
+        _ => None,
+    })
+}
+
+fn comment_lines(comment: &str) -> String {
+    let lines: Vec<&str> = comment.lines().map(str::trim_end).collect();
+    lines.join("\n")
+}
+
+/// Top level statements of different kinds are separated by a blank line,
+/// and messages and enums from everything
+fn separated(previous: Rule, next: Rule) -> bool {
+    previous != next || matches!(next, Rule::message_definition | Rule::enum_definition)
+}
+
+struct Formatter<'i> {
+    input: &'i str,
+    output: String,
+    depth: usize,
+    /// Nothing was written since the opening brace of a block
+    block_start: bool,
+}
+
+impl<'i> Formatter<'i> {
+    fn line(&mut self, text: &str) {
+        for _ in 0..self.depth {
+            self.output += INDENT;
+        }
+        self.output += text;
+        self.output.push('\n');
+        self.block_start = false;
+    }
+
+    fn blank(&mut self) {
+        if !self.output.is_empty() && !self.output.ends_with("\n\n") && !self.block_start {
+            self.output.push('\n');
+        }
+    }
+
+    /// Block comments are indented on their first line, the others are kept as they are
+    fn comment(&mut self, comment: &str) {
+        self.line(&comment_lines(comment));
+    }
+
+    /// Appends a comment to the last line written
+    fn append(&mut self, comment: &str) {
+        self.output.pop();
+        self.output.push(' ');
+        self.output += &comment_lines(comment);
+        self.output.push('\n');
+    }
+
+    /// Writes the comments of the gap before a statement, comments on the
+    /// line the gap starts on are appended to the previous line. Returns
+    /// whether the statement should follow a blank line
+    fn gap(&mut self, gap: &[Trivia], trailing: bool, separate: bool) -> bool {
+        let mut on_line = trailing;
+        let mut blank = separate;
+        for trivia in gap.iter() {
+            match trivia {
+                Trivia::Newlines(count) => {
+                    on_line = false;
+                    blank |= *count > 1;
+                }
+                Trivia::Comment(comment) if on_line => self.append(comment),
+                Trivia::Comment(comment) => {
+                    if blank {
+                        self.blank();
+                        blank = false;
+                    }
+                    self.comment(comment);
+                }
+                Trivia::Other(..) => {}
+            }
+        }
+        blank
+    }
+
+    /// Writes the statements with the gaps around them, `gaps` has one
+    /// more element than `statements`
+    fn statements(&mut self, statements: &[Pair<'i, Rule>], gaps: &[Vec<Trivia>], top_level: bool) {
+        let mut previous: Option<Rule> = None;
+        for (statement, gap) in statements.iter().zip(gaps.iter()) {
+            let rule = statement.as_rule();
+            let separate = top_level && previous.is_some_and(|previous| separated(previous, rule));
+            if self.gap(gap, previous.is_some() || !top_level, separate) {
+                self.blank();
+            }
+            self.statement(statement.clone());
+            previous = Some(rule);
+        }
+        let last = gaps
+            .last()
+            .expect("there is a gap after the last statement");
+        self.gap(last, previous.is_some() || !top_level, false);
+    }
+
+    /// Writes `head [options];`, breaking the options over several lines when too long
+    fn options_line(&mut self, head: String, options: Vec<String>) {
+        if options.is_empty() {
+            return self.line(&format!("{};", head));
+        }
+        let single = format!("{} [{}];", head, options.join(", "));
+        if INDENT.len() * self.depth + single.len() <= MAX_WIDTH {
+            return self.line(&single);
+        }
+        self.line(&format!("{} [", head));
+        self.depth += 1;
+        let count = options.len();
+        for (index, option) in options.into_iter().enumerate() {
+            match index + 1 < count {
+                true => self.line(&format!("{},", option)),
+                false => self.line(&option),
+            }
+        }
+        self.depth -= 1;
+        self.line("];");
+    }
+
+    fn statement(&mut self, statement: Pair<'i, Rule>) {
+        let rule = statement.as_rule();
+        if matches!(rule, Rule::message_definition | Rule::enum_definition) {
+            return self.block(statement);
+        }
+        for comment in inner_comments(&statement) {
+            self.comment(comment);
+        }
+        let mut head = Vec::new();
+        let mut options = Vec::new();
+        for pair in statement.into_inner() {
+            match pair.as_rule() {
+                Rule::options | Rule::legacy_options => {
+                    options.extend(pair.into_inner().map(option_text))
+                }
+                Rule::option_name => head.push(option_name_text(pair)),
+                Rule::constant => head.push(format!("= {}", constant_text(pair))),
+                Rule::range => head.push(range_text(pair)),
+                _ => head.push(pair.as_str().to_string()),
+            }
+        }
+        let head = match rule {
+            Rule::version_decl => format!("syntax = {}", head.join(" ")),
+            Rule::package_statement => format!("package {}", head.join(" ")),
+            Rule::import_statement => format!("import {}", head.join(" ")),
+            Rule::option_statement => format!("option {}", head.join(" ")),
+            Rule::reserved_statement => format!("reserved {}", head.join(", ")),
+            Rule::extensions_statement => format!("extensions {}", head.join(", ")),
+            Rule::message_field => {
+                let number = head.pop().unwrap_or_default();
+                format!("{} = {}", head.join(" "), number)
+            }
+            _ => head.join(" = "),
+        };
+        self.options_line(head, options);
+    }
+
+    /// Writes a message or enum, statements inside the braces are indented
+    fn block(&mut self, block: Pair<'i, Rule>) {
+        let keyword = match block.as_rule() {
+            Rule::message_definition => "message",
+            _ => "enum",
+        };
+        let (start, end) = (block.as_span().start(), block.as_span().end());
+        let mut inner = block.into_inner();
+        let identifier = inner.next().expect("blocks have a name");
+        let statements: Vec<_> = inner.collect();
+        let after_identifier = identifier.as_span().end();
+        let first = statements
+            .first()
+            .map_or(end, |first| first.as_span().start());
+        let open = after_identifier
+            + find_other(&self.input[after_identifier..first], '{', false).unwrap_or(0);
+        let last = statements.last().map_or(open + 1, end_of);
+        let close = last + find_other(&self.input[last..end], '}', true).unwrap_or(0);
+
+        let header = [
+            &self.input[start..identifier.as_span().start()],
+            &self.input[after_identifier..open],
+        ];
+        for comment in header.into_iter().flat_map(comments_in) {
+            self.comment(comment);
+        }
+        let mut gaps = Vec::new();
+        let mut gap_start = open + 1;
+        for statement in statements.iter() {
+            gaps.push(trivia(&self.input[gap_start..statement.as_span().start()]));
+            gap_start = end_of(statement);
+        }
+        gaps.push(trivia(&self.input[gap_start..close]));
+
+        let empty = gaps
+            .iter()
+            .flatten()
+            .all(|trivia| !matches!(trivia, Trivia::Comment(_)));
+        if statements.is_empty() && empty {
+            return self.line(&format!("{} {} {{}}", keyword, identifier.as_str()));
+        }
+        self.line(&format!("{} {} {{", keyword, identifier.as_str()));
+        self.block_start = true;
+        self.depth += 1;
+        self.statements(&statements, &gaps, false);
+        self.depth -= 1;
+        self.line("}");
+    }
+}
+
+/// Formats the contents of a .proto file. Only the syntax is checked, the
+/// schema does not need to be valid
+pub fn format(input: &str) -> Result<String, ParserError> {
+    let definition = PicoPBParser::parse(Rule::proto_definition, input)?
+        .next()
+        .expect("the parser returns the definition");
+    let mut statements = Vec::new();
+    let mut eoi = input.len();
+    for pair in definition.into_inner() {
+        match pair.as_rule() {
+            Rule::statement => {
+                let mut statement = pair.into_inner().next().expect("statements have a kind");
+                if statement.as_rule() == Rule::block_statement {
+                    statement = statement.into_inner().next().expect("blocks have a kind");
+                }
+                if statement.as_rule() != Rule::empty_statement {
+                    statements.push(statement);
+                }
+            }
+            Rule::EOI => eoi = pair.as_span().start(),
+            _ => statements.push(pair),
+        }
+    }
+    let mut gaps = Vec::new();
+    let mut gap_start = 0;
+    for statement in statements.iter() {
+        gaps.push(trivia(&input[gap_start..statement.as_span().start()]));
+        gap_start = end_of(statement);
+    }
+    gaps.push(trivia(&input[gap_start..eoi]));
+
+    let mut formatter = Formatter {
+        input,
+        output: String::new(),
+        depth: 0,
+        block_start: false,
+    };
+    formatter.statements(&statements, &gaps, true);
+    Ok(formatter.output)
+}
@@ -1,7 +1,10 @@
-use crate::common::{EnumType, FieldQualifier, FieldType, MessageField, MessageType};
+use crate::common::{
+    EnumType, FieldQualifier, FieldType, MessageField, MessageType, UnknownFieldStorage,
+};
 use crate::parser::ProtoParser;
+use crate::size::{max_encoded_lens_with, MaxEncodedLen};
 use convert_case::{Case, Casing};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::io::Write;
 
 /// This module contains the code generator
@@ -23,27 +26,78 @@ impl From<std::io::Error> for GeneratorError {
 
 pub type Result<T> = std::result::Result<T, GeneratorError>;
 
+/// Options changing the generated code
+#[derive(Debug, Default, Clone, Copy)]
+pub struct GeneratorConfig {
+    /// Adds an `unknown_fields` member to every message, the decoder keeps
+    /// unrecognized fields in it and they are encoded again after the known fields
+    pub unknown_fields: UnknownFieldStorage,
+    /// Implements `picopb::json::ToJson` and `FromJson` for messages and enums
+    pub json: bool,
+    /// Refers to `core` and `alloc` instead of `std`, the crate including the
+    /// generated code declares `extern crate alloc;`. Only the paths of the
+    /// generated code change, the picopb runtime still needs `std`
+    pub no_std: bool,
+}
+
+impl GeneratorConfig {
+    /// `core` or `std`, for the paths the generated code refers to
+    fn core(&self) -> &'static str {
+        match self.no_std {
+            true => "core",
+            false => "std",
+        }
+    }
+
+    /// the result type of `EncodeSink` methods
+    fn sink_result(&self) -> &'static str {
+        match self.no_std {
+            true => "picopb::encode::SinkResult",
+            false => "std::io::Result",
+        }
+    }
+
+    /// the Rust type of the `unknown_fields` member, if messages have one
+    fn unknown_fields_type(&self) -> Option<String> {
+        match self.unknown_fields {
+            UnknownFieldStorage::None => None,
+            UnknownFieldStorage::Unbounded => Some("Vec<u8>".to_owned()),
+            UnknownFieldStorage::Bounded(capacity) => Some(format!(
+                "picopb::common::BoundedUnknownFields<{capacity}>"
+            )),
+        }
+    }
+
+    fn has_unknown_fields(&self) -> bool {
+        self.unknown_fields != UnknownFieldStorage::None
+    }
+}
+
 fn field_to_rust_type(qualifier: &FieldQualifier, field_type: &FieldType) -> String {
     match (qualifier, field_type) {
         (FieldQualifier::Required, field_type) => match field_type {
             FieldType::Bool => "bool".to_owned(),
             FieldType::UnboundedString => "String".to_owned(),
-            FieldType::String(limit) => format!("ArrayString<{limit}>"),
+            FieldType::String(limit) => format!(
+                "picopb::arraystring::ArrayString<picopb::arraystring::typenum::U{limit}>"
+            ),
             FieldType::Bytes(limit) => format!("[u8; {}]", limit),
-            FieldType::UnboundedBytes => format!("bytes::Bytes"),
+            FieldType::UnboundedBytes => "bytes::Bytes".to_owned(),
             FieldType::Int32 => "i32".to_owned(),
             FieldType::Int64 => "i64".to_owned(),
             FieldType::Uint64 => "u64".to_owned(),
             FieldType::Uint32 => "u32".to_owned(),
-            FieldType::MessageType(s) => format!("{s}"),
-            FieldType::EnumType(s) => format!("{s}"),
+            FieldType::MessageType(s) => s.to_string(),
+            FieldType::EnumType(s) => s.to_string(),
         },
         (FieldQualifier::Optional, field_type) => match field_type {
             FieldType::Bool => "Option<bool>".to_owned(),
             FieldType::UnboundedString => "Option<String>".to_owned(),
-            FieldType::String(limit) => format!("Option<ArrayString<{limit}>>"),
+            FieldType::String(limit) => format!(
+                "Option<picopb::arraystring::ArrayString<picopb::arraystring::typenum::U{limit}>>"
+            ),
             FieldType::Bytes(limit) => format!("Option<[u8; {}]>", limit),
-            FieldType::UnboundedBytes => format!("Option<bytes::Bytes>"),
+            FieldType::UnboundedBytes => "Option<bytes::Bytes>".to_owned(),
             FieldType::Int32 => "Option<i32>".to_owned(),
             FieldType::Int64 => "Option<i64>".to_owned(),
             FieldType::Uint64 => "Option<u64>".to_owned(),
@@ -59,7 +113,7 @@ fn field_to_rust_type(qualifier: &FieldQualifier, field_type: &FieldType) -> Str
         }
         (FieldQualifier::Repeated(limit), field_type) => {
             format!(
-                "[{}; {}]",
+                "picopb::arrayvec::ArrayVec<{}, {}>",
                 field_to_rust_type(&FieldQualifier::Required, field_type),
                 limit
             )
@@ -73,7 +127,7 @@ fn field_to_rust_type(qualifier: &FieldQualifier, field_type: &FieldType) -> Str
         }
         (FieldQualifier::PackedRepeated(limit), field_type) => {
             format!(
-                "picopb::common::Packed<[{}; {}]>",
+                "picopb::common::Packed<picopb::arrayvec::ArrayVec<{}, {}>>",
                 field_to_rust_type(&FieldQualifier::Required, field_type),
                 limit
             )
@@ -91,14 +145,19 @@ fn identifier_to_const_case(identifier: &str) -> Result<String> {
 
 fn generate_enum_from_trait<T: Write>(to: &mut T, enum_type: &EnumType) -> Result<()> {
     // TryFrom
-    writeln!(to, "impl TryFrom<usize> for {} {{", enum_type.identifier)?;
+    writeln!(to, "impl TryFrom<i32> for {} {{", enum_type.identifier)?;
     writeln!(to, "    type Error = String;")?;
     writeln!(
         to,
-        "    fn try_from(value: usize) -> Result<Self, Self::Error> {{"
+        "    fn try_from(value: i32) -> Result<Self, Self::Error> {{"
     )?;
     writeln!(to, "        match value {{")?;
-    for (identifier, ordinal) in enum_type.pairs.iter() {
+    // aliased values share an ordinal, only the first one can be decoded to
+    let mut seen_ordinals = HashSet::new();
+    for (identifier, ordinal) in enum_type.values() {
+        if !seen_ordinals.insert(ordinal) {
+            continue;
+        }
         writeln!(
             to,
             "            {} => Ok({}::{}),",
@@ -116,11 +175,11 @@ fn generate_enum_from_trait<T: Write>(to: &mut T, enum_type: &EnumType) -> Resul
     writeln!(to, "    }}")?;
     writeln!(to, "}}")?;
 
-    // TryTo
-    writeln!(to, "impl Into<usize> for {} {{", enum_type.identifier)?;
-    writeln!(to, "    fn into(self) -> usize {{")?;
-    writeln!(to, "        match self {{")?;
-    for (identifier, ordinal) in enum_type.pairs.iter() {
+    // From
+    writeln!(to, "impl From<{}> for i32 {{", enum_type.identifier)?;
+    writeln!(to, "    fn from(value: {}) -> Self {{", enum_type.identifier)?;
+    writeln!(to, "        match value {{")?;
+    for (identifier, ordinal) in enum_type.values() {
         writeln!(
             to,
             "            {}::{} => {},",
@@ -135,12 +194,251 @@ fn generate_enum_from_trait<T: Write>(to: &mut T, enum_type: &EnumType) -> Resul
     Ok(())
 }
 
-fn generate_enums<T: Write>(to: &mut T, enums: &HashMap<String, EnumType>) -> Result<()> {
-    for (_, enum_type) in enums.iter() {
-        writeln!(to, "#[derive(Default, Debug)]")?;
+/// Generate the encoder and decoder for the enum, enums are encoded as varints
+fn generate_enum_wire<T: Write>(
+    to: &mut T,
+    enum_type: &EnumType,
+    config: &GeneratorConfig,
+) -> Result<()> {
+    let identifier = &enum_type.identifier;
+    writeln!(to, "impl picopb::wiretypes::WireTyped for {identifier} {{")?;
+    writeln!(to, "    fn wiretype(&self) -> WireType {{")?;
+    writeln!(to, "        WireType::VarInt")?;
+    writeln!(to, "    }}")?;
+    writeln!(to, "}}")?;
+
+    writeln!(to, "impl picopb::encode::ToWire for {identifier} {{")?;
+    writeln!(
+        to,
+        "    fn append<W: picopb::encode::EncodeSink>(&self, buf: &mut W) -> {}<usize> {{",
+        config.sink_result()
+    )?;
+    writeln!(to, "        i32::from(*self).append(buf)")?;
+    writeln!(to, "    }}")?;
+    writeln!(to, "    fn precalculate_size(&self) -> usize {{")?;
+    writeln!(to, "        i32::from(*self).precalculate_size()")?;
+    writeln!(to, "    }}")?;
+    writeln!(to, "}}")?;
+
+    writeln!(to, "impl picopb::decode::FromWire for {identifier} {{")?;
+    writeln!(to, "    fn wire_type() -> WireType {{")?;
+    writeln!(to, "        WireType::VarInt")?;
+    writeln!(to, "    }}")?;
+    writeln!(
+        to,
+        "    fn read(buf: &mut picopb::decode::DecodeBuffer<'_>) -> picopb::decode::Result<Self> {{"
+    )?;
+    // enums are encoded as int32, truncate the varint the same way
+    writeln!(to, "        let value = buf.read_varint()? as i64;")?;
+    writeln!(
+        to,
+        "        Self::try_from(value as i32).map_err(|_| picopb::decode::DecodeError::InvalidEnumValue(value))"
+    )?;
+    writeln!(to, "    }}")?;
+    writeln!(to, "}}")?;
+    Ok(())
+}
+
+/// Returns the values of the map sorted by key, making the generated output stable
+fn sorted_values<V>(map: &HashMap<String, V>) -> Vec<&V> {
+    let mut keys = map.keys().collect::<Vec<_>>();
+    keys.sort();
+    keys.into_iter().map(|key| &map[key]).collect()
+}
+
+/// Generate the JSON mapping for the enum, values are written as their names
+/// and read from either their name or their number
+fn generate_enum_json<T: Write>(
+    to: &mut T,
+    enum_type: &EnumType,
+    config: &GeneratorConfig,
+) -> Result<()> {
+    let identifier = &enum_type.identifier;
+    writeln!(to, "impl picopb::json::ToJson for {identifier} {{")?;
+    writeln!(
+        to,
+        "    fn write_json<W: {0}::fmt::Write>(&self, out: &mut W) -> {0}::fmt::Result {{",
+        config.core()
+    )?;
+    writeln!(to, "        let name = match self {{")?;
+    for (name, _) in enum_type.values() {
+        writeln!(
+            to,
+            "            {identifier}::{} => \"{name}\",",
+            enum_id_to_pascal(name)?
+        )?;
+    }
+    writeln!(to, "        }};")?;
+    writeln!(to, "        picopb::json::write_string(out, name)")?;
+    writeln!(to, "    }}")?;
+    writeln!(to, "}}")?;
+
+    writeln!(to, "impl picopb::json::FromJson for {identifier} {{")?;
+    writeln!(
+        to,
+        "    fn from_json(json: &picopb::json::JsonValue) -> picopb::json::Result<Self> {{"
+    )?;
+    writeln!(to, "        match json {{")?;
+    writeln!(
+        to,
+        "            picopb::json::JsonValue::String(name) => match name.as_str() {{"
+    )?;
+    for (name, _) in enum_type.values() {
+        writeln!(
+            to,
+            "                \"{name}\" => Ok(Self::{}),",
+            enum_id_to_pascal(name)?
+        )?;
+    }
+    writeln!(
+        to,
+        "                _ => Err(picopb::json::JsonError::InvalidEnumValue(name.clone())),"
+    )?;
+    writeln!(to, "            }},")?;
+    writeln!(to, "            number => {{")?;
+    writeln!(
+        to,
+        "                let value: i32 = picopb::json::FromJson::from_json(number)?;"
+    )?;
+    writeln!(
+        to,
+        "                Self::try_from(value).map_err(|_| picopb::json::JsonError::InvalidEnumValue(value.to_string()))"
+    )?;
+    writeln!(to, "            }}")?;
+    writeln!(to, "        }}")?;
+    writeln!(to, "    }}")?;
+    writeln!(to, "}}")?;
+    Ok(())
+}
+
+/// Generate the text format for the enum, values are written as their names
+/// and read from either their name or their number
+fn generate_enum_text_format<T: Write>(
+    to: &mut T,
+    enum_type: &EnumType,
+    config: &GeneratorConfig,
+) -> Result<()> {
+    let identifier = &enum_type.identifier;
+    writeln!(to, "impl picopb::text_format::ToText for {identifier} {{")?;
+    writeln!(
+        to,
+        "    fn write_text<W: {0}::fmt::Write>(&self, out: &mut W) -> {0}::fmt::Result {{",
+        config.core()
+    )?;
+    writeln!(to, "        let name = match self {{")?;
+    for (name, _) in enum_type.values() {
+        writeln!(
+            to,
+            "            {identifier}::{} => \"{name}\",",
+            enum_id_to_pascal(name)?
+        )?;
+    }
+    writeln!(to, "        }};")?;
+    writeln!(to, "        out.write_str(name)")?;
+    writeln!(to, "    }}")?;
+    writeln!(to, "}}")?;
+
+    writeln!(to, "impl picopb::text_format::FromText for {identifier} {{")?;
+    writeln!(
+        to,
+        "    fn from_text(text: &picopb::text_format::TextValue) -> picopb::text_format::Result<Self> {{"
+    )?;
+    writeln!(to, "        match text.as_literal()? {{")?;
+    for (name, _) in enum_type.values() {
+        writeln!(
+            to,
+            "            \"{name}\" => Ok(Self::{}),",
+            enum_id_to_pascal(name)?
+        )?;
+    }
+    writeln!(to, "            literal => {{")?;
+    writeln!(
+        to,
+        "                let invalid = || picopb::text_format::TextFormatError::InvalidEnumValue(literal.to_string());"
+    )?;
+    writeln!(
+        to,
+        "                let value: i32 = picopb::text_format::FromText::from_text(text).map_err(|_| invalid())?;"
+    )?;
+    writeln!(
+        to,
+        "                Self::try_from(value).map_err(|_| invalid())"
+    )?;
+    writeln!(to, "            }}")?;
+    writeln!(to, "        }}")?;
+    writeln!(to, "    }}")?;
+    writeln!(to, "}}")?;
+    Ok(())
+}
+
+/// Generate implementation of the Randomize trait for the enum, which picks
+/// one of the declared values
+fn generate_enum_randomize<T: Write>(to: &mut T, enum_type: &EnumType) -> Result<()> {
+    let identifier = &enum_type.identifier;
+    let mut values = Vec::new();
+    for (value, _) in enum_type.values() {
+        values.push(format!("{identifier}::{}", enum_id_to_pascal(value)?));
+    }
+    writeln!(to, "impl Randomize for {identifier} {{")?;
+    writeln!(
+        to,
+        "    fn randomized<R: picopb::randomizer::Rng + ?Sized>(rng: &mut R) -> Self {{"
+    )?;
+    writeln!(
+        to,
+        "        const VALUES: [{identifier}; {}] = [{}];",
+        values.len(),
+        values.join(", ")
+    )?;
+    writeln!(to, "        VALUES[rng.gen_range(0..VALUES.len())]")?;
+    writeln!(to, "    }}")?;
+    writeln!(to, "}}")?;
+    Ok(())
+}
+
+fn generate_enum_reflect<T: Write>(to: &mut T, enum_type: &EnumType) -> Result<()> {
+    let identifier = &enum_type.identifier;
+    writeln!(to, "impl picopb::reflect::ReflectField for {identifier} {{")?;
+    writeln!(
+        to,
+        "    fn to_value(&self) -> picopb::reflect::FieldValue<'_> {{"
+    )?;
+    writeln!(
+        to,
+        "        picopb::reflect::FieldValue::Enum(i32::from(*self))"
+    )?;
+    writeln!(to, "    }}")?;
+    writeln!(
+        to,
+        "    fn from_value(value: &picopb::reflect::FieldValue) -> picopb::reflect::Result<Self> {{"
+    )?;
+    writeln!(to, "        match value {{")?;
+    writeln!(
+        to,
+        "            picopb::reflect::FieldValue::Enum(value) => Self::try_from(*value).map_err(|_| picopb::reflect::ReflectError::InvalidEnumValue(*value)),"
+    )?;
+    writeln!(
+        to,
+        "            _ => Err(picopb::reflect::ReflectError::UnexpectedValue(\"enum\")),"
+    )?;
+    writeln!(to, "        }}")?;
+    writeln!(to, "    }}")?;
+    writeln!(to, "}}")?;
+    Ok(())
+}
+
+fn generate_enums<T: Write>(
+    to: &mut T,
+    enums: &HashMap<String, EnumType>,
+    config: &GeneratorConfig,
+) -> Result<()> {
+    for enum_type in sorted_values(enums) {
+        // proto enum values are conventionally prefixed with the enum name
+        writeln!(to, "#[allow(clippy::enum_variant_names)]")?;
+        writeln!(to, "#[derive(Default, Debug, Clone, Copy, PartialEq)]")?;
         writeln!(to, "pub enum {} {{", enum_type.identifier)?;
         let mut first = true;
-        for (identifier, _) in enum_type.pairs.iter() {
+        for (identifier, _) in enum_type.values() {
             if first {
                 writeln!(to, "    #[default]")?;
                 first = false;
@@ -150,9 +448,13 @@ fn generate_enums<T: Write>(to: &mut T, enums: &HashMap<String, EnumType>) -> Re
         writeln!(to, "}}")?;
 
         generate_enum_from_trait(to, enum_type)?;
-
-        // TODO: impl decoder
-        // TODO: impl encoder
+        generate_enum_wire(to, enum_type, config)?;
+        generate_enum_text_format(to, enum_type, config)?;
+        generate_enum_reflect(to, enum_type)?;
+        generate_enum_randomize(to, enum_type)?;
+        if config.json {
+            generate_enum_json(to, enum_type, config)?;
+        }
     }
     Ok(())
 }
@@ -188,6 +490,7 @@ fn generate_message_metadata<T: Write>(to: &mut T, message_type: &MessageType) -
         writeln!(to, "        qualifier: {},", field.qualifier.repr())?;
         writeln!(to, "        field_type: {},", field.field_type.repr())?;
         writeln!(to, "        identifier: \"{}\",", field.identifier)?;
+        writeln!(to, "        json_name: {:?},", field.json_name())?;
         writeln!(
             to,
             "        ordinal: picopb::common::Field({}),",
@@ -201,7 +504,7 @@ fn generate_message_metadata<T: Write>(to: &mut T, message_type: &MessageType) -
     writeln!(to, "impl {} {{", message_type.identifier)?;
     writeln!(
         to,
-        "    fn fields(&self) -> {}FieldsType {{",
+        "    fn fields(&self) -> {}FieldsType<'static> {{",
         message_type.identifier
     )?;
     writeln!(
@@ -215,34 +518,67 @@ fn generate_message_metadata<T: Write>(to: &mut T, message_type: &MessageType) -
     Ok(())
 }
 
-fn as_encodable_type(field: &MessageField, prefix: &str) -> String {
-    let identifier = &field.identifier;
-
-    let wrapped = match field.qualifier {
-        FieldQualifier::Repeated(_) | FieldQualifier::PackedRepeated(_) => {
-            return format!("{prefix}{identifier}")
+/// Returns an expression for the value of the field that implements ToWire,
+/// `value` is either a place (self.field) or a reference to the value
+fn as_encodable_type(field: &MessageField, value: &str, is_ref: bool) -> String {
+    match field.qualifier {
+        FieldQualifier::PackedRepeated(_) | FieldQualifier::PackedRepeatedUnbounded => {
+            format!("picopb::common::Packed({value}.0.as_slice())")
         }
-        FieldQualifier::PackedRepeatedUnbounded | FieldQualifier::RepeatedUnbounded => {
-            return format!("{prefix}{identifier}.as_slice()")
-        }
-        _ => format!("{prefix}{identifier}"),
-    };
+        _ => as_encodable_element(&field.field_type, value, is_ref),
+    }
+}
 
-    match field.field_type {
-        FieldType::UnboundedString => format!("{wrapped}.as_str()"),
-        FieldType::UnboundedBytes => format!("{wrapped}.deref()"),
-        FieldType::String(_) => format!("{wrapped}.as_slice()"),
-        FieldType::Bytes(_) => format!("{wrapped}.as_bytes()"),
-        FieldType::EnumType(_) => todo!(),
-        FieldType::MessageType(_) => format!("(&{wrapped})"),
-        FieldType::Bool
+/// Returns the expression to encode a single value of the field type,
+/// is_ref is set when the value is a reference
+fn as_encodable_element(field_type: &FieldType, value: &str, is_ref: bool) -> String {
+    match field_type {
+        FieldType::UnboundedString => format!("{value}.as_str()"),
+        FieldType::UnboundedBytes => format!("{value}.deref()"),
+        FieldType::String(_) => format!("{value}.as_str()"),
+        FieldType::Bytes(_) => format!("{value}.as_slice()"),
+        FieldType::MessageType(_) if is_ref => value.to_string(),
+        FieldType::MessageType(_) => format!("&{value}"),
+        FieldType::EnumType(_)
+        | FieldType::Bool
         | FieldType::Int32
         | FieldType::Int64
         | FieldType::Uint32
-        | FieldType::Uint64 => format!("{wrapped}"),
+        | FieldType::Uint64 => {
+            if is_ref {
+                format!("*{value}")
+            } else {
+                value.to_string()
+            }
+        }
+    }
+}
+
+/// Wraps the expression in parentheses if it is needed to call a method on it
+fn as_receiver(expression: String) -> String {
+    if expression.starts_with('*') || expression.starts_with('&') {
+        format!("({expression})")
+    } else {
+        expression
     }
 }
 
+/// Returns true if the values of the field are encoded as sub messages
+fn is_message_type(field: &MessageField) -> bool {
+    matches!(field.field_type, FieldType::MessageType(_))
+}
+
+/// Returns true if the field is a single (not repeated) sub message
+fn is_sub_message(field: &MessageField) -> bool {
+    matches!(
+        (&field.qualifier, &field.field_type),
+        (
+            FieldQualifier::Required | FieldQualifier::Optional,
+            FieldType::MessageType(_)
+        )
+    )
+}
+
 fn generate_message_wiretyped<T: Write>(to: &mut T, message_type: &MessageType) -> Result<()> {
     writeln!(
         to,
@@ -256,7 +592,11 @@ fn generate_message_wiretyped<T: Write>(to: &mut T, message_type: &MessageType)
     Ok(())
 }
 
-fn generate_message_to_wire<T: Write>(to: &mut T, message_type: &MessageType) -> Result<()> {
+fn generate_message_to_wire<T: Write>(
+    to: &mut T,
+    message_type: &MessageType,
+    config: &GeneratorConfig,
+) -> Result<()> {
     writeln!(
         to,
         "impl picopb::encode::ToWire for &{} {{",
@@ -264,61 +604,137 @@ fn generate_message_to_wire<T: Write>(to: &mut T, message_type: &MessageType) ->
     )?;
     writeln!(
         to,
-        "    fn append(&self, buf: &mut picopb::encode::EncodeBuffer) -> std::io::Result<usize> {{"
+        "    fn append<W: picopb::encode::EncodeSink>(&self, buf: &mut W) -> {}<usize> {{",
+        config.sink_result()
     )?;
     writeln!(to, "        let mut total_size = 0;")?;
     for (_, field) in message_type.fields.iter() {
         let identifier = &field.identifier;
+        let encode = match is_sub_message(field) {
+            true => "encode_sub_message",
+            false => "encode",
+        };
 
         // We don't want to encode empty optional values
         match field.qualifier {
             FieldQualifier::Optional => {
-                let value_encodable_type = as_encodable_type(field, "value_");
+                let value_encodable_type =
+                    as_encodable_type(field, &format!("value_{identifier}"), true);
                 writeln!(
                     to,
                     "        if let Some(value_{identifier}) = &self.{identifier} {{"
                 )?;
-                writeln!(to, "            total_size += buf.encode({value_encodable_type}, self.fields().{identifier}.ordinal)?;")?;
+                writeln!(to, "            total_size += buf.{encode}({value_encodable_type}, self.fields().{identifier}.ordinal)?;")?;
+                writeln!(to, "        }}")?;
+            }
+            // every value of an unpacked field is written with its own tag
+            FieldQualifier::Repeated(_) | FieldQualifier::RepeatedUnbounded => {
+                let encode = match is_message_type(field) {
+                    true => "encode_sub_message",
+                    false => "encode",
+                };
+                let value_encodable_type =
+                    as_encodable_element(&field.field_type, &format!("value_{identifier}"), true);
+                writeln!(
+                    to,
+                    "        for value_{identifier} in self.{identifier}.iter() {{"
+                )?;
+                writeln!(to, "            total_size += buf.{encode}({value_encodable_type}, self.fields().{identifier}.ordinal)?;")?;
                 writeln!(to, "        }}")?;
             }
-            _ => {
-                let self_encodable_type = as_encodable_type(field, "self.");
-                writeln!(to, "        total_size += buf.encode({self_encodable_type}, self.fields().{identifier}.ordinal)?;")?;
+            // packed fields are a single length delimited record, omitted when empty
+            FieldQualifier::PackedRepeated(_) | FieldQualifier::PackedRepeatedUnbounded => {
+                let self_encodable_type =
+                    as_encodable_type(field, &format!("self.{identifier}"), false);
+                writeln!(to, "        if !self.{identifier}.0.is_empty() {{")?;
+                writeln!(to, "            total_size += buf.encode({self_encodable_type}, self.fields().{identifier}.ordinal)?;")?;
+                writeln!(to, "        }}")?;
+            }
+            FieldQualifier::Required => {
+                let self_encodable_type =
+                    as_encodable_type(field, &format!("self.{identifier}"), false);
+                writeln!(to, "        total_size += buf.{encode}({self_encodable_type}, self.fields().{identifier}.ordinal)?;")?;
             }
         }
     }
+    // unknown fields are written back after the known fields
+    if config.has_unknown_fields() {
+        writeln!(
+            to,
+            "        total_size += buf.write_bytes(self.unknown_fields.as_bytes())?;"
+        )?;
+    }
     writeln!(to, "        Ok(total_size)")?;
     writeln!(to, "    }}")?;
 
     writeln!(to, "    fn precalculate_size(&self) -> usize {{")?;
     writeln!(to, "        let mut total_size = 0;")?;
     for (_, field) in message_type.fields.iter() {
-        let encodable_type = as_encodable_type(field, "");
-        let self_encodable_type = as_encodable_type(field, "self.");
         let identifier = &field.identifier;
+        let precalculate = match is_sub_message(field) {
+            true => "precalculate_sub_message_size",
+            false => "precalculate_field_size",
+        };
 
         // We don't want to encode empty optional values
         // and therefore should not count them towards the size
         match field.qualifier {
             FieldQualifier::Optional => {
+                let value_encodable_type =
+                    as_receiver(as_encodable_type(field, &format!("value_{identifier}"), true));
                 writeln!(
                     to,
                     "        if let Some(value_{identifier}) = &self.{identifier} {{"
                 )?;
                 writeln!(
                     to,
-                    "            total_size += value_{encodable_type}.precalculate_size();"
+                    "            total_size += {value_encodable_type}.{precalculate}(self.fields().{identifier}.ordinal);"
+                )?;
+                writeln!(to, "        }}")?;
+            }
+            FieldQualifier::Repeated(_) | FieldQualifier::RepeatedUnbounded => {
+                let precalculate = match is_message_type(field) {
+                    true => "precalculate_sub_message_size",
+                    false => "precalculate_field_size",
+                };
+                let value_encodable_type = as_receiver(as_encodable_element(
+                    &field.field_type,
+                    &format!("value_{identifier}"),
+                    true,
+                ));
+                writeln!(
+                    to,
+                    "        for value_{identifier} in self.{identifier}.iter() {{"
+                )?;
+                writeln!(
+                    to,
+                    "            total_size += {value_encodable_type}.{precalculate}(self.fields().{identifier}.ordinal);"
+                )?;
+                writeln!(to, "        }}")?;
+            }
+            FieldQualifier::PackedRepeated(_) | FieldQualifier::PackedRepeatedUnbounded => {
+                let self_encodable_type =
+                    as_encodable_type(field, &format!("self.{identifier}"), false);
+                writeln!(to, "        if !self.{identifier}.0.is_empty() {{")?;
+                writeln!(
+                    to,
+                    "            total_size += {self_encodable_type}.{precalculate}(self.fields().{identifier}.ordinal);"
                 )?;
                 writeln!(to, "        }}")?;
             }
-            _ => {
+            FieldQualifier::Required => {
+                let self_encodable_type =
+                    as_receiver(as_encodable_type(field, &format!("self.{identifier}"), false));
                 writeln!(
                     to,
-                    "        total_size += {self_encodable_type}.precalculate_size();"
+                    "        total_size += {self_encodable_type}.{precalculate}(self.fields().{identifier}.ordinal);"
                 )?;
             }
         }
     }
+    if config.has_unknown_fields() {
+        writeln!(to, "        total_size += self.unknown_fields.as_bytes().len();")?;
+    }
     writeln!(to, "        total_size")?;
     writeln!(to, "    }}")?;
 
@@ -327,21 +743,22 @@ fn generate_message_to_wire<T: Write>(to: &mut T, message_type: &MessageType) ->
 }
 
 /// Generate implementation of the Randomize trait for the message
-fn generate_message_impl_randomize<T: Write>(to: &mut T, message_type: &MessageType) -> Result<()> {
+fn generate_message_impl_randomize<T: Write>(
+    to: &mut T,
+    message_type: &MessageType,
+    config: &GeneratorConfig,
+) -> Result<()> {
+    writeln!(to, "impl Randomize for {} {{", message_type.identifier)?;
     writeln!(
         to,
-        "impl Randomize<{0}> for {0} {{",
-        message_type.identifier
+        "    fn randomized<R: picopb::randomizer::Rng + ?Sized>(rng: &mut R) -> Self {{"
     )?;
-    writeln!(to, "    fn randomized() -> {} {{", message_type.identifier)?;
     writeln!(to, "        Self {{")?;
     for (_, field) in message_type.fields.iter() {
-        let rust_type = field_to_rust_type(&field.qualifier, &field.field_type);
-        writeln!(
-            to,
-            "            {}: randomized::<{rust_type}>(),",
-            field.identifier
-        )?;
+        writeln!(to, "            {}: randomized(rng),", field.identifier)?;
+    }
+    if config.has_unknown_fields() {
+        writeln!(to, "            unknown_fields: Default::default(),")?;
     }
     writeln!(to, "        }}")?;
     writeln!(to, "    }}")?;
@@ -349,43 +766,774 @@ fn generate_message_impl_randomize<T: Write>(to: &mut T, message_type: &MessageT
     Ok(())
 }
 
-fn generate_messages<T: Write>(
+/// Returns true if Default can be derived for the message
+fn is_default_derivable(message_type: &MessageType) -> bool {
+    message_type
+        .fields
+        .values()
+        .all(|field| match (&field.qualifier, &field.field_type) {
+            (FieldQualifier::Required, FieldType::Bytes(_)) => false,
+            (FieldQualifier::Required, _) => true,
+            (
+                FieldQualifier::Optional
+                | FieldQualifier::RepeatedUnbounded
+                | FieldQualifier::Repeated(_),
+                _,
+            ) => true,
+            _ => false,
+        })
+}
+
+/// Generate the Default implementation, fixed size bytes are created with
+/// `[0; N]` as `Default` is only implemented for arrays up to 32 elements
+fn generate_message_default<T: Write>(
     to: &mut T,
-    message_types: &HashMap<String, MessageType>,
+    message_type: &MessageType,
+    config: &GeneratorConfig,
 ) -> Result<()> {
-    for (_, message_type) in message_types.iter() {
-        writeln!(to, "#[derive(Default, Debug)]")?;
-        writeln!(to, "pub struct {} {{", message_type.identifier)?;
-        for (_, field) in message_type.fields.iter() {
-            writeln!(
-                to,
-                "    pub {}: {},",
-                field.identifier,
-                field_to_rust_type(&field.qualifier, &field.field_type)
-            )?;
-        }
-        writeln!(to, "}}")?;
-        generate_message_metadata(to, message_type)?;
-        // TODO: impl decoder
+    if is_default_derivable(message_type) {
+        return Ok(());
+    }
 
-        generate_message_wiretyped(to, message_type)?;
-        generate_message_to_wire(to, message_type)?;
-        generate_message_impl_randomize(to, message_type)?;
+    writeln!(to, "impl Default for {} {{", message_type.identifier)?;
+    writeln!(to, "    fn default() -> Self {{")?;
+    writeln!(to, "        Self {{")?;
+    for (_, field) in message_type.fields.iter() {
+        // fixed size bytes are arrays as well
+        let element = match field.field_type {
+            FieldType::Bytes(limit) => format!("[0; {limit}]"),
+            _ => "Default::default()".to_owned(),
+        };
+        let value = match field.qualifier {
+            FieldQualifier::Required => element,
+            FieldQualifier::Optional
+            | FieldQualifier::RepeatedUnbounded
+            | FieldQualifier::Repeated(_) => "Default::default()".to_owned(),
+            FieldQualifier::PackedRepeated(_) => {
+                "picopb::common::Packed(Default::default())".to_owned()
+            }
+            FieldQualifier::PackedRepeatedUnbounded => {
+                "picopb::common::Packed(Vec::new())".to_owned()
+            }
+        };
+        writeln!(to, "            {}: {},", field.identifier, value)?;
+    }
+    if config.has_unknown_fields() {
+        writeln!(to, "            unknown_fields: Default::default(),")?;
     }
+    writeln!(to, "        }}")?;
+    writeln!(to, "    }}")?;
+    writeln!(to, "}}")?;
     Ok(())
 }
 
-fn generate_imports<T: Write>(to: &mut T) -> std::io::Result<()> {
-    writeln!(to, "use picopb::common::*;")?;
-    writeln!(to, "use picopb::encode::ToWire;")?;
-    writeln!(to, "use picopb::wiretypes::{{WireType, WireTyped}};")?;
-    writeln!(to, "use picopb::randomizer::{{randomized, Randomize}};")?;
-    writeln!(to, "use std::ops::Deref;")
-}
-
-pub fn generate<T: Write>(to: &mut T, parser: &ProtoParser) -> Result<()> {
-    generate_imports(to)?;
-    generate_enums(to, &parser.enum_types)?;
-    generate_messages(to, &parser.message_types)?;
+/// Generate the decoder for the message, the DecodeMessage trait decodes the
+/// fields of the message while FromWire decodes it as a length delimited sub message
+fn generate_message_from_wire<T: Write>(
+    to: &mut T,
+    message_type: &MessageType,
+    config: &GeneratorConfig,
+) -> Result<()> {
+    let message_identifier = &message_type.identifier;
+    writeln!(
+        to,
+        "impl picopb::decode::DecodeMessage for {message_identifier} {{"
+    )?;
+    writeln!(
+        to,
+        "    fn decode_from(buf: &mut picopb::decode::DecodeBuffer<'_>) -> picopb::decode::Result<Self> {{"
+    )?;
+    writeln!(to, "        let mut message = Self::default();")?;
+    for (_, field) in message_type.fields.iter() {
+        if let FieldQualifier::Required = field.qualifier {
+            writeln!(to, "        let mut has_{} = false;", field.identifier)?;
+        }
+    }
+    writeln!(to, "        while !buf.is_empty() {{")?;
+    writeln!(to, "            let (field, wire_type) = buf.read_tag()?;")?;
+    writeln!(to, "            match field.0 {{")?;
+    for (ordinal, field) in message_type.fields.iter() {
+        let identifier = &field.identifier;
+        let read = "picopb::decode::read_field(buf, field, wire_type)?";
+        writeln!(to, "                {ordinal} => {{")?;
+        match field.qualifier {
+            FieldQualifier::Required => {
+                writeln!(to, "                    message.{identifier} = {read};")?;
+                writeln!(to, "                    has_{identifier} = true;")?;
+            }
+            FieldQualifier::Optional => {
+                writeln!(to, "                    message.{identifier} = Some({read});")?;
+            }
+            // repeated scalars are accepted both packed and unpacked
+            FieldQualifier::RepeatedUnbounded | FieldQualifier::PackedRepeatedUnbounded => {
+                let values = match field.qualifier {
+                    FieldQualifier::PackedRepeatedUnbounded => format!("message.{identifier}.0"),
+                    _ => format!("message.{identifier}"),
+                };
+                writeln!(to, "                    picopb::decode::read_repeated(buf, field, wire_type, |value| {{")?;
+                writeln!(to, "                        {values}.push(value);")?;
+                writeln!(to, "                        Ok(())")?;
+                writeln!(to, "                    }})?;")?;
+            }
+            FieldQualifier::Repeated(_) | FieldQualifier::PackedRepeated(_) => {
+                let values = match field.qualifier {
+                    FieldQualifier::PackedRepeated(_) => format!("message.{identifier}.0"),
+                    _ => format!("message.{identifier}"),
+                };
+                writeln!(to, "                    picopb::decode::read_repeated(buf, field, wire_type, |value| {{")?;
+                writeln!(to, "                        picopb::decode::push_bounded(&mut {values}, value)")?;
+                writeln!(to, "                    }})?;")?;
+            }
+        }
+        writeln!(to, "                }}")?;
+    }
+    match config.has_unknown_fields() {
+        true => writeln!(
+            to,
+            "                _ => buf.read_unknown(field, wire_type, &mut message.unknown_fields)?,"
+        )?,
+        false => writeln!(to, "                _ => buf.skip_unknown(field, wire_type)?,")?,
+    }
+    writeln!(to, "            }}")?;
+    writeln!(to, "        }}")?;
+    for (_, field) in message_type.fields.iter() {
+        if let FieldQualifier::Required = field.qualifier {
+            let identifier = &field.identifier;
+            writeln!(to, "        if !has_{identifier} {{")?;
+            writeln!(to, "            return Err(picopb::decode::DecodeError::MissingRequiredField(\"{identifier}\"));")?;
+            writeln!(to, "        }}")?;
+        }
+    }
+    writeln!(to, "        Ok(message)")?;
+    writeln!(to, "    }}")?;
+    writeln!(to, "}}")?;
+
+    writeln!(to, "impl picopb::decode::FromWire for {message_identifier} {{")?;
+    writeln!(to, "    fn wire_type() -> WireType {{")?;
+    writeln!(to, "        WireType::Len")?;
+    writeln!(to, "    }}")?;
+    writeln!(
+        to,
+        "    fn read(buf: &mut picopb::decode::DecodeBuffer<'_>) -> picopb::decode::Result<Self> {{"
+    )?;
+    writeln!(
+        to,
+        "        <Self as picopb::decode::DecodeMessage>::decode_from(&mut buf.read_sub_buffer()?)"
+    )?;
+    writeln!(to, "    }}")?;
+    writeln!(to, "}}")?;
+    Ok(())
+}
+
+/// Generate the JSON mapping for the message, fields are written with their
+/// JSON name and read from either the JSON name or the field name
+fn generate_message_json<T: Write>(
+    to: &mut T,
+    message_type: &MessageType,
+    config: &GeneratorConfig,
+) -> Result<()> {
+    let message_identifier = &message_type.identifier;
+    writeln!(to, "impl picopb::json::ToJson for {message_identifier} {{")?;
+    writeln!(
+        to,
+        "    fn write_json<W: {0}::fmt::Write>(&self, out: &mut W) -> {0}::fmt::Result {{",
+        config.core()
+    )?;
+    writeln!(
+        to,
+        "        let mut object = picopb::json::ObjectWriter::new(out)?;"
+    )?;
+    for (_, field) in message_type.fields.iter() {
+        let identifier = &field.identifier;
+        let name = format!("self.fields().{identifier}.json_name");
+        match field.qualifier {
+            FieldQualifier::Required => {
+                writeln!(to, "        object.field({name}, &self.{identifier})?;")?;
+            }
+            FieldQualifier::Optional => {
+                writeln!(
+                    to,
+                    "        if let Some(value_{identifier}) = &self.{identifier} {{"
+                )?;
+                writeln!(
+                    to,
+                    "            object.field({name}, value_{identifier})?;"
+                )?;
+                writeln!(to, "        }}")?;
+            }
+            FieldQualifier::Repeated(_) | FieldQualifier::RepeatedUnbounded => {
+                writeln!(
+                    to,
+                    "        object.repeated({name}, self.{identifier}.as_slice())?;"
+                )?;
+            }
+            FieldQualifier::PackedRepeated(_) | FieldQualifier::PackedRepeatedUnbounded => {
+                writeln!(
+                    to,
+                    "        object.repeated({name}, self.{identifier}.0.as_slice())?;"
+                )?;
+            }
+        }
+    }
+    writeln!(to, "        object.finish()")?;
+    writeln!(to, "    }}")?;
+    writeln!(to, "}}")?;
+
+    writeln!(to, "impl picopb::json::FromJson for {message_identifier} {{")?;
+    writeln!(
+        to,
+        "    fn from_json(json: &picopb::json::JsonValue) -> picopb::json::Result<Self> {{"
+    )?;
+    writeln!(to, "        let mut message = Self::default();")?;
+    for (_, field) in message_type.fields.iter() {
+        if let FieldQualifier::Required = field.qualifier {
+            writeln!(to, "        let mut has_{} = false;", field.identifier)?;
+        }
+    }
+    writeln!(to, "        for (name, value) in json.as_object()? {{")?;
+    // null is the same as leaving the field out
+    writeln!(
+        to,
+        "            if *value == picopb::json::JsonValue::Null {{"
+    )?;
+    writeln!(to, "                continue;")?;
+    writeln!(to, "            }}")?;
+    writeln!(to, "            match name.as_str() {{")?;
+    for (_, field) in message_type.fields.iter() {
+        let identifier = &field.identifier;
+        let json_name = field.json_name();
+        match json_name == *identifier {
+            true => writeln!(to, "                {json_name:?} => {{")?,
+            false => writeln!(to, "                {json_name:?} | {identifier:?} => {{")?,
+        }
+        let read = "picopb::json::FromJson::from_json(value)?";
+        match field.qualifier {
+            FieldQualifier::Required => {
+                writeln!(to, "                    message.{identifier} = {read};")?;
+                writeln!(to, "                    has_{identifier} = true;")?;
+            }
+            FieldQualifier::Optional => {
+                writeln!(to, "                    message.{identifier} = Some({read});")?;
+            }
+            FieldQualifier::RepeatedUnbounded => {
+                writeln!(
+                    to,
+                    "                    message.{identifier} = picopb::json::read_vec(value)?;"
+                )?;
+            }
+            FieldQualifier::PackedRepeatedUnbounded => {
+                writeln!(
+                    to,
+                    "                    message.{identifier} = picopb::common::Packed(picopb::json::read_vec(value)?);"
+                )?;
+            }
+            FieldQualifier::Repeated(_) => {
+                writeln!(
+                    to,
+                    "                    message.{identifier} = picopb::json::read_bounded(value)?;"
+                )?;
+            }
+            FieldQualifier::PackedRepeated(_) => {
+                writeln!(
+                    to,
+                    "                    message.{identifier} = picopb::common::Packed(picopb::json::read_bounded(value)?);"
+                )?;
+            }
+        }
+        writeln!(to, "                }}")?;
+    }
+    writeln!(
+        to,
+        "                _ => return Err(picopb::json::JsonError::UnknownField(name.clone())),"
+    )?;
+    writeln!(to, "            }}")?;
+    writeln!(to, "        }}")?;
+    for (_, field) in message_type.fields.iter() {
+        if let FieldQualifier::Required = field.qualifier {
+            let identifier = &field.identifier;
+            writeln!(to, "        if !has_{identifier} {{")?;
+            writeln!(to, "            return Err(picopb::json::JsonError::MissingRequiredField(\"{identifier}\"));")?;
+            writeln!(to, "        }}")?;
+        }
+    }
+    writeln!(to, "        Ok(message)")?;
+    writeln!(to, "    }}")?;
+    writeln!(to, "}}")?;
+    Ok(())
+}
+
+/// Generate the text format for the message, fields are written and read
+/// with the field names from the message metadata
+fn generate_message_text_format<T: Write>(
+    to: &mut T,
+    message_type: &MessageType,
+    config: &GeneratorConfig,
+) -> Result<()> {
+    let message_identifier = &message_type.identifier;
+    writeln!(
+        to,
+        "impl picopb::text_format::ToText for {message_identifier} {{"
+    )?;
+    writeln!(
+        to,
+        "    fn write_text<W: {0}::fmt::Write>(&self, out: &mut W) -> {0}::fmt::Result {{",
+        config.core()
+    )?;
+    writeln!(
+        to,
+        "        let mut writer = picopb::text_format::FieldWriter::new(out);"
+    )?;
+    for (_, field) in message_type.fields.iter() {
+        let identifier = &field.identifier;
+        let name = format!("self.fields().{identifier}.identifier");
+        let write = match is_message_type(field) {
+            true => "message",
+            false => "field",
+        };
+        match field.qualifier {
+            FieldQualifier::Required => {
+                writeln!(to, "        writer.{write}({name}, &self.{identifier})?;")?;
+            }
+            FieldQualifier::Optional => {
+                writeln!(
+                    to,
+                    "        if let Some(value_{identifier}) = &self.{identifier} {{"
+                )?;
+                writeln!(
+                    to,
+                    "            writer.{write}({name}, value_{identifier})?;"
+                )?;
+                writeln!(to, "        }}")?;
+            }
+            FieldQualifier::Repeated(_) | FieldQualifier::RepeatedUnbounded => {
+                writeln!(
+                    to,
+                    "        for value_{identifier} in self.{identifier}.iter() {{"
+                )?;
+                writeln!(
+                    to,
+                    "            writer.{write}({name}, value_{identifier})?;"
+                )?;
+                writeln!(to, "        }}")?;
+            }
+            FieldQualifier::PackedRepeated(_) | FieldQualifier::PackedRepeatedUnbounded => {
+                writeln!(
+                    to,
+                    "        for value_{identifier} in self.{identifier}.0.iter() {{"
+                )?;
+                writeln!(
+                    to,
+                    "            writer.{write}({name}, value_{identifier})?;"
+                )?;
+                writeln!(to, "        }}")?;
+            }
+        }
+    }
+    writeln!(to, "        Ok(())")?;
+    writeln!(to, "    }}")?;
+    writeln!(to, "}}")?;
+
+    writeln!(
+        to,
+        "impl picopb::text_format::FromText for {message_identifier} {{"
+    )?;
+    writeln!(
+        to,
+        "    fn from_text(text: &picopb::text_format::TextValue) -> picopb::text_format::Result<Self> {{"
+    )?;
+    writeln!(to, "        let mut message = Self::default();")?;
+    for (_, field) in message_type.fields.iter() {
+        if let FieldQualifier::Required = field.qualifier {
+            writeln!(to, "        let mut has_{} = false;", field.identifier)?;
+        }
+    }
+    writeln!(to, "        for (name, value) in text.as_message()? {{")?;
+    writeln!(to, "            match name.as_str() {{")?;
+    for (_, field) in message_type.fields.iter() {
+        let identifier = &field.identifier;
+        let read = "picopb::text_format::FromText::from_text(value)?";
+        writeln!(to, "                \"{identifier}\" => {{")?;
+        match field.qualifier {
+            FieldQualifier::Required => {
+                writeln!(to, "                    message.{identifier} = {read};")?;
+                writeln!(to, "                    has_{identifier} = true;")?;
+            }
+            FieldQualifier::Optional => {
+                writeln!(to, "                    message.{identifier} = Some({read});")?;
+            }
+            FieldQualifier::RepeatedUnbounded | FieldQualifier::PackedRepeatedUnbounded => {
+                let values = match field.qualifier {
+                    FieldQualifier::PackedRepeatedUnbounded => format!("message.{identifier}.0"),
+                    _ => format!("message.{identifier}"),
+                };
+                writeln!(
+                    to,
+                    "                    picopb::text_format::read_repeated(value, |value| {{"
+                )?;
+                writeln!(to, "                        {values}.push(value);")?;
+                writeln!(to, "                        Ok(())")?;
+                writeln!(to, "                    }})?;")?;
+            }
+            FieldQualifier::Repeated(_) | FieldQualifier::PackedRepeated(_) => {
+                let values = match field.qualifier {
+                    FieldQualifier::PackedRepeated(_) => format!("message.{identifier}.0"),
+                    _ => format!("message.{identifier}"),
+                };
+                writeln!(
+                    to,
+                    "                    picopb::text_format::read_repeated(value, |value| {{"
+                )?;
+                writeln!(to, "                        picopb::text_format::push_bounded(&mut {values}, value)")?;
+                writeln!(to, "                    }})?;")?;
+            }
+        }
+        writeln!(to, "                }}")?;
+    }
+    writeln!(
+        to,
+        "                _ => return Err(picopb::text_format::TextFormatError::UnknownField(name.clone())),"
+    )?;
+    writeln!(to, "            }}")?;
+    writeln!(to, "        }}")?;
+    for (_, field) in message_type.fields.iter() {
+        if let FieldQualifier::Required = field.qualifier {
+            let identifier = &field.identifier;
+            writeln!(to, "        if !has_{identifier} {{")?;
+            writeln!(to, "            return Err(picopb::text_format::TextFormatError::MissingRequiredField(\"{identifier}\"));")?;
+            writeln!(to, "        }}")?;
+        }
+    }
+    writeln!(to, "        Ok(message)")?;
+    writeln!(to, "    }}")?;
+    writeln!(to, "}}")?;
+    Ok(())
+}
+
+/// Generate the static descriptor and the Reflect implementation, fields are
+/// read and replaced through `picopb::reflect::ReflectField`
+fn generate_message_reflect<T: Write>(to: &mut T, message_type: &MessageType) -> Result<()> {
+    let message_identifier = &message_type.identifier;
+    let fields_const = identifier_to_const_case(message_identifier)?;
+    writeln!(to, "impl {message_identifier} {{")?;
+    writeln!(
+        to,
+        "    pub const DESCRIPTOR: picopb::reflect::MessageDescriptor = picopb::reflect::MessageDescriptor {{"
+    )?;
+    writeln!(to, "        name: \"{message_identifier}\",")?;
+    writeln!(to, "        fields: &[")?;
+    for (_, field) in message_type.fields.iter() {
+        writeln!(to, "            {fields_const}_FIELDS.{},", field.identifier)?;
+    }
+    writeln!(to, "        ],")?;
+    writeln!(to, "    }};")?;
+    writeln!(to, "}}")?;
+
+    // messages without fields do not use the arguments
+    let unused = match message_type.fields.is_empty() {
+        true => "_",
+        false => "",
+    };
+    writeln!(to, "impl picopb::reflect::Reflect for {message_identifier} {{")?;
+    writeln!(
+        to,
+        "    fn descriptor(&self) -> &'static picopb::reflect::MessageDescriptor {{"
+    )?;
+    writeln!(to, "        &Self::DESCRIPTOR")?;
+    writeln!(to, "    }}")?;
+    writeln!(
+        to,
+        "    fn get(&self, {unused}number: u32) -> Option<Vec<picopb::reflect::FieldValue<'_>>> {{"
+    )?;
+    if message_type.fields.is_empty() {
+        writeln!(to, "        None")?;
+    } else {
+        let to_value = "picopb::reflect::ReflectField::to_value";
+        writeln!(to, "        match number {{")?;
+        for (_, field) in message_type.fields.iter() {
+            let identifier = &field.identifier;
+            let values = match field.qualifier {
+                FieldQualifier::Required => format!("vec![{to_value}(&self.{identifier})]"),
+                FieldQualifier::PackedRepeated(_) | FieldQualifier::PackedRepeatedUnbounded => {
+                    format!("self.{identifier}.0.iter().map({to_value}).collect()")
+                }
+                _ => format!("self.{identifier}.iter().map({to_value}).collect()"),
+            };
+            writeln!(to, "            {} => Some({values}),", field.ordinal.0)?;
+        }
+        writeln!(to, "            _ => None,")?;
+        writeln!(to, "        }}")?;
+    }
+    writeln!(to, "    }}")?;
+    writeln!(
+        to,
+        "    fn set(&mut self, number: u32, {unused}values: &[picopb::reflect::FieldValue]) -> picopb::reflect::Result<()> {{"
+    )?;
+    let unknown = "Err(picopb::reflect::ReflectError::UnknownField(number.to_string()))";
+    if message_type.fields.is_empty() {
+        writeln!(to, "        {unknown}")?;
+    } else {
+        writeln!(to, "        match number {{")?;
+        for (_, field) in message_type.fields.iter() {
+            let identifier = &field.identifier;
+            let set = match field.qualifier {
+                FieldQualifier::Required => {
+                    format!("picopb::reflect::set_required(&mut self.{identifier}, values)")
+                }
+                FieldQualifier::Optional => {
+                    format!("picopb::reflect::set_optional(&mut self.{identifier}, values)")
+                }
+                FieldQualifier::RepeatedUnbounded => {
+                    format!("picopb::reflect::set_repeated(&mut self.{identifier}, values)")
+                }
+                FieldQualifier::PackedRepeatedUnbounded => {
+                    format!("picopb::reflect::set_repeated(&mut self.{identifier}.0, values)")
+                }
+                FieldQualifier::Repeated(_) => {
+                    format!("picopb::reflect::set_bounded(&mut self.{identifier}, values)")
+                }
+                FieldQualifier::PackedRepeated(_) => {
+                    format!("picopb::reflect::set_bounded(&mut self.{identifier}.0, values)")
+                }
+            };
+            writeln!(to, "            {} => {set},", field.ordinal.0)?;
+        }
+        writeln!(to, "            _ => {unknown},")?;
+        writeln!(to, "        }}")?;
+    }
+    writeln!(to, "    }}")?;
+    writeln!(to, "}}")?;
+
+    writeln!(
+        to,
+        "impl picopb::reflect::ReflectField for {message_identifier} {{"
+    )?;
+    writeln!(
+        to,
+        "    fn to_value(&self) -> picopb::reflect::FieldValue<'_> {{"
+    )?;
+    writeln!(to, "        picopb::reflect::FieldValue::Message(self)")?;
+    writeln!(to, "    }}")?;
+    writeln!(
+        to,
+        "    fn from_value(value: &picopb::reflect::FieldValue) -> picopb::reflect::Result<Self> {{"
+    )?;
+    writeln!(to, "        let mut message = Self::default();")?;
+    writeln!(
+        to,
+        "        picopb::reflect::copy_fields(&mut message, value.as_message()?)?;"
+    )?;
+    writeln!(to, "        Ok(message)")?;
+    writeln!(to, "    }}")?;
+    writeln!(to, "}}")?;
+    Ok(())
+}
+
+/// Generate the MSGID constant for messages with the `(nanopb_msgopt).msgid` option
+fn generate_message_msgid<T: Write>(to: &mut T, message_type: &MessageType) -> Result<()> {
+    if let Some(msgid) = message_type.msgid {
+        writeln!(to, "impl {} {{", message_type.identifier)?;
+        writeln!(to, "    pub const MSGID: u32 = {};", msgid)?;
+        writeln!(to, "}}")?;
+    }
+    Ok(())
+}
+
+/// Generate the AnyMessage enum, which contains all messages that have a message id.
+/// This is used to multiplex several message types over a single link.
+fn generate_message_registry<T: Write>(
+    to: &mut T,
+    message_types: &HashMap<String, MessageType>,
+    config: &GeneratorConfig,
+) -> Result<()> {
+    let messages = sorted_values(message_types)
+        .into_iter()
+        .filter(|message_type| message_type.msgid.is_some())
+        .collect::<Vec<_>>();
+    if messages.is_empty() {
+        return Ok(());
+    }
+
+    writeln!(to, "#[derive(Debug)]")?;
+    writeln!(to, "pub enum AnyMessage {{")?;
+    for message_type in messages.iter() {
+        writeln!(to, "    {0}({0}),", message_type.identifier)?;
+    }
+    writeln!(to, "}}")?;
+
+    for message_type in messages.iter() {
+        writeln!(to, "impl From<{}> for AnyMessage {{", message_type.identifier)?;
+        writeln!(to, "    fn from(value: {}) -> Self {{", message_type.identifier)?;
+        writeln!(to, "        Self::{}(value)", message_type.identifier)?;
+        writeln!(to, "    }}")?;
+        writeln!(to, "}}")?;
+    }
+
+    writeln!(to, "impl AnyMessage {{")?;
+    writeln!(to, "    pub fn id(&self) -> u32 {{")?;
+    writeln!(to, "        match self {{")?;
+    for message_type in messages.iter() {
+        writeln!(
+            to,
+            "            Self::{0}(_) => {0}::MSGID,",
+            message_type.identifier
+        )?;
+    }
+    writeln!(to, "        }}")?;
+    writeln!(to, "    }}")?;
+
+    writeln!(
+        to,
+        "    pub fn decode_any(id: u32, bytes: &[u8]) -> picopb::decode::Result<Self> {{"
+    )?;
+    writeln!(to, "        match id {{")?;
+    for message_type in messages.iter() {
+        writeln!(
+            to,
+            "            {0}::MSGID => Ok(Self::{0}(<{0} as picopb::decode::DecodeMessage>::decode(bytes)?)),",
+            message_type.identifier
+        )?;
+    }
+    writeln!(
+        to,
+        "            _ => Err(picopb::decode::DecodeError::UnknownMessageId(id)),"
+    )?;
+    writeln!(to, "        }}")?;
+    writeln!(to, "    }}")?;
+
+    writeln!(
+        to,
+        "    /// Decodes a message written by `encode_with_id`, a varint message id followed by the message"
+    )?;
+    writeln!(
+        to,
+        "    pub fn decode_with_id(bytes: &[u8]) -> picopb::decode::Result<Self> {{"
+    )?;
+    writeln!(
+        to,
+        "        let mut buf = picopb::decode::DecodeBuffer::from_slice(bytes);"
+    )?;
+    writeln!(
+        to,
+        "        let id = u32::try_from(buf.read_varint()?).map_err(|_| picopb::decode::DecodeError::VarintTooLong)?;"
+    )?;
+    writeln!(to, "        Self::decode_any(id, buf.remaining())")?;
+    writeln!(to, "    }}")?;
+
+    writeln!(
+        to,
+        "    pub fn encode_with_id<W: picopb::encode::EncodeSink>(&self, buf: &mut W) -> {}<usize> {{",
+        config.sink_result()
+    )?;
+    writeln!(to, "        match self {{")?;
+    for message_type in messages.iter() {
+        writeln!(
+            to,
+            "            Self::{0}(message) => picopb::encode::encode_with_id(buf, {0}::MSGID, message),",
+            message_type.identifier
+        )?;
+    }
+    writeln!(to, "        }}")?;
+    writeln!(to, "    }}")?;
+    writeln!(to, "}}")?;
+    Ok(())
+}
+
+/// Generate the MAX_ENCODED_LEN constant for messages with bounded size
+fn generate_message_max_encoded_len<T: Write>(
+    to: &mut T,
+    message_type: &MessageType,
+    max_encoded_len: &MaxEncodedLen,
+) -> Result<()> {
+    match max_encoded_len {
+        MaxEncodedLen::Bounded(len) => {
+            writeln!(to, "impl {} {{", message_type.identifier)?;
+            writeln!(to, "    pub const MAX_ENCODED_LEN: usize = {};", len)?;
+            writeln!(to, "}}")?;
+        }
+        MaxEncodedLen::Unbounded(reason) => {
+            writeln!(
+                to,
+                "// {} has no MAX_ENCODED_LEN, unbounded: {}",
+                message_type.identifier, reason
+            )?;
+        }
+    }
+    Ok(())
+}
+
+fn generate_messages<T: Write>(
+    to: &mut T,
+    message_types: &HashMap<String, MessageType>,
+    max_encoded_lens: &BTreeMap<String, MaxEncodedLen>,
+    config: &GeneratorConfig,
+) -> Result<()> {
+    for message_type in sorted_values(message_types) {
+        match is_default_derivable(message_type) {
+            true => writeln!(to, "#[derive(Default, Debug)]")?,
+            false => writeln!(to, "#[derive(Debug)]")?,
+        }
+        writeln!(to, "pub struct {} {{", message_type.identifier)?;
+        for (_, field) in message_type.fields.iter() {
+            writeln!(
+                to,
+                "    pub {}: {},",
+                field.identifier,
+                field_to_rust_type(&field.qualifier, &field.field_type)
+            )?;
+        }
+        if let Some(unknown_fields_type) = config.unknown_fields_type() {
+            writeln!(to, "    pub unknown_fields: {unknown_fields_type},")?;
+        }
+        writeln!(to, "}}")?;
+        generate_message_default(to, message_type, config)?;
+        generate_message_metadata(to, message_type)?;
+        generate_message_msgid(to, message_type)?;
+        if let Some(max_encoded_len) = max_encoded_lens.get(&message_type.identifier) {
+            generate_message_max_encoded_len(to, message_type, max_encoded_len)?;
+        }
+
+        generate_message_wiretyped(to, message_type)?;
+        generate_message_to_wire(to, message_type, config)?;
+        generate_message_from_wire(to, message_type, config)?;
+        generate_message_impl_randomize(to, message_type, config)?;
+        generate_message_text_format(to, message_type, config)?;
+        generate_message_reflect(to, message_type)?;
+        if config.json {
+            generate_message_json(to, message_type, config)?;
+        }
+    }
+    generate_message_registry(to, message_types, config)?;
+    Ok(())
+}
+
+fn generate_imports<T: Write>(to: &mut T, config: &GeneratorConfig) -> std::io::Result<()> {
+    writeln!(to, "use picopb::common::*;")?;
+    writeln!(to, "use picopb::encode::{{EncodeSink, ToWire}};")?;
+    writeln!(to, "use picopb::wiretypes::{{WireType, WireTyped}};")?;
+    writeln!(to, "use picopb::randomizer::{{randomized, Randomize}};")?;
+    if config.no_std {
+        // the types of the std prelude that are not in the core prelude
+        writeln!(to, "#[allow(unused_imports)]")?;
+        writeln!(
+            to,
+            "use alloc::{{format, string::{{String, ToString}}, vec, vec::Vec}};"
+        )?;
+    }
+    writeln!(to, "use {}::ops::Deref;", config.core())
+}
+
+pub fn generate<T: Write>(to: &mut T, parser: &ProtoParser) -> Result<()> {
+    generate_with_config(to, parser, &GeneratorConfig::default())
+}
+
+pub fn generate_with_config<T: Write>(
+    to: &mut T,
+    parser: &ProtoParser,
+    config: &GeneratorConfig,
+) -> Result<()> {
+    generate_imports(to, config)?;
+    generate_enums(to, &parser.enum_types, config)?;
+    let max_encoded_lens = max_encoded_lens_with(parser, config.unknown_fields);
+    generate_messages(to, &parser.message_types, &max_encoded_lens, config)?;
     Ok(())
 }
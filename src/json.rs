@@ -0,0 +1,656 @@
+use crate::decode::MAX_MESSAGE_DEPTH;
+use arraystring::{prelude::Capacity, ArrayString};
+use arrayvec::ArrayVec;
+use std::fmt::{self, Write};
+
+/// This module contains the canonical protobuf JSON mapping
+/// (https://protobuf.dev/programming-guides/proto3/#json) for generated
+/// messages and enums: fields use their lowerCamelCase JSON name, enums
+/// are written as their names, 64 bit integers as strings and bytes as base64.
+
+#[derive(Debug, PartialEq)]
+pub enum JsonError {
+    /// The input is not valid JSON, holds the byte offset of the error
+    Syntax(usize),
+    /// Arrays and objects are nested deeper than `MAX_NESTING_DEPTH`, holds the
+    /// byte offset of the one that is too deep
+    TooDeep(usize),
+    /// The value has the wrong JSON type, holds the expected type
+    UnexpectedType(&'static str),
+    InvalidNumber(String),
+    InvalidBase64,
+    InvalidEnumValue(String),
+    UnknownField(String),
+    MissingRequiredField(&'static str),
+    CapacityExceeded,
+    Fmt,
+}
+
+impl From<fmt::Error> for JsonError {
+    fn from(_: fmt::Error) -> Self {
+        Self::Fmt
+    }
+}
+
+pub type Result<T> = std::result::Result<T, JsonError>;
+
+/// A parsed JSON document
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonValue {
+    Null,
+    Bool(bool),
+    /// The number as written, so 64 bit integers keep their precision
+    Number(String),
+    String(String),
+    Array(Vec<JsonValue>),
+    /// Members in the order they were written
+    Object(Vec<(String, JsonValue)>),
+}
+
+impl JsonValue {
+    pub fn as_object(&self) -> Result<&[(String, JsonValue)]> {
+        match self {
+            Self::Object(members) => Ok(members),
+            _ => Err(JsonError::UnexpectedType("object")),
+        }
+    }
+
+    pub fn as_array(&self) -> Result<&[JsonValue]> {
+        match self {
+            Self::Array(values) => Ok(values),
+            _ => Err(JsonError::UnexpectedType("array")),
+        }
+    }
+
+    pub fn as_str(&self) -> Result<&str> {
+        match self {
+            Self::String(value) => Ok(value),
+            _ => Err(JsonError::UnexpectedType("string")),
+        }
+    }
+
+    /// The first member of an object with the name, `None` for other values
+    pub fn get(&self, name: &str) -> Option<&JsonValue> {
+        match self {
+            Self::Object(members) => members
+                .iter()
+                .find(|(member, _)| member == name)
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+}
+
+/// The JSON name of a field: underscores are removed and the letter
+/// following them is capitalized, the same as protoc
+pub fn json_name(identifier: &str) -> String {
+    let mut name = String::with_capacity(identifier.len());
+    let mut capitalize_next = false;
+    for c in identifier.chars() {
+        if c == '_' {
+            capitalize_next = true;
+        } else if capitalize_next {
+            name.push(c.to_ascii_uppercase());
+            capitalize_next = false;
+        } else {
+            name.push(c);
+        }
+    }
+    name
+}
+
+/// The ToJson trait writes a value in the canonical JSON mapping
+pub trait ToJson {
+    fn write_json<W: Write>(&self, out: &mut W) -> fmt::Result;
+
+    fn to_json(&self) -> String {
+        let mut out = String::new();
+        // writing to a String does not fail
+        let _ = self.write_json(&mut out);
+        out
+    }
+}
+
+/// The FromJson trait reads a value from the canonical JSON mapping
+pub trait FromJson: Sized {
+    fn from_json(value: &JsonValue) -> Result<Self>;
+
+    fn from_json_str(input: &str) -> Result<Self> {
+        Self::from_json(&parse(input)?)
+    }
+}
+
+/// Writes the members of a JSON object, used by generated messages
+pub struct ObjectWriter<'w, W: Write> {
+    out: &'w mut W,
+    first: bool,
+}
+
+impl<'w, W: Write> ObjectWriter<'w, W> {
+    pub fn new(out: &'w mut W) -> std::result::Result<Self, fmt::Error> {
+        out.write_char('{')?;
+        Ok(Self { out, first: true })
+    }
+
+    fn key(&mut self, name: &str) -> fmt::Result {
+        if !self.first {
+            self.out.write_char(',')?;
+        }
+        self.first = false;
+        write_string(self.out, name)?;
+        self.out.write_char(':')
+    }
+
+    pub fn field<T: ToJson + ?Sized>(&mut self, name: &str, value: &T) -> fmt::Result {
+        self.key(name)?;
+        value.write_json(self.out)
+    }
+
+    /// writes the values as an array, empty repeated fields are omitted
+    pub fn repeated<T: ToJson>(&mut self, name: &str, values: &[T]) -> fmt::Result {
+        if values.is_empty() {
+            return Ok(());
+        }
+        self.key(name)?;
+        self.out.write_char('[')?;
+        for (i, value) in values.iter().enumerate() {
+            if i > 0 {
+                self.out.write_char(',')?;
+            }
+            value.write_json(self.out)?;
+        }
+        self.out.write_char(']')
+    }
+
+    pub fn finish(self) -> fmt::Result {
+        self.out.write_char('}')
+    }
+}
+
+/// Writes a quoted and escaped JSON string
+pub fn write_string<W: Write + ?Sized>(out: &mut W, value: &str) -> fmt::Result {
+    out.write_char('"')?;
+    for c in value.chars() {
+        match c {
+            '"' => out.write_str("\\\"")?,
+            '\\' => out.write_str("\\\\")?,
+            '\n' => out.write_str("\\n")?,
+            '\r' => out.write_str("\\r")?,
+            '\t' => out.write_str("\\t")?,
+            '\u{08}' => out.write_str("\\b")?,
+            '\u{0c}' => out.write_str("\\f")?,
+            c if (c as u32) < 0x20 => write!(out, "\\u{:04x}", c as u32)?,
+            c => out.write_char(c)?,
+        }
+    }
+    out.write_char('"')
+}
+
+/// Reads the values of a repeated field into a Vec
+pub fn read_vec<T: FromJson>(value: &JsonValue) -> Result<Vec<T>> {
+    value.as_array()?.iter().map(T::from_json).collect()
+}
+
+/// Reads the values of a bounded repeated field
+pub fn read_bounded<T: FromJson, const N: usize>(value: &JsonValue) -> Result<ArrayVec<T, N>> {
+    let mut values = ArrayVec::new();
+    for value in value.as_array()? {
+        values
+            .try_push(T::from_json(value)?)
+            .map_err(|_| JsonError::CapacityExceeded)?;
+    }
+    Ok(values)
+}
+
+/// Integers are accepted both as numbers and as strings, and in
+/// exponent notation as long as the value is integral
+fn read_integer<T: TryFrom<i128>>(value: &JsonValue) -> Result<T> {
+    let literal = match value {
+        JsonValue::Number(literal) => literal.as_str(),
+        JsonValue::String(literal) => literal.as_str(),
+        _ => return Err(JsonError::UnexpectedType("number")),
+    };
+    let invalid = || JsonError::InvalidNumber(literal.to_string());
+    let integer = match literal.parse::<i128>() {
+        Ok(integer) => integer,
+        Err(_) => {
+            let float = literal.parse::<f64>().map_err(|_| invalid())?;
+            if float.fract() != 0.0 || !float.is_finite() {
+                return Err(invalid());
+            }
+            float as i128
+        }
+    };
+    T::try_from(integer).map_err(|_| invalid())
+}
+
+/// Written without whitespace, numbers as they were written
+impl ToJson for JsonValue {
+    fn write_json<W: Write>(&self, out: &mut W) -> fmt::Result {
+        match self {
+            Self::Null => out.write_str("null"),
+            Self::Bool(value) => value.write_json(out),
+            Self::Number(literal) => out.write_str(literal),
+            Self::String(value) => write_string(out, value),
+            Self::Array(values) => {
+                out.write_char('[')?;
+                for (index, value) in values.iter().enumerate() {
+                    if index > 0 {
+                        out.write_char(',')?;
+                    }
+                    value.write_json(out)?;
+                }
+                out.write_char(']')
+            }
+            Self::Object(members) => {
+                out.write_char('{')?;
+                for (index, (name, value)) in members.iter().enumerate() {
+                    if index > 0 {
+                        out.write_char(',')?;
+                    }
+                    write_string(out, name)?;
+                    out.write_char(':')?;
+                    value.write_json(out)?;
+                }
+                out.write_char('}')
+            }
+        }
+    }
+}
+
+impl ToJson for bool {
+    fn write_json<W: Write>(&self, out: &mut W) -> fmt::Result {
+        write!(out, "{}", self)
+    }
+}
+
+impl FromJson for bool {
+    fn from_json(value: &JsonValue) -> Result<Self> {
+        match value {
+            JsonValue::Bool(value) => Ok(*value),
+            _ => Err(JsonError::UnexpectedType("bool")),
+        }
+    }
+}
+
+impl ToJson for i32 {
+    fn write_json<W: Write>(&self, out: &mut W) -> fmt::Result {
+        write!(out, "{}", self)
+    }
+}
+
+impl FromJson for i32 {
+    fn from_json(value: &JsonValue) -> Result<Self> {
+        read_integer(value)
+    }
+}
+
+impl ToJson for u32 {
+    fn write_json<W: Write>(&self, out: &mut W) -> fmt::Result {
+        write!(out, "{}", self)
+    }
+}
+
+impl FromJson for u32 {
+    fn from_json(value: &JsonValue) -> Result<Self> {
+        read_integer(value)
+    }
+}
+
+/// 64 bit integers are strings, JSON numbers are often parsed as doubles
+impl ToJson for i64 {
+    fn write_json<W: Write>(&self, out: &mut W) -> fmt::Result {
+        write!(out, "\"{}\"", self)
+    }
+}
+
+impl FromJson for i64 {
+    fn from_json(value: &JsonValue) -> Result<Self> {
+        read_integer(value)
+    }
+}
+
+impl ToJson for u64 {
+    fn write_json<W: Write>(&self, out: &mut W) -> fmt::Result {
+        write!(out, "\"{}\"", self)
+    }
+}
+
+impl FromJson for u64 {
+    fn from_json(value: &JsonValue) -> Result<Self> {
+        read_integer(value)
+    }
+}
+
+impl ToJson for str {
+    fn write_json<W: Write>(&self, out: &mut W) -> fmt::Result {
+        write_string(out, self)
+    }
+}
+
+impl ToJson for String {
+    fn write_json<W: Write>(&self, out: &mut W) -> fmt::Result {
+        write_string(out, self)
+    }
+}
+
+impl FromJson for String {
+    fn from_json(value: &JsonValue) -> Result<Self> {
+        Ok(value.as_str()?.to_string())
+    }
+}
+
+impl<SIZE: Capacity> ToJson for ArrayString<SIZE> {
+    fn write_json<W: Write>(&self, out: &mut W) -> fmt::Result {
+        write_string(out, self.as_str())
+    }
+}
+
+impl<SIZE: Capacity> FromJson for ArrayString<SIZE> {
+    fn from_json(value: &JsonValue) -> Result<Self> {
+        ArrayString::try_from_str(value.as_str()?).map_err(|_| JsonError::CapacityExceeded)
+    }
+}
+
+impl ToJson for bytes::Bytes {
+    fn write_json<W: Write>(&self, out: &mut W) -> fmt::Result {
+        write_base64(out, self)
+    }
+}
+
+impl FromJson for bytes::Bytes {
+    fn from_json(value: &JsonValue) -> Result<Self> {
+        Ok(bytes::Bytes::from(read_base64(value.as_str()?)?))
+    }
+}
+
+/// Fixed size bytes, shorter values are padded with zeros
+impl<const N: usize> ToJson for [u8; N] {
+    fn write_json<W: Write>(&self, out: &mut W) -> fmt::Result {
+        write_base64(out, self)
+    }
+}
+
+impl<const N: usize> FromJson for [u8; N] {
+    fn from_json(value: &JsonValue) -> Result<Self> {
+        let bytes = read_base64(value.as_str()?)?;
+        let mut value = [0; N];
+        value
+            .get_mut(..bytes.len())
+            .ok_or(JsonError::CapacityExceeded)?
+            .copy_from_slice(&bytes);
+        Ok(value)
+    }
+}
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Writes the bytes as a quoted, padded standard base64 string
+pub fn write_base64<W: Write + ?Sized>(out: &mut W, bytes: &[u8]) -> fmt::Result {
+    out.write_char('"')?;
+    for chunk in bytes.chunks(3) {
+        let group = match chunk {
+            [a, b, c] => (*a as u32) << 16 | (*b as u32) << 8 | *c as u32,
+            [a, b] => (*a as u32) << 16 | (*b as u32) << 8,
+            [a] => (*a as u32) << 16,
+            _ => unreachable!(),
+        };
+        for i in 0..4 {
+            match i <= chunk.len() {
+                true => out
+                    .write_char(BASE64_ALPHABET[(group >> (18 - 6 * i)) as usize & 0x3f] as char)?,
+                false => out.write_char('=')?,
+            }
+        }
+    }
+    out.write_char('"')
+}
+
+/// Reads standard or URL safe base64, with or without padding
+pub fn read_base64(input: &str) -> Result<Vec<u8>> {
+    let input = input.trim_end_matches('=');
+    let mut bytes = Vec::with_capacity(input.len() * 3 / 4);
+    let mut group: u32 = 0;
+    let mut bits = 0;
+    for c in input.bytes() {
+        let value = match c {
+            b'A'..=b'Z' => c - b'A',
+            b'a'..=b'z' => c - b'a' + 26,
+            b'0'..=b'9' => c - b'0' + 52,
+            b'+' | b'-' => 62,
+            b'/' | b'_' => 63,
+            _ => return Err(JsonError::InvalidBase64),
+        };
+        group = group << 6 | value as u32;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((group >> bits) as u8);
+        }
+    }
+    // a single leftover character can not hold a whole byte
+    if bits >= 6 {
+        return Err(JsonError::InvalidBase64);
+    }
+    Ok(bytes)
+}
+
+/// Arrays and objects nest at most this deep when parsing, enough for a
+/// message of `decode::MAX_MESSAGE_DEPTH` sub-messages with repeated fields
+pub const MAX_NESTING_DEPTH: usize = 2 * (MAX_MESSAGE_DEPTH + 1);
+
+/// Parses a JSON document
+pub fn parse(input: &str) -> Result<JsonValue> {
+    let mut parser = JsonParser {
+        input: input.as_bytes(),
+        position: 0,
+        depth: 0,
+    };
+    let value = parser.value()?;
+    parser.skip_whitespace();
+    match parser.position == parser.input.len() {
+        true => Ok(value),
+        false => Err(JsonError::Syntax(parser.position)),
+    }
+}
+
+struct JsonParser<'a> {
+    input: &'a [u8],
+    position: usize,
+    /// The arrays and objects the parser is in
+    depth: usize,
+}
+
+impl JsonParser<'_> {
+    fn error<T>(&self) -> Result<T> {
+        Err(JsonError::Syntax(self.position))
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(b' ' | b'\t' | b'\r' | b'\n') = self.input.get(self.position) {
+            self.position += 1;
+        }
+    }
+
+    fn peek(&mut self) -> Option<u8> {
+        self.skip_whitespace();
+        self.input.get(self.position).copied()
+    }
+
+    fn expect(&mut self, c: u8) -> Result<()> {
+        match self.peek() == Some(c) {
+            true => {
+                self.position += 1;
+                Ok(())
+            }
+            false => self.error(),
+        }
+    }
+
+    fn keyword(&mut self, keyword: &str, value: JsonValue) -> Result<JsonValue> {
+        match self.input[self.position..].starts_with(keyword.as_bytes()) {
+            true => {
+                self.position += keyword.len();
+                Ok(value)
+            }
+            false => self.error(),
+        }
+    }
+
+    /// Parses an array or object, the recursion is limited so that hostile
+    /// input cannot overflow the stack
+    fn nested(&mut self, parse: fn(&mut Self) -> Result<JsonValue>) -> Result<JsonValue> {
+        if self.depth == MAX_NESTING_DEPTH {
+            return Err(JsonError::TooDeep(self.position));
+        }
+        self.depth += 1;
+        let value = parse(self);
+        self.depth -= 1;
+        value
+    }
+
+    fn value(&mut self) -> Result<JsonValue> {
+        match self.peek() {
+            Some(b'{') => self.nested(Self::object),
+            Some(b'[') => self.nested(Self::array),
+            Some(b'"') => Ok(JsonValue::String(self.string()?)),
+            Some(b'-' | b'0'..=b'9') => self.number(),
+            Some(b't') => self.keyword("true", JsonValue::Bool(true)),
+            Some(b'f') => self.keyword("false", JsonValue::Bool(false)),
+            Some(b'n') => self.keyword("null", JsonValue::Null),
+            _ => self.error(),
+        }
+    }
+
+    fn object(&mut self) -> Result<JsonValue> {
+        self.expect(b'{')?;
+        let mut members = Vec::new();
+        if self.peek() == Some(b'}') {
+            self.position += 1;
+            return Ok(JsonValue::Object(members));
+        }
+        loop {
+            if self.peek() != Some(b'"') {
+                return self.error();
+            }
+            let key = self.string()?;
+            self.expect(b':')?;
+            members.push((key, self.value()?));
+            match self.peek() {
+                Some(b',') => self.position += 1,
+                Some(b'}') => {
+                    self.position += 1;
+                    return Ok(JsonValue::Object(members));
+                }
+                _ => return self.error(),
+            }
+        }
+    }
+
+    fn array(&mut self) -> Result<JsonValue> {
+        self.expect(b'[')?;
+        let mut values = Vec::new();
+        if self.peek() == Some(b']') {
+            self.position += 1;
+            return Ok(JsonValue::Array(values));
+        }
+        loop {
+            values.push(self.value()?);
+            match self.peek() {
+                Some(b',') => self.position += 1,
+                Some(b']') => {
+                    self.position += 1;
+                    return Ok(JsonValue::Array(values));
+                }
+                _ => return self.error(),
+            }
+        }
+    }
+
+    fn number(&mut self) -> Result<JsonValue> {
+        let start = self.position;
+        while let Some(b'-' | b'+' | b'.' | b'e' | b'E' | b'0'..=b'9') =
+            self.input.get(self.position)
+        {
+            self.position += 1;
+        }
+        // the characters above are all ASCII
+        let literal = std::str::from_utf8(&self.input[start..self.position]).unwrap_or_default();
+        match literal.parse::<f64>() {
+            Ok(_) => Ok(JsonValue::Number(literal.to_string())),
+            Err(_) => Err(JsonError::Syntax(start)),
+        }
+    }
+
+    fn hex4(&mut self) -> Result<u32> {
+        let digits = self
+            .input
+            .get(self.position..self.position + 4)
+            .and_then(|digits| std::str::from_utf8(digits).ok())
+            .and_then(|digits| u32::from_str_radix(digits, 16).ok());
+        match digits {
+            Some(value) => {
+                self.position += 4;
+                Ok(value)
+            }
+            None => self.error(),
+        }
+    }
+
+    fn string(&mut self) -> Result<String> {
+        self.expect(b'"')?;
+        let mut value = Vec::new();
+        loop {
+            let c = match self.input.get(self.position) {
+                Some(c) => *c,
+                None => return self.error(),
+            };
+            self.position += 1;
+            match c {
+                b'"' => break,
+                b'\\' => {
+                    let escape = match self.input.get(self.position) {
+                        Some(escape) => *escape,
+                        None => return self.error(),
+                    };
+                    self.position += 1;
+                    let c = match escape {
+                        b'"' => '"',
+                        b'\\' => '\\',
+                        b'/' => '/',
+                        b'b' => '\u{08}',
+                        b'f' => '\u{0c}',
+                        b'n' => '\n',
+                        b'r' => '\r',
+                        b't' => '\t',
+                        b'u' => {
+                            let mut code = self.hex4()?;
+                            // characters outside the BMP are written as surrogate pairs
+                            if (0xd800..0xdc00).contains(&code)
+                                && self.input[self.position..].starts_with(b"\\u")
+                            {
+                                self.position += 2;
+                                let low = self.hex4()?;
+                                if !(0xdc00..0xe000).contains(&low) {
+                                    return self.error();
+                                }
+                                code = 0x10000 + ((code - 0xd800) << 10) + (low - 0xdc00);
+                            }
+                            match char::from_u32(code) {
+                                Some(c) => c,
+                                None => return self.error(),
+                            }
+                        }
+                        _ => return self.error(),
+                    };
+                    let mut encoded = [0; 4];
+                    value.extend_from_slice(c.encode_utf8(&mut encoded).as_bytes());
+                }
+                c if c < 0x20 => return self.error(),
+                c => value.push(c),
+            }
+        }
+        String::from_utf8(value).map_err(|_| JsonError::Syntax(self.position))
+    }
+}
@@ -1,8 +1,29 @@
 pub mod common;
+pub mod compat;
 pub mod decode;
+pub mod descriptor;
+pub mod dynamic;
 pub mod encode;
+pub mod format;
 pub mod generator;
+pub mod json;
+pub mod lint;
+pub mod lsp;
 pub mod parser;
+pub mod plugin;
 pub mod randomizer;
+pub mod reflect;
+pub mod raw;
+pub mod size;
+pub mod text_format;
 pub mod validator;
 pub mod wiretypes;
+
+/// Lets generated code, which refers to `picopb::`, be included in this crate
+extern crate self as picopb;
+
+/// Re-exported for the `ArrayString` type used by generated code
+pub use arraystring;
+
+/// Re-exported for the `ArrayVec` type of bounded repeated fields in generated code
+pub use arrayvec;
@@ -0,0 +1,310 @@
+use crate::common::{FieldQualifier, FieldType};
+use crate::parser::ProtoParser;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::str::FromStr;
+
+/// This module checks a schema for style and for constructs that are
+/// costly on embedded targets. Unlike the validator it never rejects a
+/// schema, every rule can be enabled or disabled in `LintConfig`.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LintRule {
+    /// Message and enum names are UpperCamelCase
+    TypeName,
+    /// Field names are snake_case
+    FieldName,
+    /// Enum values are UPPER_SNAKE_CASE
+    EnumValueName,
+    /// Enum values start with the UPPER_SNAKE_CASE name of the enum, `Unit` has `UNIT_CELSIUS`
+    EnumValuePrefix,
+    /// The zero value of an enum is named `<PREFIX>_UNSPECIFIED`
+    EnumZeroValue,
+    /// Strings, bytes and repeated fields without `(nanopb).max_size` are
+    /// heap allocated and have no maximum encoded size
+    UnboundedField,
+    /// No field refers to a type of the imported file
+    UnusedImport,
+    /// No field refers to the message and it has no `(nanopb_msgopt).msgid`,
+    /// the messages with one are sent on their own. Not checked by default
+    /// as any message may be sent on its own
+    UnusedMessage,
+}
+
+impl LintRule {
+    pub const ALL: [LintRule; 8] = [
+        Self::TypeName,
+        Self::FieldName,
+        Self::EnumValueName,
+        Self::EnumValuePrefix,
+        Self::EnumZeroValue,
+        Self::UnboundedField,
+        Self::UnusedImport,
+        Self::UnusedMessage,
+    ];
+
+    /// The name used to configure the rule, `type-name`
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::TypeName => "type-name",
+            Self::FieldName => "field-name",
+            Self::EnumValueName => "enum-value-name",
+            Self::EnumValuePrefix => "enum-value-prefix",
+            Self::EnumZeroValue => "enum-zero-value",
+            Self::UnboundedField => "unbounded-field",
+            Self::UnusedImport => "unused-import",
+            Self::UnusedMessage => "unused-message",
+        }
+    }
+
+    /// Whether `LintConfig::default` checks the rule
+    pub fn is_default(&self) -> bool {
+        !matches!(self, Self::UnusedMessage)
+    }
+}
+
+impl fmt::Display for LintRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for LintRule {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|rule| rule.name() == name)
+            .ok_or_else(|| format!("unknown lint rule {:?}", name))
+    }
+}
+
+/// The rules to check, all of them but `unused-message` by default
+#[derive(Debug, Clone, PartialEq)]
+pub struct LintConfig {
+    pub rules: BTreeSet<LintRule>,
+}
+
+impl Default for LintConfig {
+    fn default() -> Self {
+        Self {
+            rules: LintRule::ALL
+                .into_iter()
+                .filter(LintRule::is_default)
+                .collect(),
+        }
+    }
+}
+
+impl LintConfig {
+    pub fn enable(&mut self, rule: LintRule) {
+        self.rules.insert(rule);
+    }
+
+    pub fn disable(&mut self, rule: LintRule) {
+        self.rules.remove(&rule);
+    }
+
+    pub fn is_enabled(&self, rule: LintRule) -> bool {
+        self.rules.contains(&rule)
+    }
+}
+
+/// A rule the schema does not follow at `path`: `Type`, `Type.field`,
+/// `Enum.VALUE` or an import
+#[derive(Debug, Clone, PartialEq)]
+pub struct Lint {
+    pub rule: LintRule,
+    pub path: String,
+    pub message: String,
+}
+
+/// Written as `rule: path: message`
+impl fmt::Display for Lint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}: {}", self.rule, self.path, self.message)
+    }
+}
+
+fn is_upper_camel_case(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_uppercase())
+        && name.chars().all(|c| c.is_ascii_alphanumeric())
+}
+
+fn is_snake_case(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_lowercase())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+}
+
+fn is_upper_snake_case(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_uppercase())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+}
+
+/// `SensorUnit` is `SENSOR_UNIT`, `HTTPStatus` is `HTTP_STATUS`
+fn upper_snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut result = String::new();
+    for (index, c) in chars.iter().enumerate() {
+        let previous = index.checked_sub(1).map(|index| chars[index]);
+        let next = chars.get(index + 1);
+        let word_start = c.is_ascii_uppercase()
+            && previous.is_some_and(|previous| {
+                previous.is_ascii_lowercase()
+                    || previous.is_ascii_digit()
+                    || (previous.is_ascii_uppercase() && next.is_some_and(char::is_ascii_lowercase))
+            });
+        if word_start {
+            result.push('_');
+        }
+        result.push(c.to_ascii_uppercase());
+    }
+    result
+}
+
+struct Linter<'c> {
+    config: &'c LintConfig,
+    lints: Vec<Lint>,
+}
+
+impl Linter<'_> {
+    fn push(&mut self, rule: LintRule, path: String, message: String) {
+        if self.config.is_enabled(rule) {
+            self.lints.push(Lint {
+                rule,
+                path,
+                message,
+            });
+        }
+    }
+
+    fn type_name(&mut self, name: &str, kind: &str) {
+        if !is_upper_camel_case(name) {
+            let message = format!("{} name should be UpperCamelCase", kind);
+            self.push(LintRule::TypeName, name.to_string(), message);
+        }
+    }
+
+    fn enum_values(&mut self, name: &str, values: &BTreeMap<String, i32>) {
+        let prefix = format!("{}_", upper_snake_case(name));
+        let mut values: Vec<(&String, &i32)> = values.iter().collect();
+        values.sort_by_key(|(identifier, number)| (**number, *identifier));
+        for (identifier, _) in values.iter() {
+            let path = format!("{}.{}", name, identifier);
+            if !is_upper_snake_case(identifier) {
+                let message = "enum value name should be UPPER_SNAKE_CASE".to_string();
+                self.push(LintRule::EnumValueName, path.clone(), message);
+            }
+            if !identifier.starts_with(&prefix) {
+                let message = format!("enum value name should start with {}", prefix);
+                self.push(LintRule::EnumValuePrefix, path, message);
+            }
+        }
+        let unspecified = format!("{}UNSPECIFIED", prefix);
+        // with allow_alias one of several zero values may be the unspecified one
+        let zeros: Vec<&String> = values
+            .iter()
+            .filter(|(_, number)| **number == 0)
+            .map(|(identifier, _)| *identifier)
+            .collect();
+        match zeros.first() {
+            _ if zeros.contains(&&unspecified) => {}
+            Some(identifier) => {
+                let message = format!("the zero value should be named {}", unspecified);
+                self.push(
+                    LintRule::EnumZeroValue,
+                    format!("{}.{}", name, identifier),
+                    message,
+                );
+            }
+            None => {
+                let message = format!("the enum should have a zero value named {}", unspecified);
+                self.push(LintRule::EnumZeroValue, name.to_string(), message);
+            }
+        }
+    }
+}
+
+/// The names of the message and enum types the schema defines
+fn type_names<'s>(schema: &'s ProtoParser) -> BTreeSet<&'s str> {
+    let messages = schema.message_types.keys();
+    messages
+        .chain(schema.enum_types.keys())
+        .map(String::as_str)
+        .collect()
+}
+
+/// Checks the schema with the enabled rules. `imports` are the parsed files
+/// the schema imports, by their name in the import statement. Imports
+/// missing from it are not checked
+pub fn lint(
+    schema: &ProtoParser,
+    imports: &[(&str, &ProtoParser)],
+    config: &LintConfig,
+) -> Vec<Lint> {
+    let mut linter = Linter {
+        config,
+        lints: Vec::new(),
+    };
+    let mut used = BTreeSet::new();
+    let messages: BTreeMap<_, _> = schema.message_types.iter().collect();
+    for (name, message_type) in messages.iter() {
+        linter.type_name(name, "message");
+        for field in message_type.fields.values() {
+            let path = format!("{}.{}", name, field.identifier);
+            if !is_snake_case(&field.identifier) {
+                let message = "field name should be snake_case".to_string();
+                linter.push(LintRule::FieldName, path.clone(), message);
+            }
+            let unbounded = match (&field.qualifier, &field.field_type) {
+                (
+                    FieldQualifier::RepeatedUnbounded | FieldQualifier::PackedRepeatedUnbounded,
+                    _,
+                ) => Some("the repeated values are"),
+                (_, FieldType::UnboundedString | FieldType::UnboundedBytes) => Some("the value is"),
+                _ => None,
+            };
+            if let Some(values) = unbounded {
+                let message = format!("no (nanopb).max_size, {} heap allocated", values);
+                linter.push(LintRule::UnboundedField, path, message);
+            }
+            if let FieldType::MessageType(identifier) | FieldType::EnumType(identifier) =
+                field.field_type
+            {
+                used.insert(identifier);
+            }
+        }
+    }
+    for (name, message_type) in messages.iter() {
+        if message_type.msgid.is_none() && !used.contains(name.as_str()) {
+            let message = "no field refers to the message and it has no msgid".to_string();
+            linter.push(LintRule::UnusedMessage, name.to_string(), message);
+        }
+    }
+
+    let enums: BTreeMap<_, _> = schema.enum_types.iter().collect();
+    for (name, enum_type) in enums.iter() {
+        linter.type_name(name, "enum");
+        linter.enum_values(name, &enum_type.pairs);
+    }
+
+    let defined = type_names(schema);
+    for import in schema.imports.iter() {
+        let Some((_, imported)) = imports.iter().find(|(name, _)| name == import) else {
+            continue;
+        };
+        let provides_used = type_names(imported)
+            .into_iter()
+            .any(|name| used.contains(name) && !defined.contains(name));
+        if !provides_used {
+            let message = "no field refers to a type of the imported file".to_string();
+            linter.push(LintRule::UnusedImport, import.clone(), message);
+        }
+    }
+    linter.lints
+}
@@ -0,0 +1,879 @@
+use crate::common::{FieldQualifier, FieldType};
+use crate::format::format;
+use crate::json::{self, FromJson, JsonValue, ToJson};
+use crate::lint::{lint, LintConfig};
+use crate::parser::{parse, ParserError, PicoPBParser, ProtoParser, Rule};
+use crate::validator::{validate, ValidatorError};
+use pest::error::InputLocation;
+use pest::Parser;
+use std::collections::BTreeMap;
+use std::io::{self, BufRead, Write};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use typed_arena::Arena;
+
+/// This module implements `picopb-lsp`, a language server for .proto files
+/// speaking JSON-RPC over stdio. Documents are synchronized in full, and
+/// give parser and validator errors and lints as diagnostics, go-to-definition
+/// and hover for types and fields, completion of type names and options, and
+/// formatting with `picopb fmt`. Types are looked up in the open documents.
+/// Imports are read next to the document, from the open documents or disk.
+
+#[derive(Debug)]
+struct FieldSite {
+    message: String,
+    name: String,
+    name_range: Range<usize>,
+    type_name: String,
+    type_range: Range<usize>,
+    /// From the qualifier to the last option, the span of the statement
+    /// would include the comments after it
+    range: Range<usize>,
+}
+
+/// The definitions of a document and where they are, read from the parse tree
+#[derive(Debug, Default)]
+struct Outline {
+    /// Whether each is a message or an enum, its name and where its name is
+    types: Vec<(&'static str, String, Range<usize>)>,
+    fields: Vec<FieldSite>,
+    /// `Enum.VALUE` and where the value name is
+    values: Vec<(String, Range<usize>)>,
+    /// The imported file and where its string is
+    imports: Vec<(String, Range<usize>)>,
+}
+
+impl Outline {
+    /// Outlines a document, `None` if it does not parse
+    fn read(text: &str) -> Option<Self> {
+        let definition = PicoPBParser::parse(Rule::proto_definition, text).ok()?;
+        let mut outline = Outline::default();
+        let mut current = String::new();
+        for pair in definition.flatten() {
+            let range = pair.as_span().start()..pair.as_span().end();
+            let mut inner = pair.clone().into_inner();
+            match pair.as_rule() {
+                Rule::message_definition | Rule::enum_definition => {
+                    let kind = match pair.as_rule() {
+                        Rule::message_definition => "message",
+                        _ => "enum",
+                    };
+                    let identifier = inner.next()?;
+                    current = identifier.as_str().to_string();
+                    let name_range = identifier.as_span().start()..identifier.as_span().end();
+                    outline.types.push((kind, current.clone(), name_range));
+                }
+                Rule::message_field => {
+                    let (_, field_type, identifier) = (inner.next()?, inner.next()?, inner.next()?);
+                    let end = pair.clone().into_inner().flatten().last()?.as_span().end();
+                    outline.fields.push(FieldSite {
+                        message: current.clone(),
+                        name: identifier.as_str().to_string(),
+                        name_range: identifier.as_span().start()..identifier.as_span().end(),
+                        type_name: field_type.as_str().to_string(),
+                        type_range: field_type.as_span().start()..field_type.as_span().end(),
+                        range: range.start..end,
+                    });
+                }
+                Rule::enum_field => {
+                    let identifier = inner.next()?;
+                    let path = format!("{}.{}", current, identifier.as_str());
+                    let name_range = identifier.as_span().start()..identifier.as_span().end();
+                    outline.values.push((path, name_range));
+                }
+                Rule::import_statement => {
+                    let string = inner.find(|pair| pair.as_rule() == Rule::string)?;
+                    let name = string.as_str().trim_matches(['"', '\'']).to_string();
+                    let range = string.as_span().start()..string.as_span().end();
+                    outline.imports.push((name, range));
+                }
+                _ => {}
+            }
+        }
+        Some(outline)
+    }
+
+    /// Where the name of `Type`, `Type.field`, `Enum.VALUE` or an import is
+    fn locate(&self, path: &str) -> Option<Range<usize>> {
+        let types = self
+            .types
+            .iter()
+            .map(|(_, name, range)| (name.clone(), range));
+        let fields = self.fields.iter().map(|field| {
+            let path = format!("{}.{}", field.message, field.name);
+            (path, &field.name_range)
+        });
+        let values = self
+            .values
+            .iter()
+            .map(|(path, range)| (path.clone(), range));
+        let imports = self
+            .imports
+            .iter()
+            .map(|(name, range)| (name.clone(), range));
+        types
+            .chain(fields)
+            .chain(values)
+            .chain(imports)
+            .find(|(name, _)| name == path)
+            .map(|(_, range)| range.clone())
+    }
+
+    fn definition(&self, name: &str) -> Option<&(&'static str, String, Range<usize>)> {
+        self.types
+            .iter()
+            .find(|(_, type_name, _)| type_name == name)
+    }
+}
+
+/// The JSON-RPC error codes the server uses
+const PARSE_ERROR: i32 = -32700;
+const INVALID_REQUEST: i32 = -32600;
+const METHOD_NOT_FOUND: i32 = -32601;
+const INVALID_PARAMS: i32 = -32602;
+
+const SEVERITY_ERROR: u32 = 1;
+const SEVERITY_WARNING: u32 = 2;
+
+const COMPLETION_CLASS: u32 = 7;
+const COMPLETION_PROPERTY: u32 = 10;
+const COMPLETION_ENUM: u32 = 13;
+const COMPLETION_KEYWORD: u32 = 14;
+
+const SCALAR_TYPES: [&str; 7] = [
+    "bool", "int32", "int64", "uint32", "uint64", "string", "bytes",
+];
+/// Options offered in the `[...]` of fields and enum values
+const FIELD_OPTIONS: [&str; 5] = [
+    "(nanopb).max_size",
+    "(nanopb).max_len",
+    "packed",
+    "json_name",
+    "deprecated",
+];
+/// Options offered after `option` in messages and enums
+const STATEMENT_OPTIONS: [&str; 3] = ["(nanopb_msgopt).msgid", "allow_alias", "deprecated"];
+
+fn object(members: Vec<(&str, JsonValue)>) -> JsonValue {
+    JsonValue::Object(
+        members
+            .into_iter()
+            .map(|(name, value)| (name.to_string(), value))
+            .collect(),
+    )
+}
+
+fn string(value: impl Into<String>) -> JsonValue {
+    JsonValue::String(value.into())
+}
+
+fn number(value: impl std::fmt::Display) -> JsonValue {
+    JsonValue::Number(value.to_string())
+}
+
+/// The LSP position of a byte offset, characters are counted in UTF-16 code units
+fn position(text: &str, offset: usize) -> JsonValue {
+    let before = &text[..offset];
+    let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+    let line = before.matches('\n').count();
+    let character: usize = before[line_start..].chars().map(char::len_utf16).sum();
+    object(vec![
+        ("line", number(line)),
+        ("character", number(character)),
+    ])
+}
+
+/// The byte offset of an LSP position, positions past the end of a line are at its end
+fn offset(text: &str, line: u32, character: u32) -> usize {
+    let line_start = match line {
+        0 => 0,
+        line => match text.match_indices('\n').nth(line as usize - 1) {
+            Some((index, _)) => index + 1,
+            None => return text.len(),
+        },
+    };
+    let mut units = 0;
+    for (index, c) in text[line_start..].char_indices() {
+        if units >= character as usize || c == '\n' {
+            return line_start + index;
+        }
+        units += c.len_utf16();
+    }
+    text.len()
+}
+
+fn range(text: &str, range: Range<usize>) -> JsonValue {
+    object(vec![
+        ("start", position(text, range.start)),
+        ("end", position(text, range.end)),
+    ])
+}
+
+/// Where the parser error is, the start of the document when it has no position
+fn parser_error_range(err: &ParserError) -> Range<usize> {
+    match err {
+        ParserError::PestRuleError(err) => match err.location {
+            InputLocation::Pos(position) => position..position,
+            InputLocation::Span((start, end)) => start..end,
+        },
+        ParserError::InvalidProtoDefinition(span)
+        | ParserError::ExpectedStatement(span)
+        | ParserError::InvalidVersionDeclaration(span)
+        | ParserError::InvalidProtoVersion(span)
+        | ParserError::DuplicateProtoVersion(span)
+        | ParserError::ImportMustBeNonEmpty(span)
+        | ParserError::ExpectedOption(span)
+        | ParserError::ExpectedNonempty(span)
+        | ParserError::ExpectedPredicateMatchButGot(span, _)
+        | ParserError::ExpectedRule(span, _)
+        | ParserError::ExpectedRuleButGot(span, _, _)
+        | ParserError::InvalidOptionValue(span, _)
+        | ParserError::UnknownOption(span, _)
+        | ParserError::ParseIntError(span, _)
+        | ParserError::ExpectedButGot(span, _, _)
+        | ParserError::InvalidEscape(span)
+        | ParserError::LegacyFieldOptions(span) => span.start..span.end,
+        ParserError::ExpectedOptionValue => 0..0,
+    }
+}
+
+/// Where the validator error is, the start of the document when it is not
+/// about a single definition
+fn validator_error_range(
+    err: &ValidatorError,
+    schema: &ProtoParser,
+    outline: &Outline,
+) -> Range<usize> {
+    let found = match err {
+        ValidatorError::MissingTypeDefinition(name) => outline
+            .fields
+            .iter()
+            .find(|field| &field.type_name == name)
+            .map(|field| field.type_range.clone()),
+        ValidatorError::DuplicateEnumValue(name, _) => outline.locate(name),
+        // the second message with the id
+        ValidatorError::DuplicateMessageId(msgid) => outline
+            .types
+            .iter()
+            .filter(|(_, name, _)| {
+                schema
+                    .message_types
+                    .get(name)
+                    .is_some_and(|message| message.msgid == Some(*msgid))
+            })
+            .nth(1)
+            .map(|(_, _, range)| range.clone()),
+        ValidatorError::InvalidFieldNumber(path, _)
+        | ValidatorError::InvalidPackedField(path)
+        | ValidatorError::StringTooLong(path, _) => outline.locate(path),
+        ValidatorError::InvalidProtoVersion => None,
+    };
+    found.unwrap_or(0..0)
+}
+
+fn diagnostic(
+    text: &str,
+    at: Range<usize>,
+    severity: u32,
+    code: Option<&str>,
+    message: String,
+) -> JsonValue {
+    let mut members = vec![
+        ("range", range(text, at)),
+        ("severity", number(severity)),
+        ("source", string("picopb")),
+        ("message", string(message)),
+    ];
+    if let Some(code) = code {
+        members.push(("code", string(code)));
+    }
+    object(members)
+}
+
+/// The path of a `file://` URI, with percent escapes decoded
+fn uri_path(uri: &str) -> Option<PathBuf> {
+    let mut rest = uri.strip_prefix("file://")?.as_bytes();
+    let mut bytes = Vec::new();
+    while let Some((&c, tail)) = rest.split_first() {
+        let escaped = tail
+            .get(..2)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (c, escaped) {
+            (b'%', Some(byte)) => {
+                bytes.push(byte);
+                rest = &tail[2..];
+            }
+            _ => {
+                bytes.push(c);
+                rest = tail;
+            }
+        }
+    }
+    String::from_utf8(bytes).ok().map(PathBuf::from)
+}
+
+/// Where the import statement names the file, the start of the document
+/// for the imports of imported files
+fn import_range(text: &str, name: &str) -> Range<usize> {
+    let quoted = format!("\"{}\"", name);
+    match text.find(&quoted) {
+        Some(start) => start..start + quoted.len(),
+        None => 0..0,
+    }
+}
+
+/// A file imported by a document, directly or by one of its imports
+struct Import<'s> {
+    /// As written in the import statement
+    name: String,
+    path: PathBuf,
+    schema: ProtoParser<'s>,
+}
+
+fn field_type_text(field_type: &FieldType) -> String {
+    match field_type {
+        FieldType::UnboundedString => "string".into(),
+        FieldType::UnboundedBytes => "bytes".into(),
+        FieldType::String(max_size) => format!("string, max_size {}", max_size),
+        FieldType::Bytes(max_size) => format!("bytes, max_size {}", max_size),
+        FieldType::Bool => "bool".into(),
+        FieldType::Int32 => "int32".into(),
+        FieldType::Int64 => "int64".into(),
+        FieldType::Uint32 => "uint32".into(),
+        FieldType::Uint64 => "uint64".into(),
+        FieldType::EnumType(name) => format!("enum `{}`", name),
+        FieldType::MessageType(name) => format!("message `{}`", name),
+    }
+}
+
+fn qualifier_text(qualifier: &FieldQualifier) -> String {
+    match qualifier {
+        FieldQualifier::Optional => "optional".into(),
+        FieldQualifier::Required => "required".into(),
+        FieldQualifier::RepeatedUnbounded => "repeated".into(),
+        FieldQualifier::Repeated(count) => format!("repeated, at most {}", count),
+        FieldQualifier::PackedRepeatedUnbounded => "repeated packed".into(),
+        FieldQualifier::PackedRepeated(count) => format!("repeated packed, at most {}", count),
+    }
+}
+
+/// The language server. Every message from the client is handled by
+/// `handle`, which returns the responses and notifications to send back
+#[derive(Debug, Default)]
+pub struct Server {
+    /// The text of the open documents by their URI
+    documents: BTreeMap<String, String>,
+    /// The lints reported as warnings
+    pub lint: LintConfig,
+    shutdown: bool,
+    exit: bool,
+}
+
+type RequestResult = std::result::Result<JsonValue, (i32, String)>;
+
+fn invalid_params(what: &str) -> (i32, String) {
+    (INVALID_PARAMS, format!("missing or invalid {}", what))
+}
+
+impl Server {
+    /// Whether the client asked the server to shut down
+    pub fn is_shutdown(&self) -> bool {
+        self.shutdown
+    }
+
+    /// Whether the client sent `exit`, the server should stop
+    pub fn is_exited(&self) -> bool {
+        self.exit
+    }
+
+    pub fn handle(&mut self, message: &JsonValue) -> Vec<JsonValue> {
+        let Some(method) = message
+            .get("method")
+            .and_then(|method| method.as_str().ok())
+        else {
+            // responses from the client, the server sends no requests
+            return Vec::new();
+        };
+        let params = message.get("params").unwrap_or(&JsonValue::Null);
+        let Some(id) = message.get("id") else {
+            return self.notification(method, params);
+        };
+        let result = match self.shutdown && method != "shutdown" {
+            true => Err((INVALID_REQUEST, "the server is shut down".into())),
+            false => self.request(method, params),
+        };
+        let result = match result {
+            Ok(result) => ("result", result),
+            Err((code, message)) => (
+                "error",
+                object(vec![("code", number(code)), ("message", string(message))]),
+            ),
+        };
+        vec![object(vec![
+            ("jsonrpc", string("2.0")),
+            ("id", id.clone()),
+            result,
+        ])]
+    }
+
+    fn request(&mut self, method: &str, params: &JsonValue) -> RequestResult {
+        match method {
+            "initialize" => Ok(Self::capabilities()),
+            "shutdown" => {
+                self.shutdown = true;
+                Ok(JsonValue::Null)
+            }
+            "textDocument/definition" => self.definition(params),
+            "textDocument/hover" => self.hover(params),
+            "textDocument/completion" => self.completion(params),
+            "textDocument/formatting" => self.formatting(params),
+            _ => Err((METHOD_NOT_FOUND, format!("unknown method {}", method))),
+        }
+    }
+
+    fn capabilities() -> JsonValue {
+        object(vec![
+            (
+                "capabilities",
+                object(vec![
+                    // the full text is sent on every change
+                    ("textDocumentSync", number(1)),
+                    ("definitionProvider", JsonValue::Bool(true)),
+                    ("hoverProvider", JsonValue::Bool(true)),
+                    ("completionProvider", object(Vec::new())),
+                    ("documentFormattingProvider", JsonValue::Bool(true)),
+                ]),
+            ),
+            (
+                "serverInfo",
+                object(vec![
+                    ("name", string("picopb-lsp")),
+                    ("version", string(env!("CARGO_PKG_VERSION"))),
+                ]),
+            ),
+        ])
+    }
+
+    fn notification(&mut self, method: &str, params: &JsonValue) -> Vec<JsonValue> {
+        let document = params.get("textDocument");
+        let Some(uri) = document
+            .and_then(|document| document.get("uri"))
+            .and_then(|uri| uri.as_str().ok())
+        else {
+            if method == "exit" {
+                self.exit = true;
+            }
+            return Vec::new();
+        };
+        let text = match method {
+            "textDocument/didOpen" => document.and_then(|document| document.get("text")),
+            // the last change holds the full text
+            "textDocument/didChange" => params
+                .get("contentChanges")
+                .and_then(|changes| changes.as_array().ok())
+                .and_then(|changes| changes.last())
+                .and_then(|change| change.get("text")),
+            "textDocument/didClose" => {
+                self.documents.remove(uri);
+                return vec![Self::publish(uri, Vec::new())];
+            }
+            _ => return Vec::new(),
+        };
+        match text.and_then(|text| text.as_str().ok()) {
+            Some(text) => {
+                self.documents.insert(uri.to_string(), text.to_string());
+                vec![Self::publish(uri, self.diagnostics(uri, text))]
+            }
+            None => Vec::new(),
+        }
+    }
+
+    fn publish(uri: &str, diagnostics: Vec<JsonValue>) -> JsonValue {
+        object(vec![
+            ("jsonrpc", string("2.0")),
+            ("method", string("textDocument/publishDiagnostics")),
+            (
+                "params",
+                object(vec![
+                    ("uri", string(uri)),
+                    ("diagnostics", JsonValue::Array(diagnostics)),
+                ]),
+            ),
+        ])
+    }
+
+    /// The text of the file, from the open document when there is one
+    fn read_file(&self, path: &Path) -> Option<String> {
+        let open = self
+            .documents
+            .iter()
+            .find(|(uri, _)| uri_path(uri).as_deref() == Some(path));
+        match open {
+            Some((_, text)) => Some(text.clone()),
+            None => std::fs::read_to_string(path).ok(),
+        }
+    }
+
+    /// Reads and parses the files the importer imports by these names, and
+    /// their imports, next to the importer. The imports that can not be read
+    /// or parsed are added to `failed` with the error message
+    fn read_imports<'s>(
+        &self,
+        importer: &Path,
+        names: &[String],
+        arena: &'s Arena<String>,
+        imports: &mut Vec<Import<'s>>,
+        failed: &mut Vec<(String, String)>,
+    ) {
+        for name in names {
+            let path = importer.parent().unwrap_or(Path::new("")).join(name);
+            // every file is read once, which also ends import cycles
+            if imports.iter().any(|import| import.path == path) {
+                continue;
+            }
+            let Some(text) = self.read_file(&path) else {
+                failed.push((name.clone(), format!("import \"{}\" not found", name)));
+                continue;
+            };
+            match parse(arena.alloc(text)) {
+                Ok(schema) => {
+                    let nested = schema.imports.clone();
+                    imports.push(Import {
+                        name: name.clone(),
+                        path: path.clone(),
+                        schema,
+                    });
+                    self.read_imports(&path, &nested, arena, imports, failed);
+                }
+                Err(err) => {
+                    let message = format!("import \"{}\": {}", name, err);
+                    failed.push((name.clone(), message))
+                }
+            }
+        }
+    }
+
+    /// The parser error, or the errors of the imports, the validator error and
+    /// the lints of the document
+    fn diagnostics(&self, uri: &str, text: &str) -> Vec<JsonValue> {
+        let schema = match parse(text) {
+            Ok(schema) => schema,
+            Err(err) => {
+                let at = parser_error_range(&err);
+                let message = err.to_string();
+                return vec![diagnostic(text, at, SEVERITY_ERROR, None, message)];
+            }
+        };
+        let outline = Outline::read(text).unwrap_or_default();
+        let mut diagnostics = Vec::new();
+        let arena = Arena::new();
+        let mut imports = Vec::new();
+        let mut failed = Vec::new();
+        match uri_path(uri) {
+            Some(path) => {
+                self.read_imports(&path, &schema.imports, &arena, &mut imports, &mut failed)
+            }
+            // a document that is not saved has no directory to look in
+            None => failed.extend(
+                schema
+                    .imports
+                    .iter()
+                    .map(|name| (name.clone(), format!("import \"{}\" not found", name))),
+            ),
+        }
+        for (name, message) in failed {
+            let at = import_range(text, &name);
+            diagnostics.push(diagnostic(text, at, SEVERITY_ERROR, None, message));
+        }
+        // validated with the types of the imports, the document's own take precedence
+        let mut resolved = schema.clone();
+        for import in imports.iter() {
+            for (name, enum_type) in import.schema.enum_types.iter() {
+                resolved
+                    .enum_types
+                    .entry(name.clone())
+                    .or_insert_with(|| enum_type.clone());
+            }
+            for (name, message_type) in import.schema.message_types.iter() {
+                resolved
+                    .message_types
+                    .entry(name.clone())
+                    .or_insert_with(|| message_type.clone());
+            }
+        }
+        resolved.resolve_enum_fields();
+        if let Err(err) = validate(&resolved) {
+            let at = validator_error_range(&err, &resolved, &outline);
+            diagnostics.push(diagnostic(text, at, SEVERITY_ERROR, None, err.to_string()));
+        }
+        let imports: Vec<_> = imports
+            .iter()
+            .map(|import| (import.name.as_str(), &import.schema))
+            .collect();
+        for found in lint(&schema, &imports, &self.lint) {
+            let at = outline.locate(&found.path).unwrap_or(0..0);
+            let code = Some(found.rule.name());
+            diagnostics.push(diagnostic(text, at, SEVERITY_WARNING, code, found.message));
+        }
+        diagnostics
+    }
+
+    /// The URI and text of the document and the offset of the position in the parameters
+    fn document_position<'s>(
+        &'s self,
+        params: &'s JsonValue,
+    ) -> std::result::Result<(&'s str, &'s str, usize), (i32, String)> {
+        let (uri, text) = self.document(params)?;
+        let position = params
+            .get("position")
+            .ok_or_else(|| invalid_params("position"))?;
+        let coordinate = |name| {
+            position
+                .get(name)
+                .ok_or(json::JsonError::UnexpectedType("number"))
+                .and_then(u32::from_json)
+                .map_err(|_| invalid_params("position"))
+        };
+        let at = offset(text, coordinate("line")?, coordinate("character")?);
+        Ok((uri, text, at))
+    }
+
+    fn document<'s>(
+        &'s self,
+        params: &'s JsonValue,
+    ) -> std::result::Result<(&'s str, &'s str), (i32, String)> {
+        let uri = params
+            .get("textDocument")
+            .and_then(|document| document.get("uri"))
+            .and_then(|uri| uri.as_str().ok())
+            .ok_or_else(|| invalid_params("textDocument"))?;
+        let text = self
+            .documents
+            .get(uri)
+            .ok_or_else(|| (INVALID_PARAMS, format!("{} is not open", uri)))?;
+        Ok((uri, text))
+    }
+
+    /// Goes from a field type to the message or enum, in the document or
+    /// else in the other open documents
+    fn definition(&self, params: &JsonValue) -> RequestResult {
+        let (uri, text, at) = self.document_position(params)?;
+        let Some(outline) = Outline::read(text) else {
+            return Ok(JsonValue::Null);
+        };
+        let Some(field) = outline
+            .fields
+            .iter()
+            .find(|field| field.type_range.contains(&at) || field.type_range.end == at)
+        else {
+            return Ok(JsonValue::Null);
+        };
+        let others = self
+            .documents
+            .iter()
+            .filter(|(other, _)| other.as_str() != uri);
+        let documents = [(uri, text)]
+            .into_iter()
+            .chain(others.map(|(uri, text)| (uri.as_str(), text.as_str())));
+        for (uri, text) in documents {
+            let found = Outline::read(text).and_then(|outline| {
+                let (_, _, at) = outline.definition(&field.type_name)?;
+                Some(at.clone())
+            });
+            if let Some(at) = found {
+                return Ok(object(vec![
+                    ("uri", string(uri)),
+                    ("range", range(text, at)),
+                ]));
+            }
+        }
+        Ok(JsonValue::Null)
+    }
+
+    /// Describes the field, message or enum under the position
+    fn hover(&self, params: &JsonValue) -> RequestResult {
+        let (_, text, at) = self.document_position(params)?;
+        let (Some(outline), Ok(schema)) = (Outline::read(text), parse(text)) else {
+            return Ok(JsonValue::Null);
+        };
+        let contains = |range: &Range<usize>| range.contains(&at) || range.end == at;
+        let found = if let Some(site) = outline.fields.iter().find(|field| contains(&field.range)) {
+            let field = schema
+                .message_types
+                .get(&site.message)
+                .and_then(|message| message.fields.values().find(|f| f.identifier == site.name));
+            field.map(|field| {
+                let description = format!(
+                    "field `{}` = {}: {} {}",
+                    field.identifier,
+                    field.ordinal.0,
+                    qualifier_text(&field.qualifier),
+                    field_type_text(&field.field_type)
+                );
+                (description, site.range.clone())
+            })
+        } else if let Some((kind, name, name_range)) =
+            outline.types.iter().find(|(_, _, range)| contains(range))
+        {
+            let description = match (*kind, schema.message_types.get(name)) {
+                ("message", Some(message)) => {
+                    let mut description =
+                        format!("message `{}`, {} fields", name, message.fields.len());
+                    if let Some(msgid) = message.msgid {
+                        description += &format!(", msgid {}", msgid);
+                    }
+                    description
+                }
+                _ => {
+                    let values = schema.enum_types.get(name).map_or(0, |e| e.pairs.len());
+                    format!("enum `{}`, {} values", name, values)
+                }
+            };
+            Some((description, name_range.clone()))
+        } else {
+            None
+        };
+        Ok(match found {
+            Some((description, at)) => object(vec![
+                (
+                    "contents",
+                    object(vec![
+                        ("kind", string("markdown")),
+                        ("value", string(description)),
+                    ]),
+                ),
+                ("range", range(text, at)),
+            ]),
+            None => JsonValue::Null,
+        })
+    }
+
+    /// Options inside `[...]` and after `option`, otherwise type names
+    fn completion(&self, params: &JsonValue) -> RequestResult {
+        let (_, text, at) = self.document_position(params)?;
+        let line = &text[text[..at].rfind('\n').map_or(0, |index| index + 1)..at];
+        let item = |label: &str, kind: u32, detail: &str| {
+            object(vec![
+                ("label", string(label)),
+                ("kind", number(kind)),
+                ("detail", string(detail)),
+            ])
+        };
+        let items: Vec<JsonValue> = if line.rfind('[') > line.rfind(']') {
+            FIELD_OPTIONS
+                .iter()
+                .map(|option| item(option, COMPLETION_PROPERTY, "field option"))
+                .collect()
+        } else if line.split_whitespace().next() == Some("option") {
+            STATEMENT_OPTIONS
+                .iter()
+                .map(|option| item(option, COMPLETION_PROPERTY, "option"))
+                .collect()
+        } else {
+            let mut items: Vec<JsonValue> = SCALAR_TYPES
+                .iter()
+                .map(|name| item(name, COMPLETION_KEYWORD, "scalar type"))
+                .collect();
+            let mut types = BTreeMap::new();
+            for text in self.documents.values() {
+                for (kind, name, _) in Outline::read(text).unwrap_or_default().types {
+                    types.insert(name, kind);
+                }
+            }
+            for (name, kind) in types {
+                let completion_kind = match kind {
+                    "message" => COMPLETION_CLASS,
+                    _ => COMPLETION_ENUM,
+                };
+                items.push(item(&name, completion_kind, kind));
+            }
+            items
+        };
+        Ok(JsonValue::Array(items))
+    }
+
+    /// Replaces the whole document with the formatted text, no edits when it
+    /// is formatted already or does not parse
+    fn formatting(&self, params: &JsonValue) -> RequestResult {
+        let (_, text) = self.document(params)?;
+        let edits = match format(text) {
+            Ok(formatted) if formatted != text => vec![object(vec![
+                ("range", range(text, 0..text.len())),
+                ("newText", string(formatted)),
+            ])],
+            _ => Vec::new(),
+        };
+        Ok(JsonValue::Array(edits))
+    }
+}
+
+/// Messages longer than this are rejected instead of being read into memory
+pub const MAX_MESSAGE_LEN: usize = 64 * 1024 * 1024;
+
+/// Reads the body of the next message, `None` at the end of the input
+pub fn read_message<R: BufRead>(input: &mut R) -> io::Result<Option<String>> {
+    let mut length = None;
+    loop {
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                length = value.trim().parse::<usize>().ok();
+            }
+        }
+    }
+    let length = length.ok_or_else(|| {
+        io::Error::new(io::ErrorKind::InvalidData, "missing Content-Length header")
+    })?;
+    if length > MAX_MESSAGE_LEN {
+        let message = format!("Content-Length {} is above {}", length, MAX_MESSAGE_LEN);
+        return Err(io::Error::new(io::ErrorKind::InvalidData, message));
+    }
+    let mut body = vec![0; length];
+    input.read_exact(&mut body)?;
+    String::from_utf8(body)
+        .map(Some)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
+
+pub fn write_message<W: Write>(output: &mut W, message: &JsonValue) -> io::Result<()> {
+    let body = message.to_json();
+    write!(output, "Content-Length: {}\r\n\r\n{}", body.len(), body)?;
+    output.flush()
+}
+
+/// Serves the client until it sends `exit` or closes the input. Returns
+/// whether it asked the server to shut down first, as the exit code should tell
+pub fn serve<R: BufRead, W: Write>(mut input: R, mut output: W) -> io::Result<bool> {
+    let mut server = Server::default();
+    while let Some(body) = read_message(&mut input)? {
+        let replies = match json::parse(&body) {
+            Ok(message) => server.handle(&message),
+            Err(_) => vec![object(vec![
+                ("jsonrpc", string("2.0")),
+                ("id", JsonValue::Null),
+                (
+                    "error",
+                    object(vec![
+                        ("code", number(PARSE_ERROR)),
+                        ("message", string("invalid JSON")),
+                    ]),
+                ),
+            ])],
+        };
+        for reply in replies.iter() {
+            write_message(&mut output, reply)?;
+        }
+        if server.is_exited() {
+            break;
+        }
+    }
+    Ok(server.is_shutdown())
+}
@@ -1,111 +1,761 @@
-use clap::Parser;
+use clap::{Parser, Subcommand};
+use std::collections::HashMap;
 use std::fs::read_to_string;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use typed_arena::Arena;
 // use pest::Parser;
-use picopb::{generator::generate, parser::parse, validator::validate};
+use picopb::{
+    common::UnknownFieldStorage,
+    compat::{breaking_changes, Severity},
+    descriptor::{
+        decode_descriptor_set, encode_descriptor_set, file_descriptor_from_schema,
+        proto::FileDescriptorSet, schema_from_descriptor_set,
+    },
+    dynamic::DynamicMessage,
+    encode::ToWire,
+    format::format,
+    generator::{generate_with_config, GeneratorConfig},
+    json::ToJson,
+    lint::{lint, LintConfig, LintRule},
+    parser::{parse_with_config, ParseConfig, ProtoParser},
+    raw::{decode_raw, write_raw},
+    size::{max_encoded_lens_with, MaxEncodedLen},
+    text_format::ToText,
+    validator::validate,
+};
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 struct Args {
-    #[arg(short, long, default_value_t = false)]
-    generate: bool,
-
-    #[arg(short, long, default_value_t = false)]
-    verbose: bool,
-
-    #[arg(long, default_value_t = true)]
-    validate: bool,
-
-    proto_file: String,
-}
-
-fn main() {
-    let args = Args::parse();
-
-    let input = read_to_string(args.proto_file).expect("failed to read file");
-    let result = parse(&input).expect("failed to parse input");
-    if args.verbose {
-        dbg!(&result);
-    }
-    if args.validate {
-        validate(&result).expect("failed to validate input");
-    }
-    if args.generate {
-        generate(&mut std::io::stdout(), &result).expect("failed to generate");
-    }
-
-    // let example_field = "required string name = 2;";
-    // PicoPBParser::parse(Rule::message_field, example_field).unwrap();
-
-    // let message = "message A { required string name = 2; }";
-    // PicoPBParser::parse(Rule::message_definition, message).unwrap();
-
-    // let message_several = "message A {
-    // required string name = 2;
-    // optional string name = 2;
-    // }";
-    // PicoPBParser::parse(Rule::message_definition, message_several).unwrap();
-
-    // //
-    // let example_proto = "syntax = \"proto2\";
-    //  message A {
-    //      required string name = 1;
-    //      optional string password = 123;
-    //      repeated int32 integers = 2;
-    //  }";
-    // println!("{}", example_proto);
-    // PicoPBParser::parse(Rule::proto_definition, example_proto).unwrap();
-
-    // let example_proto2 = "
-    // syntax = \"proto2\";
-    // import \"common.proto\";
-    //  message Responses {
-    //      required string name = 1;
-    //      required bool ok = 2;
-    //      optional Error error = 2;
-    //  }
-    //  enum Error {
-    //      ERROR_INVALID_PASSWORD = 1;
-    //      ERROR_INVALID_USER = 2;
-    // }";
-
-    // println!("{}", example_proto2);
-    // PicoPBParser::parse(Rule::proto_definition, example_proto2).unwrap();
-    // message Response {
-    //     required bytes value = 1; [(nanopb).max_size=64]
-    //     required bytes opaque = 2;
-    //     optional Error error = 3;
-    // }
-
-    // message RepeatedResponse {
-    //     repeated Response responses = 1; [(nanopb).max_size=64]
-    // }
-
-    // message PackedIntegers {
-    //     repeated int32 integers = 1; [packed=true, (nanopb).max_size=64]
-    // }
-
-    // import \"common.proto\";
-    // import \"shared.proto\";
-
-    // let result = parse(
-    //     "
-    //     syntax = \"proto2\";
-
-    //     message Query {
-    //         required bytes key = 1; [(nanopb).max_size=8]
-    //         required bytes opaque = 2; [(nanopb).max_size=8]
-    //     }
-
-    //     enum Error {
-    //         ERROR_INVALID_KEY = 1;
-    //         ERROR_NOT_FOUND = 2;
-    //     }
-    // ",
-    // );
-    // let result = result.unwrap();
-    // dbg!(&result);
-    // validate(&result).unwrap();
-    // generate(&result).unwrap();
+    #[command(subcommand)]
+    command: Command,
+}
+
+/// How a command finds, parses and validates its schema files
+#[derive(clap::Args, Debug)]
+struct SchemaOptions {
+    /// Read the inputs as binary `FileDescriptorSet`s, as written by `protoc -o`
+    #[arg(long, default_value_t = false)]
+    descriptor_set: bool,
+
+    /// Search this directory for input files and imports, may be given several times
+    #[arg(short = 'I', long = "include-path")]
+    include_paths: Vec<PathBuf>,
+
+    /// Accept field options after the terminator, `= 1; [(nanopb).max_size=64]`
+    #[arg(long, default_value_t = false)]
+    legacy_field_options: bool,
+
+    /// Do not validate the schema after parsing it
+    #[arg(long, default_value_t = false)]
+    no_validate: bool,
+}
+
+/// The schema files a command reads
+#[derive(clap::Args, Debug)]
+struct Inputs {
+    #[arg(required = true)]
+    proto_files: Vec<PathBuf>,
+
+    #[command(flatten)]
+    options: SchemaOptions,
+
+    /// Also write the schemas as a binary `FileDescriptorSet` to this file, like
+    /// `protoc -o`. Written only when every input succeeds
+    #[arg(long)]
+    descriptor_out: Option<PathBuf>,
+}
+
+#[derive(clap::Args, Debug)]
+struct UnknownFieldOptions {
+    /// Keep fields that are not in the schema when decoding and encode them again
+    #[arg(long, default_value_t = false)]
+    preserve_unknown_fields: bool,
+
+    /// Keep unknown fields in a fixed size buffer of this many bytes instead of a Vec
+    #[arg(long)]
+    unknown_fields_capacity: Option<usize>,
+}
+
+impl UnknownFieldOptions {
+    fn storage(&self) -> UnknownFieldStorage {
+        match (self.unknown_fields_capacity, self.preserve_unknown_fields) {
+            (Some(capacity), _) => UnknownFieldStorage::Bounded(capacity),
+            (None, true) => UnknownFieldStorage::Unbounded,
+            (None, false) => UnknownFieldStorage::None,
+        }
+    }
+}
+
+/// The least severe changes `compat` reports
+#[derive(clap::ValueEnum, Clone, Copy, Debug)]
+enum Strictness {
+    /// Changes that break decoding
+    Wire,
+    /// Also changes to the values the other version can represent, like bounds and enum values
+    Values,
+    /// Also changes to names and generated code that keep the wire format
+    Source,
+}
+
+impl Strictness {
+    fn severity(self) -> Severity {
+        match self {
+            Self::Wire => Severity::Wire,
+            Self::Values => Severity::Values,
+            Self::Source => Severity::Source,
+        }
+    }
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Parse and validate the schemas, reporting the errors of every file
+    Check {
+        #[command(flatten)]
+        inputs: Inputs,
+    },
+    /// Generate Rust code, one `<name>.rs` file per input with the types of its imports
+    Generate {
+        #[command(flatten)]
+        inputs: Inputs,
+
+        #[command(flatten)]
+        unknown_fields: UnknownFieldOptions,
+
+        /// Generate the canonical JSON mapping for messages and enums
+        #[arg(long, default_value_t = false)]
+        json: bool,
+
+        /// Refer to `core` and `alloc` instead of `std` in the generated code, the
+        /// picopb runtime it depends on still needs `std`
+        #[arg(long, default_value_t = false)]
+        no_std: bool,
+
+        /// Write the generated files to this directory instead of stdout,
+        /// required for more than one input
+        #[arg(short, long)]
+        out_dir: Option<PathBuf>,
+    },
+    /// Print the parsed schema of every input
+    DumpAst {
+        #[command(flatten)]
+        inputs: Inputs,
+    },
+    /// Print the maximum encoded size of every message type
+    MaxSize {
+        #[command(flatten)]
+        inputs: Inputs,
+
+        #[command(flatten)]
+        unknown_fields: UnknownFieldOptions,
+    },
+    /// Report the changes between two versions of a schema that affect peers
+    /// using the other version, fails if there are any
+    Compat {
+        old: PathBuf,
+        new: PathBuf,
+
+        #[command(flatten)]
+        options: SchemaOptions,
+
+        /// The least severe changes to report
+        #[arg(long, value_enum, default_value_t = Strictness::Values)]
+        strictness: Strictness,
+    },
+    /// Check the schemas for style and for unbounded fields, fails if any rule is not followed
+    Lint {
+        #[command(flatten)]
+        inputs: Inputs,
+
+        /// Also check this rule, may be given several times. Only unused-message is not
+        /// checked by default
+        #[arg(long)]
+        enable: Vec<LintRule>,
+
+        /// Do not check this rule, may be given several times. The rules are type-name,
+        /// field-name, enum-value-name, enum-value-prefix, enum-zero-value, unbounded-field,
+        /// unused-import and unused-message
+        #[arg(long)]
+        disable: Vec<LintRule>,
+    },
+    /// Rewrite the files in the canonical style, keeping their comments
+    Fmt {
+        #[arg(required = true)]
+        proto_files: Vec<PathBuf>,
+
+        /// Do not write the files, list those that are not formatted and fail if there are any
+        #[arg(long, default_value_t = false)]
+        check: bool,
+    },
+    /// Print the fields of binary wire format input without a schema, like `protoc --decode_raw`
+    DecodeRaw {
+        /// Read from this file instead of stdin
+        file: Option<PathBuf>,
+    },
+    /// Print binary wire format input as the text format, or JSON, using the message type from the schema
+    Decode {
+        proto_file: PathBuf,
+
+        #[command(flatten)]
+        options: SchemaOptions,
+
+        /// The name of the message type
+        message: String,
+        /// Print JSON instead of the text format
+        #[arg(long, default_value_t = false)]
+        json: bool,
+        /// Read from this file instead of stdin
+        file: Option<PathBuf>,
+    },
+    /// Write text format, or JSON, input as binary wire format using the message type from the schema
+    Encode {
+        proto_file: PathBuf,
+
+        #[command(flatten)]
+        options: SchemaOptions,
+
+        /// The name of the message type
+        message: String,
+        /// Read JSON instead of the text format
+        #[arg(long, default_value_t = false)]
+        json: bool,
+        /// Read from this file instead of stdin
+        file: Option<PathBuf>,
+    },
+}
+
+/// Why a command failed. Clap reports invalid arguments itself, with exit code 2
+#[derive(Debug)]
+enum Failure {
+    /// An invalid schema or invalid input data, exit code 1
+    Invalid(String),
+    /// Arguments that can not be used together, exit code 2
+    Usage(String),
+    /// A file could not be read or written, exit code 3
+    Io(String),
+    /// The failures were already printed, holds the exit code of the first one
+    Reported(u8),
+}
+
+impl Failure {
+    fn io(path: &Path, err: std::io::Error) -> Self {
+        Self::Io(format!("{}: {}", path.display(), err))
+    }
+
+    fn exit_code(&self) -> u8 {
+        match self {
+            Self::Invalid(_) => 1,
+            Self::Usage(_) => 2,
+            Self::Io(_) => 3,
+            Self::Reported(code) => *code,
+        }
+    }
+
+    fn report(&self) {
+        match self {
+            Self::Invalid(message) | Self::Usage(message) | Self::Io(message) => {
+                eprintln!("error: {}", message)
+            }
+            Self::Reported(_) => {}
+        }
+    }
+}
+
+type Result<T> = std::result::Result<T, Failure>;
+
+/// Finds the file as given, or else in the first include path that has it
+fn resolve(path: &Path, include_paths: &[PathBuf]) -> Option<PathBuf> {
+    if path.is_file() {
+        return Some(path.to_path_buf());
+    }
+    include_paths
+        .iter()
+        .map(|include_path| include_path.join(path))
+        .find(|path| path.is_file())
+}
+
+enum Contents {
+    Proto(String),
+    DescriptorSet(FileDescriptorSet),
+}
+
+/// A file imported by an input, directly or by one of its imports
+struct Import {
+    /// As written in the import statement
+    name: String,
+    path: PathBuf,
+    text: String,
+}
+
+/// A schema file read from disk
+struct Source {
+    path: PathBuf,
+    contents: Contents,
+}
+
+impl Source {
+    fn read(path: &Path, include_paths: &[PathBuf], descriptor_set: bool) -> Result<Self> {
+        let path = resolve(path, include_paths)
+            .ok_or_else(|| Failure::Io(format!("{}: file not found", path.display())))?;
+        let contents = match descriptor_set {
+            true => {
+                let bytes = std::fs::read(&path).map_err(|err| Failure::io(&path, err))?;
+                let set = decode_descriptor_set(&bytes)
+                    .map_err(|err| Failure::Invalid(format!("{}: {:?}", path.display(), err)))?;
+                Contents::DescriptorSet(set)
+            }
+            false => Contents::Proto(read_to_string(&path).map_err(|err| Failure::io(&path, err))?),
+        };
+        Ok(Self { path, contents })
+    }
+
+    /// The name of the generated file, `<stem>.rs`
+    fn output_name(&self) -> PathBuf {
+        let stem = self.path.file_stem().unwrap_or(self.path.as_os_str());
+        Path::new(stem).with_extension("rs")
+    }
+}
+
+/// A parsed input
+struct Schema<'a> {
+    /// The input with the types of its imports added, what the commands work on
+    resolved: ProtoParser<'a>,
+    /// The input alone
+    file: ProtoParser<'a>,
+    /// The imported files by their name in the import statement
+    imports: Vec<(&'a str, ProtoParser<'a>)>,
+}
+
+impl SchemaOptions {
+    fn parse_config(&self) -> ParseConfig {
+        ParseConfig {
+            legacy_field_options: self.legacy_field_options,
+        }
+    }
+
+    /// Imports are looked up next to the importing file and then in the include paths
+    fn search_paths(&self, importer: &Path) -> Vec<PathBuf> {
+        let mut search_paths = vec![importer.parent().unwrap_or(Path::new("")).to_path_buf()];
+        search_paths.extend(self.include_paths.iter().cloned());
+        search_paths
+    }
+
+    fn parse_text<'a>(&self, path: &Path, text: &'a str) -> Result<ProtoParser<'a>> {
+        parse_with_config(text, self.parse_config())
+            .map_err(|err| Failure::Invalid(format!("{}: {:?}", path.display(), err)))
+    }
+
+    fn read(&self, path: &Path) -> Result<Source> {
+        Source::read(path, &self.include_paths, self.descriptor_set)
+    }
+
+    /// Reads and parses the files the importer imports by these names, and
+    /// their imports. The texts are kept in the arena the parsed files borrow from
+    fn parse_imports<'a>(
+        &self,
+        importer: &Path,
+        names: &[String],
+        arena: &'a Arena<Import>,
+        imports: &mut Vec<(&'a Import, ProtoParser<'a>)>,
+    ) -> Result<()> {
+        let search_paths = self.search_paths(importer);
+        for name in names {
+            let path = resolve(Path::new(name), &search_paths).ok_or_else(|| {
+                Failure::Invalid(format!(
+                    "{}: import \"{}\" not found",
+                    importer.display(),
+                    name
+                ))
+            })?;
+            // every file is parsed once, which also ends import cycles
+            if imports.iter().any(|(import, _)| import.path == path) {
+                continue;
+            }
+            let text = read_to_string(&path).map_err(|err| Failure::io(&path, err))?;
+            let import = &*arena.alloc(Import {
+                name: name.clone(),
+                path,
+                text,
+            });
+            let imported = self.parse_text(&import.path, &import.text)?;
+            let nested = imported.imports.clone();
+            imports.push((import, imported));
+            self.parse_imports(&import.path, &nested, arena, imports)?;
+        }
+        Ok(())
+    }
+
+    /// Parses the source and its imports, and validates it with the types
+    /// of its imports
+    fn parse<'a>(&self, source: &'a Source, arena: &'a Arena<Import>) -> Result<Schema<'a>> {
+        let invalid = |err: &dyn std::fmt::Debug| {
+            Failure::Invalid(format!("{}: {:?}", source.path.display(), err))
+        };
+        let (file, imports) = match &source.contents {
+            Contents::Proto(text) => {
+                let file = self.parse_text(&source.path, text)?;
+                let mut imports = Vec::new();
+                self.parse_imports(&source.path, &file.imports, arena, &mut imports)?;
+                (file, imports)
+            }
+            // imports are resolved by protoc, `--include_imports` adds them to the set
+            Contents::DescriptorSet(set) => (
+                schema_from_descriptor_set(set).map_err(|err| invalid(&err))?,
+                Vec::new(),
+            ),
+        };
+        let mut resolved = file.clone();
+        for (_, imported) in imports.iter() {
+            // the input's own definitions take precedence
+            for (name, enum_type) in imported.enum_types.iter() {
+                resolved
+                    .enum_types
+                    .entry(name.clone())
+                    .or_insert_with(|| enum_type.clone());
+            }
+            for (name, message_type) in imported.message_types.iter() {
+                resolved
+                    .message_types
+                    .entry(name.clone())
+                    .or_insert_with(|| message_type.clone());
+            }
+        }
+        // fields of an imported enum type were parsed as messages
+        resolved.resolve_enum_fields();
+        if !self.no_validate {
+            validate(&resolved).map_err(|err| invalid(&err))?;
+        }
+        Ok(Schema {
+            resolved,
+            file,
+            imports: imports
+                .into_iter()
+                .map(|(import, imported)| (import.name.as_str(), imported))
+                .collect(),
+        })
+    }
+}
+
+impl Inputs {
+    /// Runs the command for every input. A failing input is reported and the
+    /// rest are still processed, the first failure decides the exit code
+    fn for_each(&self, mut command: impl FnMut(&Source, &Schema) -> Result<()>) -> Result<()> {
+        let mut exit_code = None;
+        let mut descriptors = FileDescriptorSet::default();
+        for path in self.proto_files.iter() {
+            let result = self.options.read(path).and_then(|source| {
+                let arena = Arena::new();
+                let schema = self.options.parse(&source, &arena)?;
+                command(&source, &schema)?;
+                // named as given on the command line, protoc names files relative to the include path
+                let name = path.to_string_lossy().replace('\\', "/");
+                descriptors
+                    .file
+                    .push(file_descriptor_from_schema(&schema.file, &name));
+                Ok(())
+            });
+            if let Err(failure) = result {
+                failure.report();
+                exit_code.get_or_insert(failure.exit_code());
+            }
+        }
+        if let Some(code) = exit_code {
+            return Err(Failure::Reported(code));
+        }
+        match &self.descriptor_out {
+            Some(path) => std::fs::write(path, encode_descriptor_set(&descriptors))
+                .map_err(|err| Failure::io(path, err)),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Reads the file, or stdin when no file is given
+fn read_input(file: Option<PathBuf>) -> Result<Vec<u8>> {
+    match file {
+        Some(file) => std::fs::read(&file).map_err(|err| Failure::io(&file, err)),
+        None => {
+            let mut input = Vec::new();
+            std::io::stdin()
+                .read_to_end(&mut input)
+                .map_err(|err| Failure::io(Path::new("<stdin>"), err))?;
+            Ok(input)
+        }
+    }
+}
+
+fn write_output(bytes: &[u8]) -> Result<()> {
+    std::io::stdout()
+        .write_all(bytes)
+        .map_err(|err| Failure::io(Path::new("<stdout>"), err))
+}
+
+fn run_generate(inputs: Inputs, config: GeneratorConfig, out_dir: Option<PathBuf>) -> Result<()> {
+    if out_dir.is_none() && inputs.proto_files.len() > 1 {
+        return Err(Failure::Usage(
+            "--out-dir is required to generate more than one file".into(),
+        ));
+    }
+    if let Some(out_dir) = &out_dir {
+        std::fs::create_dir_all(out_dir).map_err(|err| Failure::io(out_dir, err))?;
+    }
+    // every file has the types of its imports, files that share a type can
+    // not be used together
+    let mut generated_into: HashMap<String, PathBuf> = HashMap::new();
+    inputs.for_each(|source, schema| {
+        let resolved = &schema.resolved;
+        let names = resolved
+            .message_types
+            .keys()
+            .chain(resolved.enum_types.keys());
+        for name in names.clone() {
+            if let Some(other) = generated_into.get(name) {
+                return Err(Failure::Usage(format!(
+                    "{}: type {} is also generated from {}, generate the files in separate runs",
+                    source.path.display(),
+                    name,
+                    other.display()
+                )));
+            }
+        }
+        generated_into.extend(names.map(|name| (name.clone(), source.path.clone())));
+        // generated into memory first, so a failure does not leave a partial file behind
+        let mut output = Vec::new();
+        generate_with_config(&mut output, &schema.resolved, &config)
+            .map_err(|err| Failure::Invalid(format!("{}: {:?}", source.path.display(), err)))?;
+        match &out_dir {
+            Some(out_dir) => {
+                let path = out_dir.join(source.output_name());
+                std::fs::write(&path, output).map_err(|err| Failure::io(&path, err))
+            }
+            None => write_output(&output),
+        }
+    })
+}
+
+fn run_max_size(inputs: Inputs, unknown_fields: UnknownFieldStorage) -> Result<()> {
+    inputs.for_each(|_, schema| {
+        let mut output = String::new();
+        for (identifier, max_encoded_len) in max_encoded_lens_with(&schema.resolved, unknown_fields)
+        {
+            match max_encoded_len {
+                MaxEncodedLen::Bounded(len) => {
+                    output += &format!("{}: {} bytes\n", identifier, len)
+                }
+                MaxEncodedLen::Unbounded(reason) => {
+                    output += &format!("{}: unbounded ({})\n", identifier, reason)
+                }
+            }
+        }
+        write_output(output.as_bytes())
+    })
+}
+
+fn run_compat(
+    old: PathBuf,
+    new: PathBuf,
+    options: SchemaOptions,
+    strictness: Severity,
+) -> Result<()> {
+    let (old, new) = (options.read(&old)?, options.read(&new)?);
+    let arena = Arena::new();
+    let (old, new) = (options.parse(&old, &arena)?, options.parse(&new, &arena)?);
+    let changes = breaking_changes(&old.resolved, &new.resolved, strictness);
+    let mut output = String::new();
+    for change in changes.iter() {
+        output += &format!("{}\n", change);
+    }
+    write_output(output.as_bytes())?;
+    match changes.len() {
+        0 => Ok(()),
+        count => Err(Failure::Invalid(format!("{} incompatible changes", count))),
+    }
+}
+
+/// Prints the lints of every input, the types of its imports are only used
+/// to find the imports that are unused
+fn run_lint(inputs: Inputs, config: LintConfig) -> Result<()> {
+    let mut count = 0;
+    inputs.for_each(|source, schema| {
+        let imports: Vec<_> = schema
+            .imports
+            .iter()
+            .map(|(name, imported)| (*name, imported))
+            .collect();
+        let mut output = String::new();
+        for found in lint(&schema.file, &imports, &config) {
+            output += &format!("{}: {}\n", source.path.display(), found);
+            count += 1;
+        }
+        write_output(output.as_bytes())
+    })?;
+    match count {
+        0 => Ok(()),
+        count => Err(Failure::Invalid(format!("{} lint warnings", count))),
+    }
+}
+
+/// Formats every file, a failing file is reported and the rest are still processed
+fn run_fmt(proto_files: Vec<PathBuf>, check: bool) -> Result<()> {
+    let mut exit_code = None;
+    let mut unformatted = 0;
+    for path in proto_files.iter() {
+        let result = read_to_string(path)
+            .map_err(|err| Failure::io(path, err))
+            .and_then(|text| {
+                let formatted = format(&text)
+                    .map_err(|err| Failure::Invalid(format!("{}: {:?}", path.display(), err)))?;
+                match (formatted == text, check) {
+                    (true, _) => Ok(()),
+                    (false, true) => {
+                        unformatted += 1;
+                        write_output(format!("{}\n", path.display()).as_bytes())
+                    }
+                    (false, false) => {
+                        std::fs::write(path, formatted).map_err(|err| Failure::io(path, err))
+                    }
+                }
+            });
+        if let Err(failure) = result {
+            failure.report();
+            exit_code.get_or_insert(failure.exit_code());
+        }
+    }
+    match (exit_code, unformatted) {
+        (Some(code), _) => Err(Failure::Reported(code)),
+        (None, 0) => Ok(()),
+        (None, count) => Err(Failure::Invalid(format!(
+            "{} files are not formatted",
+            count
+        ))),
+    }
+}
+
+/// Prints the raw fields of the input
+fn run_decode_raw(file: Option<PathBuf>) -> Result<()> {
+    let input = read_input(file)?;
+    let fields = decode_raw(&input).map_err(|err| {
+        Failure::Invalid(format!(
+            "malformed input at byte {}: {:?}",
+            err.offset, err.error
+        ))
+    })?;
+    let mut output = String::new();
+    write_raw(&mut output, &fields).expect("writing to a String does not fail");
+    write_output(output.as_bytes())
+}
+
+/// Converts the input between binary and text format, or JSON, with the
+/// schema interpreted at runtime
+fn run_dynamic(
+    proto_file: PathBuf,
+    options: SchemaOptions,
+    message: String,
+    json: bool,
+    file: Option<PathBuf>,
+    encode: bool,
+) -> Result<()> {
+    let source = options.read(&proto_file)?;
+    let arena = Arena::new();
+    let schema = options.parse(&source, &arena)?.resolved;
+    let input = read_input(file)?;
+    let invalid = |err| Failure::Invalid(format!("invalid input: {:?}", err));
+    if !encode {
+        let message = DynamicMessage::decode(&schema, &message, &input).map_err(invalid)?;
+        let output = match json {
+            true => message.to_json(),
+            false => message.to_text_format(),
+        };
+        return write_output(format!("{}\n", output).as_bytes());
+    }
+    let input = String::from_utf8(input)
+        .map_err(|_| Failure::Invalid("invalid input: not valid UTF-8".into()))?;
+    let message = match json {
+        true => DynamicMessage::from_json_str(&schema, &message, &input),
+        false => DynamicMessage::from_text_format(&schema, &message, &input),
+    }
+    .map_err(invalid)?;
+    let mut output = Vec::new();
+    (&message)
+        .append(&mut output)
+        .expect("writing to a Vec does not fail");
+    write_output(&output)
+}
+
+fn run(command: Command) -> Result<()> {
+    match command {
+        Command::Check { inputs } => inputs.for_each(|_, _| Ok(())),
+        Command::Generate {
+            inputs,
+            unknown_fields,
+            json,
+            no_std,
+            out_dir,
+        } => {
+            let config = GeneratorConfig {
+                unknown_fields: unknown_fields.storage(),
+                json,
+                no_std,
+            };
+            run_generate(inputs, config, out_dir)
+        }
+        Command::DumpAst { inputs } => inputs.for_each(|source, schema| {
+            write_output(format!("{}: {:#?}\n", source.path.display(), schema.file).as_bytes())
+        }),
+        Command::MaxSize {
+            inputs,
+            unknown_fields,
+        } => run_max_size(inputs, unknown_fields.storage()),
+        Command::Compat {
+            old,
+            new,
+            options,
+            strictness,
+        } => run_compat(old, new, options, strictness.severity()),
+        Command::Lint {
+            inputs,
+            enable,
+            disable,
+        } => {
+            let mut config = LintConfig::default();
+            for rule in enable {
+                config.enable(rule);
+            }
+            for rule in disable {
+                config.disable(rule);
+            }
+            run_lint(inputs, config)
+        }
+        Command::Fmt { proto_files, check } => run_fmt(proto_files, check),
+        Command::DecodeRaw { file } => run_decode_raw(file),
+        Command::Decode {
+            proto_file,
+            options,
+            message,
+            json,
+            file,
+        } => run_dynamic(proto_file, options, message, json, file, false),
+        Command::Encode {
+            proto_file,
+            options,
+            message,
+            json,
+            file,
+        } => run_dynamic(proto_file, options, message, json, file, true),
+    }
+}
+
+fn main() -> ExitCode {
+    match run(Args::parse().command) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(failure) => {
+            failure.report();
+            ExitCode::from(failure.exit_code())
+        }
+    }
 }
 
 #[cfg(test)]
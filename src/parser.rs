@@ -15,7 +15,7 @@ use pest_derive::Parser;
 
 use crate::common::{
     EnumType, Field, FieldOption, FieldOptions, FieldQualifier, FieldType, MessageField,
-    MessageType, Version,
+    MessageType, OptionName, OptionNamePart, OptionValue, ProtoOption, Version,
 };
 
 #[derive(Parser, Debug)]
@@ -63,32 +63,132 @@ pub enum ParserError {
     ExpectedRule(StaticSpan, Rule),
     ExpectedRuleButGot(StaticSpan, Rule, Rule),
     ExpectedOptionValue,
+    InvalidOptionValue(StaticSpan, String),
     UnknownOption(StaticSpan, String),
-    PestRuleError(PestError<Rule>),
+    PestRuleError(Box<PestError<Rule>>),
     ParseIntError(StaticSpan, ParseIntError),
     ExpectedButGot(StaticSpan, String, String),
+    InvalidEscape(StaticSpan),
+    LegacyFieldOptions(StaticSpan),
+}
+
+impl std::fmt::Display for ParserError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidProtoDefinition(span) => {
+                write!(f, "invalid definition `{}`", span.string)
+            }
+            Self::ExpectedStatement(span) => {
+                write!(f, "expected a statement, got `{}`", span.string)
+            }
+            Self::InvalidVersionDeclaration(span) => {
+                write!(f, "invalid syntax declaration `{}`", span.string)
+            }
+            Self::InvalidProtoVersion(span) => {
+                write!(f, "`{}` is not supported, only proto2 is", span.string)
+            }
+            Self::DuplicateProtoVersion(_) => write!(f, "the syntax is declared more than once"),
+            Self::ImportMustBeNonEmpty(_) => write!(f, "the imported file name is empty"),
+            Self::ExpectedOption(span) => write!(f, "expected an option, got `{}`", span.string),
+            Self::ExpectedNonempty(span) => write!(f, "expected a value at `{}`", span.string),
+            Self::ExpectedPredicateMatchButGot(span, rule) => {
+                write!(f, "expected {:?}, got `{}`", rule, span.string)
+            }
+            Self::ExpectedRule(span, rule) => write!(f, "expected {:?} in `{}`", rule, span.string),
+            Self::ExpectedRuleButGot(span, expected, got) => {
+                write!(
+                    f,
+                    "expected {:?}, got {:?} `{}`",
+                    expected, got, span.string
+                )
+            }
+            Self::ExpectedOptionValue => write!(f, "expected an option value"),
+            Self::InvalidOptionValue(span, expected) => {
+                write!(
+                    f,
+                    "invalid option value `{}`, expected {}",
+                    span.string, expected
+                )
+            }
+            Self::UnknownOption(span, name) => {
+                write!(f, "unknown option {} at `{}`", name, span.string)
+            }
+            Self::PestRuleError(err) => write!(f, "{}", err.variant.message()),
+            Self::ParseIntError(span, err) => {
+                write!(f, "invalid integer `{}`: {}", span.string, err)
+            }
+            Self::ExpectedButGot(span, expected, got) => {
+                write!(
+                    f,
+                    "expected {}, got `{}` in `{}`",
+                    expected, got, span.string
+                )
+            }
+            Self::InvalidEscape(span) => write!(f, "invalid escape in `{}`", span.string),
+            Self::LegacyFieldOptions(span) => write!(
+                f,
+                "field options `{}` after the `;`, write them before it or accept the legacy form",
+                span.string
+            ),
+        }
+    }
 }
 
 impl From<PestError<Rule>> for ParserError {
     fn from(error: PestError<Rule>) -> Self {
-        ParserError::PestRuleError(error)
+        ParserError::PestRuleError(Box::new(error))
     }
 }
 
-#[derive(Debug)]
+/// ParseConfig changes which inputs the parser accepts
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ParseConfig {
+    /// Accept field and enum value options after the terminator,
+    /// `= 1; [(nanopb).max_size=64]`, as written for earlier versions of picopb
+    pub legacy_field_options: bool,
+}
+
+#[derive(Debug, Clone)]
 pub struct ProtoParser<'a> {
     pub version: Version,
+    pub package: Option<String>,
     pub imports: Vec<String>,
+    pub options: Vec<ProtoOption>,
     pub enum_types: HashMap<String, EnumType>,
     pub message_types: HashMap<String, MessageType<'a>>,
+    config: ParseConfig,
 }
 
 pub type ParseResult<'a> = Result<ProtoParser<'a>, ParserError>;
 pub type EmptyParseResult = Result<(), ParserError>;
 
 impl<'a> ProtoParser<'a> {
-    fn usize_from_str(span: Span<'_>, s: &str) -> Result<usize, ParserError> {
-        str::parse::<usize>(s).map_err(|err| ParserError::ParseIntError(span.into(), err))
+    /// An empty schema, filled in by the parser or from descriptors
+    pub(crate) fn with_config(config: ParseConfig) -> Self {
+        Self {
+            version: Version::Unknown,
+            package: None,
+            imports: Vec::new(),
+            options: Vec::new(),
+            enum_types: HashMap::new(),
+            message_types: HashMap::new(),
+            config,
+        }
+    }
+
+    /// Fields are parsed as messages when their type is not a scalar, this
+    /// retypes those that refer to an enum. Run it again after adding the
+    /// types of imported files
+    pub fn resolve_enum_fields(&mut self) {
+        for message_type in self.message_types.values_mut() {
+            for field in message_type.fields.values_mut() {
+                if let FieldType::MessageType(identifier) = field.field_type {
+                    if self.enum_types.contains_key(identifier) {
+                        field.field_type = FieldType::EnumType(identifier);
+                    }
+                }
+            }
+        }
     }
 
     fn expect_rule(
@@ -99,11 +199,11 @@ impl<'a> ProtoParser<'a> {
         if pair.as_rule() == rule {
             return Ok(pair);
         }
-        return Err(ParserError::ExpectedRuleButGot(
+        Err(ParserError::ExpectedRuleButGot(
             pair.as_span().into(),
             rule,
             pair.as_rule(),
-        ));
+        ))
     }
 
     fn expect_next_rule(
@@ -136,106 +236,176 @@ impl<'a> ProtoParser<'a> {
         }
     }
 
-    fn parse_nanopb_option(
-        &mut self,
-        option_statement: PestPair<'a, Rule>,
-    ) -> Result<FieldOption, ParserError> {
-        let option = self.expect_rule(option_statement, Rule::nanopb_option)?;
-        let option_span = option.as_span();
-        let mut inner = option.into_inner();
+    fn parse_option_name(&mut self, name: PestPair<'a, Rule>) -> Result<OptionName, ParserError> {
+        let name = self.expect_rule(name, Rule::option_name)?;
+        let mut parts = Vec::new();
+        for part in name.into_inner() {
+            let span = part.as_span();
+            let part = self.expect_rule(part, Rule::option_name_part)?;
+            let mut part_inner = part.into_inner();
+            let variant = self.expect_next_match(span, &mut part_inner, |pair| {
+                let rule = pair.as_rule();
+                rule == Rule::extension_name || rule == Rule::identifier
+            })?;
+            match variant.as_rule() {
+                Rule::extension_name => {
+                    let mut extension_inner = variant.into_inner();
+                    let identifier =
+                        self.expect_next_rule(span, &mut extension_inner, Rule::full_identifier)?;
+                    parts.push(OptionNamePart::Extension(Self::identifier_from_span(
+                        identifier.as_span(),
+                    )));
+                }
+                _ => parts.push(OptionNamePart::Simple(Self::identifier_from_span(
+                    variant.as_span(),
+                ))),
+            }
+        }
+        Ok(OptionName(parts))
+    }
 
-        let variant = self.expect_next_match(option_span, &mut inner, |pair| {
-            let rule = pair.as_rule();
-            rule == Rule::max_size_option || rule == Rule::max_len_option
-        })?;
-        let variant_span = variant.as_span();
-        let variant_rule = variant.as_rule();
-
-        // dbg!(variant_span);
-
-        let number_value = self.expect_next_rule(option_span, &mut inner, Rule::number)?;
-
-        match variant_rule {
-            Rule::max_size_option => Ok(FieldOption::MaxSize(Self::usize_from_str(
-                variant_span,
-                number_value.as_str(),
-            )?)),
-            Rule::max_len_option => Ok(FieldOption::MaxLen(Self::usize_from_str(
-                variant_span,
-                number_value.as_str(),
-            )?)),
+    /// parse_constant parses the value assigned to an option
+    fn parse_constant(&mut self, constant: PestPair<'a, Rule>) -> Result<OptionValue, ParserError> {
+        let constant = self.expect_rule(constant, Rule::constant)?;
+        let span = constant.as_span();
+        let mut inner = constant.into_inner();
+        let value = inner.next().ok_or(ParserError::ExpectedOptionValue)?;
+        let value_span = value.as_span();
+
+        match value.as_rule() {
+            Rule::bool => Ok(OptionValue::Bool(value.as_str() == "true")),
+            Rule::int_constant => Self::int_from_span(value_span).map(OptionValue::Int),
+            Rule::float_constant => value
+                .as_str()
+                .parse::<f64>()
+                .map(OptionValue::Float)
+                .map_err(|_| ParserError::InvalidOptionValue(value_span.into(), "float".into())),
+            Rule::string => Ok(OptionValue::String(Self::string_from_span(value_span)?)),
+            Rule::full_identifier => Ok(OptionValue::Identifier(Self::identifier_from_span(
+                value_span,
+            ))),
+            Rule::aggregate => self.parse_aggregate(value),
             _ => Err(ParserError::ExpectedButGot(
-                variant_span.into(),
-                "max_size_option or max_len_option".into(),
-                "ERR".into(),
+                span.into(),
+                "option value".into(),
+                value.as_str().to_string(),
             )),
         }
     }
 
-    fn parse_packed_option(
+    /// parse_aggregate parses aggregate option values
+    /// Example: { max_size: 10 inner { value: true } }
+    fn parse_aggregate(
         &mut self,
-        option_statement: PestPair<'a, Rule>,
-    ) -> Result<FieldOption, ParserError> {
-        let option = self.expect_rule(option_statement, Rule::packed_option)?;
-        let option_span = option.as_span();
-        let mut inner = option.into_inner();
-
-        let packed_bool = self.expect_next_rule(option_span, &mut inner, Rule::bool)?;
-
-        match packed_bool.as_str() {
-            "true" => Ok(FieldOption::Packed(true)),
-            "false" => Ok(FieldOption::Packed(false)),
-            s => {
-                return Err(ParserError::ExpectedButGot(
-                    packed_bool.as_span().into(),
-                    "bool".into(),
-                    s.to_string(),
-                ))
-            }
+        aggregate: PestPair<'a, Rule>,
+    ) -> Result<OptionValue, ParserError> {
+        let aggregate = self.expect_rule(aggregate, Rule::aggregate)?;
+        let mut fields = Vec::new();
+        for field in aggregate.into_inner() {
+            let span = field.as_span();
+            let field = self.expect_rule(field, Rule::aggregate_field)?;
+            let mut field_inner = field.into_inner();
+            let identifier = self.expect_next_rule(span, &mut field_inner, Rule::identifier)?;
+            let value = self.expect_next_match(span, &mut field_inner, |pair| {
+                let rule = pair.as_rule();
+                rule == Rule::constant || rule == Rule::aggregate
+            })?;
+            let value = match value.as_rule() {
+                Rule::constant => self.parse_constant(value)?,
+                _ => self.parse_aggregate(value)?,
+            };
+            fields.push((Self::identifier_from_span(identifier.as_span()), value));
         }
+        Ok(OptionValue::Aggregate(fields))
     }
 
     /// parse_option parses a single option
+    /// Example: (nanopb).max_size=64
     fn parse_option(
         &mut self,
         option_statement: PestPair<'a, Rule>,
-    ) -> Result<FieldOption, ParserError> {
-        let option = self.expect_rule(option_statement, Rule::option)?;
-        let span = option.as_span();
-
-        let mut option_inner = option.into_inner();
+    ) -> Result<ProtoOption, ParserError> {
+        let option_rule = option_statement.as_rule();
+        if option_rule != Rule::option && option_rule != Rule::option_statement {
+            return Err(ParserError::ExpectedRuleButGot(
+                option_statement.as_span().into(),
+                Rule::option,
+                option_rule,
+            ));
+        }
+        let span = option_statement.as_span();
+        let mut option_inner = option_statement.into_inner();
 
-        let option_variant = self.expect_next_match(span, &mut option_inner, |pair| {
-            let rule = pair.as_rule();
-            rule == Rule::nanopb_option || rule == Rule::packed_option
-        })?;
+        let name = self.expect_next_rule(span, &mut option_inner, Rule::option_name)?;
+        let value = self.expect_next_rule(span, &mut option_inner, Rule::constant)?;
 
-        match option_variant.as_rule() {
-            Rule::nanopb_option => self.parse_nanopb_option(option_variant),
-            Rule::packed_option => self.parse_packed_option(option_variant),
-            _ => {
-                return Err(ParserError::ExpectedButGot(
-                    option_variant.as_span().into(),
-                    "nanopb_option or packed_option".into(),
-                    format!("{}", option_variant),
-                ))
-            }
-        }
+        Ok(ProtoOption {
+            name: self.parse_option_name(name)?,
+            value: self.parse_constant(value)?,
+        })
     }
 
     /// parse_options parses the list of options that can be specified
     /// Example: [(nanopb).max_size=<value>,packed=true]
     fn parse_options(
         &mut self,
-        options_statement: PestPair<'a, Rule>,
-    ) -> Result<Vec<FieldOption>, ParserError> {
-        let options = self.expect_rule(options_statement, Rule::options)?;
+        options: PestPair<'a, Rule>,
+    ) -> Result<Vec<ProtoOption>, ParserError> {
+        if options.as_rule() == Rule::legacy_options {
+            if !self.config.legacy_field_options {
+                return Err(ParserError::LegacyFieldOptions(options.as_span().into()));
+            }
+        } else {
+            self.expect_rule(options.clone(), Rule::options)?;
+        }
         options
             .into_inner()
             .map(|option| self.parse_option(option))
             .collect()
     }
 
+    fn usize_from_option(span: Span<'_>, value: &OptionValue) -> Result<usize, ParserError> {
+        match value {
+            OptionValue::Int(value) => usize::try_from(*value).map_err(|_| {
+                ParserError::InvalidOptionValue(span.into(), "non-negative integer".into())
+            }),
+            _ => Err(ParserError::InvalidOptionValue(
+                span.into(),
+                "integer".into(),
+            )),
+        }
+    }
+
+    fn bool_from_option(span: Span<'_>, value: &OptionValue) -> Result<bool, ParserError> {
+        match value {
+            OptionValue::Bool(value) => Ok(*value),
+            _ => Err(ParserError::InvalidOptionValue(span.into(), "bool".into())),
+        }
+    }
+
+    /// Interprets the field options that picopb knows about,
+    /// unknown options are left as they are
+    fn field_option(
+        span: Span<'_>,
+        option: &ProtoOption,
+    ) -> Result<Option<FieldOption>, ParserError> {
+        match option.name.to_string().as_str() {
+            "(nanopb).max_size" => Ok(Some(FieldOption::MaxSize(Self::usize_from_option(
+                span,
+                &option.value,
+            )?))),
+            "(nanopb).max_len" => Ok(Some(FieldOption::MaxLen(Self::usize_from_option(
+                span,
+                &option.value,
+            )?))),
+            "packed" => Ok(Some(FieldOption::Packed(Self::bool_from_option(
+                span,
+                &option.value,
+            )?))),
+            _ => Ok(None),
+        }
+    }
+
     fn parse_message_definition(
         &mut self,
         message_statement: PestPair<'a, Rule>,
@@ -255,6 +425,8 @@ impl<'a> ProtoParser<'a> {
         let mut message_type = MessageType {
             identifier: identifier.clone(),
             fields: BTreeMap::new(),
+            msgid: None,
+            options: Vec::new(),
         };
 
         for value in inner {
@@ -272,22 +444,26 @@ impl<'a> ProtoParser<'a> {
                     let field_number =
                         self.expect_next_rule(value_span, &mut message_inner, Rule::number)?;
 
-                    // TODO:
-                    let mut options = FieldOptions {
-                        max_size: None,
-                        max_len: None,
-                        packed: false,
-                    };
-
-                    // parse optional options
-                    if let Some(next) = message_inner.next() {
-                        // dbg!(&next);
-                        let opts = self.parse_options(next)?;
-                        opts.iter().for_each(|option| match option {
-                            FieldOption::MaxLen(max_len) => options.max_len = Some(*max_len),
-                            FieldOption::MaxSize(max_size) => options.max_size = Some(*max_size),
-                            FieldOption::Packed(value) => options.packed = *value,
-                        });
+                    let mut options = FieldOptions::default();
+
+                    // parse optional options, both before and (legacy) after the terminator
+                    let mut field_options = Vec::new();
+                    for next in message_inner {
+                        let options_span = next.as_span();
+                        let parsed_options = self.parse_options(next)?;
+                        for option in parsed_options.iter() {
+                            match Self::field_option(options_span, option)? {
+                                Some(FieldOption::MaxLen(max_len)) => {
+                                    options.max_len = Some(max_len)
+                                }
+                                Some(FieldOption::MaxSize(max_size)) => {
+                                    options.max_size = Some(max_size)
+                                }
+                                Some(FieldOption::Packed(value)) => options.packed = value,
+                                None => {}
+                            }
+                        }
+                        field_options.extend(parsed_options);
                     }
 
                     let field_identifier: String = Self::identifier_from_span(identifier.as_span());
@@ -298,9 +474,21 @@ impl<'a> ProtoParser<'a> {
                         field_type: FieldType::from_str(field_type.as_str(), options.max_size),
                         identifier: field_identifier,
                         ordinal: Field(field_ordinal),
+                        options: field_options,
                     };
                     message_type.fields.insert(field_ordinal, value);
                 }
+                Rule::option_statement => {
+                    let option = self.parse_option(value)?;
+                    if option.name.to_string() == "(nanopb_msgopt).msgid" {
+                        let msgid = Self::usize_from_option(value_span, &option.value)?;
+                        message_type.msgid = Some(u32::try_from(msgid).map_err(|_| {
+                            ParserError::InvalidOptionValue(value_span.into(), "u32".into())
+                        })?);
+                    }
+                    message_type.options.push(option);
+                }
+                Rule::reserved_statement | Rule::extensions_statement => {}
                 _ => unreachable!(),
             }
         }
@@ -314,15 +502,96 @@ impl<'a> ProtoParser<'a> {
         identifier_str.to_string()
     }
 
-    fn string_from_span(span: Span<'_>) -> String {
-        let identifier_str = span.as_str();
-        identifier_str[1..identifier_str.len() - 1].to_string()
+    /// Returns the contents of a quoted string literal with the escapes resolved
+    fn string_from_span(span: Span<'_>) -> Result<String, ParserError> {
+        let quoted = span.as_str();
+        let mut chars = quoted[1..quoted.len() - 1].chars().peekable();
+        let mut bytes: Vec<u8> = Vec::new();
+        let invalid = || ParserError::InvalidEscape(span.into());
+
+        while let Some(c) = chars.next() {
+            if c != '\\' {
+                let mut utf8 = [0; 4];
+                bytes.extend_from_slice(c.encode_utf8(&mut utf8).as_bytes());
+                continue;
+            }
+            let escape = chars.next().ok_or_else(invalid)?;
+            match escape {
+                'a' => bytes.push(0x07),
+                'b' => bytes.push(0x08),
+                'f' => bytes.push(0x0C),
+                'n' => bytes.push(b'\n'),
+                'r' => bytes.push(b'\r'),
+                't' => bytes.push(b'\t'),
+                'v' => bytes.push(0x0B),
+                'x' | 'X' => {
+                    let mut digits = String::new();
+                    while digits.len() < 2 && chars.peek().is_some_and(|c| c.is_ascii_hexdigit()) {
+                        digits.extend(chars.next());
+                    }
+                    bytes.push(u8::from_str_radix(&digits, 16).map_err(|_| invalid())?);
+                }
+                'u' | 'U' => {
+                    let len = if escape == 'u' { 4 } else { 8 };
+                    let digits: String = chars.by_ref().take(len).collect();
+                    let c = u32::from_str_radix(&digits, 16)
+                        .ok()
+                        .and_then(char::from_u32)
+                        .ok_or_else(invalid)?;
+                    let mut utf8 = [0; 4];
+                    bytes.extend_from_slice(c.encode_utf8(&mut utf8).as_bytes());
+                }
+                '0'..='7' => {
+                    let mut digits = String::from(escape);
+                    while digits.len() < 3 && chars.peek().is_some_and(|c| ('0'..='7').contains(c))
+                    {
+                        digits.extend(chars.next());
+                    }
+                    bytes.push(u8::from_str_radix(&digits, 8).map_err(|_| invalid())?);
+                }
+                _ => bytes.push(escape as u8),
+            }
+        }
+        Ok(String::from_utf8_lossy(&bytes).into_owned())
+    }
+
+    /// Splits an integer literal into its signed digits and radix, as taken by
+    /// `from_str_radix`. `0x` prefixes hexadecimal and a leading `0` octal
+    fn split_radix(literal: &str) -> (String, u32) {
+        let (sign, unsigned) = match literal.strip_prefix(['-', '+']) {
+            Some(unsigned) => (&literal[..1], unsigned),
+            None => ("", literal),
+        };
+        let (digits, radix) = if let Some(digits) = unsigned
+            .strip_prefix("0x")
+            .or_else(|| unsigned.strip_prefix("0X"))
+        {
+            (digits, 16)
+        } else if unsigned.len() > 1 && unsigned.starts_with('0') {
+            (&unsigned[1..], 8)
+        } else {
+            (unsigned, 10)
+        };
+        (format!("{sign}{digits}"), radix)
+    }
+
+    fn int_from_span(span: Span<'_>) -> Result<i64, ParserError> {
+        let (digits, radix) = Self::split_radix(span.as_str());
+        i64::from_str_radix(&digits, radix)
+            .map_err(|err| ParserError::ParseIntError(span.into(), err))
     }
 
     fn ordinal_from_span(span: Span<'_>) -> Result<u32, ParserError> {
-        let ordinal_str = span.as_str();
-        let parse_result = ordinal_str.parse::<u32>();
-        parse_result.map_err(|err| ParserError::ParseIntError(span.into(), err))
+        let (digits, radix) = Self::split_radix(span.as_str());
+        u32::from_str_radix(&digits, radix)
+            .map_err(|err| ParserError::ParseIntError(span.into(), err))
+    }
+
+    /// Enum values are signed 32 bit integers
+    fn enum_value_from_span(span: Span<'_>) -> Result<i32, ParserError> {
+        let (digits, radix) = Self::split_radix(span.as_str());
+        i32::from_str_radix(&digits, radix)
+            .map_err(|err| ParserError::ParseIntError(span.into(), err))
     }
 
     fn parse_enum_definition(&mut self, enum_statement: PestPair<'a, Rule>) -> EmptyParseResult {
@@ -340,6 +609,10 @@ impl<'a> ProtoParser<'a> {
         let mut enum_type = EnumType {
             identifier: identifier.clone(),
             pairs: BTreeMap::new(),
+            declared: Vec::new(),
+            allow_alias: false,
+            options: Vec::new(),
+            value_options: BTreeMap::new(),
         };
 
         for value in inner {
@@ -351,13 +624,33 @@ impl<'a> ProtoParser<'a> {
                     let identifier =
                         self.expect_next_rule(value_span, &mut enum_inner, Rule::identifier)?;
                     let number =
-                        self.expect_next_rule(value_span, &mut enum_inner, Rule::number)?;
+                        self.expect_next_rule(value_span, &mut enum_inner, Rule::enum_number)?;
 
                     let field_identifier = Self::identifier_from_span(identifier.as_span());
-                    let field_ordinal = Self::ordinal_from_span(number.as_span())?;
+                    let field_ordinal = Self::enum_value_from_span(number.as_span())?;
 
+                    let mut value_options = Vec::new();
+                    for next in enum_inner {
+                        value_options.extend(self.parse_options(next)?);
+                    }
+                    if !value_options.is_empty() {
+                        enum_type
+                            .value_options
+                            .insert(field_identifier.clone(), value_options);
+                    }
+                    if !enum_type.pairs.contains_key(&field_identifier) {
+                        enum_type.declared.push(field_identifier.clone());
+                    }
                     enum_type.pairs.insert(field_identifier, field_ordinal);
                 }
+                Rule::option_statement => {
+                    let option = self.parse_option(value)?;
+                    if option.name.to_string() == "allow_alias" {
+                        enum_type.allow_alias = Self::bool_from_option(value_span, &option.value)?;
+                    }
+                    enum_type.options.push(option);
+                }
+                Rule::reserved_statement => {}
                 _ => unreachable!(),
             }
         }
@@ -389,23 +682,35 @@ impl<'a> ProtoParser<'a> {
 
     fn parse_import_statement(&mut self, statement: PestPair<'_, Rule>) -> EmptyParseResult {
         let span = statement.as_span();
-        if let Some(value) = statement.into_inner().next() {
-            let slice = value.as_span().as_str();
-            if slice.len() == 2 {
+        // the string follows the optional `weak` or `public` modifier
+        if let Some(value) = statement
+            .into_inner()
+            .find(|pair| pair.as_rule() == Rule::string)
+        {
+            let import = Self::string_from_span(value.as_span())?;
+            if import.is_empty() {
                 return Err(ParserError::ImportMustBeNonEmpty(span.into()));
             }
-            self.imports.push(slice[1..slice.len() - 1].to_owned())
+            self.imports.push(import)
 
             // TODO: open files, and parse them here
         }
         Ok(())
     }
 
+    fn parse_package_statement(&mut self, statement: PestPair<'a, Rule>) -> EmptyParseResult {
+        let span = statement.as_span();
+        let mut inner = statement.into_inner();
+        let package = self.expect_next_rule(span, &mut inner, Rule::full_identifier)?;
+        self.package = Some(Self::identifier_from_span(package.as_span()));
+        Ok(())
+    }
+
     fn parse_version_decl(&mut self, statement: PestPair<'_, Rule>) -> EmptyParseResult {
         let span = statement.as_span();
         if let Some(value) = statement.into_inner().next() {
             // TODO:
-            if Self::string_from_span(value.as_span()) == "proto2" {
+            if Self::string_from_span(value.as_span())? == "proto2" {
                 self.version = Version::Proto2;
                 return Ok(());
             } else {
@@ -422,12 +727,23 @@ impl<'a> ProtoParser<'a> {
 
         let statement_variant = self.expect_next_match(span, &mut statement_inner, |pair| {
             let rule = pair.as_rule();
-            rule == Rule::block_statement || rule == Rule::import_statement
+            rule == Rule::block_statement
+                || rule == Rule::import_statement
+                || rule == Rule::package_statement
+                || rule == Rule::option_statement
+                || rule == Rule::empty_statement
         })?;
 
         match statement_variant.as_rule() {
             Rule::block_statement => self.parse_block_statement(statement_variant),
             Rule::import_statement => self.parse_import_statement(statement_variant),
+            Rule::package_statement => self.parse_package_statement(statement_variant),
+            Rule::option_statement => {
+                let option = self.parse_option(statement_variant)?;
+                self.options.push(option);
+                Ok(())
+            }
+            Rule::empty_statement => Ok(()),
             _ => Err(ParserError::ExpectedButGot(
                 span.into(),
                 "block statement".to_string(),
@@ -441,16 +757,15 @@ impl<'a> ProtoParser<'a> {
     }
 }
 
-pub fn parse(input: &str) -> ParseResult {
+pub fn parse(input: &str) -> ParseResult<'_> {
+    parse_with_config(input, ParseConfig::default())
+}
+
+pub fn parse_with_config(input: &str, config: ParseConfig) -> ParseResult<'_> {
     let parse = PicoPBParser::parse(Rule::proto_definition, input)?;
     // dbg!(&parse);
 
-    let mut output = ProtoParser {
-        version: Version::Unknown,
-        imports: Vec::new(),
-        enum_types: HashMap::new(),
-        message_types: HashMap::new(),
-    };
+    let mut output = ProtoParser::with_config(config);
 
     // Do a single pass and extract enum and message types
     for pair in parse.into_iter() {
@@ -477,30 +792,6 @@ pub fn parse(input: &str) -> ParseResult {
     }
 
     // Now we know which types are primitives, sub-messages, and Enums
-    // Iterate through all fields in all message types and fix up those that we now know are
-    // enums
-    // TODO: fixup this ugly mess
-    let mut new_message_types: HashMap<String, MessageType> = HashMap::new();
-    for (identifier, message_type) in output.message_types.into_iter() {
-        let mut new_fields = message_type.fields.clone();
-        let mut new_message_type = message_type.clone();
-
-        for (ordinal, field) in message_type.fields {
-            match field.field_type {
-                FieldType::MessageType(identifier) => {
-                    if output.enum_types.contains_key(identifier) {
-                        // this field has a type that we now know is a Enum (not a Message)
-                        let mut new_field = field.clone();
-                        new_field.field_type = FieldType::EnumType(identifier.clone());
-                        new_fields.insert(ordinal, new_field);
-                    }
-                }
-                _ => continue,
-            }
-        }
-        new_message_type.fields = new_fields;
-        new_message_types.insert(identifier.clone(), new_message_type);
-    }
-    output.message_types = new_message_types;
+    output.resolve_enum_fields();
     Ok(output)
 }
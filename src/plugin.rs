@@ -0,0 +1,154 @@
+use crate::common::UnknownFieldStorage;
+use crate::descriptor::proto::{
+    CodeGeneratorRequest, CodeGeneratorResponse, CodeGeneratorResponseFile, FileDescriptorProto,
+};
+use crate::descriptor::{schema_from_files_with_config, DescriptorConfig, DescriptorError};
+use crate::generator::{generate_with_config, GeneratorConfig, GeneratorError};
+use crate::validator::{validate, ValidatorError};
+
+/// This module implements `protoc-gen-picopb`, run by protoc with
+/// `--picopb_out=<dir>` and optionally `--picopb_opt=<parameters>`.
+/// Every generated file is self contained: the types of the files it
+/// imports are generated into it as well, as picopb has no references
+/// between generated files.
+
+#[derive(Debug)]
+pub enum PluginError {
+    /// Holds the parameter, the supported parameters are `json`, `no_std`,
+    /// `preserve_unknown_fields`, `unknown_fields_capacity=<n>` and `max_size=<n>`
+    UnknownParameter(String),
+    InvalidParameter(String),
+    /// A file to generate, or one of its imports, is not in the request
+    FileNotFound(String),
+    Descriptor(DescriptorError),
+    Validator(ValidatorError),
+    Generator(GeneratorError),
+}
+
+impl From<DescriptorError> for PluginError {
+    fn from(value: DescriptorError) -> Self {
+        Self::Descriptor(value)
+    }
+}
+
+impl From<ValidatorError> for PluginError {
+    fn from(value: ValidatorError) -> Self {
+        Self::Validator(value)
+    }
+}
+
+impl From<GeneratorError> for PluginError {
+    fn from(value: GeneratorError) -> Self {
+        Self::Generator(value)
+    }
+}
+
+pub type Result<T> = std::result::Result<T, PluginError>;
+
+/// The parameters given with `--picopb_opt`, separated by commas
+#[derive(Debug, Default, Clone, Copy)]
+pub struct PluginParameters {
+    pub generator: GeneratorConfig,
+    pub descriptor: DescriptorConfig,
+}
+
+fn parse_size(parameter: &str, value: Option<&str>) -> Result<usize> {
+    value
+        .and_then(|value| value.parse().ok())
+        .ok_or_else(|| PluginError::InvalidParameter(parameter.into()))
+}
+
+pub fn parse_parameters(parameters: &str) -> Result<PluginParameters> {
+    let mut result = PluginParameters::default();
+    for parameter in parameters.split(',').map(str::trim) {
+        let (name, value) = match parameter.split_once('=') {
+            Some((name, value)) => (name, Some(value)),
+            None => (parameter, None),
+        };
+        match name {
+            "" => {}
+            "json" => result.generator.json = true,
+            "no_std" => result.generator.no_std = true,
+            "preserve_unknown_fields" => {
+                if result.generator.unknown_fields == UnknownFieldStorage::None {
+                    result.generator.unknown_fields = UnknownFieldStorage::Unbounded;
+                }
+            }
+            "unknown_fields_capacity" => {
+                let capacity = parse_size(parameter, value)?;
+                result.generator.unknown_fields = UnknownFieldStorage::Bounded(capacity);
+            }
+            "max_size" => {
+                result.descriptor.default_max_size = Some(parse_size(parameter, value)?);
+            }
+            _ => return Err(PluginError::UnknownParameter(parameter.into())),
+        }
+    }
+    Ok(result)
+}
+
+/// The file and everything it imports, imports before the files importing them
+fn with_imports<'a>(
+    files: &'a [FileDescriptorProto],
+    name: &str,
+    result: &mut Vec<&'a FileDescriptorProto>,
+) -> Result<()> {
+    let file = files
+        .iter()
+        .find(|file| file.name.as_deref() == Some(name))
+        .ok_or_else(|| PluginError::FileNotFound(name.into()))?;
+    if result.iter().any(|included| std::ptr::eq(*included, file)) {
+        return Ok(());
+    }
+    for dependency in file.dependency.iter() {
+        with_imports(files, dependency, result)?;
+    }
+    result.push(file);
+    Ok(())
+}
+
+/// `dir/name.proto` is generated into `dir/name.rs`
+fn output_name(name: &str) -> String {
+    format!("{}.rs", name.strip_suffix(".proto").unwrap_or(name))
+}
+
+fn generate_file(
+    request: &CodeGeneratorRequest,
+    name: &str,
+    parameters: &PluginParameters,
+) -> Result<CodeGeneratorResponseFile> {
+    let mut files = Vec::new();
+    with_imports(&request.proto_file, name, &mut files)?;
+    let schema = schema_from_files_with_config(files, parameters.descriptor)?;
+    validate(&schema)?;
+    let mut content = Vec::new();
+    generate_with_config(&mut content, &schema, &parameters.generator)?;
+    Ok(CodeGeneratorResponseFile {
+        name: Some(output_name(name)),
+        content: Some(String::from_utf8(content).expect("generated code is valid UTF-8")),
+    })
+}
+
+/// Generates one Rust file for every file to generate. Errors are reported
+/// in the response, as protoc expects, prefixed by the name of the file
+pub fn generate_response(request: &CodeGeneratorRequest) -> CodeGeneratorResponse {
+    let mut response = CodeGeneratorResponse::default();
+    let parameters = match parse_parameters(request.parameter.as_deref().unwrap_or("")) {
+        Ok(parameters) => parameters,
+        Err(err) => {
+            response.error = Some(format!("{:?}", err));
+            return response;
+        }
+    };
+    for name in request.file_to_generate.iter() {
+        match generate_file(request, name, &parameters) {
+            Ok(file) => response.file.push(file),
+            Err(err) => {
+                response.error = Some(format!("{}: {:?}", name, err));
+                response.file.clear();
+                break;
+            }
+        }
+    }
+    response
+}
@@ -1,134 +1,109 @@
+use arraystring::{prelude::Capacity, ArrayString};
+use arrayvec::ArrayVec;
 use rand::distributions::Uniform;
+
+/// Re-exported for the `Randomize` impls of generated code
+pub use rand::{rngs::StdRng, Rng, SeedableRng};
+
+/// Strings, bytes and repeated fields without `(nanopb).max_size` get at
+/// most this many values, bounded ones are limited by their type
+pub const MAX_UNBOUNDED_LEN: usize = 32;
+
 /// To be able to verify that PicoPB is correct we make all
 /// types that PicoPB supports implement a Randomize trait,
 /// which generates random values.
 ///
 /// The trait is a constructor that creates an instance with
-/// random values.
-///
-/// This trait can then be used to generate randomized values for
-/// encoding and decoding.
+/// random values drawn from `rng`. Bounded fields, `ArrayString<N>` and
+/// `ArrayVec<T, N>`, stay within their bounds so the value can be encoded.
 ///
-/// SHOULD NOT BE USED OUTSIDE OF TESTING
-/// Because this uses Box::leak() to make it possible to return
-/// 'static references.
-use rand::prelude::*;
-
-const MAX_STRING_LEN: usize = 100;
-const MAX_BYTES_LEN: usize = 100;
-
-pub trait Randomize<T> {
-    fn randomized() -> T;
+/// Use a seeded RNG, `seeded(42)`, to be able to reproduce a failure.
+pub trait Randomize: Sized {
+    fn randomized<R: Rng + ?Sized>(rng: &mut R) -> Self;
 }
 
-pub fn randomized<T>() -> T
-where
-    T: Randomize<T>,
-{
-    T::randomized()
+pub fn randomized<T: Randomize, R: Rng + ?Sized>(rng: &mut R) -> T {
+    T::randomized(rng)
 }
 
-impl Randomize<i32> for i32 {
-    fn randomized() -> i32 {
-        rand::random::<i32>()
-    }
+/// A reproducible RNG for `Randomize`, the same seed gives the same values
+pub fn seeded(seed: u64) -> StdRng {
+    StdRng::seed_from_u64(seed)
 }
 
-impl Randomize<u32> for u32 {
-    fn randomized() -> u32 {
-        rand::random::<u32>()
-    }
+macro_rules! randomize_standard {
+    ($($t:ty),*) => {
+        $(
+            impl Randomize for $t {
+                fn randomized<R: Rng + ?Sized>(rng: &mut R) -> Self {
+                    rng.gen()
+                }
+            }
+        )*
+    };
 }
 
-impl Randomize<i64> for i64 {
-    fn randomized() -> i64 {
-        rand::random::<i64>()
-    }
+randomize_standard!(i32, u32, i64, u64, bool, u8);
+
+/// Printable ASCII, so the length in bytes is the number of characters
+fn printable<R: Rng + ?Sized>(rng: &mut R, len: usize) -> impl Iterator<Item = char> + '_ {
+    rng.sample_iter(Uniform::new_inclusive(' ', '~')).take(len)
 }
 
-impl Randomize<u64> for u64 {
-    fn randomized() -> u64 {
-        rand::random::<u64>()
+impl Randomize for String {
+    fn randomized<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        let len = rng.gen_range(0..=MAX_UNBOUNDED_LEN);
+        printable(rng, len).collect()
     }
 }
 
-impl Randomize<u8> for u8 {
-    fn randomized() -> u8 {
-        rand::random::<u8>()
+impl<SIZE: Capacity> Randomize for ArrayString<SIZE> {
+    fn randomized<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        let len = rng.gen_range(0..=usize::from(Self::capacity()));
+        let value: String = printable(rng, len).collect();
+        Self::from_str_truncate(value)
     }
 }
 
-impl Randomize<String> for String {
-    fn randomized() -> String {
-        let rand_len = rand::random::<usize>() % MAX_STRING_LEN;
-        rand::thread_rng()
-            .sample_iter(Uniform::new(char::from(32), char::from(126)))
-            .take(rand_len)
-            .map(char::from)
-            .collect::<String>()
+impl Randomize for bytes::Bytes {
+    fn randomized<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        Self::from(Vec::<u8>::randomized(rng))
     }
 }
 
-// impl<T> Randomize<T> for T
-// where T: Distribution<T>
-//  {
-//     fn randomized() -> T {
-//         rand::random::<T>()
-//     }
-// }
-
-// /// Leaks memory
-// impl Randomize<&[u8]> for &[u8] {
-//     fn randomized() -> &'static [u8] {
-//         let rand_len = rand::random::<usize>().min(MAX_STRING_LEN);
-//         let bytes: Vec<u8> = rand::thread_rng()
-//             .sample_iter(Uniform::new(0, 255)).take(rand_len).map(u8::from).collect::<Vec<_>>();
-//         Box::leak(bytes.into_boxed_slice())
-//     }
-// }
-
-/// Leaks memory
-impl<T> Randomize<&[T]> for &[T]
-where
-    T: 'static + Randomize<T>,
-{
-    fn randomized() -> &'static [T] {
-        let rand_len = rand::random::<usize>().min(MAX_STRING_LEN);
-        let values = (0..rand_len).map(|_| T::randomized()).collect::<Vec<T>>();
-        Box::leak(values.into_boxed_slice())
+impl<T: Randomize> Randomize for Vec<T> {
+    fn randomized<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        let len = rng.gen_range(0..=MAX_UNBOUNDED_LEN);
+        (0..len).map(|_| T::randomized(rng)).collect()
     }
 }
 
-impl Randomize<bytes::Bytes> for bytes::Bytes {
-    fn randomized() -> bytes::Bytes {
-        let rand_len = rand::random::<usize>().min(MAX_STRING_LEN);
-        let values = (0..rand_len)
-            .map(|_| randomized::<u8>())
-            .collect::<Vec<u8>>();
-        bytes::Bytes::from_static(Box::leak(values.into_boxed_slice()))
+/// Fixed size bytes, all of the bytes are encoded
+impl<T: Randomize, const N: usize> Randomize for [T; N] {
+    fn randomized<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        core::array::from_fn(|_| T::randomized(rng))
     }
 }
 
-impl<T> Randomize<Vec<T>> for Vec<T>
-where
-    T: Randomize<T>,
-{
-    fn randomized() -> Vec<T> {
-        let rand_len = rand::random::<usize>().min(MAX_STRING_LEN);
-        (0..rand_len).map(|_| T::randomized()).collect::<Vec<_>>()
+impl<T: Randomize, const N: usize> Randomize for ArrayVec<T, N> {
+    fn randomized<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        let len = rng.gen_range(0..=N);
+        (0..len).map(|_| T::randomized(rng)).collect()
     }
 }
 
-impl<T> Randomize<Option<T>> for Option<T>
-where
-    T: Randomize<T>,
-{
-    fn randomized() -> Option<T> {
-        let is_none = rand::random::<bool>();
-        if is_none {
-            None
+impl<T: Randomize> Randomize for Option<T> {
+    fn randomized<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        if rng.gen() {
+            Some(T::randomized(rng))
         } else {
-            Some(T::randomized())
+            None
         }
     }
 }
+
+impl<T: Randomize> Randomize for crate::common::Packed<T> {
+    fn randomized<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        crate::common::Packed(T::randomized(rng))
+    }
+}
@@ -0,0 +1,136 @@
+use crate::common::Field;
+use crate::decode::{DecodeBuffer, DecodeError, MAX_GROUP_DEPTH};
+use crate::text_format::write_bytes;
+use crate::wiretypes::WireType;
+use std::fmt::{self, Write};
+
+/// This module decodes the wire format without a schema, like
+/// `protoc --decode_raw`. Length delimited values that parse as
+/// messages are shown as messages, anything else as bytes.
+
+#[derive(Debug, PartialEq)]
+pub enum RawValue<'a> {
+    VarInt(u64),
+    I64(u64),
+    I32(u32),
+    /// A length delimited value that parses as a message
+    Message(Vec<RawField<'a>>),
+    /// Any other length delimited value
+    Bytes(&'a [u8]),
+    Group(Vec<RawField<'a>>),
+}
+
+#[derive(Debug, PartialEq)]
+pub struct RawField<'a> {
+    /// Offset of the tag from the start of the input
+    pub offset: usize,
+    pub field: Field,
+    pub wire_type: WireType,
+    pub value: RawValue<'a>,
+}
+
+/// The field starting at the offset is malformed
+#[derive(Debug, PartialEq)]
+pub struct RawError {
+    pub offset: usize,
+    pub error: DecodeError,
+}
+
+/// Decodes every field in the bytes
+pub fn decode_raw(bytes: &[u8]) -> Result<Vec<RawField<'_>>, RawError> {
+    let mut buf = DecodeBuffer::from_slice(bytes);
+    read_fields(&mut buf, 0, 0, None)
+}
+
+/// Reads fields until the buffer is empty, or until the end of the group
+fn read_fields<'a>(
+    buf: &mut DecodeBuffer<'a>,
+    base: usize,
+    depth: usize,
+    group: Option<Field>,
+) -> Result<Vec<RawField<'a>>, RawError> {
+    let mut fields = Vec::new();
+    while !buf.is_empty() {
+        let offset = base + buf.position();
+        let error = |error| RawError { offset, error };
+        let (field, wire_type) = buf.read_tag().map_err(error)?;
+        let value = match wire_type {
+            WireType::VarInt => RawValue::VarInt(buf.read_varint().map_err(error)?),
+            WireType::I64 => RawValue::I64(buf.read_fixed64().map_err(error)?),
+            WireType::I32 => RawValue::I32(buf.read_fixed32().map_err(error)?),
+            WireType::Len => {
+                let bytes = buf.read_len_delimited().map_err(error)?;
+                let start = base + buf.position() - bytes.len();
+                read_message(bytes, start, depth + 1)
+            }
+            WireType::SGroup if depth >= MAX_GROUP_DEPTH => {
+                return Err(error(DecodeError::GroupTooDeep));
+            }
+            WireType::SGroup => RawValue::Group(read_fields(buf, base, depth + 1, Some(field))?),
+            WireType::EGroup => match group == Some(field) {
+                true => return Ok(fields),
+                false => return Err(error(DecodeError::UnexpectedEndGroup)),
+            },
+        };
+        fields.push(RawField {
+            offset,
+            field,
+            wire_type,
+            value,
+        });
+    }
+    match group {
+        Some(_) => Err(RawError {
+            offset: base + buf.position(),
+            error: DecodeError::UnexpectedEndOfBuffer,
+        }),
+        None => Ok(fields),
+    }
+}
+
+/// Parses the length delimited value as a message if possible, values nested
+/// deeper than `MAX_GROUP_DEPTH` are kept as bytes, as deeper groups are an error
+fn read_message(bytes: &[u8], base: usize, depth: usize) -> RawValue<'_> {
+    if bytes.is_empty() || depth > MAX_GROUP_DEPTH {
+        return RawValue::Bytes(bytes);
+    }
+    match read_fields(&mut DecodeBuffer::from_slice(bytes), base, depth, None) {
+        Ok(fields) => RawValue::Message(fields),
+        Err(_) => RawValue::Bytes(bytes),
+    }
+}
+
+/// Writes the fields as an indented tree, one field per line:
+/// `1 VarInt: 150`, `2 Len { ... }`, `3 Len: "text"`
+pub fn write_raw<W: Write>(out: &mut W, fields: &[RawField]) -> fmt::Result {
+    write_fields(out, fields, 0)
+}
+
+fn write_fields<W: Write>(out: &mut W, fields: &[RawField], indent: usize) -> fmt::Result {
+    for field in fields {
+        write!(
+            out,
+            "{:indent$}{} {:?}",
+            "",
+            field.field.0,
+            field.wire_type,
+            indent = indent
+        )?;
+        match &field.value {
+            RawValue::VarInt(value) => writeln!(out, ": {}", value)?,
+            RawValue::I64(value) => writeln!(out, ": 0x{:016x}", value)?,
+            RawValue::I32(value) => writeln!(out, ": 0x{:08x}", value)?,
+            RawValue::Bytes(bytes) => {
+                out.write_str(": ")?;
+                write_bytes(out, bytes)?;
+                out.write_char('\n')?;
+            }
+            RawValue::Message(fields) | RawValue::Group(fields) => {
+                out.write_str(" {\n")?;
+                write_fields(out, fields, indent + 2)?;
+                writeln!(out, "{:indent$}}}", "", indent = indent)?;
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,258 @@
+use crate::common::ConstMessageField;
+use arraystring::{prelude::Capacity, ArrayString};
+use arrayvec::ArrayVec;
+use std::fmt;
+
+/// This module gives generic access to generated messages: the static
+/// descriptor of their fields, and reading and replacing the values of a
+/// field by its number or name, for tools that work with any message type
+
+#[derive(Debug, PartialEq)]
+pub enum ReflectError {
+    /// Holds the number or name of the field
+    UnknownField(String),
+    /// The value has the wrong type for the field, holds the expected type
+    UnexpectedValue(&'static str),
+    /// A required field was given no value, or a singular field several values
+    InvalidCount(usize),
+    InvalidEnumValue(i32),
+    CapacityExceeded,
+    /// A message value was given for a field of another message type, holds its name
+    WrongMessageType(&'static str),
+}
+
+pub type Result<T> = std::result::Result<T, ReflectError>;
+
+/// The name and fields of a message type, generated as `Message::DESCRIPTOR`
+#[derive(Debug)]
+pub struct MessageDescriptor {
+    pub name: &'static str,
+    /// The fields ordered by their number
+    pub fields: &'static [ConstMessageField<'static>],
+}
+
+impl MessageDescriptor {
+    pub fn field(&self, number: u32) -> Option<&'static ConstMessageField<'static>> {
+        self.fields.iter().find(|field| field.ordinal.0 == number)
+    }
+
+    pub fn field_by_name(&self, name: &str) -> Option<&'static ConstMessageField<'static>> {
+        self.fields.iter().find(|field| field.identifier == name)
+    }
+}
+
+/// A single value of a field, borrowed from the message. Enums are kept as
+/// their number, bounded strings and bytes as slices
+#[derive(Debug, Clone, Copy)]
+pub enum FieldValue<'v> {
+    Bool(bool),
+    Int32(i32),
+    Int64(i64),
+    Uint32(u32),
+    Uint64(u64),
+    String(&'v str),
+    Bytes(&'v [u8]),
+    Enum(i32),
+    Message(&'v dyn Reflect),
+}
+
+impl<'v> FieldValue<'v> {
+    pub fn as_message(&self) -> Result<&'v dyn Reflect> {
+        match self {
+            Self::Message(message) => Ok(*message),
+            _ => Err(ReflectError::UnexpectedValue("message")),
+        }
+    }
+}
+
+/// Messages are equal when they have the same type and every field has the same values
+impl PartialEq for FieldValue<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Bool(a), Self::Bool(b)) => a == b,
+            (Self::Int32(a), Self::Int32(b)) => a == b,
+            (Self::Int64(a), Self::Int64(b)) => a == b,
+            (Self::Uint32(a), Self::Uint32(b)) => a == b,
+            (Self::Uint64(a), Self::Uint64(b)) => a == b,
+            (Self::String(a), Self::String(b)) => a == b,
+            (Self::Bytes(a), Self::Bytes(b)) => a == b,
+            (Self::Enum(a), Self::Enum(b)) => a == b,
+            (Self::Message(a), Self::Message(b)) => {
+                let descriptor = a.descriptor();
+                descriptor.name == b.descriptor().name
+                    && descriptor
+                        .fields
+                        .iter()
+                        .all(|field| a.get(field.ordinal.0) == b.get(field.ordinal.0))
+            }
+            _ => false,
+        }
+    }
+}
+
+/// The Reflect trait is implemented by generated messages. Every field has a
+/// list of values: none for an unset optional field, one for a set singular
+/// field and every element of a repeated field
+pub trait Reflect: fmt::Debug {
+    fn descriptor(&self) -> &'static MessageDescriptor;
+
+    /// The values of the field, `None` if the message has no such field
+    fn get(&self, number: u32) -> Option<Vec<FieldValue<'_>>>;
+
+    /// Replaces the values of the field. Fixed size repeated fields keep
+    /// their size, elements without a value are reset to their default
+    fn set(&mut self, number: u32, values: &[FieldValue]) -> Result<()>;
+
+    fn get_by_name(&self, name: &str) -> Option<Vec<FieldValue<'_>>> {
+        self.get(self.descriptor().field_by_name(name)?.ordinal.0)
+    }
+
+    fn set_by_name(&mut self, name: &str, values: &[FieldValue]) -> Result<()> {
+        let field = self
+            .descriptor()
+            .field_by_name(name)
+            .ok_or_else(|| ReflectError::UnknownField(name.to_string()))?;
+        self.set(field.ordinal.0, values)
+    }
+}
+
+/// Sets every field of the target to the values of the same field in the
+/// source, both must be of the same message type
+pub fn copy_fields(target: &mut dyn Reflect, source: &dyn Reflect) -> Result<()> {
+    let descriptor = target.descriptor();
+    if descriptor.name != source.descriptor().name {
+        return Err(ReflectError::WrongMessageType(source.descriptor().name));
+    }
+    for field in descriptor.fields.iter() {
+        let values = source.get(field.ordinal.0).unwrap_or_default();
+        target.set(field.ordinal.0, &values)?;
+    }
+    Ok(())
+}
+
+/// The ReflectField trait converts a single value of a field, generated
+/// code implements it for messages and enums
+pub trait ReflectField: Sized {
+    fn to_value(&self) -> FieldValue<'_>;
+    fn from_value(value: &FieldValue) -> Result<Self>;
+}
+
+pub fn set_required<T: ReflectField>(field: &mut T, values: &[FieldValue]) -> Result<()> {
+    match values {
+        [value] => {
+            *field = T::from_value(value)?;
+            Ok(())
+        }
+        _ => Err(ReflectError::InvalidCount(values.len())),
+    }
+}
+
+pub fn set_optional<T: ReflectField>(field: &mut Option<T>, values: &[FieldValue]) -> Result<()> {
+    *field = match values {
+        [] => None,
+        [value] => Some(T::from_value(value)?),
+        _ => return Err(ReflectError::InvalidCount(values.len())),
+    };
+    Ok(())
+}
+
+pub fn set_repeated<T: ReflectField>(field: &mut Vec<T>, values: &[FieldValue]) -> Result<()> {
+    *field = values.iter().map(T::from_value).collect::<Result<_>>()?;
+    Ok(())
+}
+
+/// Bounded repeated fields, an invalid value or too many values leave the field unchanged
+pub fn set_bounded<T: ReflectField, const N: usize>(
+    field: &mut ArrayVec<T, N>,
+    values: &[FieldValue],
+) -> Result<()> {
+    if values.len() > N {
+        return Err(ReflectError::CapacityExceeded);
+    }
+    *field = values.iter().map(T::from_value).collect::<Result<_>>()?;
+    Ok(())
+}
+
+macro_rules! reflect_scalar {
+    ($type:ty, $variant:ident, $name:literal) => {
+        impl ReflectField for $type {
+            fn to_value(&self) -> FieldValue<'_> {
+                FieldValue::$variant(*self)
+            }
+
+            fn from_value(value: &FieldValue) -> Result<Self> {
+                match value {
+                    FieldValue::$variant(value) => Ok(*value),
+                    _ => Err(ReflectError::UnexpectedValue($name)),
+                }
+            }
+        }
+    };
+}
+
+reflect_scalar!(bool, Bool, "bool");
+reflect_scalar!(i32, Int32, "int32");
+reflect_scalar!(i64, Int64, "int64");
+reflect_scalar!(u32, Uint32, "uint32");
+reflect_scalar!(u64, Uint64, "uint64");
+
+fn as_str<'v>(value: &FieldValue<'v>) -> Result<&'v str> {
+    match value {
+        FieldValue::String(value) => Ok(value),
+        _ => Err(ReflectError::UnexpectedValue("string")),
+    }
+}
+
+fn as_bytes<'v>(value: &FieldValue<'v>) -> Result<&'v [u8]> {
+    match value {
+        FieldValue::Bytes(value) => Ok(value),
+        _ => Err(ReflectError::UnexpectedValue("bytes")),
+    }
+}
+
+impl ReflectField for String {
+    fn to_value(&self) -> FieldValue<'_> {
+        FieldValue::String(self)
+    }
+
+    fn from_value(value: &FieldValue) -> Result<Self> {
+        Ok(as_str(value)?.to_string())
+    }
+}
+
+impl<SIZE: Capacity> ReflectField for ArrayString<SIZE> {
+    fn to_value(&self) -> FieldValue<'_> {
+        FieldValue::String(self.as_str())
+    }
+
+    fn from_value(value: &FieldValue) -> Result<Self> {
+        ArrayString::try_from_str(as_str(value)?).map_err(|_| ReflectError::CapacityExceeded)
+    }
+}
+
+impl ReflectField for bytes::Bytes {
+    fn to_value(&self) -> FieldValue<'_> {
+        FieldValue::Bytes(self)
+    }
+
+    fn from_value(value: &FieldValue) -> Result<Self> {
+        Ok(bytes::Bytes::copy_from_slice(as_bytes(value)?))
+    }
+}
+
+/// Fixed size bytes, shorter values are padded with zeros
+impl<const N: usize> ReflectField for [u8; N] {
+    fn to_value(&self) -> FieldValue<'_> {
+        FieldValue::Bytes(self)
+    }
+
+    fn from_value(value: &FieldValue) -> Result<Self> {
+        let bytes = as_bytes(value)?;
+        let mut value = [0; N];
+        value
+            .get_mut(..bytes.len())
+            .ok_or(ReflectError::CapacityExceeded)?
+            .copy_from_slice(bytes);
+        Ok(value)
+    }
+}
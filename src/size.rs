@@ -0,0 +1,169 @@
+use std::collections::{BTreeMap, HashSet};
+
+/// This module calculates the maximum encoded size of message types,
+/// making it possible to size buffers statically (like the `Foo_size`
+/// defines generated by nanopb).
+use crate::common::{FieldQualifier, FieldType, MessageField, MessageType, UnknownFieldStorage};
+use crate::encode::precalculate_prefix_size;
+use crate::parser::ProtoParser;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MaxEncodedLen {
+    Bounded(usize),
+    /// The message has no upper bound on its size, holds the
+    /// path (Message.field) of the first field causing this
+    Unbounded(String),
+}
+
+impl MaxEncodedLen {
+    pub fn bound(&self) -> Option<usize> {
+        match self {
+            Self::Bounded(len) => Some(*len),
+            Self::Unbounded(_) => None,
+        }
+    }
+}
+
+/// Maximum size of a varint encoded value of the given type
+fn varint_max_len(field_type: &FieldType) -> Option<usize> {
+    match field_type {
+        FieldType::Bool => Some(1),
+        FieldType::Uint32 => Some(5),
+        // negative values are sign extended to 64 bits
+        FieldType::Int32 | FieldType::Int64 | FieldType::Uint64 | FieldType::EnumType(_) => {
+            Some(10)
+        }
+        _ => None,
+    }
+}
+
+fn tag_len(field: &MessageField) -> usize {
+    precalculate_prefix_size((field.ordinal.0 as usize) << 3)
+}
+
+fn len_delimited(len: usize) -> usize {
+    precalculate_prefix_size(len) + len
+}
+
+struct Sizer<'p, 'a> {
+    parser: &'p ProtoParser<'a>,
+    visiting: HashSet<&'p str>,
+    unknown_fields: UnknownFieldStorage,
+}
+
+impl<'p, 'a> Sizer<'p, 'a> {
+    fn message(&mut self, message_type: &'p MessageType<'a>) -> MaxEncodedLen {
+        let identifier = message_type.identifier.as_str();
+        if !self.visiting.insert(identifier) {
+            return MaxEncodedLen::Unbounded(format!("{} is recursive", identifier));
+        }
+
+        let mut total = match self.unknown_fields {
+            UnknownFieldStorage::None => 0,
+            UnknownFieldStorage::Bounded(capacity) => capacity,
+            UnknownFieldStorage::Unbounded => {
+                self.visiting.remove(identifier);
+                return MaxEncodedLen::Unbounded(format!("{}.unknown_fields", identifier));
+            }
+        };
+        for (_, field) in message_type.fields.iter() {
+            match self.field(message_type, field) {
+                MaxEncodedLen::Bounded(len) => total += len,
+                unbounded => {
+                    self.visiting.remove(identifier);
+                    return unbounded;
+                }
+            }
+        }
+        self.visiting.remove(identifier);
+        MaxEncodedLen::Bounded(total)
+    }
+
+    /// Maximum size of a single value (without tag) of the field
+    fn value(&mut self, message_type: &MessageType, field: &'p MessageField<'a>) -> MaxEncodedLen {
+        let unbounded = || {
+            MaxEncodedLen::Unbounded(format!("{}.{}", message_type.identifier, field.identifier))
+        };
+        match &field.field_type {
+            FieldType::String(limit) | FieldType::Bytes(limit) => {
+                MaxEncodedLen::Bounded(len_delimited(*limit))
+            }
+            FieldType::UnboundedString | FieldType::UnboundedBytes => unbounded(),
+            FieldType::MessageType(identifier) => {
+                match self.parser.message_types.get(*identifier) {
+                    Some(sub_message) => match self.message(sub_message) {
+                        MaxEncodedLen::Bounded(len) => MaxEncodedLen::Bounded(len_delimited(len)),
+                        unbounded => unbounded,
+                    },
+                    None => MaxEncodedLen::Unbounded(format!("{} is not defined", identifier)),
+                }
+            }
+            scalar => MaxEncodedLen::Bounded(varint_max_len(scalar).unwrap_or(0)),
+        }
+    }
+
+    fn field(&mut self, message_type: &MessageType, field: &'p MessageField<'a>) -> MaxEncodedLen {
+        let value = match self.value(message_type, field) {
+            MaxEncodedLen::Bounded(len) => len,
+            unbounded => return unbounded,
+        };
+        let tag = tag_len(field);
+        match field.qualifier {
+            FieldQualifier::Required | FieldQualifier::Optional => {
+                MaxEncodedLen::Bounded(tag + value)
+            }
+            FieldQualifier::Repeated(count) => MaxEncodedLen::Bounded(count * (tag + value)),
+            FieldQualifier::PackedRepeated(count) => {
+                MaxEncodedLen::Bounded(tag + len_delimited(count * value))
+            }
+            FieldQualifier::RepeatedUnbounded | FieldQualifier::PackedRepeatedUnbounded => {
+                MaxEncodedLen::Unbounded(format!(
+                    "{}.{}",
+                    message_type.identifier, field.identifier
+                ))
+            }
+        }
+    }
+}
+
+/// Calculates the maximum encoded size of the message type with the given identifier
+pub fn max_encoded_len(parser: &ProtoParser, identifier: &str) -> MaxEncodedLen {
+    max_encoded_len_with(parser, identifier, UnknownFieldStorage::None)
+}
+
+/// Like `max_encoded_len`, including the preserved unknown fields of every message
+pub fn max_encoded_len_with(
+    parser: &ProtoParser,
+    identifier: &str,
+    unknown_fields: UnknownFieldStorage,
+) -> MaxEncodedLen {
+    let mut sizer = Sizer {
+        parser,
+        visiting: HashSet::new(),
+        unknown_fields,
+    };
+    match parser.message_types.get(identifier) {
+        Some(message_type) => sizer.message(message_type),
+        None => MaxEncodedLen::Unbounded(format!("{} is not defined", identifier)),
+    }
+}
+
+/// Calculates the maximum encoded size of all message types
+pub fn max_encoded_lens(parser: &ProtoParser) -> BTreeMap<String, MaxEncodedLen> {
+    max_encoded_lens_with(parser, UnknownFieldStorage::None)
+}
+
+/// Like `max_encoded_lens`, including the preserved unknown fields of every message
+pub fn max_encoded_lens_with(
+    parser: &ProtoParser,
+    unknown_fields: UnknownFieldStorage,
+) -> BTreeMap<String, MaxEncodedLen> {
+    parser
+        .message_types
+        .keys()
+        .map(|identifier| {
+            let len = max_encoded_len_with(parser, identifier, unknown_fields);
+            (identifier.clone(), len)
+        })
+        .collect()
+}
@@ -0,0 +1,563 @@
+use crate::decode::MAX_MESSAGE_DEPTH;
+use arraystring::{prelude::Capacity, ArrayString};
+use arrayvec::ArrayVec;
+use std::fmt::{self, Write};
+
+/// This module contains the protobuf text format
+/// (https://protobuf.dev/reference/protobuf/textformat-spec/) for generated
+/// messages, written on a single line: `name: "x" sub { id: 3 } values: 1 values: 2`
+
+#[derive(Debug, PartialEq)]
+pub enum TextFormatError {
+    /// The input is not valid text format, holds the byte offset of the error
+    Syntax(usize),
+    /// Messages and lists are nested deeper than `MAX_NESTING_DEPTH`, holds the
+    /// byte offset of the one that is too deep
+    TooDeep(usize),
+    /// The value has the wrong kind, holds the expected kind
+    UnexpectedValue(&'static str),
+    InvalidNumber(String),
+    InvalidUtf8,
+    InvalidEnumValue(String),
+    UnknownField(String),
+    MissingRequiredField(&'static str),
+    CapacityExceeded,
+}
+
+pub type Result<T> = std::result::Result<T, TextFormatError>;
+
+/// A parsed field value
+#[derive(Debug, Clone, PartialEq)]
+pub enum TextValue {
+    /// A number or identifier (enum names, `true`) as written
+    Literal(String),
+    /// A quoted string with escapes resolved, adjacent strings are concatenated.
+    /// Kept as bytes as bytes fields may hold any value
+    String(Vec<u8>),
+    /// The fields of a message in the order they were written
+    Message(Vec<(String, TextValue)>),
+    /// The `[a, b]` shorthand for the values of a repeated field
+    List(Vec<TextValue>),
+}
+
+impl TextValue {
+    pub fn as_message(&self) -> Result<&[(String, TextValue)]> {
+        match self {
+            Self::Message(fields) => Ok(fields),
+            _ => Err(TextFormatError::UnexpectedValue("message")),
+        }
+    }
+
+    pub fn as_literal(&self) -> Result<&str> {
+        match self {
+            Self::Literal(literal) => Ok(literal),
+            _ => Err(TextFormatError::UnexpectedValue("literal")),
+        }
+    }
+
+    pub fn as_bytes(&self) -> Result<&[u8]> {
+        match self {
+            Self::String(bytes) => Ok(bytes),
+            _ => Err(TextFormatError::UnexpectedValue("string")),
+        }
+    }
+}
+
+/// The ToText trait writes a value in the text format, messages write
+/// their fields without surrounding braces
+pub trait ToText {
+    fn write_text<W: Write>(&self, out: &mut W) -> fmt::Result;
+
+    fn to_text_format(&self) -> String {
+        let mut out = String::new();
+        // writing to a String does not fail
+        let _ = self.write_text(&mut out);
+        out
+    }
+}
+
+/// The FromText trait reads a value from the text format
+pub trait FromText: Sized {
+    fn from_text(value: &TextValue) -> Result<Self>;
+
+    fn from_text_format(input: &str) -> Result<Self> {
+        Self::from_text(&parse(input)?)
+    }
+}
+
+/// Writes the fields of a message, used by generated messages
+pub struct FieldWriter<'w, W: Write> {
+    out: &'w mut W,
+    first: bool,
+}
+
+impl<'w, W: Write> FieldWriter<'w, W> {
+    pub fn new(out: &'w mut W) -> Self {
+        Self { out, first: true }
+    }
+
+    fn name(&mut self, name: &str) -> fmt::Result {
+        if !self.first {
+            self.out.write_char(' ')?;
+        }
+        self.first = false;
+        self.out.write_str(name)
+    }
+
+    pub fn field<T: ToText + ?Sized>(&mut self, name: &str, value: &T) -> fmt::Result {
+        self.name(name)?;
+        self.out.write_str(": ")?;
+        value.write_text(self.out)
+    }
+
+    /// writes a sub message as `name { fields }`, or `name {}` when empty
+    pub fn message<T: ToText>(&mut self, name: &str, value: &T) -> fmt::Result {
+        self.name(name)?;
+        self.out.write_str(" {")?;
+        let mut inner = Padded {
+            out: self.out,
+            padded: false,
+        };
+        // a trait object keeps recursive messages from instantiating Padded<Padded<..>>
+        value.write_text(&mut (&mut inner as &mut dyn Write))?;
+        match inner.padded {
+            true => self.out.write_str(" }"),
+            false => self.out.write_char('}'),
+        }
+    }
+}
+
+/// Writes a space before the first output, so empty messages stay `{}`
+struct Padded<'w, W: Write> {
+    out: &'w mut W,
+    padded: bool,
+}
+
+impl<W: Write> Write for Padded<'_, W> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if !self.padded && !s.is_empty() {
+            self.out.write_char(' ')?;
+            self.padded = true;
+        }
+        self.out.write_str(s)
+    }
+}
+
+/// Writes a quoted and escaped string
+pub fn write_string<W: Write + ?Sized>(out: &mut W, value: &str) -> fmt::Result {
+    out.write_char('"')?;
+    for c in value.chars() {
+        match c {
+            '\n' => out.write_str("\\n")?,
+            '\r' => out.write_str("\\r")?,
+            '\t' => out.write_str("\\t")?,
+            '"' => out.write_str("\\\"")?,
+            '\'' => out.write_str("\\'")?,
+            '\\' => out.write_str("\\\\")?,
+            c if (c as u32) < 0x20 || c == '\u{7f}' => write!(out, "\\{:03o}", c as u32)?,
+            c => out.write_char(c)?,
+        }
+    }
+    out.write_char('"')
+}
+
+/// Writes quoted bytes, anything but printable ASCII is octal escaped
+pub fn write_bytes<W: Write + ?Sized>(out: &mut W, bytes: &[u8]) -> fmt::Result {
+    out.write_char('"')?;
+    for byte in bytes {
+        match byte {
+            b'\n' => out.write_str("\\n")?,
+            b'\r' => out.write_str("\\r")?,
+            b'\t' => out.write_str("\\t")?,
+            b'"' => out.write_str("\\\"")?,
+            b'\'' => out.write_str("\\'")?,
+            b'\\' => out.write_str("\\\\")?,
+            0x20..=0x7e => out.write_char(*byte as char)?,
+            _ => write!(out, "\\{:03o}", byte)?,
+        }
+    }
+    out.write_char('"')
+}
+
+/// Reads the values of a repeated field, written either once per value
+/// or as a `[a, b]` list
+pub fn read_repeated<T: FromText>(
+    value: &TextValue,
+    mut push: impl FnMut(T) -> Result<()>,
+) -> Result<()> {
+    match value {
+        TextValue::List(values) => values
+            .iter()
+            .try_for_each(|value| push(T::from_text(value)?)),
+        value => push(T::from_text(value)?),
+    }
+}
+
+/// Stores the next value of a bounded repeated field
+pub fn push_bounded<T, const N: usize>(values: &mut ArrayVec<T, N>, value: T) -> Result<()> {
+    values
+        .try_push(value)
+        .map_err(|_| TextFormatError::CapacityExceeded)
+}
+
+/// Integers may be decimal, hexadecimal (`0x1f`) or octal (`017`)
+fn read_integer<T: TryFrom<i128>>(value: &TextValue) -> Result<T> {
+    let literal = value.as_literal()?;
+    let invalid = || TextFormatError::InvalidNumber(literal.to_string());
+    let (negative, digits) = match literal.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, literal),
+    };
+    if digits.starts_with(['-', '+']) {
+        return Err(invalid());
+    }
+    let (digits, radix) = if let Some(hex) = digits
+        .strip_prefix("0x")
+        .or_else(|| digits.strip_prefix("0X"))
+    {
+        (hex, 16)
+    } else if digits.len() > 1 && digits.starts_with('0') {
+        (&digits[1..], 8)
+    } else {
+        (digits, 10)
+    };
+    let magnitude = i128::from_str_radix(digits, radix).map_err(|_| invalid())?;
+    let integer = match negative {
+        true => -magnitude,
+        false => magnitude,
+    };
+    T::try_from(integer).map_err(|_| invalid())
+}
+
+impl ToText for bool {
+    fn write_text<W: Write>(&self, out: &mut W) -> fmt::Result {
+        write!(out, "{}", self)
+    }
+}
+
+impl FromText for bool {
+    fn from_text(value: &TextValue) -> Result<Self> {
+        match value.as_literal()? {
+            "true" | "True" | "t" | "1" => Ok(true),
+            "false" | "False" | "f" | "0" => Ok(false),
+            _ => Err(TextFormatError::UnexpectedValue("bool")),
+        }
+    }
+}
+
+impl ToText for i32 {
+    fn write_text<W: Write>(&self, out: &mut W) -> fmt::Result {
+        write!(out, "{}", self)
+    }
+}
+
+impl FromText for i32 {
+    fn from_text(value: &TextValue) -> Result<Self> {
+        read_integer(value)
+    }
+}
+
+impl ToText for i64 {
+    fn write_text<W: Write>(&self, out: &mut W) -> fmt::Result {
+        write!(out, "{}", self)
+    }
+}
+
+impl FromText for i64 {
+    fn from_text(value: &TextValue) -> Result<Self> {
+        read_integer(value)
+    }
+}
+
+impl ToText for u32 {
+    fn write_text<W: Write>(&self, out: &mut W) -> fmt::Result {
+        write!(out, "{}", self)
+    }
+}
+
+impl FromText for u32 {
+    fn from_text(value: &TextValue) -> Result<Self> {
+        read_integer(value)
+    }
+}
+
+impl ToText for u64 {
+    fn write_text<W: Write>(&self, out: &mut W) -> fmt::Result {
+        write!(out, "{}", self)
+    }
+}
+
+impl FromText for u64 {
+    fn from_text(value: &TextValue) -> Result<Self> {
+        read_integer(value)
+    }
+}
+
+impl ToText for String {
+    fn write_text<W: Write>(&self, out: &mut W) -> fmt::Result {
+        write_string(out, self)
+    }
+}
+
+impl FromText for String {
+    fn from_text(value: &TextValue) -> Result<Self> {
+        String::from_utf8(value.as_bytes()?.to_vec()).map_err(|_| TextFormatError::InvalidUtf8)
+    }
+}
+
+impl<SIZE: Capacity> ToText for ArrayString<SIZE> {
+    fn write_text<W: Write>(&self, out: &mut W) -> fmt::Result {
+        write_string(out, self.as_str())
+    }
+}
+
+impl<SIZE: Capacity> FromText for ArrayString<SIZE> {
+    fn from_text(value: &TextValue) -> Result<Self> {
+        let s = std::str::from_utf8(value.as_bytes()?).map_err(|_| TextFormatError::InvalidUtf8)?;
+        ArrayString::try_from_str(s).map_err(|_| TextFormatError::CapacityExceeded)
+    }
+}
+
+impl ToText for bytes::Bytes {
+    fn write_text<W: Write>(&self, out: &mut W) -> fmt::Result {
+        write_bytes(out, self)
+    }
+}
+
+impl FromText for bytes::Bytes {
+    fn from_text(value: &TextValue) -> Result<Self> {
+        Ok(bytes::Bytes::copy_from_slice(value.as_bytes()?))
+    }
+}
+
+/// Fixed size bytes, shorter values are padded with zeros
+impl<const N: usize> ToText for [u8; N] {
+    fn write_text<W: Write>(&self, out: &mut W) -> fmt::Result {
+        write_bytes(out, self)
+    }
+}
+
+impl<const N: usize> FromText for [u8; N] {
+    fn from_text(value: &TextValue) -> Result<Self> {
+        let bytes = value.as_bytes()?;
+        let mut value = [0; N];
+        value
+            .get_mut(..bytes.len())
+            .ok_or(TextFormatError::CapacityExceeded)?
+            .copy_from_slice(bytes);
+        Ok(value)
+    }
+}
+
+/// Messages and lists nest at most this deep when parsing, enough for
+/// `decode::MAX_MESSAGE_DEPTH` sub-messages in repeated fields
+pub const MAX_NESTING_DEPTH: usize = 2 * MAX_MESSAGE_DEPTH;
+
+/// Parses the fields of a message, returned as a `TextValue::Message`
+pub fn parse(input: &str) -> Result<TextValue> {
+    let mut parser = TextParser {
+        input: input.as_bytes(),
+        position: 0,
+        depth: 0,
+    };
+    let fields = parser.fields(None)?;
+    Ok(TextValue::Message(fields))
+}
+
+struct TextParser<'a> {
+    input: &'a [u8],
+    position: usize,
+    /// The messages and lists the parser is in
+    depth: usize,
+}
+
+impl TextParser<'_> {
+    fn error<T>(&self) -> Result<T> {
+        Err(TextFormatError::Syntax(self.position))
+    }
+
+    /// skips whitespace and `#` comments
+    fn skip_whitespace(&mut self) {
+        while let Some(c) = self.input.get(self.position) {
+            match c {
+                b' ' | b'\t' | b'\r' | b'\n' | b'\x0b' | b'\x0c' => self.position += 1,
+                b'#' => {
+                    while !matches!(self.input.get(self.position), None | Some(b'\n')) {
+                        self.position += 1;
+                    }
+                }
+                _ => break,
+            }
+        }
+    }
+
+    fn peek(&mut self) -> Option<u8> {
+        self.skip_whitespace();
+        self.input.get(self.position).copied()
+    }
+
+    fn is_literal_char(c: u8) -> bool {
+        c.is_ascii_alphanumeric() || matches!(c, b'_' | b'.' | b'-' | b'+')
+    }
+
+    fn literal(&mut self) -> Result<String> {
+        self.skip_whitespace();
+        let start = self.position;
+        while let Some(c) = self.input.get(self.position) {
+            if !Self::is_literal_char(*c) {
+                break;
+            }
+            self.position += 1;
+        }
+        match start == self.position {
+            true => self.error(),
+            // the literal characters are all ASCII
+            false => Ok(String::from_utf8_lossy(&self.input[start..self.position]).into_owned()),
+        }
+    }
+
+    /// reads fields until the closing delimiter, or the end of input at the top level
+    fn fields(&mut self, close: Option<u8>) -> Result<Vec<(String, TextValue)>> {
+        let mut fields = Vec::new();
+        loop {
+            match (self.peek(), close) {
+                (None, None) => return Ok(fields),
+                (Some(c), Some(close)) if c == close => {
+                    self.position += 1;
+                    return Ok(fields);
+                }
+                (None, Some(_)) => return self.error(),
+                _ => {}
+            }
+            let name = self.literal()?;
+            let value = match self.peek() {
+                Some(b':') => {
+                    self.position += 1;
+                    self.value()?
+                }
+                // the colon is optional before messages
+                Some(b'{' | b'<') => self.value()?,
+                _ => return self.error(),
+            };
+            fields.push((name, value));
+            if let Some(b',' | b';') = self.peek() {
+                self.position += 1;
+            }
+        }
+    }
+
+    /// Parses a message or list after its opening delimiter, the recursion is
+    /// limited so that hostile input cannot overflow the stack
+    fn nested(&mut self, parse: impl FnOnce(&mut Self) -> Result<TextValue>) -> Result<TextValue> {
+        if self.depth == MAX_NESTING_DEPTH {
+            return Err(TextFormatError::TooDeep(self.position));
+        }
+        self.position += 1;
+        self.depth += 1;
+        let value = parse(self);
+        self.depth -= 1;
+        value
+    }
+
+    fn list(&mut self) -> Result<TextValue> {
+        let mut values = Vec::new();
+        if self.peek() == Some(b']') {
+            self.position += 1;
+            return Ok(TextValue::List(values));
+        }
+        loop {
+            values.push(self.value()?);
+            match self.peek() {
+                Some(b',') => self.position += 1,
+                Some(b']') => {
+                    self.position += 1;
+                    return Ok(TextValue::List(values));
+                }
+                _ => return self.error(),
+            }
+        }
+    }
+
+    fn value(&mut self) -> Result<TextValue> {
+        match self.peek() {
+            Some(b'{') => self.nested(|parser| Ok(TextValue::Message(parser.fields(Some(b'}'))?))),
+            Some(b'<') => self.nested(|parser| Ok(TextValue::Message(parser.fields(Some(b'>'))?))),
+            Some(b'[') => self.nested(Self::list),
+            Some(b'"' | b'\'') => {
+                let mut bytes = Vec::new();
+                while let Some(b'"' | b'\'') = self.peek() {
+                    self.string(&mut bytes)?;
+                }
+                Ok(TextValue::String(bytes))
+            }
+            Some(_) => Ok(TextValue::Literal(self.literal()?)),
+            None => self.error(),
+        }
+    }
+
+    fn digits(&mut self, radix: u32, max: usize) -> Result<u32> {
+        let start = self.position;
+        while self.position - start < max
+            && self
+                .input
+                .get(self.position)
+                .is_some_and(|c| (*c as char).is_digit(radix))
+        {
+            self.position += 1;
+        }
+        let digits = std::str::from_utf8(&self.input[start..self.position]).unwrap_or_default();
+        match u32::from_str_radix(digits, radix) {
+            Ok(value) => Ok(value),
+            Err(_) => self.error(),
+        }
+    }
+
+    fn string(&mut self, bytes: &mut Vec<u8>) -> Result<()> {
+        let quote = self.input[self.position];
+        self.position += 1;
+        loop {
+            let c = match self.input.get(self.position) {
+                Some(c) => *c,
+                None => return self.error(),
+            };
+            self.position += 1;
+            match c {
+                c if c == quote => return Ok(()),
+                b'\n' => return self.error(),
+                b'\\' => {
+                    let escape = match self.input.get(self.position) {
+                        Some(escape) => *escape,
+                        None => return self.error(),
+                    };
+                    self.position += 1;
+                    match escape {
+                        b'n' => bytes.push(b'\n'),
+                        b'r' => bytes.push(b'\r'),
+                        b't' => bytes.push(b'\t'),
+                        b'a' => bytes.push(0x07),
+                        b'b' => bytes.push(0x08),
+                        b'f' => bytes.push(0x0c),
+                        b'v' => bytes.push(0x0b),
+                        b'\\' | b'\'' | b'"' | b'?' => bytes.push(escape),
+                        b'0'..=b'7' => {
+                            self.position -= 1;
+                            bytes.push(self.digits(8, 3)? as u8);
+                        }
+                        b'x' | b'X' => bytes.push(self.digits(16, 2)? as u8),
+                        b'u' | b'U' => {
+                            let len = if escape == b'u' { 4 } else { 8 };
+                            let c = match char::from_u32(self.digits(16, len)?) {
+                                Some(c) => c,
+                                None => return self.error(),
+                            };
+                            let mut encoded = [0; 4];
+                            bytes.extend_from_slice(c.encode_utf8(&mut encoded).as_bytes());
+                        }
+                        _ => return self.error(),
+                    }
+                }
+                c => bytes.push(c),
+            }
+        }
+    }
+}
@@ -1,16 +1,59 @@
-use crate::common::{FieldType, Version};
+use crate::common::{FieldQualifier, FieldType, Version};
 /// This module contains a basic validator for the protobuf definition
 /// It will check that all types are valid (either defined or builtin)
 use crate::parser::ProtoParser;
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 #[derive(Debug)]
 pub enum ValidatorError {
     InvalidProtoVersion,
     MissingTypeDefinition(String),
+    /// Two values in the enum share an ordinal without `option allow_alias = true;`
+    DuplicateEnumValue(String, String),
+    /// Two messages share the same `(nanopb_msgopt).msgid`
+    DuplicateMessageId(u32),
+    /// The field number is outside 1 to 2^29 - 1 or in the reserved range 19000 to 19999
+    InvalidFieldNumber(String, u32),
+    /// Only repeated scalar fields can be packed, not strings, bytes or messages
+    InvalidPackedField(String),
+    /// The `(nanopb).max_size` of a string is above `MAX_STRING_SIZE`
+    StringTooLong(String, usize),
 }
 
+impl std::fmt::Display for ValidatorError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidProtoVersion => write!(f, "missing `syntax = \"proto2\";`"),
+            Self::MissingTypeDefinition(name) => write!(f, "type {} is not defined", name),
+            Self::DuplicateEnumValue(name, values) => write!(f, "enum {}: {}", name, values),
+            Self::DuplicateMessageId(msgid) => {
+                write!(f, "msgid {} is used by more than one message", msgid)
+            }
+            Self::InvalidFieldNumber(path, number) => write!(
+                f,
+                "{}: field number {} is not in 1 to {} or is reserved, 19000 to 19999",
+                path, number, MAX_FIELD_NUMBER
+            ),
+            Self::InvalidPackedField(path) => {
+                write!(f, "{}: only repeated scalar fields can be packed", path)
+            }
+            Self::StringTooLong(path, limit) => write!(
+                f,
+                "{}: max_size {} is above {}, the largest bounded string",
+                path, limit, MAX_STRING_SIZE
+            ),
+        }
+    }
+}
+
+/// The largest bounded string, the capacity of the generated `ArrayString`
+/// is limited to 255 bytes
+pub const MAX_STRING_SIZE: usize = 255;
+
+const MAX_FIELD_NUMBER: u32 = (1 << 29) - 1;
+const RESERVED_FIELD_NUMBERS: std::ops::RangeInclusive<u32> = 19000..=19999;
+
 pub fn validate(parser: &ProtoParser) -> Result<(), ValidatorError> {
     if let Version::Unknown = parser.version {
         return Err(ValidatorError::InvalidProtoVersion);
@@ -26,16 +69,75 @@ pub fn validate(parser: &ProtoParser) -> Result<(), ValidatorError> {
     });
 
     for (_, message_type) in parser.message_types.iter() {
-        for (_, field) in message_type.fields.iter() {
-            match field.field_type {
-                FieldType::MessageType(identifier) => {
-                    if !valid_message_types.contains(identifier) {
-                        return Err(ValidatorError::MissingTypeDefinition(
-                            identifier.to_string(),
-                        ));
-                    }
+        for (ordinal, field) in message_type.fields.iter() {
+            if *ordinal == 0
+                || *ordinal > MAX_FIELD_NUMBER
+                || RESERVED_FIELD_NUMBERS.contains(ordinal)
+            {
+                return Err(ValidatorError::InvalidFieldNumber(
+                    format!("{}.{}", message_type.identifier, field.identifier),
+                    *ordinal,
+                ));
+            }
+            let is_packed = matches!(
+                field.qualifier,
+                FieldQualifier::PackedRepeated(_) | FieldQualifier::PackedRepeatedUnbounded
+            );
+            let is_scalar = !matches!(
+                field.field_type,
+                FieldType::UnboundedString
+                    | FieldType::String(_)
+                    | FieldType::UnboundedBytes
+                    | FieldType::Bytes(_)
+                    | FieldType::MessageType(_)
+            );
+            if is_packed && !is_scalar {
+                return Err(ValidatorError::InvalidPackedField(format!(
+                    "{}.{}",
+                    message_type.identifier, field.identifier
+                )));
+            }
+            if let FieldType::String(limit) = field.field_type {
+                if limit > MAX_STRING_SIZE {
+                    return Err(ValidatorError::StringTooLong(
+                        format!("{}.{}", message_type.identifier, field.identifier),
+                        limit,
+                    ));
+                }
+            }
+            if let FieldType::MessageType(identifier) = field.field_type {
+                if !valid_message_types.contains(identifier) {
+                    return Err(ValidatorError::MissingTypeDefinition(
+                        identifier.to_string(),
+                    ));
                 }
-                _ => {}
+            }
+        }
+    }
+
+    let mut message_ids = HashSet::new();
+    for (_, message_type) in parser.message_types.iter() {
+        if let Some(msgid) = message_type.msgid {
+            if !message_ids.insert(msgid) {
+                return Err(ValidatorError::DuplicateMessageId(msgid));
+            }
+        }
+    }
+
+    for (_, enum_type) in parser.enum_types.iter() {
+        if enum_type.allow_alias {
+            continue;
+        }
+        let mut seen_ordinals = HashMap::new();
+        for (identifier, ordinal) in enum_type.pairs.iter() {
+            if let Some(previous) = seen_ordinals.insert(*ordinal, identifier) {
+                return Err(ValidatorError::DuplicateEnumValue(
+                    enum_type.identifier.clone(),
+                    format!(
+                        "{} and {} share the value {}",
+                        previous, identifier, ordinal
+                    ),
+                ));
             }
         }
     }
@@ -1,7 +1,6 @@
 use crate::common::{Field, Packed};
 
 /// This file contains the predefine wiretypes for the types where this is applicable
-/// TODO: ZigZag encoding of signed types
 pub struct WireTypeId(pub u32);
 pub struct Tag(pub u32);
 
@@ -27,23 +26,24 @@ impl ToVarint for u64 {
     }
 }
 
+/// int32 is encoded as an int64, negative values are sign extended
+/// and always take 10 bytes
 impl ToVarint for i32 {
     fn to_varint_encoding(&self) -> ([u8; 10], usize) {
-        let mut out: [u8; 10] = [0; 10];
-        let mut writable = &mut out[..];
-        let count = leb128::write::signed(&mut writable, *self as i64).unwrap();
-        dbg!(count);
-        dbg!(out);
-        (out, count)
+        (*self as i64).to_varint_encoding()
     }
 }
 
+/// int64 is encoded as the two's complement u64, not zigzag encoded
 impl ToVarint for i64 {
     fn to_varint_encoding(&self) -> ([u8; 10], usize) {
-        let mut out: [u8; 10] = [0; 10];
-        let mut writable = &mut out[..];
-        let count = leb128::write::signed(&mut writable, *self).unwrap();
-        (out, count)
+        (*self as u64).to_varint_encoding()
+    }
+}
+
+impl ToVarint for bool {
+    fn to_varint_encoding(&self) -> ([u8; 10], usize) {
+        (*self as u64).to_varint_encoding()
     }
 }
 
@@ -60,7 +60,7 @@ impl Tag {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum WireType {
     VarInt,
     I64,
@@ -98,7 +98,7 @@ impl TryFrom<u32> for WireType {
 }
 
 impl WireType {
-    fn to_id(&self) -> WireTypeId {
+    fn to_id(self) -> WireTypeId {
         match self {
             WireType::VarInt => WireTypeId(VARINT_ID),
             WireType::I64 => WireTypeId(I64_ID),
@@ -144,24 +144,39 @@ impl WireTyped for i32 {
     }
 }
 
-// TODO: Should not be defined here
-// Should be generated instead
-impl WireTyped for &str {
+impl WireTyped for i64 {
     fn wiretype(&self) -> WireType {
-        WireType::Len
+        WireType::VarInt
     }
 }
 
-impl WireTyped for String {
+impl WireTyped for u32 {
+    fn wiretype(&self) -> WireType {
+        WireType::VarInt
+    }
+}
+
+impl WireTyped for u64 {
+    fn wiretype(&self) -> WireType {
+        WireType::VarInt
+    }
+}
+
+impl WireTyped for bool {
+    fn wiretype(&self) -> WireType {
+        WireType::VarInt
+    }
+}
+
+// TODO: Should not be defined here
+// Should be generated instead
+impl WireTyped for &str {
     fn wiretype(&self) -> WireType {
         WireType::Len
     }
 }
 
-impl<T> WireTyped for &[T]
-where
-    T: WireTyped,
-{
+impl WireTyped for String {
     fn wiretype(&self) -> WireType {
         WireType::Len
     }
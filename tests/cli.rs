@@ -0,0 +1,433 @@
+use picopb::decode::DecodeMessage;
+use picopb::encode::ToWire;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+mod imports {
+    #![allow(dead_code, unused_imports)]
+    include!("../protos/imports.rs");
+}
+
+fn picopb(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_picopb"))
+        .args(args)
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .output()
+        .unwrap()
+}
+
+/// An empty directory for the test's files
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("picopb-{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn check_exit_codes() {
+    assert_eq!(
+        picopb(&["check", "protos/registry.proto"]).status.code(),
+        Some(0)
+    );
+
+    let dir = temp_dir("check");
+    let invalid = dir.join("invalid.proto");
+    std::fs::write(
+        &invalid,
+        "syntax = \"proto2\"; message A { required B b = 1; }",
+    )
+    .unwrap();
+    let invalid = invalid.to_str().unwrap();
+    let output = picopb(&["check", invalid, "protos/registry.proto"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("MissingTypeDefinition(\"B\")"));
+    assert_eq!(
+        picopb(&["check", "--no-validate", invalid]).status.code(),
+        Some(0)
+    );
+
+    // a missing file fails after the other inputs were checked
+    let output = picopb(&["check", "protos/missing.proto", invalid]);
+    assert_eq!(output.status.code(), Some(3));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("missing.proto: file not found"));
+    assert!(stderr.contains("invalid.proto"));
+}
+
+#[test]
+fn check_resolves_imports() {
+    let dir = temp_dir("imports");
+    std::fs::create_dir_all(dir.join("include")).unwrap();
+    std::fs::write(
+        dir.join("include/common.proto"),
+        "syntax = \"proto2\"; import \"unit.proto\"; message Common { optional Unit unit = 1; }",
+    )
+    .unwrap();
+    std::fs::write(
+        dir.join("include/unit.proto"),
+        "syntax = \"proto2\"; enum Unit { UNIT_UNSPECIFIED = 0; UNIT_KELVIN = 1; }",
+    )
+    .unwrap();
+    std::fs::write(
+        dir.join("main.proto"),
+        "syntax = \"proto2\"; import \"common.proto\"; message Main { optional Common common = 1; }",
+    )
+    .unwrap();
+    let dir = dir.to_str().unwrap();
+    let output = picopb(&["check", "-I", dir, "main.proto"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("import \"common.proto\" not found"));
+    let include = format!("{}/include", dir);
+    let output = picopb(&["check", "-I", dir, "-I", &include, "main.proto"]);
+    assert_eq!(output.status.code(), Some(0));
+
+    // the types of the imports, and of their imports, are generated as well
+    let output = picopb(&["generate", "-I", dir, "-I", &include, "main.proto"]);
+    assert_eq!(output.status.code(), Some(0));
+    let generated = String::from_utf8(output.stdout).unwrap();
+    assert!(generated.contains("pub struct Main {"));
+    assert!(generated.contains("pub struct Common {"));
+    assert!(generated.contains("pub enum Unit {"));
+}
+
+#[test]
+fn encode_decode_resolve_imports() {
+    let dir = temp_dir("dynamic-imports");
+    std::fs::write(
+        dir.join("common.proto"),
+        "syntax = \"proto2\"; message Common { optional int32 id = 1; }",
+    )
+    .unwrap();
+    std::fs::create_dir_all(dir.join("main")).unwrap();
+    std::fs::write(
+        dir.join("main/main.proto"),
+        "syntax = \"proto2\"; import \"common.proto\"; message Main { optional Common common = 1; }",
+    )
+    .unwrap();
+    std::fs::write(dir.join("main.txt"), "common { id: 3 }").unwrap();
+    let dir = dir.to_str().unwrap();
+    let proto = format!("{}/main/main.proto", dir);
+    let text = format!("{}/main.txt", dir);
+    let output = picopb(&["encode", &proto, "Main", &text]);
+    assert_eq!(output.status.code(), Some(1));
+
+    let output = picopb(&["encode", &proto, "-I", dir, "Main", &text]);
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(output.stdout, [0x0a, 0x02, 0x08, 0x03]);
+    let binary = format!("{}/main.bin", dir);
+    std::fs::write(&binary, output.stdout).unwrap();
+    let output = picopb(&["decode", &proto, "-I", dir, "Main", &binary]);
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "common { id: 3 }\n"
+    );
+}
+
+#[test]
+fn generate_out_dir() {
+    let dir = temp_dir("generate");
+    let out_dir = dir.join("out");
+    let output = picopb(&[
+        "generate",
+        "--out-dir",
+        out_dir.to_str().unwrap(),
+        "-I",
+        "protos",
+        "registry.proto",
+        "unknown.proto",
+    ]);
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(
+        std::fs::read_to_string(out_dir.join("registry.rs")).unwrap(),
+        include_str!("../protos/registry.rs")
+    );
+    assert_eq!(
+        std::fs::read(out_dir.join("unknown.rs")).unwrap(),
+        picopb(&["generate", "protos/unknown.proto"]).stdout
+    );
+
+    let output = picopb(&["generate", "protos/registry.proto"]);
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        include_str!("../protos/registry.rs")
+    );
+    let output = picopb(&["generate", "protos/registry.proto", "protos/json.proto"]);
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn descriptor_out() {
+    let dir = temp_dir("descriptor-out");
+    let descriptor = dir.join("registry.pb");
+    let descriptor = descriptor.to_str().unwrap();
+    let output = picopb(&[
+        "check",
+        "--descriptor-out",
+        descriptor,
+        "-I",
+        "protos",
+        "registry.proto",
+    ]);
+    assert_eq!(output.status.code(), Some(0));
+    let output = picopb(&["generate", "--descriptor-set", descriptor]);
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        include_str!("../protos/registry.rs")
+    );
+
+    // nothing is written when an input fails
+    std::fs::remove_file(descriptor).unwrap();
+    let output = picopb(&[
+        "check",
+        "--descriptor-out",
+        descriptor,
+        "protos/registry.proto",
+        "protos/missing.proto",
+    ]);
+    assert_eq!(output.status.code(), Some(3));
+    assert!(!Path::new(descriptor).exists());
+}
+
+#[test]
+fn compat_strictness() {
+    let dir = temp_dir("compat");
+    std::fs::write(
+        dir.join("old.proto"),
+        "syntax = \"proto2\"; message A { optional int32 a = 1; optional string b = 2; }",
+    )
+    .unwrap();
+    std::fs::write(
+        dir.join("new.proto"),
+        "syntax = \"proto2\"; message A { optional int32 a = 1; optional string c = 2; }",
+    )
+    .unwrap();
+    let dir = dir.to_str().unwrap();
+    let output = picopb(&["compat", "-I", dir, "old.proto", "new.proto"]);
+    assert_eq!(output.status.code(), Some(0));
+    assert!(output.stdout.is_empty());
+
+    let output = picopb(&[
+        "compat",
+        "--strictness",
+        "source",
+        "-I",
+        dir,
+        "old.proto",
+        "new.proto",
+    ]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "source: A.b: field renamed to c\n"
+    );
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("1 incompatible changes"));
+}
+
+#[test]
+fn fmt_check() {
+    let dir = temp_dir("fmt");
+    let path = dir.join("reading.proto");
+    std::fs::write(
+        &path,
+        "syntax=\"proto2\";\nmessage Reading{optional int32 value=1;}\n",
+    )
+    .unwrap();
+    let path = path.to_str().unwrap();
+
+    let output = picopb(&["fmt", "--check", path]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        format!("{}\n", path)
+    );
+
+    assert_eq!(picopb(&["fmt", path]).status.code(), Some(0));
+    assert_eq!(
+        std::fs::read_to_string(path).unwrap(),
+        "syntax = \"proto2\";\n\nmessage Reading {\n    optional int32 value = 1;\n}\n"
+    );
+    let output = picopb(&["fmt", "--check", path]);
+    assert_eq!(output.status.code(), Some(0));
+    assert!(output.stdout.is_empty());
+
+    std::fs::write(dir.join("invalid.proto"), "message {").unwrap();
+    let invalid = dir.join("invalid.proto");
+    let output = picopb(&["fmt", invalid.to_str().unwrap(), path]);
+    assert_eq!(output.status.code(), Some(1));
+}
+
+#[test]
+fn lint_unused_import() {
+    let dir = temp_dir("lint");
+    std::fs::write(
+        dir.join("common.proto"),
+        "syntax = \"proto2\"; message Common {}",
+    )
+    .unwrap();
+    std::fs::write(
+        dir.join("main.proto"),
+        "syntax = \"proto2\"; import \"common.proto\"; message Main { option (nanopb_msgopt).msgid = 1; optional string name = 1; } message Extra {}",
+    )
+    .unwrap();
+    let main = dir.join("main.proto");
+    let main = main.to_str().unwrap();
+
+    let output = picopb(&["lint", main]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        format!(
+            "{main}: unbounded-field: Main.name: no (nanopb).max_size, the value is heap allocated\n\
+             {main}: unused-import: common.proto: no field refers to a type of the imported file\n"
+        )
+    );
+
+    let output = picopb(&[
+        "lint",
+        "--disable",
+        "unbounded-field",
+        "--disable",
+        "unused-import",
+        main,
+    ]);
+    assert_eq!(output.status.code(), Some(0));
+
+    let output = picopb(&[
+        "lint",
+        "--disable",
+        "unbounded-field",
+        "--disable",
+        "unused-import",
+        "--enable",
+        "unused-message",
+        main,
+    ]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        format!(
+            "{main}: unused-message: Extra: no field refers to the message and it has no msgid\n"
+        )
+    );
+    assert_eq!(
+        picopb(&["lint", "--disable", "no-such-rule", main])
+            .status
+            .code(),
+        Some(2)
+    );
+}
+
+#[test]
+fn decode_raw_deeply_nested_groups() {
+    let dir = temp_dir("decode-raw");
+    let input = dir.join("groups.bin");
+    std::fs::write(&input, vec![0x0b; 2_000_000]).unwrap();
+    let output = picopb(&["decode-raw", input.to_str().unwrap()]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("malformed input at byte 64: GroupTooDeep"));
+}
+
+#[test]
+fn lint_used_import() {
+    let dir = temp_dir("lint-used");
+    std::fs::create_dir_all(dir.join("include")).unwrap();
+    // the imported file is not linted itself
+    std::fs::write(
+        dir.join("include/common.proto"),
+        "syntax = \"proto2\"; message common { optional int32 ID = 1; }",
+    )
+    .unwrap();
+    std::fs::write(
+        dir.join("main.proto"),
+        "syntax = \"proto2\"; import \"common.proto\"; message Main { option (nanopb_msgopt).msgid = 1; optional common common = 1; }",
+    )
+    .unwrap();
+    let include = dir.join("include");
+    let main = dir.join("main.proto");
+    let output = picopb(&[
+        "lint",
+        "-I",
+        include.to_str().unwrap(),
+        main.to_str().unwrap(),
+    ]);
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "");
+    assert_eq!(output.status.code(), Some(0));
+}
+
+#[test]
+fn generate_imported_enum() {
+    // `Color` is defined in palette.proto, a field of its type is an enum and not a message
+    let output = picopb(&["generate", "protos/imports.proto"]);
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        include_str!("../protos/imports.rs")
+    );
+
+    let dir = temp_dir("imported-enum");
+    let text = dir.join("pixel.txt");
+    std::fs::write(&text, "color: COLOR_GREEN x: 3").unwrap();
+    let output = picopb(&[
+        "encode",
+        "protos/imports.proto",
+        "Pixel",
+        text.to_str().unwrap(),
+    ]);
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(output.stdout, [0x08, 0x02, 0x10, 0x03]);
+
+    let pixel = imports::Pixel::decode(&output.stdout).unwrap();
+    assert_eq!(pixel.color, imports::Color::ColorGreen);
+    let mut encoded: Vec<u8> = Vec::new();
+    (&pixel).append(&mut encoded).unwrap();
+    assert_eq!(encoded, output.stdout);
+}
+
+#[test]
+fn generate_rejects_shared_imports() {
+    let dir = temp_dir("generate-shared");
+    std::fs::write(
+        dir.join("second.proto"),
+        "syntax = \"proto2\"; import \"palette.proto\"; message Brush { optional Color color = 1; }",
+    )
+    .unwrap();
+    let out_dir = dir.join("out");
+    let second = dir.join("second.proto");
+    // both files import palette.proto, each generated file would define `Color`
+    let output = picopb(&[
+        "generate",
+        "-o",
+        out_dir.to_str().unwrap(),
+        "-I",
+        "protos",
+        "protos/imports.proto",
+        second.to_str().unwrap(),
+    ]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("type Color is also generated from protos/imports.proto"));
+    assert!(out_dir.join("imports.rs").is_file());
+    assert!(!out_dir.join("second.rs").exists());
+
+    let output = picopb(&[
+        "generate",
+        "-o",
+        out_dir.to_str().unwrap(),
+        "-I",
+        "protos",
+        second.to_str().unwrap(),
+    ]);
+    assert_eq!(output.status.code(), Some(0));
+}
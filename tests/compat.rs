@@ -0,0 +1,195 @@
+use picopb::compat::{breaking_changes, compare, Change, ChangeKind, Severity};
+use picopb::parser::parse;
+
+const OLD: &str = r#"syntax = "proto2";
+message Reading {
+    option (nanopb_msgopt).msgid = 2;
+    required string sensor = 1 [(nanopb).max_size = 16];
+    optional int32 value = 2;
+    optional int32 offset = 3;
+    repeated int32 samples = 4 [(nanopb).max_size = 8];
+    required uint32 checksum = 5;
+    optional bytes label = 6;
+    optional int32 flags = 7;
+}
+message Removed { optional int32 x = 1; }
+enum Unit {
+    UNIT_UNKNOWN = 0;
+    UNIT_CELSIUS = 1;
+    UNIT_KELVIN = 2;
+    UNIT_RANKINE = 3;
+}"#;
+
+const NEW: &str = r#"syntax = "proto2";
+package sensors;
+message Reading {
+    option (nanopb_msgopt).msgid = 4;
+    required string sensor = 1 [(nanopb).max_size = 8];
+    optional int64 value = 5;
+    optional int32 bias = 3;
+    repeated int32 samples = 4 [(nanopb).max_size = 8, packed = true];
+    optional string label = 6;
+    repeated int32 flags = 7;
+    required bool valid = 8;
+    optional string comment = 2;
+}
+enum Unit {
+    UNIT_UNKNOWN = 0;
+    UNIT_C = 1;
+    UNIT_KELVIN = 4;
+    UNIT_FAHRENHEIT = 5;
+}"#;
+
+fn change(severity: Severity, path: &str, kind: ChangeKind) -> Change {
+    Change {
+        severity,
+        path: path.into(),
+        kind,
+    }
+}
+
+#[test]
+fn compare_schemas() {
+    let old = parse(OLD).unwrap();
+    let new = parse(NEW).unwrap();
+    assert_eq!(
+        compare(&old, &new),
+        vec![
+            change(
+                Severity::Source,
+                "package",
+                ChangeKind::PackageChanged(None, Some("sensors".into()))
+            ),
+            change(
+                Severity::Wire,
+                "Reading",
+                ChangeKind::MsgidChanged(Some(2), Some(4))
+            ),
+            change(
+                Severity::Values,
+                "Reading.sensor",
+                ChangeKind::BoundsChanged(Some(16), Some(8))
+            ),
+            change(
+                Severity::Wire,
+                "Reading.value",
+                ChangeKind::FieldRenumbered(2, 5)
+            ),
+            change(
+                Severity::Wire,
+                "Reading.value",
+                ChangeKind::FieldNumberReused("comment".into())
+            ),
+            change(
+                Severity::Source,
+                "Reading.offset",
+                ChangeKind::FieldRenamed("bias".into())
+            ),
+            change(
+                Severity::Source,
+                "Reading.samples",
+                ChangeKind::PackedChanged(true)
+            ),
+            change(
+                Severity::Wire,
+                "Reading.checksum",
+                ChangeKind::FieldRemoved(true)
+            ),
+            change(
+                Severity::Values,
+                "Reading.label",
+                ChangeKind::TypeChanged("bytes".into(), "string".into())
+            ),
+            change(
+                Severity::Wire,
+                "Reading.flags",
+                ChangeKind::CardinalityChanged("optional", "repeated")
+            ),
+            change(
+                Severity::Wire,
+                "Reading.valid",
+                ChangeKind::RequiredFieldAdded
+            ),
+            change(Severity::Source, "Removed", ChangeKind::MessageRemoved),
+            change(
+                Severity::Source,
+                "Unit.UNIT_CELSIUS",
+                ChangeKind::EnumValueRenamed("UNIT_C".into())
+            ),
+            change(
+                Severity::Wire,
+                "Unit.UNIT_KELVIN",
+                ChangeKind::EnumValueRenumbered(2, 4)
+            ),
+            change(
+                Severity::Wire,
+                "Unit.UNIT_RANKINE",
+                ChangeKind::EnumValueRemoved(3)
+            ),
+            change(
+                Severity::Values,
+                "Unit.UNIT_FAHRENHEIT",
+                ChangeKind::EnumValueAdded(5)
+            ),
+        ]
+    );
+}
+
+#[test]
+fn strictness_levels() {
+    let old = parse(OLD).unwrap();
+    let new = parse(NEW).unwrap();
+    assert_eq!(breaking_changes(&old, &new, Severity::Wire).len(), 8);
+    assert_eq!(breaking_changes(&old, &new, Severity::Values).len(), 11);
+    assert_eq!(breaking_changes(&old, &new, Severity::Source).len(), 16);
+    assert_eq!(compare(&old, &old), vec![]);
+
+    // compatible additions are not reported
+    let added = NEW.replace(
+        "}\nenum",
+        "    optional int32 extra = 9;\n}\nmessage Added {}\nenum",
+    );
+    assert_eq!(compare(&new, &parse(&added).unwrap()), vec![]);
+    assert_eq!(
+        compare(&old, &new)[3].to_string(),
+        "wire: Reading.value: field number changed from 2 to 5"
+    );
+}
+
+#[test]
+fn message_type_changes_break_the_wire() {
+    let schema = |field_type: &str| {
+        format!(
+            "syntax = \"proto2\";
+            message A {{ optional int32 a = 1; }}
+            message B {{ optional string b = 1; }}
+            message Reading {{ optional {} value = 1; }}",
+            field_type
+        )
+    };
+    let type_change = |old: &str, new: &str| {
+        let (old, new) = (schema(old), schema(new));
+        compare(&parse(&old).unwrap(), &parse(&new).unwrap())
+            .into_iter()
+            .map(|change| (change.severity, change.kind))
+            .collect::<Vec<_>>()
+    };
+    for (old, new) in [("A", "B"), ("string", "A"), ("A", "bytes"), ("int32", "A")] {
+        let kind = ChangeKind::TypeChanged(type_name(old), type_name(new));
+        assert_eq!(type_change(old, new), vec![(Severity::Wire, kind)]);
+    }
+
+    // strings and bytes are read as each other
+    let kind = ChangeKind::TypeChanged("string".into(), "bytes".into());
+    assert_eq!(
+        type_change("string", "bytes"),
+        vec![(Severity::Values, kind)]
+    );
+}
+
+fn type_name(field_type: &str) -> String {
+    match field_type {
+        "A" | "B" => format!("message {}", field_type),
+        _ => field_type.into(),
+    }
+}
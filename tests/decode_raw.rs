@@ -0,0 +1,85 @@
+use picopb::common::Field;
+use picopb::decode::DecodeError;
+use picopb::raw::{decode_raw, write_raw, RawError, RawField, RawValue};
+use picopb::wiretypes::WireType;
+
+fn raw_text(bytes: &[u8]) -> String {
+    let mut output = String::new();
+    write_raw(&mut output, &decode_raw(bytes).unwrap()).unwrap();
+    output
+}
+
+#[test]
+fn decode_raw_fields() {
+    let bytes = [0x08, 0x96, 0x01, 0x1a, 0x02, 0x08, 0x01];
+    assert_eq!(
+        decode_raw(&bytes).unwrap(),
+        vec![
+            RawField {
+                offset: 0,
+                field: Field(1),
+                wire_type: WireType::VarInt,
+                value: RawValue::VarInt(150),
+            },
+            RawField {
+                offset: 3,
+                field: Field(3),
+                wire_type: WireType::Len,
+                value: RawValue::Message(vec![RawField {
+                    offset: 5,
+                    field: Field(1),
+                    wire_type: WireType::VarInt,
+                    value: RawValue::VarInt(1),
+                }]),
+            },
+        ]
+    );
+}
+
+#[test]
+fn write_raw_tree() {
+    let bytes = [
+        0x0a, 0x04, b't', b'e', b'm', b'p', 0x12, 0x04, 0x0a, 0x02, 0x08, 0x2a, 0x10, 0x1b, 0x1b,
+        0x08, 0x00, 0x1c, 0x21, 0x01, 0, 0, 0, 0, 0, 0, 0x80, 0x2a, 0x00,
+    ];
+    assert_eq!(
+        raw_text(&bytes),
+        "1 Len: \"temp\"
+2 Len {
+  1 Len {
+    1 VarInt: 42
+  }
+}
+2 VarInt: 27
+3 SGroup {
+  1 VarInt: 0
+}
+4 I64: 0x8000000000000001
+5 Len: \"\"
+"
+    );
+}
+
+#[test]
+fn decode_raw_malformed() {
+    // the length of field 2 runs past the end of the input
+    assert_eq!(
+        decode_raw(&[0x08, 0x01, 0x12, 0x05, 0x01]),
+        Err(RawError {
+            offset: 2,
+            error: DecodeError::UnexpectedEndOfBuffer,
+        })
+    );
+    assert_eq!(
+        decode_raw(&[0x08, 0x01, 0x07]),
+        Err(RawError {
+            offset: 2,
+            error: DecodeError::InvalidWireType(7),
+        })
+    );
+    // an end group without a start group
+    assert_eq!(
+        decode_raw(&[0x0c]).unwrap_err().error,
+        DecodeError::UnexpectedEndGroup
+    );
+}
@@ -0,0 +1,120 @@
+use picopb::decode::{decode_delimited, DecodeError, DelimitedReader};
+use picopb::encode::{EncodeBuffer, EncodeSink};
+
+mod registry {
+    #![allow(dead_code, unused_imports)]
+    include!("../protos/registry.rs");
+}
+
+use registry::{Ping, Reading};
+
+#[test]
+fn encode_delimited_prefixes_length() {
+    let mut static_buffer: [u8; 32] = [0; 32];
+    let mut buffer = EncodeBuffer::from_static(&mut static_buffer);
+
+    let count = buffer.encode_delimited(&Ping { sequence: 150 }).unwrap();
+    // length 3, tag 0x08, varint 150
+    assert_eq!(buffer.as_slice(), &[0x03, 0x08, 0x96, 0x01]);
+    assert_eq!(count, 4);
+}
+
+fn encode_pings(sequences: &[i32], buffer: &mut EncodeBuffer) {
+    for sequence in sequences {
+        let ping = Ping {
+            sequence: *sequence,
+        };
+        buffer.encode_delimited(&ping).unwrap();
+    }
+}
+
+#[test]
+fn decode_delimited_slice() {
+    let mut static_buffer: [u8; 32] = [0; 32];
+    let mut buffer = EncodeBuffer::from_static(&mut static_buffer);
+    encode_pings(&[1, 2, 300], &mut buffer);
+
+    let sequences = decode_delimited::<Ping>(buffer.as_slice())
+        .map(|ping| ping.unwrap().sequence)
+        .collect::<Vec<_>>();
+    assert_eq!(sequences, vec![1, 2, 300]);
+}
+
+#[test]
+fn decode_delimited_slice_truncated() {
+    let mut static_buffer: [u8; 32] = [0; 32];
+    let mut buffer = EncodeBuffer::from_static(&mut static_buffer);
+    encode_pings(&[1, 2], &mut buffer);
+    let bytes = buffer.as_slice();
+    let truncated = &bytes[..bytes.len() - 1];
+
+    let mut records = decode_delimited::<Ping>(truncated);
+    assert_eq!(records.next().unwrap().unwrap().sequence, 1);
+    assert_eq!(
+        records.next().unwrap().unwrap_err(),
+        DecodeError::TruncatedRecord(3)
+    );
+    assert!(records.next().is_none());
+}
+
+#[test]
+fn decode_delimited_continues_after_invalid_message() {
+    // an empty Ping is missing its required field, the framing is still intact
+    let bytes = [0x00, 0x02, 0x08, 0x05];
+    let mut records = decode_delimited::<Ping>(&bytes);
+    assert_eq!(
+        records.next().unwrap().unwrap_err(),
+        DecodeError::MissingRequiredField("sequence")
+    );
+    assert_eq!(records.next().unwrap().unwrap().sequence, 5);
+    assert!(records.next().is_none());
+}
+
+#[test]
+fn decode_delimited_reader() {
+    let mut static_buffer: [u8; 64] = [0; 64];
+    let mut buffer = EncodeBuffer::from_static(&mut static_buffer);
+    let reading = Reading {
+        sensor: "temp".into(),
+        value: Some(21),
+        location: None,
+    };
+    buffer.encode_delimited(&reading).unwrap();
+    encode_pings(&[7], &mut buffer);
+
+    // the stream holds two different message types, read the first one as a Reading
+    let bytes = buffer.as_slice().to_vec();
+    let mut reader = DelimitedReader::<_, Reading>::new(&bytes[..]);
+    let decoded = reader.next().unwrap().unwrap();
+    assert_eq!(decoded.sensor, "temp");
+    assert_eq!(decoded.value, Some(21));
+    assert_eq!(reader.position(), 9);
+
+    let mut reader = DelimitedReader::<_, Ping>::new(&bytes[9..]);
+    assert_eq!(reader.next().unwrap().unwrap().sequence, 7);
+    assert!(reader.next().is_none());
+}
+
+#[test]
+fn decode_delimited_reader_truncated() {
+    let bytes = [0x02, 0x08, 0x01, 0x05, 0x08];
+    let mut reader = DelimitedReader::<_, Ping>::new(&bytes[..]);
+    assert_eq!(reader.next().unwrap().unwrap().sequence, 1);
+    assert_eq!(
+        reader.next().unwrap().unwrap_err(),
+        DecodeError::TruncatedRecord(3)
+    );
+    assert!(reader.next().is_none());
+}
+
+#[test]
+fn decode_delimited_reader_huge_length_prefix() {
+    // a length prefix of 2^62 - 1 followed by a single byte
+    let bytes = [0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x3f, 0x08];
+    let mut reader = DelimitedReader::<_, Ping>::new(&bytes[..]);
+    assert_eq!(
+        reader.next().unwrap().unwrap_err(),
+        DecodeError::TruncatedRecord(0)
+    );
+    assert_eq!(reader.position(), 10);
+}
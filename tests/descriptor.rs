@@ -0,0 +1,326 @@
+use picopb::decode::DecodeError;
+use picopb::descriptor::proto::{
+    DescriptorProto, EnumDescriptorProto, EnumValueDescriptorProto, FieldDescriptorProto,
+    FieldOptions, FileDescriptorProto, FileDescriptorSet, MessageOptions, NanoPBOptions,
+};
+use picopb::descriptor::{
+    decode_descriptor_set, encode_descriptor_set, file_descriptor_from_schema,
+    schema_from_descriptor_set, DescriptorError,
+};
+use picopb::encode::ToWire;
+use picopb::{generator::generate, parser::parse, validator::validate};
+
+#[test]
+fn generated_descriptor_is_up_to_date() {
+    let result = parse(include_str!("../protos/descriptor.proto")).unwrap();
+    validate(&result).unwrap();
+    let mut output = Vec::new();
+    generate(&mut output, &result).unwrap();
+    assert_eq!(
+        String::from_utf8(output).unwrap(),
+        include_str!("../protos/descriptor.rs")
+    );
+}
+
+fn field(name: &str, number: i32, label: i32, field_type: i32) -> FieldDescriptorProto {
+    FieldDescriptorProto {
+        name: Some(name.into()),
+        number: Some(number),
+        label: Some(label),
+        field_type: Some(field_type),
+        json_name: Some(picopb::json::json_name(name)),
+        ..Default::default()
+    }
+}
+
+fn message_field(name: &str, number: i32, label: i32, type_name: &str) -> FieldDescriptorProto {
+    FieldDescriptorProto {
+        type_name: Some(type_name.into()),
+        ..field(name, number, label, 11)
+    }
+}
+
+fn message(name: &str, msgid: Option<u32>, field: Vec<FieldDescriptorProto>) -> DescriptorProto {
+    DescriptorProto {
+        name: Some(name.into()),
+        field,
+        options: msgid.map(|msgid| MessageOptions {
+            nanopb_msgopt: Some(NanoPBOptions {
+                msgid: Some(msgid),
+                ..Default::default()
+            }),
+        }),
+        ..Default::default()
+    }
+}
+
+/// protos/registry.proto as protoc describes it, with Location nested in Reading
+fn registry() -> FileDescriptorProto {
+    let mut reading = message(
+        "Reading",
+        Some(2),
+        vec![
+            field("sensor", 1, 2, 9),
+            field("value", 2, 1, 5),
+            message_field("location", 3, 1, ".registry.Reading.Location"),
+        ],
+    );
+    reading.nested_type.push(message(
+        "Location",
+        None,
+        vec![
+            field("latitude", 1, 2, 5),
+            field("longitude", 2, 2, 5),
+            field("label", 3, 1, 12),
+        ],
+    ));
+    let samples = FieldDescriptorProto {
+        options: Some(FieldOptions {
+            packed: Some(true),
+            nanopb: None,
+        }),
+        ..field("samples", 1, 3, 5)
+    };
+    let unit = EnumDescriptorProto {
+        name: Some("Unit".into()),
+        value: [
+            ("UNIT_UNKNOWN", 0),
+            ("UNIT_CELSIUS", 1),
+            ("UNIT_KELVIN", 2),
+            ("UNIT_INVALID", -1),
+        ]
+        .into_iter()
+        .map(|(name, number)| EnumValueDescriptorProto {
+            name: Some(name.into()),
+            number: Some(number),
+        })
+        .collect(),
+        options: None,
+    };
+    FileDescriptorProto {
+        name: Some("registry.proto".into()),
+        package: Some("registry".into()),
+        message_type: vec![
+            message("Ping", Some(1), vec![field("sequence", 1, 2, 5)]),
+            reading,
+            message(
+                "Batch",
+                Some(3),
+                vec![
+                    samples,
+                    field("tags", 2, 3, 9),
+                    message_field("locations", 3, 3, ".registry.Reading.Location"),
+                    field("counters", 4, 3, 13),
+                ],
+            ),
+        ],
+        enum_type: vec![unit],
+        ..Default::default()
+    }
+}
+
+fn encode(set: &FileDescriptorSet) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    set.append(&mut bytes).unwrap();
+    bytes
+}
+
+#[test]
+fn descriptor_set_generates_like_the_proto() {
+    let bytes = encode(&FileDescriptorSet {
+        file: vec![registry()],
+    });
+    let set = decode_descriptor_set(&bytes).unwrap();
+    let schema = schema_from_descriptor_set(&set).unwrap();
+    validate(&schema).unwrap();
+    assert_eq!(schema.package.as_deref(), Some("registry"));
+    let mut output = Vec::new();
+    generate(&mut output, &schema).unwrap();
+    assert_eq!(
+        String::from_utf8(output).unwrap(),
+        include_str!("../protos/registry.rs")
+    );
+}
+
+#[test]
+fn descriptor_options() {
+    let bounded = FieldDescriptorProto {
+        options: Some(FieldOptions {
+            packed: None,
+            nanopb: Some(NanoPBOptions {
+                max_count: Some(4),
+                ..Default::default()
+            }),
+        }),
+        json_name: Some("v".into()),
+        ..field("values", 1, 3, 5)
+    };
+    let set = FileDescriptorSet {
+        file: vec![FileDescriptorProto {
+            message_type: vec![message("Bounded", None, vec![bounded])],
+            dependency: vec!["other.proto".into()],
+            ..Default::default()
+        }],
+    };
+    let schema = schema_from_descriptor_set(&set).unwrap();
+    assert_eq!(schema.imports, vec!["other.proto".to_string()]);
+    let field = &schema.message_types["Bounded"].fields[&1];
+    assert_eq!(field.json_name(), "v");
+    assert_eq!(
+        field.qualifier.repr(),
+        "picopb::common::FieldQualifier::Repeated(4)"
+    );
+}
+
+#[test]
+fn descriptor_errors() {
+    let convert = |file: FileDescriptorProto| {
+        schema_from_descriptor_set(&FileDescriptorSet { file: vec![file] })
+            .map(|_| ())
+            .unwrap_err()
+    };
+    assert_eq!(
+        convert(FileDescriptorProto {
+            syntax: Some("proto3".into()),
+            ..Default::default()
+        }),
+        DescriptorError::UnsupportedSyntax("proto3".into())
+    );
+    let with_field = |field| FileDescriptorProto {
+        message_type: vec![message("A", None, vec![field])],
+        ..Default::default()
+    };
+    assert_eq!(
+        convert(with_field(field("x", 1, 1, 17))),
+        DescriptorError::UnsupportedFieldType("A.x".into(), 17)
+    );
+    assert_eq!(
+        convert(with_field(FieldDescriptorProto {
+            oneof_index: Some(0),
+            ..field("x", 1, 1, 5)
+        })),
+        DescriptorError::UnsupportedOneof("A.x".into())
+    );
+    assert_eq!(
+        convert(FileDescriptorProto {
+            message_type: vec![message("A", None, vec![]), message("A", None, vec![])],
+            ..Default::default()
+        }),
+        DescriptorError::DuplicateType("A".into())
+    );
+    // A.Inner and B.Inner are both flattened to Inner
+    let with_inner = |name| DescriptorProto {
+        nested_type: vec![message("Inner", None, vec![])],
+        ..message(name, None, vec![])
+    };
+    assert_eq!(
+        convert(FileDescriptorProto {
+            message_type: vec![with_inner("A"), with_inner("B")],
+            ..Default::default()
+        }),
+        DescriptorError::DuplicateType("Inner".into())
+    );
+    // a type outside the set with the flattened name of A.Inner
+    assert_eq!(
+        convert(FileDescriptorProto {
+            package: Some("sensors".into()),
+            message_type: vec![
+                with_inner("A"),
+                message("B", None, vec![message_field("x", 1, 1, ".other.Inner")]),
+            ],
+            ..Default::default()
+        }),
+        DescriptorError::DuplicateType("Inner".into())
+    );
+    assert_eq!(
+        decode_descriptor_set(&[0x0a, 0x05])
+            .map(|_| ())
+            .unwrap_err(),
+        DescriptorError::Decode(DecodeError::UnexpectedEndOfBuffer)
+    );
+}
+
+#[test]
+fn schema_round_trips_through_descriptors() {
+    let schema = parse(include_str!("../protos/registry.proto")).unwrap();
+    let file = file_descriptor_from_schema(&schema, "registry.proto");
+    assert_eq!(file.name.as_deref(), Some("registry.proto"));
+    assert_eq!(file.syntax.as_deref(), Some("proto2"));
+    let reading = &file.message_type[3];
+    assert_eq!(reading.name.as_deref(), Some("Reading"));
+    assert_eq!(reading.field[2].type_name.as_deref(), Some(".Location"));
+    // values keep their declaration order, the first one is the default
+    let values: Vec<_> = file.enum_type[0]
+        .value
+        .iter()
+        .map(|value| (value.name.as_deref().unwrap(), value.number.unwrap()))
+        .collect();
+    assert_eq!(
+        values,
+        [
+            ("UNIT_UNKNOWN", 0),
+            ("UNIT_CELSIUS", 1),
+            ("UNIT_KELVIN", 2),
+            ("UNIT_INVALID", -1)
+        ]
+    );
+
+    let bytes = encode_descriptor_set(&FileDescriptorSet { file: vec![file] });
+    let set = decode_descriptor_set(&bytes).unwrap();
+    let schema = schema_from_descriptor_set(&set).unwrap();
+    validate(&schema).unwrap();
+    assert_eq!(
+        schema.enum_types["Unit"].declared,
+        [
+            "UNIT_UNKNOWN",
+            "UNIT_CELSIUS",
+            "UNIT_KELVIN",
+            "UNIT_INVALID"
+        ]
+    );
+    let mut output = Vec::new();
+    generate(&mut output, &schema).unwrap();
+    assert_eq!(
+        String::from_utf8(output).unwrap(),
+        include_str!("../protos/registry.rs")
+    );
+}
+
+#[test]
+fn schema_options_in_descriptors() {
+    let schema = parse(
+        r#"syntax = "proto2";
+        enum State { option allow_alias = true; IDLE = 0; OFF = 0; ON = 1; }
+        message Bounded {
+            option (nanopb_msgopt).msgid = 7;
+            repeated int32 values = 1 [(nanopb).max_size = 4, packed = true];
+            optional string name = 2 [(nanopb).max_len = 15, json_name = "n"];
+            optional bytes data = 3 [(nanopb).max_size = 32];
+        }"#,
+    )
+    .unwrap();
+    let file = file_descriptor_from_schema(&schema, "bounded.proto");
+    assert_eq!(file.package, None);
+    let state = &file.enum_type[0];
+    assert_eq!(state.options.as_ref().unwrap().allow_alias, Some(true));
+    let values: Vec<_> = state
+        .value
+        .iter()
+        .map(|value| (value.name.as_deref().unwrap(), value.number.unwrap()))
+        .collect();
+    assert_eq!(values, vec![("IDLE", 0), ("OFF", 0), ("ON", 1)]);
+
+    let bounded = &file.message_type[0];
+    let msgid = bounded.options.as_ref().unwrap().nanopb_msgopt.as_ref();
+    assert_eq!(msgid.unwrap().msgid, Some(7));
+    let options = bounded.field[0].options.as_ref().unwrap();
+    assert_eq!(options.packed, Some(true));
+    let nanopb = options.nanopb.as_ref().unwrap();
+    assert_eq!((nanopb.max_size, nanopb.max_count), (None, Some(4)));
+    let name = &bounded.field[1];
+    assert_eq!(name.json_name.as_deref(), Some("n"));
+    let nanopb = name.options.as_ref().unwrap().nanopb.as_ref().unwrap();
+    assert_eq!(nanopb.max_length, Some(15));
+    let nanopb = bounded.field[2].options.as_ref().unwrap().nanopb.as_ref();
+    assert_eq!(nanopb.unwrap().max_size, Some(32));
+}
@@ -0,0 +1,313 @@
+use picopb::common::Packed;
+use picopb::decode::{DecodeError, DecodeMessage, MAX_MESSAGE_DEPTH};
+use picopb::dynamic::{DynamicError, DynamicMessage, Value};
+use picopb::encode::{EncodeBuffer, ToWire};
+use picopb::json::{JsonError, JsonValue, ToJson};
+use picopb::parser::parse;
+use picopb::text_format::{TextFormatError, TextValue, ToText};
+
+mod registry {
+    #![allow(dead_code, unused_imports)]
+    include!("../protos/registry.rs");
+}
+
+use registry::{Batch, Location, Reading};
+
+fn batch() -> Batch {
+    Batch {
+        samples: Packed(vec![3, -1]),
+        tags: vec!["a".into(), "æ".into()],
+        locations: vec![
+            Location {
+                latitude: 1,
+                longitude: -2,
+                label: Some(bytes::Bytes::from_static(b"\x00\xff")),
+            },
+            Location::default(),
+        ],
+        counters: vec![u32::MAX],
+    }
+}
+
+fn encode(message: impl ToWire) -> Vec<u8> {
+    let mut output = Vec::new();
+    message.append(&mut output).unwrap();
+    output
+}
+
+#[test]
+fn dynamic_matches_generated() {
+    let schema = parse(include_str!("../protos/registry.proto")).unwrap();
+    let bytes = encode(&batch());
+    let message = DynamicMessage::decode(&schema, "Batch", &bytes).unwrap();
+    assert_eq!(message.to_text_format(), batch().to_text_format());
+    assert_eq!(encode(&message), bytes);
+
+    let parsed = DynamicMessage::from_text_format(&schema, "Batch", &batch().to_text_format());
+    assert_eq!(parsed.unwrap(), message);
+    let decoded = Batch::decode(&encode(&message)).unwrap();
+    assert_eq!(
+        decoded.locations[0].label.as_deref(),
+        Some(&b"\x00\xff"[..])
+    );
+    assert_eq!(decoded.counters, vec![u32::MAX]);
+}
+
+#[test]
+fn dynamic_json() {
+    let schema = parse(include_str!("../protos/json.proto")).unwrap();
+    let json = r#"{"device_id": 3, "fw": "x", "uptimeMs": "-5", "payload": "AP8=",
+                   "counters": ["1", 2], "samples": [{"value": -1}]}"#;
+    let message = DynamicMessage::from_json_str(&schema, "Telemetry", json).unwrap();
+    assert_eq!(
+        message.to_json(),
+        concat!(
+            "{\"deviceId\":3,\"uptimeMs\":\"-5\",\"fw\":\"x\",\"payload\":\"AP8=\",",
+            "\"counters\":[\"1\",\"2\"],\"samples\":[{\"value\":-1}]}"
+        )
+    );
+    let decoded = DynamicMessage::decode(&schema, "Telemetry", &encode(&message)).unwrap();
+    assert_eq!(decoded, message);
+}
+
+#[test]
+fn dynamic_enums() {
+    let schema = parse(
+        "syntax = \"proto2\";
+         message Status { required Level level = 1; repeated Level history = 2 [packed = true]; }
+         enum Level { LEVEL_LOW = 0; LEVEL_HIGH = 1; }",
+    )
+    .unwrap();
+    let message =
+        DynamicMessage::from_text_format(&schema, "Status", "level: LEVEL_HIGH history: [0, 7]")
+            .unwrap();
+    assert_eq!(encode(&message), [0x08, 0x01, 0x12, 0x02, 0x00, 0x07]);
+    assert_eq!(
+        message.to_text_format(),
+        "level: LEVEL_HIGH history: LEVEL_LOW history: 7"
+    );
+    assert_eq!(
+        message.to_json(),
+        "{\"level\":\"LEVEL_HIGH\",\"history\":[\"LEVEL_LOW\",7]}"
+    );
+    // unpacked input is accepted for packed fields
+    let decoded =
+        DynamicMessage::decode(&schema, "Status", &[0x08, 0x01, 0x10, 0x00, 0x10, 0x07]).unwrap();
+    assert_eq!(decoded, message);
+}
+
+#[test]
+fn dynamic_errors() {
+    let schema = parse(include_str!("../protos/registry.proto")).unwrap();
+    assert_eq!(
+        DynamicMessage::new(&schema, "Unknown").unwrap_err(),
+        DynamicError::UnknownMessageType("Unknown".into())
+    );
+    let reading = Reading {
+        sensor: "temp".into(),
+        value: Some(1),
+        location: None,
+    };
+    let bytes = encode(&reading);
+    assert_eq!(
+        DynamicMessage::decode(&schema, "Reading", &bytes[..3]).unwrap_err(),
+        DynamicError::Decode(DecodeError::UnexpectedEndOfBuffer)
+    );
+    assert_eq!(
+        DynamicMessage::decode(&schema, "Reading", &bytes[6..]).unwrap_err(),
+        DynamicError::MissingRequiredField("sensor".into())
+    );
+    assert_eq!(
+        DynamicMessage::from_text_format(&schema, "Reading", "sensor: 1").unwrap_err(),
+        DynamicError::TextFormat(TextFormatError::UnexpectedValue("string"))
+    );
+    assert_eq!(
+        DynamicMessage::from_json_str(&schema, "Reading", r#"{"other": 1}"#).unwrap_err(),
+        DynamicError::Json(JsonError::UnknownField("other".into()))
+    );
+}
+
+#[test]
+fn dynamic_build_and_encode() {
+    let schema = parse(include_str!("../protos/registry.proto")).unwrap();
+    let mut location = DynamicMessage::new(&schema, "Location").unwrap();
+    location.set(1, vec![Value::Int32(1)]).unwrap();
+    location
+        .set_by_name("longitude", vec![Value::Int32(-2)])
+        .unwrap();
+    let mut reading = DynamicMessage::new(&schema, "Reading").unwrap();
+    reading
+        .set_by_name("sensor", vec![Value::String("temp".into())])
+        .unwrap();
+    reading
+        .set_by_name("location", vec![Value::Message(location)])
+        .unwrap();
+
+    let mut storage = [0u8; 32];
+    let mut buffer = EncodeBuffer::from_static(&mut storage);
+    let len = reading.encode(&mut buffer).unwrap();
+    let decoded = Reading::decode(buffer.as_slice()).unwrap();
+    assert_eq!(len, buffer.as_slice().len());
+    assert_eq!(decoded.sensor, "temp");
+    assert_eq!(decoded.value, None);
+    assert_eq!(decoded.location.as_ref().unwrap().longitude, -2);
+
+    let reading = DynamicMessage::decode(&schema, "Reading", buffer.as_slice()).unwrap();
+    assert_eq!(reading.get(1), Some(&[Value::String("temp".into())][..]));
+    assert_eq!(reading.get_by_name("value"), Some(&[][..]));
+    assert_eq!(reading.get(4), None);
+    let names: Vec<_> = reading
+        .fields()
+        .map(|(field, values)| (field.identifier.as_str(), values.len()))
+        .collect();
+    assert_eq!(names, vec![("sensor", 1), ("location", 1)]);
+    match &reading.get(3).unwrap()[0] {
+        Value::Message(location) => assert_eq!(location.get(1), Some(&[Value::Int32(1)][..])),
+        value => panic!("unexpected {:?}", value),
+    }
+
+    let mut batch = DynamicMessage::new(&schema, "Batch").unwrap();
+    batch
+        .set(1, vec![Value::Int32(3), Value::Int32(-1)])
+        .unwrap();
+    batch.set(4, vec![Value::Uint32(u32::MAX)]).unwrap();
+    let decoded = Batch::decode(&encode(&batch)).unwrap();
+    assert_eq!(decoded.samples.0, vec![3, -1]);
+    assert_eq!(decoded.counters, vec![u32::MAX]);
+    batch.set(1, vec![]).unwrap();
+    assert_eq!(batch.fields().count(), 1);
+}
+
+#[test]
+fn dynamic_set_errors() {
+    let schema = parse(include_str!("../protos/registry.proto")).unwrap();
+    let mut reading = DynamicMessage::new(&schema, "Reading").unwrap();
+    assert_eq!(
+        reading.set(9, vec![]),
+        Err(DynamicError::UnknownField("9".into()))
+    );
+    assert_eq!(
+        reading.set_by_name("other", vec![]),
+        Err(DynamicError::UnknownField("other".into()))
+    );
+    assert_eq!(
+        reading.set(1, vec![Value::Int32(1)]),
+        Err(DynamicError::InvalidValue("sensor".into()))
+    );
+    assert_eq!(
+        reading.set(2, vec![Value::Int32(1), Value::Int32(2)]),
+        Err(DynamicError::InvalidValue("value".into()))
+    );
+    let ping = DynamicMessage::new(&schema, "Ping").unwrap();
+    assert_eq!(
+        reading.set(3, vec![Value::Message(ping)]),
+        Err(DynamicError::InvalidValue("location".into()))
+    );
+
+    // required fields are checked in sub messages as well
+    reading.set(1, vec![Value::String("x".into())]).unwrap();
+    let location = DynamicMessage::new(&schema, "Location").unwrap();
+    reading.set(3, vec![Value::Message(location)]).unwrap();
+    assert_eq!(
+        reading.encode(&mut Vec::new()),
+        Err(DynamicError::MissingRequiredField("latitude".into()))
+    );
+    reading.set(3, vec![]).unwrap();
+    let mut storage = [0u8; 2];
+    assert_eq!(
+        reading.encode(&mut EncodeBuffer::from_static(&mut storage)),
+        Err(DynamicError::Encode(std::io::ErrorKind::OutOfMemory))
+    );
+}
+
+#[test]
+fn dynamic_bounds_and_enums() {
+    let schema = parse(
+        "syntax = \"proto2\";
+         message Frame {
+             optional string name = 1 [(nanopb).max_size = 4];
+             repeated Level levels = 2 [(nanopb).max_size = 2];
+         }
+         enum Level { LEVEL_LOW = 0; LEVEL_HIGH = 1; }",
+    )
+    .unwrap();
+    let mut frame = DynamicMessage::new(&schema, "Frame").unwrap();
+    let high = frame.enum_value("levels", "LEVEL_HIGH").unwrap();
+    assert_eq!(high, Value::Enum(1));
+    assert_eq!(
+        frame.enum_value("levels", "LEVEL_OTHER"),
+        Err(DynamicError::InvalidValue("levels".into()))
+    );
+    frame.set(2, vec![high.clone(), Value::Enum(0)]).unwrap();
+    assert_eq!(
+        frame.set(2, vec![high.clone(); 3]),
+        Err(DynamicError::CapacityExceeded("levels".into()))
+    );
+    assert_eq!(
+        frame.set(1, vec![Value::String("12345".into())]),
+        Err(DynamicError::CapacityExceeded("name".into()))
+    );
+    assert_eq!(
+        frame.to_text_format(),
+        "levels: LEVEL_HIGH levels: LEVEL_LOW"
+    );
+
+    // decoding enforces the same bounds
+    assert_eq!(
+        DynamicMessage::decode(&schema, "Frame", b"\x0a\x05hello").unwrap_err(),
+        DynamicError::CapacityExceeded("name".into())
+    );
+    assert_eq!(
+        DynamicMessage::decode(&schema, "Frame", &[0x12, 0x03, 0x00, 0x01, 0x00]).unwrap_err(),
+        DynamicError::CapacityExceeded("levels".into())
+    );
+}
+
+/// `depth` messages, each the `child` of the one before
+fn nested_nodes(depth: usize) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    for _ in 0..depth {
+        let mut outer = vec![0x0a];
+        leb128::write::unsigned(&mut outer, bytes.len() as u64).unwrap();
+        outer.extend(bytes);
+        bytes = outer;
+    }
+    bytes
+}
+
+#[test]
+fn dynamic_decode_depth_limit() {
+    let schema = parse("syntax = \"proto2\"; message Node { optional Node child = 1; }").unwrap();
+    assert!(DynamicMessage::decode(&schema, "Node", &nested_nodes(MAX_MESSAGE_DEPTH)).is_ok());
+    assert_eq!(
+        DynamicMessage::decode(&schema, "Node", &nested_nodes(MAX_MESSAGE_DEPTH + 1)).unwrap_err(),
+        DynamicError::MessageTooDeep
+    );
+}
+
+#[test]
+fn dynamic_text_and_json_depth_limit() {
+    let schema = parse("syntax = \"proto2\"; message Node { optional Node child = 1; }").unwrap();
+    let node = schema.message_types.get("Node").unwrap();
+    let text = |depth| {
+        (0..depth).fold(TextValue::Message(Vec::new()), |child, _| {
+            TextValue::Message(vec![("child".to_string(), child)])
+        })
+    };
+    assert!(DynamicMessage::from_text(&schema, node, &text(MAX_MESSAGE_DEPTH)).is_ok());
+    assert_eq!(
+        DynamicMessage::from_text(&schema, node, &text(MAX_MESSAGE_DEPTH + 1)).unwrap_err(),
+        DynamicError::MessageTooDeep
+    );
+
+    let json = |depth| {
+        (0..depth).fold(JsonValue::Object(Vec::new()), |child, _| {
+            JsonValue::Object(vec![("child".to_string(), child)])
+        })
+    };
+    assert!(DynamicMessage::from_json(&schema, node, &json(MAX_MESSAGE_DEPTH)).is_ok());
+    assert_eq!(
+        DynamicMessage::from_json(&schema, node, &json(MAX_MESSAGE_DEPTH + 1)).unwrap_err(),
+        DynamicError::MessageTooDeep
+    );
+}
@@ -1,5 +1,5 @@
 use picopb::common::Field;
-use picopb::encode::EncodeBuffer;
+use picopb::encode::{EncodeBuffer, EncodeSink};
 
 #[test]
 fn encode_person_simple() {
@@ -0,0 +1,113 @@
+use picopb::common::{Field, Packed};
+use picopb::decode::{DecodeBuffer, FromWire};
+use picopb::encode::{EncodeSink, ToWire};
+
+/// Encodes the value as field 1 and checks it against the protoc encoding
+fn assert_encodes_to(encodable: impl ToWire + Copy, expected: &[u8]) {
+    let mut buffer: Vec<u8> = Vec::new();
+    let count = buffer.encode(encodable, Field(1)).unwrap();
+    assert_eq!(buffer, expected);
+    assert_eq!(count, expected.len());
+    assert_eq!(encodable.precalculate_field_size(Field(1)), expected.len());
+}
+
+/// Decodes the value written after the tag
+fn decode<T: FromWire>(bytes: &[u8]) -> T {
+    let mut buf = DecodeBuffer::from_slice(bytes);
+    buf.read_tag().unwrap();
+    let value = T::read(&mut buf).unwrap();
+    assert!(buf.is_empty());
+    value
+}
+
+#[test]
+fn encode_int32() {
+    assert_encodes_to(0i32, &[0x08, 0x00]);
+    assert_encodes_to(150i32, &[0x08, 0x96, 0x01]);
+    assert_encodes_to(i32::MAX, &[0x08, 0xff, 0xff, 0xff, 0xff, 0x07]);
+
+    // negative int32 values are sign extended to 64 bits
+    let minus_one = [
+        0x08, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01,
+    ];
+    assert_encodes_to(-1i32, &minus_one);
+    assert_eq!(decode::<i32>(&minus_one), -1);
+
+    let min = [
+        0x08, 0x80, 0x80, 0x80, 0x80, 0xf8, 0xff, 0xff, 0xff, 0xff, 0x01,
+    ];
+    assert_encodes_to(i32::MIN, &min);
+    assert_eq!(decode::<i32>(&min), i32::MIN);
+}
+
+#[test]
+fn encode_int64() {
+    assert_encodes_to(1i64 << 40, &[0x08, 0x80, 0x80, 0x80, 0x80, 0x80, 0x20]);
+
+    let minus_two = [
+        0x08, 0xfe, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01,
+    ];
+    assert_encodes_to(-2i64, &minus_two);
+    assert_eq!(decode::<i64>(&minus_two), -2);
+
+    let min = [
+        0x08, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x01,
+    ];
+    assert_encodes_to(i64::MIN, &min);
+    assert_eq!(decode::<i64>(&min), i64::MIN);
+}
+
+#[test]
+fn encode_uint32() {
+    assert_encodes_to(300u32, &[0x08, 0xac, 0x02]);
+
+    let max = [0x08, 0xff, 0xff, 0xff, 0xff, 0x0f];
+    assert_encodes_to(u32::MAX, &max);
+    assert_eq!(decode::<u32>(&max), u32::MAX);
+}
+
+#[test]
+fn encode_uint64() {
+    let max = [
+        0x08, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01,
+    ];
+    assert_encodes_to(u64::MAX, &max);
+    assert_eq!(decode::<u64>(&max), u64::MAX);
+}
+
+#[test]
+fn encode_bool() {
+    assert_encodes_to(true, &[0x08, 0x01]);
+    assert_encodes_to(false, &[0x08, 0x00]);
+    assert!(decode::<bool>(&[0x08, 0x01]));
+}
+
+#[test]
+fn encode_strings_and_bytes() {
+    let expected = [0x0a, 0x03, b'a', b'b', b'c'];
+    assert_encodes_to("abc", &expected);
+    assert_encodes_to(&String::from("abc"), &expected);
+    assert_encodes_to(&b"abc"[..], &expected);
+
+    // owned strings are not Copy, encode them through the sink directly
+    let mut buffer: Vec<u8> = Vec::new();
+    buffer.encode_tag(String::from("abc"), Field(1)).unwrap();
+    let count = buffer.encode_value(String::from("abc")).unwrap();
+    assert_eq!(buffer, expected);
+    assert_eq!(count, String::from("abc").precalculate_size());
+}
+
+#[test]
+fn encode_packed() {
+    // repeated int32 d = 4 [packed = true]; d: [3, 270, 86942]
+    let values = [3i32, 270, 86942];
+    let mut buffer: Vec<u8> = Vec::new();
+    let count = buffer.encode(Packed(&values[..]), Field(4)).unwrap();
+    assert_eq!(buffer, [0x22, 0x06, 0x03, 0x8e, 0x02, 0x9e, 0xa7, 0x05]);
+    assert_eq!(count, buffer.len());
+    assert_eq!(
+        Packed(&values[..]).precalculate_field_size(Field(4)),
+        buffer.len()
+    );
+    assert_eq!(Packed(&values[..]).precalculate_size(), buffer.len() - 1);
+}
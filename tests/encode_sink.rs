@@ -0,0 +1,105 @@
+use picopb::encode::{BufMutSink, CountingSink, EncodeBuffer, EncodeSink, IoSink, ToWire};
+
+mod registry {
+    #![allow(dead_code, unused_imports)]
+    include!("../protos/registry.rs");
+}
+
+use registry::{AnyMessage, Location, Reading};
+
+fn reading() -> Reading {
+    Reading {
+        sensor: "temp".to_string(),
+        value: Some(-3),
+        location: Some(Location {
+            latitude: 59,
+            longitude: 10,
+            label: None,
+        }),
+    }
+}
+
+fn encode_to_buffer(message: &Reading) -> Vec<u8> {
+    let mut static_buffer: [u8; 64] = [0; 64];
+    let mut buffer = EncodeBuffer::from_static(&mut static_buffer);
+    message.append(&mut buffer).unwrap();
+    buffer.as_slice().to_vec()
+}
+
+#[test]
+fn encode_to_vec() {
+    let message = reading();
+    let mut out: Vec<u8> = Vec::new();
+    let count = (&message).append(&mut out).unwrap();
+    assert_eq!(count, out.len());
+    assert_eq!(out, encode_to_buffer(&message));
+}
+
+#[test]
+fn encode_to_bytes_mut() {
+    let message = reading();
+    let mut out = bytes::BytesMut::new();
+    (&message).append(&mut out).unwrap();
+    assert_eq!(&out[..], &encode_to_buffer(&message)[..]);
+}
+
+#[test]
+fn encode_to_io_write() {
+    let message = reading();
+    let mut sink = IoSink(std::io::Cursor::new(Vec::new()));
+    sink.encode_delimited(&message).unwrap();
+
+    let expected = encode_to_buffer(&message);
+    let written = sink.0.into_inner();
+    assert_eq!(written[0] as usize, expected.len());
+    assert_eq!(&written[1..], &expected[..]);
+}
+
+#[test]
+fn encode_to_buf_mut() {
+    let message = reading();
+    let mut static_buffer: [u8; 64] = [0; 64];
+    let mut sink = BufMutSink(&mut static_buffer[..]);
+    let count = (&message).append(&mut sink).unwrap();
+    assert_eq!(&static_buffer[..count], &encode_to_buffer(&message)[..]);
+}
+
+#[test]
+fn buf_mut_out_of_space() {
+    let mut static_buffer: [u8; 4] = [0; 4];
+    let mut sink = BufMutSink(&mut static_buffer[..]);
+    assert!((&reading()).append(&mut sink).is_err());
+}
+
+#[test]
+fn counting_sink_matches_precalculated_size() {
+    let message = reading();
+    let mut sink = CountingSink::default();
+    let count = (&message).append(&mut sink).unwrap();
+    assert_eq!(count, sink.count);
+    assert_eq!(sink.count, (&message).precalculate_size());
+}
+
+#[test]
+fn encode_buffer_exact_fit() {
+    let message = reading();
+    let size = (&message).precalculate_size();
+    let mut static_buffer = vec![0; size];
+    let mut buffer = EncodeBuffer::from_static(&mut static_buffer);
+    assert_eq!((&message).append(&mut buffer).unwrap(), size);
+
+    let mut static_buffer = vec![0; size - 1];
+    let mut buffer = EncodeBuffer::from_static(&mut static_buffer);
+    assert!((&message).append(&mut buffer).is_err());
+}
+
+#[test]
+fn encode_with_id_to_dyn_sink() {
+    let message = AnyMessage::from(reading());
+    let mut out: Vec<u8> = Vec::new();
+    let mut sink: &mut dyn EncodeSink = &mut out;
+    message.encode_with_id(&mut sink).unwrap();
+
+    let decoded = AnyMessage::decode_with_id(&out).unwrap();
+    assert_eq!(decoded.id(), 2);
+}
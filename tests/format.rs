@@ -0,0 +1,104 @@
+use picopb::compat::compare;
+use picopb::format::format;
+use picopb::parser::{parse, parse_with_config, ParseConfig};
+
+const INPUT: &str = r#"// License header
+
+syntax="proto2";
+package  sensors ;
+import public "common.proto";import "units.proto";
+option java_package = "com.example"; // trailing
+message   Reading{ // on the brace
+  /* leading
+     block */
+  required string sensor=1 [(nanopb).max_size=16];   // the sensor
+
+
+  optional int32 value = 2 /* inner */ [ packed=true,deprecated = false ];
+  repeated int32 samples = 3; [(nanopb).max_count=8]
+  reserved 4, 6 to 8, 100 to max;
+  reserved "old","older";
+  option (nanopb_msgopt).msgid = 0x10;
+  optional string description_of_the_reading = 9 [(nanopb).max_size = 64, json_name = "description", (custom) = { a: 1 b { c: "d" } }];
+  // at the end
+}
+message Empty {;}
+enum Unit { option allow_alias=true; UNIT_UNKNOWN=0; UNIT_INVALID = -1 [deprecated=true]; }
+// final comment
+"#;
+
+const EXPECTED: &str = r#"// License header
+
+syntax = "proto2";
+
+package sensors;
+
+import public "common.proto";
+import "units.proto";
+
+option java_package = "com.example"; // trailing
+
+message Reading { // on the brace
+    /* leading
+     block */
+    required string sensor = 1 [(nanopb).max_size = 16]; // the sensor
+
+    /* inner */
+    optional int32 value = 2 [packed = true, deprecated = false];
+    repeated int32 samples = 3 [(nanopb).max_count = 8];
+    reserved 4, 6 to 8, 100 to max;
+    reserved "old", "older";
+    option (nanopb_msgopt).msgid = 0x10;
+    optional string description_of_the_reading = 9 [
+        (nanopb).max_size = 64,
+        json_name = "description",
+        (custom) = { a: 1, b { c: "d" } }
+    ];
+    // at the end
+}
+
+message Empty {}
+
+enum Unit {
+    option allow_alias = true;
+    UNIT_UNKNOWN = 0;
+    UNIT_INVALID = -1 [deprecated = true];
+}
+// final comment
+"#;
+
+#[test]
+fn format_canonical_style() {
+    let formatted = format(INPUT).unwrap();
+    assert_eq!(formatted, EXPECTED);
+    assert_eq!(format(&formatted).unwrap(), formatted);
+    assert_eq!(format("").unwrap(), "");
+}
+
+#[test]
+fn format_keeps_the_schema() {
+    let config = ParseConfig {
+        legacy_field_options: true,
+    };
+    let old = parse_with_config(INPUT, config).unwrap();
+    let new = parse(EXPECTED).unwrap();
+    assert_eq!(compare(&old, &new), Vec::new());
+
+    for path in ["protos/registry.proto", "protos/descriptor.proto"] {
+        let text = std::fs::read_to_string(path).unwrap();
+        assert_eq!(format(&text).unwrap(), text, "{}", path);
+    }
+    let text = std::fs::read_to_string("protos/pb_examples.proto").unwrap();
+    let formatted = format(&text).unwrap();
+    assert_ne!(formatted, text);
+    assert_eq!(
+        compare(&parse(&text).unwrap(), &parse(&formatted).unwrap()),
+        Vec::new()
+    );
+}
+
+#[test]
+fn format_errors() {
+    assert!(format("message Reading {").is_err());
+    assert!(format("syntax = \"proto2\"; message Reading { optional int32 = 1; }").is_err());
+}
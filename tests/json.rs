@@ -0,0 +1,157 @@
+use picopb::json::{
+    json_name, parse, read_base64, FromJson, JsonError, JsonValue, ToJson, MAX_NESTING_DEPTH,
+};
+use picopb::{
+    common::Packed,
+    generator::{generate_with_config, GeneratorConfig},
+    parser::parse as parse_proto,
+    validator::validate,
+};
+
+mod json {
+    #![allow(dead_code, unused_imports)]
+    include!("../protos/json.rs");
+}
+
+use json::{Sample, Status, Telemetry};
+
+#[test]
+fn generated_json_is_up_to_date() {
+    let result = parse_proto(include_str!("../protos/json.proto")).unwrap();
+    validate(&result).unwrap();
+    let config = GeneratorConfig {
+        json: true,
+        ..Default::default()
+    };
+    let mut output = Vec::new();
+    generate_with_config(&mut output, &result, &config).unwrap();
+    assert_eq!(
+        String::from_utf8(output).unwrap(),
+        include_str!("../protos/json.rs")
+    );
+}
+
+fn telemetry() -> Telemetry {
+    Telemetry {
+        device_id: 12,
+        uptime_ms: Some(-9007199254740993),
+        firmware_version: Some("1.2 \"beta\"".into()),
+        payload: Some(bytes::Bytes::from_static(b"\x00\xffab")),
+        counters: Packed(vec![u64::MAX]),
+        samples: vec![Sample {
+            value: -4,
+            delta: None,
+        }],
+        labels: Vec::new(),
+    }
+}
+
+#[test]
+fn message_to_json() {
+    assert_eq!(
+        telemetry().to_json(),
+        concat!(
+            "{\"deviceId\":12,\"uptimeMs\":\"-9007199254740993\",",
+            "\"fw\":\"1.2 \\\"beta\\\"\",\"payload\":\"AP9hYg==\",",
+            "\"counters\":[\"18446744073709551615\"],\"samples\":[{\"value\":-4}]}"
+        )
+    );
+}
+
+#[test]
+fn message_json_round_trip() {
+    let decoded = Telemetry::from_json_str(&telemetry().to_json()).unwrap();
+    assert_eq!(decoded.device_id, 12);
+    assert_eq!(decoded.uptime_ms, Some(-9007199254740993));
+    assert_eq!(decoded.firmware_version.as_deref(), Some("1.2 \"beta\""));
+    assert_eq!(decoded.payload.as_deref(), Some(&b"\x00\xffab"[..]));
+    assert_eq!(decoded.counters.0, vec![u64::MAX]);
+    assert_eq!(decoded.samples[0].value, -4);
+    assert!(decoded.labels.is_empty());
+}
+
+#[test]
+fn message_from_json_accepts_proto_names() {
+    let decoded = Telemetry::from_json_str(
+        r#"{ "device_id": "7", "firmware_version": "x", "uptimeMs": 1e3,
+             "payload": "AP9hYg", "samples": [{"value": 1, "delta": null}],
+             "labels": ["aæ😀"] }"#,
+    )
+    .unwrap();
+    assert_eq!(decoded.device_id, 7);
+    assert_eq!(decoded.firmware_version.as_deref(), Some("x"));
+    assert_eq!(decoded.uptime_ms, Some(1000));
+    assert_eq!(decoded.payload.as_deref(), Some(&b"\x00\xffab"[..]));
+    assert_eq!(decoded.samples[0].delta, None);
+    assert_eq!(decoded.labels, vec!["aæ😀".to_string()]);
+}
+
+#[test]
+fn message_from_json_errors() {
+    assert_eq!(
+        Telemetry::from_json_str("{}").unwrap_err(),
+        JsonError::MissingRequiredField("device_id")
+    );
+    assert_eq!(
+        Telemetry::from_json_str(r#"{"deviceId": 1, "unknown": 2}"#).unwrap_err(),
+        JsonError::UnknownField("unknown".into())
+    );
+    assert_eq!(
+        Telemetry::from_json_str(r#"{"deviceId": 1.5}"#).unwrap_err(),
+        JsonError::InvalidNumber("1.5".into())
+    );
+    assert_eq!(
+        Telemetry::from_json_str(r#"{"deviceId": -1}"#).unwrap_err(),
+        JsonError::InvalidNumber("-1".into())
+    );
+    assert_eq!(
+        Telemetry::from_json_str(r#"{"deviceId": 1,}"#).unwrap_err(),
+        JsonError::Syntax(15)
+    );
+}
+
+#[test]
+fn enum_json() {
+    assert_eq!(Status::StatusFailed.to_json(), "\"STATUS_FAILED\"");
+    assert_eq!(
+        Status::from_json_str("\"STATUS_DEGRADED\"").unwrap(),
+        Status::StatusDegraded
+    );
+    assert_eq!(Status::from_json_str("-1").unwrap(), Status::StatusFailed);
+    assert_eq!(
+        Status::from_json_str("\"STATUS_UNKNOWN\"").unwrap_err(),
+        JsonError::InvalidEnumValue("STATUS_UNKNOWN".into())
+    );
+}
+
+#[test]
+fn json_helpers() {
+    assert_eq!(json_name("firmware_version"), "firmwareVersion");
+    assert_eq!(json_name("sensor_2_id"), "sensor2Id");
+    assert_eq!(read_base64("-_8"), Ok(vec![0xfb, 0xff]));
+    assert_eq!(read_base64("A"), Err(JsonError::InvalidBase64));
+    assert_eq!(
+        parse(r#"[true, null, "\n", {}]"#),
+        Ok(JsonValue::Array(vec![
+            JsonValue::Bool(true),
+            JsonValue::Null,
+            JsonValue::String("\n".into()),
+            JsonValue::Object(Vec::new()),
+        ]))
+    );
+}
+
+#[test]
+fn parse_nesting_limit() {
+    let nested = |depth| "[".repeat(depth) + &"]".repeat(depth);
+    assert!(parse(&nested(MAX_NESTING_DEPTH)).is_ok());
+    assert_eq!(
+        parse(&nested(MAX_NESTING_DEPTH + 1)),
+        Err(JsonError::TooDeep(MAX_NESTING_DEPTH))
+    );
+    // fails before the stack overflows
+    assert_eq!(
+        parse(&"[{\"a\":".repeat(100_000)),
+        Err(JsonError::TooDeep(MAX_NESTING_DEPTH / 2 * 6))
+    );
+}
@@ -0,0 +1,156 @@
+use picopb::lint::{lint, Lint, LintConfig, LintRule};
+use picopb::parser::parse;
+
+const SCHEMA: &str = r#"syntax = "proto2";
+import "units.proto";
+import "unused.proto";
+message Reading {
+    option (nanopb_msgopt).msgid = 1;
+    required string sensorName = 1 [(nanopb).max_size = 16];
+    optional Unit unit = 2;
+    optional sensor_location location = 3;
+    repeated int32 samples = 4 [(nanopb).max_size = 8];
+    optional bytes label = 5;
+}
+message sensor_location {
+    required int32 latitude = 1;
+}
+message Orphan {
+    repeated int32 values = 1;
+}
+enum SensorState {
+    SENSOR_STATE_UNSPECIFIED = 0;
+    SENSOR_STATE_ON = 1;
+    Off = 2;
+}
+enum HTTPStatus {
+    OK = 1;
+}
+"#;
+
+const UNITS: &str = r#"syntax = "proto2";
+enum Unit {
+    UNIT_UNSPECIFIED = 0;
+}
+"#;
+
+const UNUSED: &str = r#"syntax = "proto2";
+message Unused {}
+"#;
+
+fn found(rule: LintRule, path: &str, message: &str) -> Lint {
+    Lint {
+        rule,
+        path: path.into(),
+        message: message.into(),
+    }
+}
+
+#[test]
+fn lint_rules() {
+    let schema = parse(SCHEMA).unwrap();
+    let units = parse(UNITS).unwrap();
+    let unused = parse(UNUSED).unwrap();
+    let imports = [("units.proto", &units), ("unused.proto", &unused)];
+    let lints = lint(&schema, &imports, &LintConfig::default());
+    assert_eq!(
+        lints,
+        vec![
+            found(
+                LintRule::UnboundedField,
+                "Orphan.values",
+                "no (nanopb).max_size, the repeated values are heap allocated"
+            ),
+            found(
+                LintRule::FieldName,
+                "Reading.sensorName",
+                "field name should be snake_case"
+            ),
+            found(
+                LintRule::UnboundedField,
+                "Reading.label",
+                "no (nanopb).max_size, the value is heap allocated"
+            ),
+            found(
+                LintRule::TypeName,
+                "sensor_location",
+                "message name should be UpperCamelCase"
+            ),
+            found(
+                LintRule::EnumValuePrefix,
+                "HTTPStatus.OK",
+                "enum value name should start with HTTP_STATUS_"
+            ),
+            found(
+                LintRule::EnumZeroValue,
+                "HTTPStatus",
+                "the enum should have a zero value named HTTP_STATUS_UNSPECIFIED"
+            ),
+            found(
+                LintRule::EnumValueName,
+                "SensorState.Off",
+                "enum value name should be UPPER_SNAKE_CASE"
+            ),
+            found(
+                LintRule::EnumValuePrefix,
+                "SensorState.Off",
+                "enum value name should start with SENSOR_STATE_"
+            ),
+            found(
+                LintRule::UnusedImport,
+                "unused.proto",
+                "no field refers to a type of the imported file"
+            ),
+        ]
+    );
+    assert_eq!(
+        lints[1].to_string(),
+        "field-name: Reading.sensorName: field name should be snake_case"
+    );
+    // imports that were not given are not checked
+    let lints = lint(&schema, &imports[..1], &LintConfig::default());
+    assert!(lints.iter().all(|lint| lint.rule != LintRule::UnusedImport));
+}
+
+#[test]
+fn lint_unused_message_is_opt_in() {
+    let schema = parse(SCHEMA).unwrap();
+    let mut config = LintConfig::default();
+    assert!(!config.is_enabled(LintRule::UnusedMessage));
+
+    config.rules.clear();
+    config.enable(LintRule::UnusedMessage);
+    assert_eq!(
+        lint(&schema, &[], &config),
+        vec![found(
+            LintRule::UnusedMessage,
+            "Orphan",
+            "no field refers to the message and it has no msgid"
+        )]
+    );
+}
+
+#[test]
+fn lint_config() {
+    let schema = parse(SCHEMA).unwrap();
+    let mut config = LintConfig::default();
+    config.disable(LintRule::UnboundedField);
+    config.disable(LintRule::EnumValuePrefix);
+    assert!(!config.is_enabled(LintRule::UnboundedField));
+    let lints = lint(&schema, &[], &config);
+    assert_eq!(lints.len(), 4);
+    assert!(lints
+        .iter()
+        .all(|lint| config.is_enabled(lint.rule) && lint.rule != LintRule::UnusedImport));
+
+    config.rules.clear();
+    config.enable(LintRule::TypeName);
+    let lints = lint(&schema, &[], &config);
+    assert_eq!(lints.len(), 1);
+    assert_eq!(lints[0].path, "sensor_location");
+
+    for rule in LintRule::ALL {
+        assert_eq!(rule.name().parse::<LintRule>(), Ok(rule));
+    }
+    assert!("unknown-rule".parse::<LintRule>().is_err());
+}
@@ -0,0 +1,329 @@
+use picopb::format::format;
+use picopb::json::{parse, JsonValue, ToJson};
+use picopb::lsp::{read_message, serve, Server};
+use std::io::Cursor;
+
+const URI: &str = "file:///sensors/reading.proto";
+
+const READING: &str = "syntax = \"proto2\";
+message Reading {
+    option (nanopb_msgopt).msgid = 1;
+    required string sensor = 1 [(nanopb).max_size = 16];
+    optional Unit unit = 2;
+    repeated Location locations = 3 [(nanopb).max_size = 4];
+}
+message Location {
+    required int32 latitude = 1;
+}
+enum Unit {
+    UNIT_UNSPECIFIED = 0;
+}
+";
+
+fn message(json: &str) -> JsonValue {
+    parse(json).unwrap()
+}
+
+fn open(server: &mut Server, uri: &str, text: &str) -> Vec<JsonValue> {
+    let mut text_json = String::new();
+    picopb::json::write_string(&mut text_json, text).unwrap();
+    server.handle(&message(&format!(
+        r#"{{"jsonrpc":"2.0","method":"textDocument/didOpen","params":{{"textDocument":{{"uri":"{}","languageId":"proto","version":1,"text":{}}}}}}}"#,
+        uri, text_json
+    )))
+}
+
+/// Sends a request at the position and returns its result
+fn request(server: &mut Server, method: &str, line: u32, character: u32) -> JsonValue {
+    let replies = server.handle(&message(&format!(
+        r#"{{"jsonrpc":"2.0","id":7,"method":"{}","params":{{"textDocument":{{"uri":"{}"}},"position":{{"line":{},"character":{}}},"options":{{"tabSize":4,"insertSpaces":true}}}}}}"#,
+        method, URI, line, character
+    )));
+    assert_eq!(replies.len(), 1);
+    assert_eq!(replies[0].get("id"), Some(&JsonValue::Number("7".into())));
+    replies[0].get("result").cloned().unwrap()
+}
+
+fn diagnostics(published: &[JsonValue]) -> Vec<(String, String)> {
+    let params = published[0].get("params").unwrap();
+    let diagnostics = params.get("diagnostics").unwrap().as_array().unwrap();
+    diagnostics
+        .iter()
+        .map(|diagnostic| {
+            let start = diagnostic.get("range").unwrap().get("start").unwrap();
+            (
+                start.to_json(),
+                diagnostic.get("message").unwrap().to_json(),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn lsp_diagnostics() {
+    let mut server = Server::default();
+    let published = open(&mut server, URI, READING);
+    assert_eq!(
+        published[0].get("method"),
+        Some(&JsonValue::String("textDocument/publishDiagnostics".into()))
+    );
+    assert_eq!(diagnostics(&published), Vec::new());
+
+    let invalid = READING.replace("optional Unit", "optional Units");
+    let published = open(&mut server, URI, &invalid);
+    assert_eq!(
+        diagnostics(&published),
+        vec![(
+            r#"{"line":4,"character":13}"#.to_string(),
+            r#""type Units is not defined""#.to_string()
+        )]
+    );
+
+    let lints = READING.replace("[(nanopb).max_size = 16]", "");
+    let published = open(&mut server, URI, &lints);
+    let params = published[0].get("params").unwrap();
+    let diagnostic = &params.get("diagnostics").unwrap().as_array().unwrap()[0];
+    assert_eq!(
+        diagnostic.get("code"),
+        Some(&JsonValue::String("unbounded-field".into()))
+    );
+    assert_eq!(
+        diagnostic.get("severity"),
+        Some(&JsonValue::Number("2".into()))
+    );
+
+    let published = open(&mut server, URI, "message Reading {");
+    assert_eq!(diagnostics(&published)[0].0, r#"{"line":0,"character":17}"#);
+
+    let published = server.handle(&message(&format!(
+        r#"{{"jsonrpc":"2.0","method":"textDocument/didClose","params":{{"textDocument":{{"uri":"{}"}}}}}}"#,
+        URI
+    )));
+    assert_eq!(diagnostics(&published), Vec::new());
+}
+
+#[test]
+fn lsp_navigation() {
+    let mut server = Server::default();
+    open(&mut server, URI, READING);
+
+    // `Location` in `repeated Location locations`
+    let location = request(&mut server, "textDocument/definition", 5, 15);
+    assert_eq!(location.get("uri"), Some(&JsonValue::String(URI.into())));
+    assert_eq!(
+        location.get("range").unwrap().to_json(),
+        r#"{"start":{"line":7,"character":8},"end":{"line":7,"character":16}}"#
+    );
+    assert_eq!(
+        request(&mut server, "textDocument/definition", 3, 30),
+        JsonValue::Null
+    );
+
+    // types are also found in the other open documents
+    open(
+        &mut server,
+        "file:///sensors/units.proto",
+        "enum Other { OTHER_UNSPECIFIED = 0; }",
+    );
+    let changed = READING.replace("optional Unit unit", "optional Other unit");
+    server.handle(&message(&format!(
+        r#"{{"jsonrpc":"2.0","method":"textDocument/didChange","params":{{"textDocument":{{"uri":"{}","version":2}},"contentChanges":[{{"text":{}}}]}}}}"#,
+        URI,
+        JsonValue::String(changed).to_json()
+    )));
+    let location = request(&mut server, "textDocument/definition", 4, 14);
+    assert_eq!(
+        location.get("uri"),
+        Some(&JsonValue::String("file:///sensors/units.proto".into()))
+    );
+    open(&mut server, URI, READING);
+
+    let hover = request(&mut server, "textDocument/hover", 5, 25);
+    assert_eq!(
+        hover.get("contents").unwrap().get("value"),
+        Some(&JsonValue::String(
+            "field `locations` = 3: repeated, at most 4 message `Location`".into()
+        ))
+    );
+    let hover = request(&mut server, "textDocument/hover", 3, 20);
+    assert_eq!(
+        hover.get("contents").unwrap().get("value"),
+        Some(&JsonValue::String(
+            "field `sensor` = 1: required string, max_size 16".into()
+        ))
+    );
+    let hover = request(&mut server, "textDocument/hover", 1, 10);
+    assert_eq!(
+        hover.get("contents").unwrap().get("value"),
+        Some(&JsonValue::String(
+            "message `Reading`, 3 fields, msgid 1".into()
+        ))
+    );
+    assert_eq!(
+        request(&mut server, "textDocument/hover", 0, 0),
+        JsonValue::Null
+    );
+}
+
+fn labels(items: &JsonValue) -> Vec<String> {
+    let items = items.as_array().unwrap();
+    items
+        .iter()
+        .map(|item| item.get("label").unwrap().as_str().unwrap().to_string())
+        .collect()
+}
+
+#[test]
+fn lsp_completion_and_formatting() {
+    let mut server = Server::default();
+    open(&mut server, URI, READING);
+
+    let types = labels(&request(&mut server, "textDocument/completion", 4, 13));
+    assert!(types.contains(&"int32".to_string()));
+    assert!(types.contains(&"Location".to_string()));
+    assert!(types.contains(&"Unit".to_string()));
+    // after the `[` of the field options
+    let options = labels(&request(&mut server, "textDocument/completion", 3, 33));
+    assert!(options.contains(&"(nanopb).max_size".to_string()));
+    assert!(!options.contains(&"Location".to_string()));
+    let options = labels(&request(&mut server, "textDocument/completion", 2, 11));
+    assert!(options.contains(&"(nanopb_msgopt).msgid".to_string()));
+
+    open(&mut server, URI, &format(READING).unwrap());
+    let edits = request(&mut server, "textDocument/formatting", 0, 0);
+    assert_eq!(edits, JsonValue::Array(Vec::new()));
+    open(&mut server, URI, "message  A{optional int32 a=1;}");
+    let edits = request(&mut server, "textDocument/formatting", 0, 0);
+    let edit = &edits.as_array().unwrap()[0];
+    assert_eq!(
+        edit.get("newText"),
+        Some(&JsonValue::String(
+            "message A {\n    optional int32 a = 1;\n}\n".into()
+        ))
+    );
+    assert_eq!(
+        edit.get("range").unwrap().get("end").unwrap().to_json(),
+        r#"{"line":0,"character":31}"#
+    );
+}
+
+#[test]
+fn lsp_session() {
+    let mut input = Vec::new();
+    for body in [
+        r#"{"jsonrpc":"2.0","id":1,"method":"initialize","params":{"capabilities":{}}}"#,
+        r#"{"jsonrpc":"2.0","method":"initialized","params":{}}"#,
+        r#"{"jsonrpc":"2.0","id":2,"method":"textDocument/unknown","params":{}}"#,
+        "{not json",
+        r#"{"jsonrpc":"2.0","id":3,"method":"shutdown"}"#,
+        r#"{"jsonrpc":"2.0","method":"exit"}"#,
+        r#"{"jsonrpc":"2.0","id":4,"method":"shutdown"}"#,
+    ] {
+        input.extend(format!("Content-Length: {}\r\n\r\n{}", body.len(), body).bytes());
+    }
+    let mut output = Vec::new();
+    assert!(serve(Cursor::new(input), &mut output).unwrap());
+
+    let mut output = Cursor::new(output);
+    let mut replies = Vec::new();
+    while let Some(body) = read_message(&mut output).unwrap() {
+        replies.push(parse(&body).unwrap());
+    }
+    // nothing is read after exit
+    assert_eq!(replies.len(), 4);
+    let capabilities = replies[0]
+        .get("result")
+        .unwrap()
+        .get("capabilities")
+        .unwrap();
+    assert_eq!(
+        capabilities.get("hoverProvider"),
+        Some(&JsonValue::Bool(true))
+    );
+    let code = |reply: &JsonValue| reply.get("error").unwrap().get("code").cloned();
+    assert_eq!(code(&replies[1]), Some(JsonValue::Number("-32601".into())));
+    assert_eq!(code(&replies[2]), Some(JsonValue::Number("-32700".into())));
+    assert_eq!(replies[3].get("result"), Some(&JsonValue::Null));
+
+    // exit without shutdown
+    let body = r#"{"jsonrpc":"2.0","method":"exit"}"#;
+    let input = format!("Content-Length: {}\r\n\r\n{}", body.len(), body);
+    assert!(!serve(Cursor::new(input), Vec::new()).unwrap());
+}
+
+#[test]
+fn lsp_rejects_huge_content_length() {
+    let mut input = Cursor::new(b"Content-Length: 4611686018427387903\r\n\r\n{}".to_vec());
+    let err = read_message(&mut input).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+
+    let mut input = Cursor::new(b"Content-Length: 2\r\n\r\n{}".to_vec());
+    assert_eq!(read_message(&mut input).unwrap().as_deref(), Some("{}"));
+}
+
+#[test]
+fn lsp_rejects_deeply_nested_json() {
+    let body = "[".repeat(100_000);
+    let input = format!("Content-Length: {}\r\n\r\n{}", body.len(), body);
+    let mut output = Vec::new();
+    assert!(!serve(Cursor::new(input), &mut output).unwrap());
+    let body = read_message(&mut Cursor::new(output)).unwrap().unwrap();
+    let reply = parse(&body).unwrap();
+    assert_eq!(
+        reply.get("error").unwrap().get("code"),
+        Some(&JsonValue::Number("-32700".into()))
+    );
+}
+
+#[test]
+fn lsp_diagnostics_read_imports() {
+    let dir = std::env::temp_dir().join(format!("picopb-lsp-imports-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(
+        dir.join("unit.proto"),
+        "syntax = \"proto2\"; enum Unit { UNIT_UNSPECIFIED = 0; }",
+    )
+    .unwrap();
+    let uri = |name: &str| format!("file://{}/{}", dir.display(), name).replace(' ', "%20");
+    let mut server = Server::default();
+    // location.proto is only open in the editor, unit.proto is read from disk
+    open(
+        &mut server,
+        &uri("location.proto"),
+        "syntax = \"proto2\"; import \"unit.proto\"; message Location { required int32 latitude = 1; optional Unit unit = 2; }",
+    );
+    let main = "syntax = \"proto2\";
+import \"location.proto\";
+message Reading {
+    option (nanopb_msgopt).msgid = 1;
+    repeated Location locations = 1 [(nanopb).max_size = 4];
+    optional Unit unit = 2;
+}
+";
+    let published = open(&mut server, &uri("reading.proto"), main);
+    assert_eq!(diagnostics(&published), Vec::new());
+
+    let missing = main.replace("location.proto", "missing.proto");
+    let published = open(&mut server, &uri("reading.proto"), &missing);
+    assert_eq!(
+        diagnostics(&published)[0],
+        (
+            r#"{"line":1,"character":7}"#.to_string(),
+            r#""import \"missing.proto\" not found""#.to_string()
+        )
+    );
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn lsp_diagnostics_messages() {
+    let mut server = Server::default();
+    let published = open(&mut server, URI, "syntax = \"proto3\";");
+    assert_eq!(
+        diagnostics(&published),
+        vec![(
+            r#"{"line":0,"character":0}"#.to_string(),
+            r#""`syntax = \"proto3\";` is not supported, only proto2 is""#.to_string()
+        )]
+    );
+}
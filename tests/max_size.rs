@@ -0,0 +1,62 @@
+use picopb::parser::parse;
+use picopb::size::{max_encoded_len, MaxEncodedLen};
+
+const PROTO: &str = "
+    syntax = \"proto2\";
+
+    message Query {
+        required bytes key = 1 [(nanopb).max_size=128];
+        required bytes opaque = 2 [(nanopb).max_size=64];
+        optional Error error = 3;
+    }
+
+    message Response {
+        required bytes value = 1 [(nanopb).max_size=64];
+        required bytes opaque = 2;
+    }
+
+    message Wrapper {
+        repeated Query queries = 1 [(nanopb).max_size=4];
+        repeated int32 values = 2 [(nanopb).max_size=3, packed=true];
+        required uint32 id = 16;
+    }
+
+    message Node {
+        optional Node next = 1;
+    }
+
+    enum Error {
+        ERROR_INVALID_KEY = 1;
+    }
+";
+
+#[test]
+fn max_encoded_len_bounded() {
+    let result = parse(PROTO).unwrap();
+
+    // key: 1 + 2 + 128, opaque: 1 + 1 + 64, error: 1 + 10
+    assert_eq!(
+        max_encoded_len(&result, "Query"),
+        MaxEncodedLen::Bounded(208)
+    );
+
+    // queries: 4 * (1 + 2 + 208), values: 1 + 1 + 3 * 10, id: 2 + 5
+    assert_eq!(
+        max_encoded_len(&result, "Wrapper"),
+        MaxEncodedLen::Bounded(883)
+    );
+}
+
+#[test]
+fn max_encoded_len_unbounded() {
+    let result = parse(PROTO).unwrap();
+
+    assert_eq!(
+        max_encoded_len(&result, "Response"),
+        MaxEncodedLen::Unbounded("Response.opaque".into())
+    );
+    assert_eq!(
+        max_encoded_len(&result, "Node"),
+        MaxEncodedLen::Unbounded("Node is recursive".into())
+    );
+}
@@ -1,10 +1,11 @@
 use pest::{iterators::Pairs, Parser};
 use picopb::{
+    common::{find_option, FieldQualifier, FieldType, OptionValue},
     parser::{PicoPBParser, Rule},
-    validator::validate,
+    validator::{validate, ValidatorError},
 };
 
-use picopb::parser::parse;
+use picopb::parser::{parse, parse_with_config, ParseConfig, ParserError};
 
 fn assert_eq_parse_result(result: Pairs<'_, Rule>, expected: &str) {
     let output = format!("{:?}", result);
@@ -20,9 +21,9 @@ fn parse_syntax_proto2() {
 
 #[test]
 fn parse_bytes_field_max_size() {
-    let bytes_with_max_size = "required bytes value = 1; [(nanopb).max_size=64]";
+    let bytes_with_max_size = "required bytes value = 1 [(nanopb).max_size=64];";
     let result = PicoPBParser::parse(Rule::message_field, bytes_with_max_size).unwrap();
-    let expected = "[Pair { rule: message_field, span: Span { str: \"required bytes value = 1; [(nanopb).max_size=64]\", start: 0, end: 48 }, inner: [Pair { rule: qualifier, span: Span { str: \"required\", start: 0, end: 8 }, inner: [] }, Pair { rule: field_type, span: Span { str: \"bytes\", start: 9, end: 14 }, inner: [] }, Pair { rule: identifier, span: Span { str: \"value\", start: 15, end: 20 }, inner: [] }, Pair { rule: number, span: Span { str: \"1\", start: 23, end: 24 }, inner: [] }, Pair { rule: options, span: Span { str: \"[(nanopb).max_size=64]\", start: 26, end: 48 }, inner: [Pair { rule: option, span: Span { str: \"(nanopb).max_size=64\", start: 27, end: 47 }, inner: [Pair { rule: nanopb_option, span: Span { str: \"(nanopb).max_size=64\", start: 27, end: 47 }, inner: [Pair { rule: max_size_option, span: Span { str: \"max_size\", start: 36, end: 44 }, inner: [] }, Pair { rule: number, span: Span { str: \"64\", start: 45, end: 47 }, inner: [] }] }] }] }] }]";
+    let expected = "[Pair { rule: message_field, span: Span { str: \"required bytes value = 1 [(nanopb).max_size=64];\", start: 0, end: 48 }, inner: [Pair { rule: qualifier, span: Span { str: \"required\", start: 0, end: 8 }, inner: [] }, Pair { rule: field_type, span: Span { str: \"bytes\", start: 9, end: 14 }, inner: [] }, Pair { rule: identifier, span: Span { str: \"value\", start: 15, end: 20 }, inner: [] }, Pair { rule: number, span: Span { str: \"1\", start: 23, end: 24 }, inner: [] }, Pair { rule: options, span: Span { str: \"[(nanopb).max_size=64]\", start: 25, end: 47 }, inner: [Pair { rule: option, span: Span { str: \"(nanopb).max_size=64\", start: 26, end: 46 }, inner: [Pair { rule: option_name, span: Span { str: \"(nanopb).max_size\", start: 26, end: 43 }, inner: [Pair { rule: option_name_part, span: Span { str: \"(nanopb)\", start: 26, end: 34 }, inner: [Pair { rule: extension_name, span: Span { str: \"(nanopb)\", start: 26, end: 34 }, inner: [Pair { rule: full_identifier, span: Span { str: \"nanopb\", start: 27, end: 33 }, inner: [] }] }] }, Pair { rule: option_name_part, span: Span { str: \"max_size\", start: 35, end: 43 }, inner: [Pair { rule: identifier, span: Span { str: \"max_size\", start: 35, end: 43 }, inner: [] }] }] }, Pair { rule: constant, span: Span { str: \"64\", start: 44, end: 46 }, inner: [Pair { rule: int_constant, span: Span { str: \"64\", start: 44, end: 46 }, inner: [] }] }] }] }] }]";
     assert_eq_parse_result(result, expected)
 }
 
@@ -34,18 +35,18 @@ fn parse_complex_proto2() {
         import \"shared.proto\";
 
         message Query {
-            required bytes key = 1; [(nanopb).max_size=128]
-            required bytes opaque = 2; [(nanopb).max_size=64]
+            required bytes key = 1 [(nanopb).max_size=128];
+            required bytes opaque = 2 [(nanopb).max_size=64];
         }
 
         message Response {
-            required bytes value = 1; [(nanopb).max_size=64]
+            required bytes value = 1 [(nanopb).max_size=64];
             required bytes opaque = 2;
             optional Error error = 3;
         }
 
         message RepeatedResponse {
-            repeated Response responses = 1; [(nanopb).max_size=64]
+            repeated Response responses = 1 [(nanopb).max_size=64];
         }
 
         enum Error {
@@ -56,3 +57,286 @@ fn parse_complex_proto2() {
     let result = parse(proto_def).unwrap();
     validate(&result).unwrap();
 }
+
+#[test]
+fn parse_general_options() {
+    let proto_def: &str = "
+        syntax = \"proto2\";
+        option java_package = \"com.example.picopb\";
+        option optimize_for = SPEED;
+
+        message Query {
+            option (nanopb_msgopt).msgid = 5;
+            required bytes key = 1 [(nanopb).max_size=128, deprecated = true, json_name = \"k\"];
+            required string name = 2 [(nanopb) = { max_size: 16 type: FT_STATIC }];
+        }
+
+        enum Error {
+            option allow_alias = true;
+            ERROR_INVALID_KEY = 1;
+            ERROR_NOT_FOUND = 2 [deprecated = true];
+            ERROR_MISSING = 2;
+        }
+";
+    let result = parse(proto_def).unwrap();
+    validate(&result).unwrap();
+
+    assert_eq!(
+        find_option(&result.options, "java_package"),
+        Some(&OptionValue::String("com.example.picopb".into()))
+    );
+    assert_eq!(
+        find_option(&result.options, "optimize_for"),
+        Some(&OptionValue::Identifier("SPEED".into()))
+    );
+
+    let query = &result.message_types["Query"];
+    assert_eq!(
+        find_option(&query.options, "(nanopb_msgopt).msgid"),
+        Some(&OptionValue::Int(5))
+    );
+
+    let key = &query.fields[&1];
+    assert!(matches!(key.field_type, FieldType::Bytes(128)));
+    assert_eq!(
+        find_option(&key.options, "deprecated"),
+        Some(&OptionValue::Bool(true))
+    );
+    assert_eq!(
+        find_option(&key.options, "json_name"),
+        Some(&OptionValue::String("k".into()))
+    );
+
+    let name = &query.fields[&2];
+    assert!(matches!(name.qualifier, FieldQualifier::Required));
+    assert_eq!(
+        find_option(&name.options, "(nanopb)"),
+        Some(&OptionValue::Aggregate(vec![
+            ("max_size".into(), OptionValue::Int(16)),
+            ("type".into(), OptionValue::Identifier("FT_STATIC".into())),
+        ]))
+    );
+
+    let error = &result.enum_types["Error"];
+    assert!(error.allow_alias);
+    assert_eq!(
+        find_option(&error.value_options["ERROR_NOT_FOUND"], "deprecated"),
+        Some(&OptionValue::Bool(true))
+    );
+}
+
+#[test]
+fn parse_invalid_known_option_value() {
+    let proto_def: &str = "
+        syntax = \"proto2\";
+        message Query {
+            required bytes key = 1 [(nanopb).max_size=true];
+        }
+";
+    assert!(parse(proto_def).is_err());
+}
+
+#[test]
+fn validate_enum_alias_requires_option() {
+    let proto_def: &str = "
+        syntax = \"proto2\";
+        enum Error {
+            ERROR_NOT_FOUND = 2;
+            ERROR_MISSING = 2;
+        }
+";
+    let result = parse(proto_def).unwrap();
+    assert!(matches!(
+        validate(&result),
+        Err(ValidatorError::DuplicateEnumValue(_, _))
+    ));
+}
+
+#[test]
+fn parse_spec_lexical_elements() {
+    let proto_def: &str = "// leading comment\r\n\
+        syntax = 'proto2';\r\n\
+        package example.v1;\r\n\
+        import public \"common.proto\";\r\n\
+        option java_package = 'com.example\\x2Epicopb';\r\n\
+        \r\n\
+        /* block comment */\r\n\
+        message Query {\r\n\
+        \t// field comment\r\n\
+        \trequired bytes key = 0x1 [(nanopb).max_size = 010]; // trailing comment\r\n\
+        \toptional string name = 2 [default = \"a\\tb\\101\\u00e6\"];\r\n\
+        \toptional double_value value = 3;\r\n\
+        \treserved 4, 6 to 8, 100 to max;\r\n\
+        \treserved \"old\";\r\n\
+        \textensions 1000 to 2000;\r\n\
+        \t;\r\n\
+        }\r\n\
+        message double_value {}\r\n\
+        enum Error {\r\n\
+        \tERROR_NONE = 0;\r\n\
+        \tERROR_NOT_FOUND = 2 [deprecated = true];\r\n\
+        \treserved 5;\r\n\
+        }\r\n";
+    let result = parse(proto_def).unwrap();
+    validate(&result).unwrap();
+
+    assert_eq!(result.package.as_deref(), Some("example.v1"));
+    assert_eq!(result.imports, vec!["common.proto".to_string()]);
+    assert_eq!(
+        find_option(&result.options, "java_package"),
+        Some(&OptionValue::String("com.example.picopb".into()))
+    );
+
+    let query = &result.message_types["Query"];
+    assert!(matches!(query.fields[&1].field_type, FieldType::Bytes(8)));
+    assert_eq!(
+        find_option(&query.fields[&2].options, "default"),
+        Some(&OptionValue::String("a\tbAæ".into()))
+    );
+    assert!(matches!(
+        query.fields[&3].field_type,
+        FieldType::MessageType("double_value")
+    ));
+    assert_eq!(result.enum_types["Error"].pairs["ERROR_NONE"], 0);
+}
+
+#[test]
+fn parse_legacy_field_options() {
+    let proto_def: &str = "
+        syntax = \"proto2\";
+        message Query {
+            required bytes key = 1; [(nanopb).max_size=128]
+        }
+        enum Error {
+            ERROR_NOT_FOUND = 2; [deprecated = true]
+        }
+";
+    assert!(matches!(
+        parse(proto_def),
+        Err(ParserError::LegacyFieldOptions(_))
+    ));
+
+    let config = ParseConfig {
+        legacy_field_options: true,
+    };
+    let result = parse_with_config(proto_def, config).unwrap();
+    validate(&result).unwrap();
+    assert!(matches!(
+        result.message_types["Query"].fields[&1].field_type,
+        FieldType::Bytes(128)
+    ));
+    assert!(result.enum_types["Error"]
+        .value_options
+        .contains_key("ERROR_NOT_FOUND"));
+}
+
+#[test]
+fn validate_field_number_range() {
+    for number in ["0", "19000", "536870912"] {
+        let proto_def = format!(
+            "syntax = \"proto2\"; message Query {{ required int32 key = {}; }}",
+            number
+        );
+        let result = parse(&proto_def).unwrap();
+        assert!(matches!(
+            validate(&result),
+            Err(ValidatorError::InvalidFieldNumber(_, _))
+        ));
+    }
+}
+
+#[test]
+fn parse_numeric_literals() {
+    let proto_def: &str = "
+        syntax = \"proto2\";
+        option (picopb).offset = -0x10;
+        option (picopb).mask = 0777;
+        message Query {
+            required int32 key = 0x0F;
+            required int32 value = 020;
+        }
+        enum Error {
+            ERROR_NONE = 0;
+            ERROR_HEX = 0x7fffffff;
+            ERROR_OCTAL = 010;
+            ERROR_NEGATIVE = -1;
+            ERROR_MIN = -0x80000000;
+        }
+";
+    let result = parse(proto_def).unwrap();
+    validate(&result).unwrap();
+
+    assert_eq!(
+        find_option(&result.options, "(picopb).offset"),
+        Some(&OptionValue::Int(-16))
+    );
+    assert_eq!(
+        find_option(&result.options, "(picopb).mask"),
+        Some(&OptionValue::Int(511))
+    );
+
+    let query = &result.message_types["Query"];
+    assert_eq!(query.fields[&15].identifier, "key");
+    assert_eq!(query.fields[&16].identifier, "value");
+
+    let error = &result.enum_types["Error"];
+    assert_eq!(error.pairs["ERROR_NONE"], 0);
+    assert_eq!(error.pairs["ERROR_HEX"], i32::MAX);
+    assert_eq!(error.pairs["ERROR_OCTAL"], 8);
+    assert_eq!(error.pairs["ERROR_NEGATIVE"], -1);
+    assert_eq!(error.pairs["ERROR_MIN"], i32::MIN);
+}
+
+#[test]
+fn parse_enum_value_out_of_range() {
+    let proto_def: &str = "
+        syntax = \"proto2\";
+        enum Error {
+            ERROR_TOO_LARGE = 0x80000000;
+        }
+";
+    assert!(matches!(
+        parse(proto_def),
+        Err(ParserError::ParseIntError(_, _))
+    ));
+}
+
+#[test]
+fn parse_unsupported_constructs() {
+    let definitions = [
+        "message Query { message Key {} }",
+        "message Query { enum Kind { KIND_UNSPECIFIED = 0; } }",
+        "message Query { oneof key { int32 id = 1; } }",
+        "message Query { map<string, int32> values = 1; }",
+        "message Query { optional group Key = 1 {} }",
+        "message Query { optional common.Key key = 1; }",
+        "extend Query { optional int32 key = 100; }",
+        "service Search {}",
+    ];
+    for definition in definitions {
+        let proto_def = format!("syntax = \"proto2\"; {}", definition);
+        assert!(
+            matches!(parse(&proto_def), Err(ParserError::PestRuleError(_))),
+            "{}",
+            definition
+        );
+    }
+}
+
+#[test]
+fn validate_unsupported_scalar_types() {
+    let types = [
+        "float", "double", "fixed32", "fixed64", "sfixed32", "sfixed64", "sint32", "sint64",
+    ];
+    for field_type in types {
+        let proto_def = format!(
+            "syntax = \"proto2\"; message Query {{ optional {} key = 1; }}",
+            field_type
+        );
+        let result = parse(&proto_def).unwrap();
+        assert!(matches!(
+            validate(&result),
+            Err(ValidatorError::MissingTypeDefinition(name)) if name == field_type
+        ));
+    }
+}
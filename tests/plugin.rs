@@ -0,0 +1,181 @@
+use picopb::descriptor::proto::{
+    CodeGeneratorRequest, DescriptorProto, EnumDescriptorProto, EnumValueDescriptorProto,
+    FieldDescriptorProto, FileDescriptorProto,
+};
+use picopb::plugin::{generate_response, parse_parameters, PluginError};
+use picopb::{generator::generate, parser::parse, validator::validate};
+
+fn field(
+    name: &str,
+    number: i32,
+    field_type: i32,
+    type_name: Option<&str>,
+) -> FieldDescriptorProto {
+    FieldDescriptorProto {
+        name: Some(name.into()),
+        number: Some(number),
+        label: Some(1),
+        field_type: Some(field_type),
+        type_name: type_name.map(Into::into),
+        json_name: Some(picopb::json::json_name(name)),
+        ..Default::default()
+    }
+}
+
+/// units.proto declares Unit, reading.proto imports it
+fn files() -> Vec<FileDescriptorProto> {
+    let units = FileDescriptorProto {
+        name: Some("units.proto".into()),
+        package: Some("sensors".into()),
+        enum_type: vec![EnumDescriptorProto {
+            name: Some("Unit".into()),
+            value: [("UNIT_UNKNOWN", 0), ("UNIT_CELSIUS", 1)]
+                .into_iter()
+                .map(|(name, number)| EnumValueDescriptorProto {
+                    name: Some(name.into()),
+                    number: Some(number),
+                })
+                .collect(),
+            options: None,
+        }],
+        ..Default::default()
+    };
+    let reading = FileDescriptorProto {
+        name: Some("sensors/reading.proto".into()),
+        package: Some("sensors".into()),
+        dependency: vec!["units.proto".into()],
+        message_type: vec![DescriptorProto {
+            name: Some("Reading".into()),
+            field: vec![
+                field("sensor", 1, 9, None),
+                field("unit", 2, 14, Some(".sensors.Unit")),
+            ],
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    vec![units, reading]
+}
+
+fn request(parameter: Option<&str>, file_to_generate: &[&str]) -> CodeGeneratorRequest {
+    CodeGeneratorRequest {
+        file_to_generate: file_to_generate
+            .iter()
+            .map(|name| name.to_string())
+            .collect(),
+        parameter: parameter.map(Into::into),
+        proto_file: files(),
+    }
+}
+
+#[test]
+fn plugin_generates_imported_types() {
+    let response = generate_response(&request(None, &["sensors/reading.proto"]));
+    assert_eq!(response.error, None);
+    assert_eq!(response.file.len(), 1);
+    assert_eq!(response.file[0].name.as_deref(), Some("sensors/reading.rs"));
+
+    let schema = parse(
+        r#"syntax = "proto2";
+        package sensors;
+        enum Unit { UNIT_UNKNOWN = 0; UNIT_CELSIUS = 1; }
+        message Reading {
+            optional string sensor = 1;
+            optional Unit unit = 2;
+        }"#,
+    )
+    .unwrap();
+    validate(&schema).unwrap();
+    let mut expected = Vec::new();
+    generate(&mut expected, &schema).unwrap();
+    assert_eq!(
+        response.file[0].content.as_deref(),
+        Some(String::from_utf8(expected).unwrap().as_str())
+    );
+}
+
+#[test]
+fn plugin_parameters() {
+    let response = generate_response(&request(
+        Some("json,max_size=8"),
+        &["units.proto", "sensors/reading.proto"],
+    ));
+    assert_eq!(response.error, None);
+    assert_eq!(response.file.len(), 2);
+    let content = response.file[1].content.as_deref().unwrap();
+    assert!(content.contains("picopb::common::FieldType::String(8)"));
+    assert!(content.contains("impl picopb::json::ToJson"));
+
+    let parameters =
+        parse_parameters(" preserve_unknown_fields , unknown_fields_capacity=4").unwrap();
+    assert_eq!(
+        parameters.generator.unknown_fields,
+        picopb::common::UnknownFieldStorage::Bounded(4)
+    );
+    assert!(parse_parameters("no_std").unwrap().generator.no_std);
+    assert!(matches!(
+        parse_parameters("no_alloc"),
+        Err(PluginError::UnknownParameter(parameter)) if parameter == "no_alloc"
+    ));
+    assert!(matches!(
+        parse_parameters("max_size=-1"),
+        Err(PluginError::InvalidParameter(_))
+    ));
+}
+
+#[test]
+fn plugin_no_std() {
+    let response = generate_response(&request(Some("no_std"), &["sensors/reading.proto"]));
+    assert_eq!(response.error, None);
+    let content = response.file[0].content.as_deref().unwrap();
+    assert!(!content.contains("std::"));
+    assert!(content.contains("use core::ops::Deref;"));
+    assert!(content.contains("core::fmt::Write"));
+    assert!(content.contains("-> picopb::encode::SinkResult<usize>"));
+}
+
+#[test]
+fn plugin_reports_errors() {
+    let response = generate_response(&request(Some("no_alloc"), &["units.proto"]));
+    assert_eq!(
+        response.error.as_deref(),
+        Some("UnknownParameter(\"no_alloc\")")
+    );
+
+    let mut request = request(None, &["units.proto", "sensors/reading.proto"]);
+    request.proto_file.remove(0);
+    let response = generate_response(&request);
+    assert!(response.file.is_empty());
+    assert_eq!(
+        response.error.as_deref(),
+        Some("units.proto: FileNotFound(\"units.proto\")")
+    );
+}
+
+#[test]
+fn plugin_binary_round_trip() {
+    use picopb::decode::DecodeMessage;
+    use picopb::descriptor::proto::CodeGeneratorResponse;
+    use picopb::encode::ToWire;
+    use std::io::Write;
+    use std::process::{Command, Stdio};
+
+    let mut input = Vec::new();
+    (&request(None, &["sensors/reading.proto"]))
+        .append(&mut input)
+        .unwrap();
+    let mut child = Command::new(env!("CARGO_BIN_EXE_protoc-gen-picopb"))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(&input).unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success());
+    let response = CodeGeneratorResponse::decode(&output.stdout).unwrap();
+    let expected = generate_response(&request(None, &["sensors/reading.proto"]));
+    assert_eq!(response.error, None);
+    assert_eq!(response.file.len(), 1);
+    assert_eq!(response.file[0].name, expected.file[0].name);
+    assert_eq!(response.file[0].content, expected.file[0].content);
+}
@@ -0,0 +1,115 @@
+use picopb::decode::{DecodeError, DecodeMessage};
+use picopb::encode::ToWire;
+use picopb::generator::generate;
+use picopb::parser::parse;
+use picopb::randomizer::{randomized, seeded, Randomize, MAX_UNBOUNDED_LEN};
+use picopb::validator::validate;
+
+mod bounded {
+    #![allow(dead_code, unused_imports)]
+    include!("../protos/bounded.rs");
+}
+
+mod registry {
+    #![allow(dead_code, unused_imports)]
+    include!("../protos/registry.rs");
+}
+
+use bounded::{Frame, Mode};
+
+#[test]
+fn generated_bounded_is_up_to_date() {
+    let result = parse(include_str!("../protos/bounded.proto")).unwrap();
+    validate(&result).unwrap();
+    let mut output = Vec::new();
+    generate(&mut output, &result).unwrap();
+    assert_eq!(
+        String::from_utf8(output).unwrap(),
+        include_str!("../protos/bounded.rs")
+    );
+}
+
+fn encode(frame: &Frame) -> Vec<u8> {
+    let mut encoded: Vec<u8> = Vec::new();
+    frame.append(&mut encoded).unwrap();
+    encoded
+}
+
+#[test]
+fn same_seed_same_message() {
+    let first: Frame = randomized(&mut seeded(7));
+    let second = Frame::randomized(&mut seeded(7));
+    assert_eq!(encode(&first), encode(&second));
+
+    let mut rng = seeded(7);
+    let first: registry::Batch = randomized(&mut rng);
+    let second: registry::Batch = randomized(&mut rng);
+    assert_ne!(first.samples.0, second.samples.0);
+}
+
+#[test]
+fn randomized_bounded_message_round_trips() {
+    let mut rng = seeded(1);
+    for _ in 0..200 {
+        let frame: Frame = randomized(&mut rng);
+        assert!(frame.name.len() <= 8);
+        assert!(frame.label.is_none_or(|label| label.len() <= 4));
+
+        let encoded = encode(&frame);
+        let decoded = Frame::decode(&encoded).unwrap();
+        assert_eq!(decoded.name, frame.name);
+        assert_eq!(decoded.payload, frame.payload);
+        assert_eq!(decoded.offsets.0, frame.offsets.0);
+        assert_eq!(decoded.mode, frame.mode);
+        assert_eq!(encode(&decoded), encoded);
+    }
+}
+
+#[test]
+fn bounded_repeated_fields_encode_their_values() {
+    let mut frame = Frame::default();
+    frame.samples.push(bounded::Sample {
+        index: 1,
+        mode: None,
+    });
+    frame.offsets.0.extend([1, 2]);
+
+    let encoded = encode(&frame);
+    // one sample and a packed record of two offsets
+    assert!(encoded.windows(4).any(|w| w == [0x22, 0x02, 0x08, 0x01]));
+    assert!(encoded.windows(4).any(|w| w == [0x2a, 0x02, 0x01, 0x02]));
+    assert_eq!(encoded.iter().filter(|&&b| b == 0x22).count(), 1);
+
+    let decoded = Frame::decode(&encoded).unwrap();
+    assert_eq!(decoded.samples.len(), 1);
+    assert_eq!(decoded.offsets.0.as_slice(), [1, 2]);
+    assert_eq!(encode(&decoded), encoded);
+
+    let mut encoded = encoded;
+    encoded.extend([0x2a, 0x03, 0x03, 0x04, 0x05]);
+    assert_eq!(
+        Frame::decode(&encoded).unwrap_err(),
+        DecodeError::CapacityExceeded
+    );
+}
+
+#[test]
+fn randomized_unbounded_fields_are_limited() {
+    let mut rng = seeded(2);
+    for _ in 0..50 {
+        let batch: registry::Batch = randomized(&mut rng);
+        assert!(batch.samples.0.len() <= MAX_UNBOUNDED_LEN);
+        assert!(batch.tags.len() <= MAX_UNBOUNDED_LEN);
+        assert!(batch.tags.iter().all(|tag| tag.len() <= MAX_UNBOUNDED_LEN
+            && tag.bytes().all(|b| b.is_ascii_graphic() || b == b' ')));
+    }
+}
+
+#[test]
+fn randomized_enum_picks_every_value() {
+    let mut rng = seeded(3);
+    let modes: Vec<Mode> = (0..100).map(|_| randomized(&mut rng)).collect();
+    for mode in [Mode::ModeUnspecified, Mode::ModeFast, Mode::ModeSlow] {
+        assert!(modes.contains(&mode), "{:?} never picked", mode);
+    }
+}
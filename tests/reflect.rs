@@ -0,0 +1,178 @@
+use picopb::arrayvec::ArrayVec;
+use picopb::common::{FieldType, Packed};
+use picopb::reflect::{copy_fields, set_bounded, FieldValue, Reflect, ReflectError, ReflectField};
+
+mod registry {
+    #![allow(dead_code, unused_imports)]
+    include!("../protos/registry.rs");
+}
+
+use registry::{Batch, Location, Reading, Unit};
+
+fn location() -> Location {
+    Location {
+        latitude: 1,
+        longitude: -2,
+        label: Some(bytes::Bytes::from_static(b"home")),
+    }
+}
+
+/// Writes every field of any message, as a generic logger would
+fn log(message: &dyn Reflect) -> String {
+    let mut fields = Vec::new();
+    for field in message.descriptor().fields {
+        for value in message.get(field.ordinal.0).unwrap() {
+            let value = match value {
+                FieldValue::Message(message) => format!("{{{}}}", log(message)),
+                FieldValue::String(value) => format!("{:?}", value),
+                value => format!("{:?}", value),
+            };
+            fields.push(format!("{}={}", field.identifier, value));
+        }
+    }
+    fields.join(" ")
+}
+
+#[test]
+fn message_descriptor() {
+    let descriptor = &Batch::DESCRIPTOR;
+    assert_eq!(descriptor.name, "Batch");
+    let names: Vec<_> = descriptor
+        .fields
+        .iter()
+        .map(|field| field.identifier)
+        .collect();
+    assert_eq!(names, vec!["samples", "tags", "locations", "counters"]);
+    assert_eq!(descriptor.field_by_name("tags").unwrap().ordinal.0, 2);
+    assert!(matches!(
+        descriptor.field(3).unwrap().field_type,
+        FieldType::MessageType("Location")
+    ));
+    assert!(descriptor.field(5).is_none());
+    assert_eq!(Batch::default().descriptor().name, "Batch");
+}
+
+#[test]
+fn get_fields() {
+    let reading = Reading {
+        sensor: "temp".into(),
+        value: None,
+        location: Some(location()),
+    };
+    assert_eq!(reading.get(1), Some(vec![FieldValue::String("temp")]));
+    assert_eq!(reading.get_by_name("value"), Some(vec![]));
+    let location = location();
+    assert_eq!(reading.get(3), Some(vec![FieldValue::Message(&location)]));
+    assert_eq!(reading.get(4), None);
+    assert_eq!(reading.get_by_name("missing"), None);
+    assert_eq!(
+        log(&reading),
+        "sensor=\"temp\" location={latitude=Int32(1) longitude=Int32(-2) label=Bytes([104, 111, 109, 101])}"
+    );
+
+    let batch = Batch {
+        samples: Packed(vec![3, -1]),
+        ..Default::default()
+    };
+    assert_eq!(
+        batch.get(1),
+        Some(vec![FieldValue::Int32(3), FieldValue::Int32(-1)])
+    );
+}
+
+#[test]
+fn set_fields() {
+    let mut reading = Reading::default();
+    reading.set(1, &[FieldValue::String("temp")]).unwrap();
+    reading
+        .set_by_name("value", &[FieldValue::Int32(7)])
+        .unwrap();
+    let location = location();
+    reading
+        .set_by_name("location", &[FieldValue::Message(&location)])
+        .unwrap();
+    assert_eq!(reading.sensor, "temp");
+    assert_eq!(reading.value, Some(7));
+    assert_eq!(reading.location.as_ref().unwrap().latitude, 1);
+    assert_eq!(
+        reading.location.as_ref().unwrap().label.as_deref(),
+        Some(&b"home"[..])
+    );
+    reading.set(2, &[]).unwrap();
+    assert_eq!(reading.value, None);
+
+    let mut batch = Batch::default();
+    batch
+        .set(1, &[FieldValue::Int32(1), FieldValue::Int32(2)])
+        .unwrap();
+    batch
+        .set_by_name("locations", &[FieldValue::Message(&location); 2])
+        .unwrap();
+    assert_eq!(batch.samples.0, vec![1, 2]);
+    assert_eq!(batch.locations.len(), 2);
+
+    let mut copy = Batch::default();
+    copy_fields(&mut copy, &batch).unwrap();
+    assert_eq!(FieldValue::Message(&copy), FieldValue::Message(&batch));
+    assert_ne!(
+        FieldValue::Message(&copy),
+        FieldValue::Message(&Batch::default())
+    );
+}
+
+#[test]
+fn set_errors() {
+    let mut reading = Reading::default();
+    assert_eq!(reading.set(1, &[]), Err(ReflectError::InvalidCount(0)));
+    assert_eq!(
+        reading.set(2, &[FieldValue::Int32(1), FieldValue::Int32(2)]),
+        Err(ReflectError::InvalidCount(2))
+    );
+    assert_eq!(
+        reading.set(1, &[FieldValue::Int32(1)]),
+        Err(ReflectError::UnexpectedValue("string"))
+    );
+    assert_eq!(
+        reading.set(9, &[]),
+        Err(ReflectError::UnknownField("9".into()))
+    );
+    assert_eq!(
+        reading.set_by_name("missing", &[]),
+        Err(ReflectError::UnknownField("missing".into()))
+    );
+    let other = Reading::default();
+    assert_eq!(
+        reading.set(3, &[FieldValue::Message(&other)]),
+        Err(ReflectError::WrongMessageType("Reading"))
+    );
+}
+
+#[test]
+fn reflect_field_values() {
+    assert_eq!(Unit::UnitKelvin.to_value(), FieldValue::Enum(2));
+    assert_eq!(
+        Unit::from_value(&FieldValue::Enum(-1)),
+        Ok(Unit::UnitInvalid)
+    );
+    assert_eq!(
+        Unit::from_value(&FieldValue::Enum(5)),
+        Err(ReflectError::InvalidEnumValue(5))
+    );
+
+    let mut values = ArrayVec::<i32, 3>::new();
+    set_bounded(&mut values, &[FieldValue::Int32(4)]).unwrap();
+    assert_eq!(values.as_slice(), [4]);
+    assert_eq!(
+        set_bounded(&mut values, &[FieldValue::Int32(1); 4]),
+        Err(ReflectError::CapacityExceeded)
+    );
+    assert_eq!(values.as_slice(), [4]);
+    assert_eq!(
+        <[u8; 4]>::from_value(&FieldValue::Bytes(b"ab")),
+        Ok(*b"ab\0\0")
+    );
+    assert_eq!(
+        <[u8; 1]>::from_value(&FieldValue::Bytes(b"ab")),
+        Err(ReflectError::CapacityExceeded)
+    );
+}
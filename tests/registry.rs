@@ -0,0 +1,287 @@
+use picopb::decode::{DecodeBuffer, DecodeError, DecodeMessage, FromWire};
+use picopb::encode::{EncodeBuffer, ToWire};
+use picopb::{
+    generator::{generate, generate_with_config, GeneratorConfig},
+    parser::parse,
+    validator::{validate, ValidatorError},
+};
+
+mod registry {
+    #![allow(dead_code, unused_imports)]
+    include!("../protos/registry.rs");
+}
+
+extern crate alloc;
+
+mod registry_no_std {
+    #![allow(dead_code, unused_imports)]
+    include!("../protos/registry_no_std.rs");
+}
+
+use registry::{AnyMessage, Batch, Location, Ping, Reading, Unit};
+
+#[test]
+fn generated_registry_is_up_to_date() {
+    let result = parse(include_str!("../protos/registry.proto")).unwrap();
+    validate(&result).unwrap();
+    let mut output = Vec::new();
+    generate(&mut output, &result).unwrap();
+    assert_eq!(
+        String::from_utf8(output).unwrap(),
+        include_str!("../protos/registry.rs")
+    );
+}
+
+#[test]
+fn generated_no_std_registry_is_up_to_date() {
+    let result = parse(include_str!("../protos/registry.proto")).unwrap();
+    let config = GeneratorConfig {
+        no_std: true,
+        ..Default::default()
+    };
+    let mut output = Vec::new();
+    generate_with_config(&mut output, &result, &config).unwrap();
+    assert_eq!(
+        String::from_utf8(output).unwrap(),
+        include_str!("../protos/registry_no_std.rs")
+    );
+
+    let ping = registry_no_std::Ping { sequence: 7 };
+    let mut encoded: Vec<u8> = Vec::new();
+    (&ping).append(&mut encoded).unwrap();
+    assert_eq!(registry_no_std::Ping::decode(&encoded).unwrap().sequence, 7);
+}
+
+#[test]
+fn decode_reading() {
+    /*
+     * sensor: "temp", value: 21, location { latitude: 1 longitude: 150 }
+     */
+    let bytes = [
+        0x0a, 0x04, b't', b'e', b'm', b'p', 0x10, 0x15, 0x1a, 0x05, 0x08, 0x01, 0x10, 0x96, 0x01,
+    ];
+    let reading = Reading::decode(&bytes).unwrap();
+    assert_eq!(reading.sensor, "temp");
+    assert_eq!(reading.value, Some(21));
+    let location = reading.location.unwrap();
+    assert_eq!(location.latitude, 1);
+    assert_eq!(location.longitude, 150);
+    assert_eq!(location.label, None);
+}
+
+#[test]
+fn decode_skips_unknown_fields() {
+    // sequence: 7 followed by unknown fields 2 (varint) and 3 (len)
+    let bytes = [0x08, 0x07, 0x10, 0xff, 0x01, 0x1a, 0x02, 0x01, 0x02];
+    assert_eq!(Ping::decode(&bytes).unwrap().sequence, 7);
+}
+
+#[test]
+fn decode_skips_nested_groups() {
+    // sequence: 7 followed by unknown group 2 holding group 3 holding a varint
+    let bytes = [0x08, 0x07, 0x13, 0x1b, 0x08, 0x01, 0x1c, 0x14];
+    assert_eq!(Ping::decode(&bytes).unwrap().sequence, 7);
+
+    // unterminated groups nested far deeper than the stack could hold
+    let mut bytes = vec![0x08, 0x07];
+    bytes.resize(2_000_000, 0x13);
+    assert_eq!(Ping::decode(&bytes).unwrap_err(), DecodeError::GroupTooDeep);
+}
+
+#[test]
+fn decode_missing_required_field() {
+    assert_eq!(
+        Ping::decode(&[]).unwrap_err(),
+        DecodeError::MissingRequiredField("sequence")
+    );
+}
+
+#[test]
+fn decode_truncated() {
+    assert_eq!(
+        Reading::decode(&[0x0a, 0x04, b't']).unwrap_err(),
+        DecodeError::UnexpectedEndOfBuffer
+    );
+}
+
+#[test]
+fn any_message_round_trip() {
+    let reading = Reading {
+        sensor: "humidity".into(),
+        value: Some(40),
+        location: Some(Location {
+            latitude: 59,
+            longitude: 10,
+            label: Some(bytes::Bytes::from_static(b"lab")),
+        }),
+    };
+    let message = AnyMessage::from(reading);
+    assert_eq!(message.id(), Reading::MSGID);
+
+    let mut static_buffer: [u8; 64] = [0; 64];
+    let mut buffer = EncodeBuffer::from_static(&mut static_buffer);
+    let count = message.encode_with_id(&mut buffer).unwrap();
+    assert_eq!(count, buffer.as_slice().len());
+    assert_eq!(buffer.as_slice()[0], 2);
+
+    match AnyMessage::decode_with_id(buffer.as_slice()).unwrap() {
+        AnyMessage::Reading(decoded) => {
+            assert_eq!(decoded.sensor, "humidity");
+            assert_eq!(decoded.value, Some(40));
+            let location = decoded.location.unwrap();
+            assert_eq!(location.latitude, 59);
+            assert_eq!(location.longitude, 10);
+            assert_eq!(location.label.as_deref(), Some(&b"lab"[..]));
+        }
+        other => panic!("unexpected message {:?}", other),
+    }
+}
+
+#[test]
+fn decode_any_dispatches_on_id() {
+    match AnyMessage::decode_any(Ping::MSGID, &[0x08, 0x2a]).unwrap() {
+        AnyMessage::Ping(ping) => assert_eq!(ping.sequence, 42),
+        other => panic!("unexpected message {:?}", other),
+    }
+    assert_eq!(
+        AnyMessage::decode_any(99, &[]).unwrap_err(),
+        DecodeError::UnknownMessageId(99)
+    );
+}
+
+#[test]
+fn enum_i32_conversions() {
+    assert_eq!(Unit::try_from(-1), Ok(Unit::UnitInvalid));
+    assert_eq!(Unit::try_from(2), Ok(Unit::UnitKelvin));
+    assert!(Unit::try_from(3).is_err());
+    assert_eq!(i32::from(Unit::UnitInvalid), -1);
+    assert_eq!(i32::from(Unit::UnitUnknown), 0);
+}
+
+#[test]
+fn negative_enum_value_round_trip() {
+    // -1 is sign extended to a 10 byte varint
+    let bytes = [0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01];
+    let mut buf = DecodeBuffer::from_slice(&bytes);
+    assert_eq!(Unit::read(&mut buf).unwrap(), Unit::UnitInvalid);
+
+    let mut encoded: Vec<u8> = Vec::new();
+    Unit::UnitInvalid.append(&mut encoded).unwrap();
+    assert_eq!(encoded, bytes);
+
+    let mut buf = DecodeBuffer::from_slice(&[0x03]);
+    assert_eq!(
+        Unit::read(&mut buf).unwrap_err(),
+        DecodeError::InvalidEnumValue(3)
+    );
+}
+
+fn batch() -> Batch {
+    let mut batch = Batch::default();
+    batch.samples.0 = vec![1, 150];
+    batch.tags = vec!["a".to_string(), "b".to_string()];
+    batch.locations = vec![Location {
+        latitude: 1,
+        longitude: 2,
+        label: None,
+    }];
+    batch.counters = vec![7];
+    batch
+}
+
+#[test]
+fn encode_repeated_fields() {
+    let batch = batch();
+    let mut encoded: Vec<u8> = Vec::new();
+    let count = (&batch).append(&mut encoded).unwrap();
+
+    let expected = [
+        // samples, packed into a single record
+        0x0a, 0x03, 0x01, 0x96, 0x01, //
+        // tags, one record per value
+        0x12, 0x01, b'a', 0x12, 0x01, b'b', //
+        // locations
+        0x1a, 0x04, 0x08, 0x01, 0x10, 0x02, //
+        // counters
+        0x20, 0x07,
+    ];
+    assert_eq!(encoded, expected);
+    assert_eq!(count, expected.len());
+    assert_eq!((&batch).precalculate_size(), expected.len());
+
+    let decoded = Batch::decode(&encoded).unwrap();
+    assert_eq!(decoded.samples.0, vec![1, 150]);
+    assert_eq!(decoded.tags, vec!["a", "b"]);
+    assert_eq!(decoded.locations[0].longitude, 2);
+    assert_eq!(decoded.counters, vec![7]);
+}
+
+#[test]
+fn encode_empty_packed_field_is_omitted() {
+    let mut encoded: Vec<u8> = Vec::new();
+    (&Batch::default()).append(&mut encoded).unwrap();
+    assert!(encoded.is_empty());
+}
+
+#[test]
+fn decode_repeated_scalars_packed_and_unpacked() {
+    // samples unpacked, counters packed, and both forms mixed for samples
+    let bytes = [
+        0x08, 0x01, 0x08, 0x96, 0x01, 0x22, 0x02, 0x07, 0x08, 0x0a, 0x01, 0x03,
+    ];
+    let decoded = Batch::decode(&bytes).unwrap();
+    assert_eq!(decoded.samples.0, vec![1, 150, 3]);
+    assert_eq!(decoded.counters, vec![7, 8]);
+}
+
+#[test]
+fn validate_packed_requires_scalar() {
+    for field_type in ["string", "bytes", "Location"] {
+        let proto_def = format!(
+            "syntax = \"proto2\";
+            message Location {{ required int32 latitude = 1; }}
+            message Batch {{ repeated {} values = 1 [packed = true]; }}",
+            field_type
+        );
+        let result = parse(&proto_def).unwrap();
+        assert!(matches!(
+            validate(&result),
+            Err(ValidatorError::InvalidPackedField(_))
+        ));
+    }
+}
+
+#[test]
+fn validate_string_max_size() {
+    let proto_def = |limit| {
+        format!(
+            "syntax = \"proto2\";
+            message Reading {{ required string sensor = 1 [(nanopb).max_size = {}]; }}",
+            limit
+        )
+    };
+    let largest = proto_def(255);
+    assert!(validate(&parse(&largest).unwrap()).is_ok());
+    let too_long = proto_def(300);
+    let result = parse(&too_long).unwrap();
+    assert!(matches!(
+        validate(&result),
+        Err(ValidatorError::StringTooLong(path, 300)) if path == "Reading.sensor"
+    ));
+}
+
+#[test]
+fn generated_enum_follows_declaration_order() {
+    // proto2 defaults to the first declared value
+    assert_eq!(Unit::default(), Unit::UnitUnknown);
+
+    let proto = "syntax = \"proto2\";
+        enum Mode { option allow_alias = true; MODE_OFF = 0; MODE_ON = 1; MODE_ENABLED = 1; }";
+    let mut output = Vec::new();
+    generate(&mut output, &parse(proto).unwrap()).unwrap();
+    let generated = String::from_utf8(output).unwrap();
+    assert!(generated.contains("    #[default]\n    ModeOff,\n    ModeOn,\n    ModeEnabled,\n"));
+    // of the aliases the first declared one is decoded
+    assert!(generated.contains("1 => Ok(Mode::ModeOn),"));
+    assert!(!generated.contains("1 => Ok(Mode::ModeEnabled),"));
+}
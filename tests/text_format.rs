@@ -0,0 +1,144 @@
+use picopb::common::Packed;
+use picopb::text_format::{parse, FromText, TextFormatError, TextValue, ToText, MAX_NESTING_DEPTH};
+
+mod registry {
+    #![allow(dead_code, unused_imports)]
+    include!("../protos/registry.rs");
+}
+
+use registry::{Batch, Location, Reading, Unit};
+
+fn batch() -> Batch {
+    Batch {
+        samples: Packed(vec![3, -1]),
+        tags: vec!["a\"b".into(), "æ\n".into()],
+        locations: vec![
+            Location {
+                latitude: 1,
+                longitude: 2,
+                label: Some(bytes::Bytes::from_static(b"\x00\xffok")),
+            },
+            Location::default(),
+        ],
+        counters: Vec::new(),
+    }
+}
+
+#[test]
+fn message_to_text_format() {
+    let reading = Reading {
+        sensor: "temp".into(),
+        value: None,
+        location: Some(Location::default()),
+    };
+    assert_eq!(
+        reading.to_text_format(),
+        "sensor: \"temp\" location { latitude: 0 longitude: 0 }"
+    );
+    assert_eq!(
+        batch().to_text_format(),
+        concat!(
+            "samples: 3 samples: -1 tags: \"a\\\"b\" tags: \"æ\\n\" ",
+            "locations { latitude: 1 longitude: 2 label: \"\\000\\377ok\" } ",
+            "locations { latitude: 0 longitude: 0 }"
+        )
+    );
+}
+
+#[test]
+fn message_text_format_round_trip() {
+    let decoded = Batch::from_text_format(&batch().to_text_format()).unwrap();
+    assert_eq!(decoded.samples.0, vec![3, -1]);
+    assert_eq!(decoded.tags, vec!["a\"b".to_string(), "æ\n".to_string()]);
+    assert_eq!(decoded.locations.len(), 2);
+    assert_eq!(
+        decoded.locations[0].label.as_deref(),
+        Some(&b"\x00\xffok"[..])
+    );
+    assert!(decoded.counters.is_empty());
+}
+
+#[test]
+fn message_from_text_format() {
+    let reading = Reading::from_text_format(
+        "# a hand written fixture
+         sensor: 'te' \"mp\"
+         value: -0x10,
+         location: < latitude: 010; longitude: 2 >",
+    )
+    .unwrap();
+    assert_eq!(reading.sensor, "temp");
+    assert_eq!(reading.value, Some(-16));
+    let location = reading.location.unwrap();
+    assert_eq!(location.latitude, 8);
+    assert_eq!(location.longitude, 2);
+
+    let batch = Batch::from_text_format("counters: [1, 2] counters: 3 samples: []").unwrap();
+    assert_eq!(batch.counters, vec![1, 2, 3]);
+    assert!(batch.samples.0.is_empty());
+}
+
+#[test]
+fn message_from_text_format_errors() {
+    assert_eq!(
+        Reading::from_text_format("value: 1").unwrap_err(),
+        TextFormatError::MissingRequiredField("sensor")
+    );
+    assert_eq!(
+        Reading::from_text_format("sensor: \"a\" unknown: 1").unwrap_err(),
+        TextFormatError::UnknownField("unknown".into())
+    );
+    assert_eq!(
+        Reading::from_text_format("sensor: \"a\" value: 4294967296").unwrap_err(),
+        TextFormatError::InvalidNumber("4294967296".into())
+    );
+    assert_eq!(
+        Reading::from_text_format("sensor: \"a\" location { latitude: 1").unwrap_err(),
+        TextFormatError::Syntax(34)
+    );
+}
+
+#[test]
+fn enum_text_format() {
+    assert_eq!(Unit::UnitKelvin.to_text_format(), "UNIT_KELVIN");
+    let literal = |literal: &str| TextValue::Literal(literal.into());
+    assert_eq!(
+        Unit::from_text(&literal("UNIT_CELSIUS")),
+        Ok(Unit::UnitCelsius)
+    );
+    assert_eq!(Unit::from_text(&literal("-1")), Ok(Unit::UnitInvalid));
+    assert_eq!(
+        Unit::from_text(&literal("UNIT_OTHER")),
+        Err(TextFormatError::InvalidEnumValue("UNIT_OTHER".into()))
+    );
+}
+
+#[test]
+fn parse_text_format_escapes() {
+    assert_eq!(
+        parse(r#"a: "\x41\101æ\'\?" b {}"#),
+        Ok(TextValue::Message(vec![
+            ("a".into(), TextValue::String("AAæ'?".as_bytes().to_vec())),
+            ("b".into(), TextValue::Message(Vec::new())),
+        ]))
+    );
+}
+
+#[test]
+fn parse_nesting_limit() {
+    let nested = |depth| "a { ".repeat(depth) + &"}".repeat(depth);
+    assert!(parse(&nested(MAX_NESTING_DEPTH)).is_ok());
+    assert_eq!(
+        parse(&nested(MAX_NESTING_DEPTH + 1)),
+        Err(TextFormatError::TooDeep(MAX_NESTING_DEPTH * 4 + 2))
+    );
+    // fails before the stack overflows
+    assert_eq!(
+        parse(&"child {".repeat(100_000)),
+        Err(TextFormatError::TooDeep(MAX_NESTING_DEPTH * 7 + 6))
+    );
+    assert_eq!(
+        parse(&format!("a: {}", "[".repeat(100_000))),
+        Err(TextFormatError::TooDeep(MAX_NESTING_DEPTH + 3))
+    );
+}
@@ -0,0 +1,138 @@
+use picopb::common::{UnknownFieldStorage, UnknownFields};
+use picopb::decode::{DecodeError, DecodeMessage, UnknownFieldPolicy};
+use picopb::encode::ToWire;
+use picopb::{
+    common::Field,
+    generator::{generate_with_config, GeneratorConfig},
+    parser::parse,
+    validator::validate,
+};
+
+mod unknown {
+    #![allow(dead_code, unused_imports)]
+    include!("../protos/unknown.rs");
+}
+
+mod unknown_bounded {
+    #![allow(dead_code, unused_imports)]
+    include!("../protos/unknown_bounded.rs");
+}
+
+use unknown::{PositionV2, Sensor, SensorV2};
+
+fn generated(unknown_fields: UnknownFieldStorage) -> String {
+    let result = parse(include_str!("../protos/unknown.proto")).unwrap();
+    validate(&result).unwrap();
+    let mut output = Vec::new();
+    let config = GeneratorConfig {
+        unknown_fields,
+        ..Default::default()
+    };
+    generate_with_config(&mut output, &result, &config).unwrap();
+    String::from_utf8(output).unwrap()
+}
+
+#[test]
+fn generated_unknown_is_up_to_date() {
+    assert_eq!(
+        generated(UnknownFieldStorage::Unbounded),
+        include_str!("../protos/unknown.rs")
+    );
+    assert_eq!(
+        generated(UnknownFieldStorage::Bounded(16)),
+        include_str!("../protos/unknown_bounded.rs")
+    );
+}
+
+fn sensor_v2() -> SensorV2 {
+    SensorV2 {
+        id: 7,
+        position: Some(PositionV2 {
+            x: -3,
+            y: Some(12),
+            unknown_fields: Vec::new(),
+        }),
+        name: Some("north".into()),
+        offsets: picopb::common::Packed(vec![1, -1, 300]),
+        unknown_fields: Vec::new(),
+    }
+}
+
+fn encode<T>(message: T) -> Vec<u8>
+where
+    T: ToWire,
+{
+    let mut bytes = Vec::new();
+    message.append(&mut bytes).unwrap();
+    bytes
+}
+
+#[test]
+fn unknown_fields_round_trip() {
+    let original = encode(&sensor_v2());
+
+    let sensor = Sensor::decode(&original).unwrap();
+    assert_eq!(sensor.id, 7);
+    assert_eq!(sensor.position.as_ref().unwrap().x, -3);
+    // name and offsets, and y of the nested position
+    assert!(!sensor.unknown_fields.is_empty());
+    assert_eq!(
+        sensor.position.as_ref().unwrap().unknown_fields,
+        [0x10, 0x0c]
+    );
+
+    let reencoded = encode(&sensor);
+    assert_eq!(reencoded.len(), (&sensor).precalculate_size());
+    // the known fields come first in both schemas, so no bytes move
+    assert_eq!(reencoded, original);
+
+    let decoded = SensorV2::decode(&reencoded).unwrap();
+    assert_eq!(decoded.id, 7);
+    assert_eq!(decoded.position.as_ref().unwrap().y, Some(12));
+    assert_eq!(decoded.name.as_deref(), Some("north"));
+    assert_eq!(decoded.offsets.0, vec![1, -1, 300]);
+    assert!(decoded.unknown_fields.is_empty());
+}
+
+#[test]
+fn unknown_field_policy() {
+    let original = encode(&sensor_v2());
+
+    let sensor = Sensor::decode_with_policy(&original, UnknownFieldPolicy::Skip).unwrap();
+    assert!(sensor.unknown_fields.is_empty());
+    assert!(sensor.position.unwrap().unknown_fields.is_empty());
+
+    // the policy applies to sub messages as well, y is the first unknown field
+    assert_eq!(
+        Sensor::decode_with_policy(&original, UnknownFieldPolicy::Error).unwrap_err(),
+        DecodeError::UnknownField(Field(2))
+    );
+    assert!(SensorV2::decode_with_policy(&original, UnknownFieldPolicy::Error).is_ok());
+}
+
+#[test]
+fn bounded_unknown_fields() {
+    assert_eq!(unknown_bounded::Sensor::MAX_ENCODED_LEN, 51);
+
+    let mut sensor_v2 = sensor_v2();
+    sensor_v2.name = None;
+    let original = encode(&sensor_v2);
+    let sensor = unknown_bounded::Sensor::decode(&original).unwrap();
+    assert_eq!(encode(&sensor), original);
+
+    // name does not fit in the 16 bytes left for unknown fields
+    sensor_v2.name = Some("a name that is too long".into());
+    assert_eq!(
+        unknown_bounded::Sensor::decode(&encode(&sensor_v2)).unwrap_err(),
+        DecodeError::CapacityExceeded
+    );
+}
+
+#[test]
+fn bounded_unknown_fields_storage() {
+    let mut storage = picopb::common::BoundedUnknownFields::<4>::default();
+    assert!(storage.push_field(&[0x10, 0x01]));
+    assert!(storage.push_field(&[0x18, 0x02]));
+    assert!(!storage.push_field(&[0x20]));
+    assert_eq!(storage.as_bytes(), [0x10, 0x01, 0x18, 0x02]);
+}